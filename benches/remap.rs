@@ -76,6 +76,42 @@ fn benchmark_remap(c: &mut Criterion) {
         );
     });
 
+    group.bench_function("add_fields/remap_compiled", |b| {
+        let mut tform: Box<dyn SyncTransform> = Box::new(
+            Remap::new_compiled(
+                RemapConfig {
+                    source: Some(
+                        indoc! {r#".foo = "bar"
+                            .bar = "baz"
+                            .copy = string!(.copy_from)
+                        "#}
+                        .to_string(),
+                    ),
+                    file: None,
+                    timezone: TimeZone::default(),
+                    drop_on_error: true,
+                    drop_on_abort: true,
+                    ..Default::default()
+                },
+                &Default::default(),
+            )
+            .unwrap()
+            .0,
+        );
+
+        let event = {
+            let mut event = Event::Log(LogEvent::from("augment me"));
+            event.as_mut_log().insert("copy_from", "buz".to_owned());
+            event
+        };
+
+        b.iter_batched(
+            || event.clone(),
+            |event| add_fields_runner(&mut tform, event),
+            BatchSize::SmallInput,
+        );
+    });
+
     let json_parser_runner = |tform: &mut Box<dyn SyncTransform>, event: Event| {
         let mut outputs =
             TransformOutputsBuf::new_with_capacity(vec![Output::default(DataType::all())], 1);
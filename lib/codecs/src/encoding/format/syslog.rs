@@ -0,0 +1,668 @@
+use std::fmt::Write as _;
+
+use crate::encoding::BuildError;
+use bytes::BytesMut;
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, SecondsFormat, Utc,
+};
+use derivative::Derivative;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The names of the facilities, indexed by their code.
+const FACILITIES: [&str; 24] = [
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron", "authpriv",
+    "ftp", "ntp", "audit", "alert", "clockd", "local0", "local1", "local2", "local3", "local4",
+    "local5", "local6", "local7",
+];
+
+/// The names of the severities, indexed by their code.
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+const DEFAULT_FACILITY: u8 = 1; // user
+const DEFAULT_SEVERITY: u8 = 6; // info
+
+/// The nil value of RFC 5424 header fields and structured data.
+const NIL: &str = "-";
+
+static FIELD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{(?P<field>[^\}]+)\}\}").unwrap());
+
+/// Config used to build a `SyslogSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    #[serde(default)]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> Result<SyslogSerializer, BuildError> {
+        SyslogSerializer::new(&self.syslog)
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// The format of syslog messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogRfc {
+    /// The BSD syslog format of [RFC 3164](https://tools.ietf.org/html/rfc3164).
+    Rfc3164,
+
+    /// The syslog format of [RFC 5424](https://tools.ietf.org/html/rfc5424).
+    #[derivative(Default)]
+    Rfc5424,
+}
+
+/// Syslog serializer options.
+///
+/// The header fields of a message can be set to a fixed string, or to a template referring to fields of the event, such
+/// as `{{ service }}`, and to the timestamp of the event with `strftime` specifiers, such as `%Y-%m-%d`. An event
+/// missing a field referred to by a template fails to encode. If not set, the header fields are read from the fields
+/// of the same name that the `syslog` decoder produces.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct SyslogSerializerOptions {
+    /// The format of the messages.
+    #[serde(default)]
+    pub rfc: SyslogRfc,
+
+    /// The facility of the messages, either as a name such as `local0`, or as a number.
+    ///
+    /// If not set, the `facility` field is used, and `user` if it's missing.
+    pub facility: Option<String>,
+
+    /// The severity of the messages, either as a name such as `warning`, or as a number.
+    ///
+    /// If not set, the `severity` field is used, and `info` if it's missing.
+    pub severity: Option<String>,
+
+    /// The hostname of the messages.
+    ///
+    /// If not set, the `hostname` field is used, and the host field of the global log schema if it's missing.
+    pub hostname: Option<String>,
+
+    /// The application name of the messages, which is the tag of RFC 3164 messages.
+    ///
+    /// If not set, the `appname` field is used.
+    pub app_name: Option<String>,
+
+    /// The process ID of the messages.
+    ///
+    /// If not set, the `procid` field is used.
+    pub proc_id: Option<String>,
+
+    /// The message ID of the messages. Only used by RFC 5424.
+    ///
+    /// If not set, the `msgid` field is used.
+    pub msg_id: Option<String>,
+
+    /// The field holding the structured data of the messages. Only used by RFC 5424.
+    ///
+    /// The field must be an object of structured data IDs, each holding an object of parameters, such as `{"origin":
+    /// {"ip": "192.0.2.1"}}`.
+    pub structured_data: Option<String>,
+}
+
+/// A part of a header template.
+#[derive(Debug, Clone)]
+enum TemplatePart {
+    /// Literal text, which is formatted with the event timestamp if it has `strftime` specifiers.
+    Literal { text: String, has_ts: bool },
+    /// A `{{ field }}` reference.
+    Field(String),
+}
+
+/// A header field of a message, set to a template or read from a field.
+#[derive(Debug, Clone)]
+enum HeaderField {
+    Template(Vec<TemplatePart>),
+    Field(&'static str),
+}
+
+impl HeaderField {
+    fn new(template: &Option<String>, field: &'static str) -> Result<Self, BuildError> {
+        let template = match template {
+            Some(template) => template,
+            None => return Ok(Self::Field(field)),
+        };
+
+        let mut parts = Vec::new();
+        let mut last = 0;
+        for captures in FIELD_RE.captures_iter(template) {
+            let range = captures.get(0).expect("match is present").range();
+            parts.push(literal(&template[last..range.start], field)?);
+            parts.push(TemplatePart::Field(captures["field"].trim().to_owned()));
+            last = range.end;
+        }
+        parts.push(literal(&template[last..], field)?);
+        parts.retain(|part| !matches!(part, TemplatePart::Literal { text, .. } if text.is_empty()));
+
+        Ok(Self::Template(parts))
+    }
+
+    /// Renders the field, which is `None` if it's empty.
+    ///
+    /// Like Vector templates, rendering a template fails if the event is missing any of the fields it refers to.
+    fn render(
+        &self,
+        log: &LogEvent,
+        timestamp: DateTime<Utc>,
+    ) -> vector_core::Result<Option<String>> {
+        let rendered = match self {
+            Self::Template(parts) => {
+                let mut rendered = String::new();
+                let mut missing = Vec::new();
+                for part in parts {
+                    match part {
+                        TemplatePart::Literal { text, has_ts: true } => {
+                            let _ = write!(rendered, "{}", timestamp.format(text));
+                        }
+                        TemplatePart::Literal {
+                            text,
+                            has_ts: false,
+                        } => rendered.push_str(text),
+                        TemplatePart::Field(field) => match log.get(field.as_str()) {
+                            Some(value) => rendered.push_str(&value.to_string_lossy()),
+                            None => missing.push(field.as_str()),
+                        },
+                    }
+                }
+                if !missing.is_empty() {
+                    return Err(format!("Missing fields on event: {:?}", missing).into());
+                }
+                rendered
+            }
+            Self::Field(field) => match log.get(*field) {
+                Some(value) => value.to_string_lossy(),
+                None => return Ok(None),
+            },
+        };
+        Ok((!rendered.is_empty()).then(|| rendered))
+    }
+}
+
+/// Parses the literal text of a header template, checking its `strftime` specifiers.
+fn literal(text: &str, field: &str) -> Result<TemplatePart, BuildError> {
+    let mut has_ts = false;
+    for item in StrftimeItems::new(text) {
+        match item {
+            Item::Error => {
+                return Err(format!(
+                    "Invalid strftime item in syslog {} template: {:?}",
+                    field, text
+                )
+                .into())
+            }
+            Item::Fixed(_) | Item::Numeric(_, _) => has_ts = true,
+            _ => {}
+        }
+    }
+    Ok(TemplatePart::Literal {
+        text: text.to_owned(),
+        has_ts,
+    })
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    rfc: SyslogRfc,
+    facility: HeaderField,
+    severity: HeaderField,
+    hostname: HeaderField,
+    app_name: HeaderField,
+    proc_id: HeaderField,
+    msg_id: HeaderField,
+    structured_data: Option<String>,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub fn new(options: &SyslogSerializerOptions) -> Result<Self, BuildError> {
+        Ok(Self {
+            rfc: options.rfc,
+            facility: HeaderField::new(&options.facility, "facility")?,
+            severity: HeaderField::new(&options.severity, "severity")?,
+            hostname: HeaderField::new(&options.hostname, "hostname")?,
+            app_name: HeaderField::new(&options.app_name, "appname")?,
+            proc_id: HeaderField::new(&options.proc_id, "procid")?,
+            msg_id: HeaderField::new(&options.msg_id, "msgid")?,
+            structured_data: options.structured_data.clone(),
+        })
+    }
+
+    fn priority(&self, log: &LogEvent, timestamp: DateTime<Utc>) -> vector_core::Result<u8> {
+        let facility = match self.facility.render(log, timestamp)? {
+            Some(facility) => code(&facility, &FACILITIES)
+                .ok_or_else(|| format!("Invalid syslog facility: {:?}", facility))?,
+            None => DEFAULT_FACILITY,
+        };
+        let severity = match self.severity.render(log, timestamp)? {
+            Some(severity) => code(&severity, &SEVERITIES)
+                .ok_or_else(|| format!("Invalid syslog severity: {:?}", severity))?,
+            None => DEFAULT_SEVERITY,
+        };
+
+        Ok(facility * 8 + severity)
+    }
+
+    fn hostname(
+        &self,
+        log: &LogEvent,
+        timestamp: DateTime<Utc>,
+    ) -> vector_core::Result<Option<String>> {
+        Ok(self.hostname.render(log, timestamp)?.or_else(|| {
+            log.get(log_schema().host_key())
+                .map(Value::to_string_lossy)
+                .filter(|host| !host.is_empty())
+        }))
+    }
+
+    fn encode_rfc5424(
+        &self,
+        log: &LogEvent,
+        priority: u8,
+        timestamp: DateTime<Utc>,
+    ) -> vector_core::Result<String> {
+        let mut message = format!(
+            "<{}>1 {} {} {} {} {} ",
+            priority,
+            timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            header(self.hostname(log, timestamp)?, 255),
+            header(self.app_name.render(log, timestamp)?, 48),
+            header(self.proc_id.render(log, timestamp)?, 128),
+            header(self.msg_id.render(log, timestamp)?, 32),
+        );
+
+        let elements = self
+            .structured_data
+            .as_ref()
+            .and_then(|field| log.get(field.as_str()))
+            .and_then(Value::as_object);
+        match elements {
+            Some(elements) if !elements.is_empty() => {
+                for (id, params) in elements {
+                    message.push('[');
+                    message.push_str(&sd_name(id));
+                    for (name, value) in params.as_object().into_iter().flatten() {
+                        let _ = write!(
+                            message,
+                            r#" {}="{}""#,
+                            sd_name(name),
+                            sd_value(&value.to_string_lossy())
+                        );
+                    }
+                    message.push(']');
+                }
+            }
+            _ => message.push_str(NIL),
+        }
+
+        if let Some(msg) = self.message(log) {
+            message.push(' ');
+            message.push_str(&msg);
+        }
+        Ok(message)
+    }
+
+    fn encode_rfc3164(
+        &self,
+        log: &LogEvent,
+        priority: u8,
+        timestamp: DateTime<Utc>,
+    ) -> vector_core::Result<String> {
+        let mut message = format!(
+            "<{}>{} {}",
+            priority,
+            timestamp.format("%b %e %H:%M:%S"),
+            header(self.hostname(log, timestamp)?, 255),
+        );
+
+        // The tag is alphanumeric, and at most 32 characters long.
+        let tag = self.app_name.render(log, timestamp)?.map(|app_name| {
+            app_name
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+                .take(32)
+                .collect::<String>()
+        });
+        if let Some(tag) = tag.filter(|tag| !tag.is_empty()) {
+            message.push(' ');
+            message.push_str(&tag);
+            if let Some(proc_id) = self.proc_id.render(log, timestamp)? {
+                let _ = write!(message, "[{}]", header(Some(proc_id), 128));
+            }
+            message.push(':');
+        }
+
+        if let Some(msg) = self.message(log) {
+            message.push(' ');
+            message.push_str(&msg);
+        }
+        Ok(message)
+    }
+
+    fn message(&self, log: &LogEvent) -> Option<String> {
+        log.get(log_schema().message_key())
+            .map(Value::to_string_lossy)
+            .filter(|message| !message.is_empty())
+    }
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.as_log();
+
+        let timestamp = log
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .copied()
+            .unwrap_or_else(Utc::now);
+        let priority = self.priority(log, timestamp)?;
+
+        let message = match self.rfc {
+            SyslogRfc::Rfc3164 => self.encode_rfc3164(log, priority, timestamp)?,
+            SyslogRfc::Rfc5424 => self.encode_rfc5424(log, priority, timestamp)?,
+        };
+        buffer.extend_from_slice(message.as_bytes());
+
+        Ok(())
+    }
+}
+
+/// Looks up the code of a facility or severity, given as a name or number.
+fn code(value: &str, names: &[&str]) -> Option<u8> {
+    let value = value.trim();
+    match value.parse::<u8>() {
+        Ok(code) => (usize::from(code) < names.len()).then(|| code),
+        Err(_) => names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+            .map(|code| code as u8),
+    }
+}
+
+/// Formats a header field, which is printable ASCII without spaces, and at most `max_len` characters long.
+fn header(value: Option<String>, max_len: usize) -> String {
+    match value {
+        Some(value) => value
+            .chars()
+            .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+            .take(max_len)
+            .collect(),
+        None => NIL.to_owned(),
+    }
+}
+
+/// Formats the name of a structured data element or parameter, which is printable ASCII other than `=`, `]`, `"`
+/// and spaces, and at most 32 characters long.
+fn sd_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(32)
+        .collect()
+}
+
+/// Escapes the value of a structured data parameter.
+fn sd_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::TimeZone;
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn encode(options: SyslogSerializerOptions, log: LogEvent) -> String {
+        let mut serializer = SyslogSerializerConfig::new(options).build().unwrap();
+        let mut buffer = BytesMut::new();
+        serializer.encode(log.into(), &mut buffer).unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    fn log(fields: std::collections::BTreeMap<String, Value>) -> LogEvent {
+        let mut log = LogEvent::from(fields);
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 8, 4).and_hms_micro(10, 30, 5, 123_456),
+        );
+        log
+    }
+
+    #[test]
+    fn serialize_rfc5424_from_fields() {
+        let log = log(btreemap! {
+            "message" => "disk almost full",
+            "facility" => "local3",
+            "severity" => "warning",
+            "hostname" => "storage-1",
+            "appname" => "diskmon",
+            "procid" => 4242,
+            "msgid" => "DISK",
+        });
+
+        assert_eq!(
+            encode(SyslogSerializerOptions::default(), log),
+            "<156>1 2022-08-04T10:30:05.123456Z storage-1 diskmon 4242 DISK - disk almost full"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_defaults() {
+        let log = log(btreemap! {
+            "message" => "hello",
+        });
+
+        assert_eq!(
+            encode(SyslogSerializerOptions::default(), log),
+            "<14>1 2022-08-04T10:30:05.123456Z - - - - - hello"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_from_templates() {
+        let log = log(btreemap! {
+            "message" => "login failed",
+            "service" => "sshd",
+            "host" => "bastion",
+            "level" => "err",
+            "user" => "root",
+        });
+        let options = SyslogSerializerOptions {
+            facility: Some("10".to_string()),
+            severity: Some("{{ level }}".to_string()),
+            app_name: Some("{{ service }}".to_string()),
+            msg_id: Some("AUTH-{{ user }}".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode(options, log),
+            "<83>1 2022-08-04T10:30:05.123456Z bastion sshd - AUTH-root - login failed"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_structured_data() {
+        let mut log = log(btreemap! {
+            "message" => "hello",
+        });
+        log.insert("sd.origin.ip", "192.0.2.1");
+        log.insert(r#"sd."meta@32473".note"#, r#"a "quoted" [value]"#);
+        log.insert(r#"sd."meta@32473".sequence"#, 7);
+        let options = SyslogSerializerOptions {
+            structured_data: Some("sd".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode(options, log),
+            r#"<14>1 2022-08-04T10:30:05.123456Z - - - - [meta@32473 note="a \"quoted\" [value\]" sequence="7"][origin ip="192.0.2.1"] hello"#
+        );
+    }
+
+    #[test]
+    fn serialize_rfc5424_sanitizes_header_fields() {
+        let log = log(btreemap! {
+            "message" => "hello",
+            "appname" => "my app",
+            "msgid" => "x".repeat(40),
+        });
+
+        assert_eq!(
+            encode(SyslogSerializerOptions::default(), log),
+            format!(
+                "<14>1 2022-08-04T10:30:05.123456Z - my_app - {} - hello",
+                "x".repeat(32)
+            )
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let log = log(btreemap! {
+            "message" => "disk almost full",
+            "hostname" => "storage-1",
+            "appname" => "diskmon",
+            "procid" => 4242,
+        });
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            facility: Some("daemon".to_string()),
+            severity: Some("notice".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode(options, log),
+            "<29>Aug  4 10:30:05 storage-1 diskmon[4242]: disk almost full"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164_without_tag() {
+        let log = log(btreemap! {
+            "message" => "hello",
+            "host" => "web-1",
+        });
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..Default::default()
+        };
+
+        assert_eq!(encode(options, log), "<14>Aug  4 10:30:05 web-1 hello");
+    }
+
+    #[test]
+    fn serialize_invalid_severity() {
+        let mut serializer = SyslogSerializerConfig::new(SyslogSerializerOptions::default())
+            .build()
+            .unwrap();
+        let log = log(btreemap! {
+            "message" => "hello",
+            "severity" => "verbose",
+        });
+
+        let error = serializer
+            .encode(log.into(), &mut BytesMut::new())
+            .unwrap_err();
+        assert_eq!(error.to_string(), r#"Invalid syslog severity: "verbose""#);
+    }
+
+    #[test]
+    fn serialize_rfc5424_timestamp_template() {
+        let log = log(btreemap! {
+            "message" => "hello",
+            "service" => "api",
+        });
+        let options = SyslogSerializerOptions {
+            app_name: Some("{{ service }}-%Y%m%d".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode(options, log),
+            "<14>1 2022-08-04T10:30:05.123456Z - api-20220804 - - - hello"
+        );
+    }
+
+    #[test]
+    fn serialize_missing_template_field() {
+        let options = SyslogSerializerOptions {
+            app_name: Some("{{ service }}".to_string()),
+            msg_id: Some("{{ kind }}-{{ user }}".to_string()),
+            ..Default::default()
+        };
+        let mut serializer = SyslogSerializerConfig::new(options).build().unwrap();
+        let log = log(btreemap! {
+            "message" => "hello",
+            "service" => "api",
+            "kind" => "AUTH",
+        });
+
+        let error = serializer
+            .encode(log.into(), &mut BytesMut::new())
+            .unwrap_err();
+        assert_eq!(error.to_string(), r#"Missing fields on event: ["user"]"#);
+    }
+
+    #[test]
+    fn invalid_strftime_template() {
+        let options = SyslogSerializerOptions {
+            msg_id: Some("{{ kind }}-%E".to_string()),
+            ..Default::default()
+        };
+
+        assert!(SyslogSerializerConfig::new(options).build().is_err());
+    }
+
+    #[test]
+    fn facility_and_severity_codes() {
+        assert_eq!(code("LOCAL7", &FACILITIES), Some(23));
+        assert_eq!(code("23", &FACILITIES), Some(23));
+        assert_eq!(code("24", &FACILITIES), None);
+        assert_eq!(code("debug", &SEVERITIES), Some(7));
+        assert_eq!(code("8", &SEVERITIES), None);
+    }
+}
@@ -0,0 +1,96 @@
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build an `OctetCountingEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OctetCountingEncoderConfig;
+
+impl OctetCountingEncoderConfig {
+    /// Creates an `OctetCountingEncoderConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `OctetCountingEncoder` from this configuration.
+    pub const fn build(&self) -> OctetCountingEncoder {
+        OctetCountingEncoder::new()
+    }
+}
+
+/// An encoder for handling bytes that are prefixed by their length in octets, using the `Octet Counting` format as
+/// specified in https://tools.ietf.org/html/rfc6587#section-3.4.1.
+#[derive(Debug, Clone, Default)]
+pub struct OctetCountingEncoder;
+
+impl OctetCountingEncoder {
+    /// Creates an `OctetCountingEncoder`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<()> for OctetCountingEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let frame = buffer.split();
+        let prefix = format!("{} ", frame.len());
+        buffer.reserve(prefix.len() + frame.len());
+        buffer.extend_from_slice(prefix.as_bytes());
+        buffer.unsplit(frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_util::codec::Decoder;
+
+    use super::*;
+    use crate::decoding::OctetCountingDecoder;
+
+    #[test]
+    fn encode() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::from("<34>1 - - - - - hello");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"21 <34>1 - - - - - hello");
+    }
+
+    #[test]
+    fn encode_empty() {
+        let mut codec = OctetCountingEncoder::new();
+
+        let mut buffer = BytesMut::new();
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"0 ");
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut encoder = OctetCountingEncoder::new();
+        let mut decoder = OctetCountingDecoder::new();
+
+        let mut stream = BytesMut::new();
+        for message in ["first message", "second\nmessage with a newline"] {
+            let mut frame = BytesMut::from(message);
+            encoder.encode((), &mut frame).unwrap();
+            stream.extend_from_slice(&frame);
+        }
+
+        assert_eq!(
+            decoder.decode(&mut stream).unwrap().unwrap(),
+            "first message"
+        );
+        assert_eq!(
+            decoder.decode(&mut stream).unwrap().unwrap(),
+            "second\nmessage with a newline"
+        );
+    }
+}
@@ -11,13 +11,15 @@ pub use format::{
     AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
     GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
     LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
+    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, SyslogRfc,
+    SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions, TextSerializer,
     TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
     CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    OctetCountingEncoder, OctetCountingEncoderConfig,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -75,6 +77,11 @@ pub enum FramingConfig {
 
     /// Event data is delimited by a newline (LF) character.
     NewlineDelimited,
+
+    /// Event data is prefixed with its length in bytes, as a decimal number followed by a space.
+    ///
+    /// This is the octet counting framing of syslog over TCP, as specified in RFC 6587.
+    OctetCounting,
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -103,6 +110,12 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<OctetCountingEncoderConfig> for FramingConfig {
+    fn from(_: OctetCountingEncoderConfig) -> Self {
+        Self::OctetCounting
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -122,6 +135,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
+            FramingConfig::OctetCounting => {
+                Framer::OctetCounting(OctetCountingEncoderConfig.build())
+            }
         }
     }
 }
@@ -137,6 +153,8 @@ pub enum Framer {
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
     NewlineDelimited(NewlineDelimitedEncoder),
+    /// Uses an `OctetCountingEncoder` for framing.
+    OctetCounting(OctetCountingEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -165,6 +183,12 @@ impl From<NewlineDelimitedEncoder> for Framer {
     }
 }
 
+impl From<OctetCountingEncoder> for Framer {
+    fn from(encoder: OctetCountingEncoder) -> Self {
+        Self::OctetCounting(encoder)
+    }
+}
+
 impl From<BoxedFramer> for Framer {
     fn from(encoder: BoxedFramer) -> Self {
         Self::Boxed(encoder)
@@ -180,6 +204,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
+            Framer::OctetCounting(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
         }
     }
@@ -219,6 +244,16 @@ pub enum SerializerConfig {
     /// event.
     RawMessage,
 
+    /// Syslog serialization.
+    ///
+    /// Messages are formatted as described in RFC 5424, or RFC 3164. The `message` field of a log event is the
+    /// message, and the header fields are set from the options.
+    Syslog {
+        /// Options for the syslog serializer.
+        #[serde(default)]
+        syslog: SyslogSerializerOptions,
+    },
+
     /// Plaintext serialization.
     ///
     /// This encoding, specifically, will only encode the `message` field of a log event. Users should take care if
@@ -270,6 +305,14 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<SyslogSerializerConfig> for SerializerConfig {
+    fn from(config: SyslogSerializerConfig) -> Self {
+        Self::Syslog {
+            syslog: config.syslog,
+        }
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(_: TextSerializerConfig) -> Self {
         Self::Text
@@ -293,6 +336,9 @@ impl SerializerConfig {
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
+            SerializerConfig::Syslog { syslog } => Ok(Serializer::Syslog(
+                SyslogSerializerConfig::new(syslog.clone()).build()?,
+            )),
             SerializerConfig::Text => Ok(Serializer::Text(TextSerializerConfig.build())),
         }
     }
@@ -309,6 +355,9 @@ impl SerializerConfig {
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).input_type()
+            }
             SerializerConfig::Text => TextSerializerConfig.input_type(),
         }
     }
@@ -325,6 +374,9 @@ impl SerializerConfig {
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::Syslog { syslog } => {
+                SyslogSerializerConfig::new(syslog.clone()).schema_requirement()
+            }
             SerializerConfig::Text => TextSerializerConfig.schema_requirement(),
         }
     }
@@ -347,6 +399,8 @@ pub enum Serializer {
     NativeJson(NativeJsonSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SyslogSerializer` for serialization.
+    Syslog(SyslogSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
 }
@@ -360,7 +414,8 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => false,
         }
     }
 
@@ -379,7 +434,8 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => {
                 panic!("Serializer does not support JSON")
             }
         }
@@ -428,6 +484,12 @@ impl From<RawMessageSerializer> for Serializer {
     }
 }
 
+impl From<SyslogSerializer> for Serializer {
+    fn from(serializer: SyslogSerializer) -> Self {
+        Self::Syslog(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
@@ -446,6 +508,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::Syslog(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
     }
@@ -56,6 +56,18 @@ pub struct TransformContext {
     /// information, such as the `remap` transform, which passes this information along to the VRL
     /// compiler such that type coercion becomes less of a need for operators writing VRL programs.
     pub merged_schema_definition: schema::Definition,
+
+    /// The clock used by VRL programs in place of the system clock, if any.
+    ///
+    /// This is set when running unit tests against a virtual clock.
+    #[cfg(feature = "vrl")]
+    pub vrl_clock: Option<vrl_lib::Clock>,
+
+    /// The log VRL programs record a trace of each run into, if any.
+    ///
+    /// This is set when running unit tests with tracing enabled.
+    #[cfg(feature = "vrl")]
+    pub vrl_traces: Option<vrl_lib::TraceLog>,
 }
 
 impl Default for TransformContext {
@@ -67,6 +79,10 @@ impl Default for TransformContext {
             enrichment_tables: Default::default(),
             schema_definitions: HashMap::from([(None, schema::Definition::any())]),
             merged_schema_definition: schema::Definition::any(),
+            #[cfg(feature = "vrl")]
+            vrl_clock: None,
+            #[cfg(feature = "vrl")]
+            vrl_traces: None,
         }
     }
 }
@@ -0,0 +1,60 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::store::{state_store, StateStore};
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetState;
+
+impl Function for GetState {
+    fn identifier(&self) -> &'static str {
+        "get_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get the state of a key",
+            source: r#"set_state("last_seen", "2022-01-01"); get_state("last_seen")"#,
+            result: Ok(r#""2022-01-01""#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let store = state_store(ctx);
+
+        Ok(Box::new(GetStateFn { key, store }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetStateFn {
+    key: Box<dyn Expression>,
+    store: StateStore,
+}
+
+impl Expression for GetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?;
+
+        Ok(self.store.get(&key).unwrap_or(Value::Null))
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
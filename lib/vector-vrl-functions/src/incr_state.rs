@@ -0,0 +1,76 @@
+use vrl::prelude::*;
+
+use crate::store::{state_store, StateStore};
+
+#[derive(Clone, Copy, Debug)]
+pub struct IncrState;
+
+impl Function for IncrState {
+    fn identifier(&self) -> &'static str {
+        "incr_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "by",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Count the events of a user",
+                source: r#"incr_state!("user:alice")"#,
+                result: Ok("1"),
+            },
+            Example {
+                title: "Increment by a given amount",
+                source: r#"incr_state!("bytes", 10); incr_state!("bytes", by: 32)"#,
+                result: Ok("42"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let by = arguments.optional("by").unwrap_or_else(|| expr!(1));
+        let store = state_store(ctx);
+
+        Ok(Box::new(IncrStateFn { key, by, store }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IncrStateFn {
+    key: Box<dyn Expression>,
+    by: Box<dyn Expression>,
+    store: StateStore,
+}
+
+impl Expression for IncrStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?;
+        let by = self.by.resolve(ctx)?.try_integer()?;
+
+        Ok(self.store.incr(&key, by)?.into())
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
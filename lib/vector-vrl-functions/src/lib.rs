@@ -2,16 +2,22 @@
 
 pub mod get_metadata_field;
 pub mod get_secret;
+pub mod get_state;
+pub mod incr_state;
 pub mod remove_metadata_field;
 pub mod remove_secret;
 pub mod set_metadata_field;
 pub mod set_secret;
 pub mod set_semantic_meaning;
+pub mod set_state;
+pub mod store;
 
 use ::value::Value;
 use vrl::prelude::expression::Query;
 use vrl::prelude::*;
 
+pub use store::StateStore;
+
 pub(crate) fn legacy_keys() -> Vec<Value> {
     LEGACY_METADATA_KEYS
         .iter()
@@ -37,6 +43,9 @@ pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
         Box::new(get_secret::GetSecret) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
+        Box::new(get_state::GetState) as _,
+        Box::new(set_state::SetState) as _,
+        Box::new(incr_state::IncrState) as _,
     ]
 }
 
@@ -0,0 +1,78 @@
+use vrl::prelude::*;
+
+use crate::store::{state_store, StateStore};
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetState;
+
+impl Function for SetState {
+    fn identifier(&self) -> &'static str {
+        "set_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Set the state of a key",
+                source: r#"set_state("last_status", 200)"#,
+                result: Ok("200"),
+            },
+            Example {
+                title: "Remove the state of a key",
+                source: r#"set_state("last_status", 200); set_state("last_status", null); get_state("last_status")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let store = state_store(ctx);
+
+        Ok(Box::new(SetStateFn { key, value, store }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetStateFn {
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    store: StateStore,
+}
+
+impl Expression for SetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?;
+        let value = self.value.resolve(ctx)?;
+
+        self.store.set(&key, value.clone());
+
+        Ok(value)
+    }
+
+    fn type_def(&self, state: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        self.value.type_def(state)
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use ::value::Value;
+use lru::LruCache;
+use vrl::prelude::FunctionCompileContext;
+
+/// The default maximum number of keys of a state store.
+pub const DEFAULT_MAX_KEYS: usize = 10_000;
+
+/// The storage of the stateful functions, such as `incr_state`.
+///
+/// The store is shared by all clones of the programs compiled with it, so that
+/// state set while processing an event is visible when processing the next
+/// one. Once the store holds its maximum number of keys, the least recently
+/// used key is evicted. Keys can also expire a fixed time after they were last
+/// set.
+#[derive(Clone)]
+pub struct StateStore(Arc<Mutex<Inner>>);
+
+struct Inner {
+    entries: LruCache<String, Entry>,
+    ttl: Option<Duration>,
+}
+
+struct Entry {
+    value: Value,
+    expires_at: Option<Instant>,
+}
+
+impl StateStore {
+    #[must_use]
+    pub fn new(max_keys: usize, ttl: Option<Duration>) -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            entries: LruCache::new(max_keys.max(1)),
+            ttl,
+        })))
+    }
+
+    /// Get the value of the key, if it is set and has not expired.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.lock().get(key, Instant::now()).cloned()
+    }
+
+    /// Set the value of the key. Setting a key to `null` removes it.
+    pub fn set(&self, key: &str, value: Value) {
+        self.lock().set(key, value, Instant::now());
+    }
+
+    /// Increment the integer value of the key, treating a key that is not set
+    /// as zero, and return the new value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is set to a value that is not an integer.
+    pub fn incr(&self, key: &str, by: i64) -> Result<i64, String> {
+        let mut inner = self.lock();
+        let now = Instant::now();
+
+        let count = match inner.get(key, now) {
+            None => by,
+            Some(Value::Integer(count)) => count.saturating_add(by),
+            Some(value) => {
+                return Err(format!(
+                    r#"state of key "{}" is not an integer, but {}"#,
+                    key,
+                    value.kind()
+                ))
+            }
+        };
+
+        inner.set(key, count.into(), now);
+
+        Ok(count)
+    }
+
+    /// The number of keys in the store, including expired keys that have not
+    /// been removed yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.0.lock().expect("state store lock poisoned")
+    }
+}
+
+impl Default for StateStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_KEYS, None)
+    }
+}
+
+impl fmt::Debug for StateStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.lock();
+
+        f.debug_struct("StateStore")
+            .field("len", &inner.entries.len())
+            .field("max_keys", &inner.entries.cap())
+            .field("ttl", &inner.ttl)
+            .finish()
+    }
+}
+
+impl Inner {
+    fn get(&mut self, key: &str, now: Instant) -> Option<&Value> {
+        let key = key.to_owned();
+        let expired = self
+            .entries
+            .peek(&key)?
+            .expires_at
+            .map_or(false, |expires_at| expires_at <= now);
+
+        if expired {
+            self.entries.pop(&key);
+            return None;
+        }
+
+        self.entries.get(&key).map(|entry| &entry.value)
+    }
+
+    fn set(&mut self, key: &str, value: Value, now: Instant) {
+        if value.is_null() {
+            self.entries.pop(&key.to_owned());
+            return;
+        }
+
+        let expires_at = self.ttl.map(|ttl| now + ttl);
+        self.entries
+            .put(key.to_owned(), Entry { value, expires_at });
+    }
+}
+
+/// Get the state store of the program being compiled.
+///
+/// If the program isn't compiled with a store, such as in the VRL CLI, a store
+/// is created and shared by all stateful functions in the program.
+pub(crate) fn state_store(ctx: &mut FunctionCompileContext) -> StateStore {
+    if let Some(store) = ctx.get_external_context::<StateStore>() {
+        return store.clone();
+    }
+
+    let store = StateStore::default();
+    ctx.set_external_context(store.clone());
+    store
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let store = StateStore::new(2, None);
+
+        store.set("a", 1.into());
+        store.set("b", 2.into());
+        assert_eq!(store.get("a"), Some(1.into()));
+
+        store.set("c", 3.into());
+
+        assert_eq!(store.get("a"), Some(1.into()));
+        assert_eq!(store.get("b"), None);
+        assert_eq!(store.get("c"), Some(3.into()));
+    }
+
+    #[test]
+    fn expires_keys() {
+        let mut inner = Inner {
+            entries: LruCache::new(10),
+            ttl: Some(Duration::from_secs(10)),
+        };
+        let now = Instant::now();
+
+        inner.set("a", 1.into(), now);
+        assert_eq!(
+            inner.get("a", now + Duration::from_secs(9)),
+            Some(&1.into())
+        );
+        assert_eq!(inner.get("a", now + Duration::from_secs(10)), None);
+        assert_eq!(inner.entries.len(), 0);
+    }
+
+    #[test]
+    fn increments() {
+        let store = StateStore::default();
+
+        assert_eq!(store.incr("a", 1), Ok(1));
+        assert_eq!(store.incr("a", 5), Ok(6));
+
+        store.set("a", Value::Null);
+        assert!(store.is_empty());
+
+        store.set("b", "x".into());
+        assert!(store.incr("b", 1).is_err());
+    }
+}
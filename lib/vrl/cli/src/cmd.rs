@@ -13,7 +13,7 @@ use lookup::LookupBuf;
 use value::Secrets;
 use vector_common::TimeZone;
 use vrl::state::ExternalEnv;
-use vrl::{diagnostic::Formatter, state, Program, Runtime, Target, VrlRuntime};
+use vrl::{diagnostic::Formatter, state, Program, Runtime, Target, Trace, VrlRuntime};
 
 #[cfg(feature = "repl")]
 use super::repl;
@@ -51,6 +51,11 @@ pub struct Opts {
     // Should the CLI emit warnings
     #[clap(long = "print-warnings")]
     print_warnings: bool,
+
+    /// Print the expressions evaluated for each event to stderr, along with the values they
+    /// resolved to and the changes they made to the event. Tracing always uses the AST runtime.
+    #[clap(long)]
+    trace: bool,
 }
 
 impl Opts {
@@ -128,6 +133,11 @@ fn run(opts: &Opts) -> Result<(), Error> {
         // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
         external.set_read_only_metadata_path(LookupBuf::from("vector"), true);
 
+        // Imports are resolved relative to the program file, if any.
+        if let Some(dir) = opts.program_file.as_ref().and_then(|path| path.parent()) {
+            external.set_external_context(vrl::ImportRoot::new(dir));
+        }
+
         let (program, warnings) =
             vrl::compile_with_external(&source, &stdlib::all(), &mut external).map_err(
                 |diagnostics| {
@@ -152,7 +162,12 @@ fn run(opts: &Opts) -> Result<(), Error> {
             let state = state::Runtime::default();
             let runtime = Runtime::new(state);
 
-            let result = execute(&mut target, &program, tz, runtime, opts.runtime).map(|v| {
+            let result = if opts.trace {
+                trace(&mut target, &program, tz, runtime)
+            } else {
+                execute(&mut target, &program, tz, runtime, opts.runtime)
+            }
+            .map(|v| {
                 if opts.print_object {
                     object.to_string()
                 } else {
@@ -206,9 +221,29 @@ fn execute(
         VrlRuntime::Ast => runtime
             .resolve(object, program, &timezone)
             .map_err(Error::Runtime),
+        VrlRuntime::Compiled => runtime
+            .resolve_compiled(object, &program.compile(), &timezone)
+            .map_err(Error::Runtime),
     }
 }
 
+fn trace(
+    object: &mut impl Target,
+    program: &Program,
+    timezone: TimeZone,
+    mut runtime: Runtime,
+) -> Result<Value, Error> {
+    let mut trace = Trace::default();
+    let result = runtime.resolve_traced(object, program, &timezone, &mut trace);
+
+    #[allow(clippy::print_stderr)]
+    {
+        eprint!("{}", trace);
+    }
+
+    result.map_err(Error::Runtime)
+}
+
 fn serde_to_vrl(value: serde_json::Value) -> Value {
     use serde_json::Value as JsonValue;
 
@@ -18,7 +18,7 @@ use rustyline::{
 use value::Secrets;
 use vector_common::TimeZone;
 use vector_vrl_functions::vrl_functions;
-use vrl::{diagnostic::Formatter, prelude::BTreeMap, state, Runtime, Target, VrlRuntime};
+use vrl::{diagnostic::Formatter, prelude::BTreeMap, state, Runtime, Target, Trace, VrlRuntime};
 
 // Create a list of all possible error values for potential docs lookup
 static ERRORS: Lazy<Vec<String>> = Lazy::new(|| {
@@ -44,6 +44,7 @@ const RESERVED_TERMS: &[&str] = &[
     "help funcs",
     "help fs",
     "help docs",
+    "trace",
 ];
 
 pub(crate) fn run(
@@ -58,6 +59,7 @@ pub(crate) fn run(
     let mut external_state = state::ExternalEnv::default();
     let mut local_state = state::LocalEnv::default();
     let mut rt = Runtime::new(state::Runtime::default());
+    let mut tracing = false;
     let mut rl = Editor::<Repl>::new()?;
     rl.set_helper(Some(Repl::new()));
 
@@ -75,6 +77,15 @@ pub(crate) fn run(
                 print_function_list()
             }
             Ok(line) if line == "help docs" => open_url(DOCS_URL),
+            Ok(line) if line == "trace" => {
+                tracing = !tracing;
+
+                #[allow(clippy::print_stdout)]
+                {
+                    let state = if tracing { "enabled" } else { "disabled" };
+                    println!("tracing {}\n", state);
+                }
+            }
             // Capture "help error <code>"
             Ok(line) if error_docs_regex.is_match(line) => show_error_docs(line, &error_docs_regex),
             // Capture "help docs <func_name>"
@@ -124,6 +135,7 @@ pub(crate) fn run(
                     std::mem::take(&mut local_state),
                     timezone,
                     vrl_runtime,
+                    tracing,
                 );
 
                 let _v = std::mem::replace(&mut local_state, local);
@@ -151,6 +163,7 @@ pub(crate) fn run(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn resolve(
     target: &mut TargetValue,
     runtime: &mut Runtime,
@@ -159,6 +172,7 @@ fn resolve(
     local: state::LocalEnv,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
+    tracing: bool,
 ) -> (state::LocalEnv, Result<Value, String>) {
     let mut functions = stdlib::all();
     functions.extend(vector_vrl_functions::vrl_functions());
@@ -178,7 +192,7 @@ fn resolve(
 
     (
         program.local_env().clone(),
-        execute(runtime, &program, target, timezone, vrl_runtime),
+        execute(runtime, &program, target, timezone, vrl_runtime, tracing),
     )
 }
 
@@ -188,11 +202,28 @@ fn execute(
     object: &mut dyn Target,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
+    tracing: bool,
 ) -> Result<Value, String> {
+    // Tracing is only supported by the AST runtime.
+    if tracing {
+        let mut trace = Trace::default();
+        let result = runtime.resolve_traced(object, program, &timezone, &mut trace);
+
+        #[allow(clippy::print_stdout)]
+        {
+            print!("{}", trace);
+        }
+
+        return result.map_err(|err| err.to_string());
+    }
+
     match vrl_runtime {
         VrlRuntime::Ast => runtime
             .resolve(object, program, &timezone)
             .map_err(|err| err.to_string()),
+        VrlRuntime::Compiled => runtime
+            .resolve_compiled(object, &program.compile(), &timezone)
+            .map_err(|err| err.to_string()),
     }
 }
 
@@ -311,6 +342,7 @@ impl Validator for Repl {
             local_state,
             timezone,
             VrlRuntime::Ast,
+            false,
         );
 
         let result = match result {
@@ -423,6 +455,7 @@ const HELP_TEXT: &str = indoc! {r#"
       help error <code>  Navigate to the docs for a specific error code
       next               Load the next object or create a new one
       prev               Load the previous object
+      trace              Toggle printing the expressions evaluated by each program, with their values
       exit               Terminate the program
 "#};
 
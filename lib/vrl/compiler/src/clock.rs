@@ -0,0 +1,29 @@
+use std::{fmt, sync::Arc};
+
+use chrono::{DateTime, Utc};
+
+/// A source of the current time for functions such as `now`.
+///
+/// When set as external context while compiling a program, it replaces the system clock, which
+/// allows callers to run programs against a controlled (e.g. virtual) notion of time.
+#[derive(Clone)]
+pub struct Clock(Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>);
+
+impl Clock {
+    /// Create a new [`Clock`] that returns the time given by `now`.
+    pub fn new(now: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(now))
+    }
+
+    /// Get the current time of this clock.
+    #[must_use]
+    pub fn now(&self) -> DateTime<Utc> {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Clock").finish()
+    }
+}
@@ -0,0 +1,332 @@
+//! Lowering of compiled programs into closures.
+//!
+//! The tree-walking runtime dispatches on the type of every expression it
+//! resolves, for every event. The compiled runtime instead lowers the typed
+//! expression tree once, into a tree of closures that only contain the work
+//! left to do at runtime:
+//!
+//! - expressions that always resolve to the same value (such as literals, or
+//!   operations on literals) are folded into constants,
+//! - branches of `if` statements with a constant predicate are removed,
+//! - queries into variables only clone the queried value, instead of the entire
+//!   variable.
+//!
+//! Function calls are resolved by the function implementations themselves, the
+//! same as in the tree-walking runtime.
+
+use std::fmt;
+
+use value::Value;
+
+use crate::{
+    expression::{Expr, Resolved},
+    Context, Expression, ProgramInfo,
+};
+
+/// A lowered expression, resolved at runtime.
+pub(crate) type Closure = Box<dyn Fn(&mut Context) -> Resolved + Send + Sync>;
+
+/// The result of lowering an expression.
+pub(crate) enum Lowered {
+    /// The expression always resolves to this value, without any side-effects.
+    Constant(Value),
+
+    /// The expression is resolved at runtime.
+    Dynamic(Closure),
+}
+
+impl Lowered {
+    pub(crate) fn dynamic(
+        closure: impl Fn(&mut Context) -> Resolved + Send + Sync + 'static,
+    ) -> Self {
+        Self::Dynamic(Box::new(closure))
+    }
+
+    pub(crate) fn as_constant(&self) -> Option<&Value> {
+        match self {
+            Self::Constant(value) => Some(value),
+            Self::Dynamic(_) => None,
+        }
+    }
+
+    pub(crate) fn into_constant(self) -> Option<Value> {
+        match self {
+            Self::Constant(value) => Some(value),
+            Self::Dynamic(_) => None,
+        }
+    }
+
+    pub(crate) fn into_closure(self) -> Closure {
+        match self {
+            Self::Constant(value) => Box::new(move |_| Ok(value.clone())),
+            Self::Dynamic(closure) => closure,
+        }
+    }
+}
+
+/// Lower an expression for the compiled runtime.
+pub(crate) trait Lower {
+    fn lower(&self) -> Lowered;
+}
+
+/// Lower an expression without a specialized lowering, by resolving it as-is.
+pub(crate) fn fallback<T>(expr: &T) -> Lowered
+where
+    T: Expression + Clone + 'static,
+{
+    let expr = expr.clone();
+
+    Lowered::dynamic(move |ctx| expr.resolve(ctx))
+}
+
+/// Lower a sequence of expressions that resolves to the value of its last
+/// expression, such as a block.
+pub(crate) fn sequence(exprs: &[Expr]) -> Lowered {
+    let (last, other) = match exprs.split_last() {
+        Some(exprs) => exprs,
+        None => return Lowered::Constant(Value::Null),
+    };
+
+    // The value of any but the last expression is discarded, so constant
+    // expressions in those positions can be dropped entirely.
+    let mut other = other
+        .iter()
+        .filter_map(|expr| match expr.lower() {
+            Lowered::Constant(_) => None,
+            Lowered::Dynamic(closure) => Some(closure),
+        })
+        .collect::<Vec<_>>();
+
+    let last = last.lower();
+
+    match other.len() {
+        0 => last,
+        1 => {
+            let first = other.pop().expect("one expression");
+            let last = last.into_closure();
+
+            Lowered::dynamic(move |ctx| {
+                first(ctx)?;
+                if ctx.is_interrupted() {
+                    return Ok(Value::Null);
+                }
+
+                last(ctx)
+            })
+        }
+        _ => {
+            let last = last.into_closure();
+
+            Lowered::dynamic(move |ctx| {
+                for closure in &other {
+                    closure(ctx)?;
+
+                    // A `break` or `continue` ends the sequence, up to the
+                    // enclosing loop.
+                    if ctx.is_interrupted() {
+                        return Ok(Value::Null);
+                    }
+                }
+
+                last(ctx)
+            })
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A [`Program`](crate::Program) lowered into closures, resolved by the
+/// compiled runtime.
+pub struct CompiledProgram {
+    closure: Closure,
+    info: ProgramInfo,
+}
+
+impl CompiledProgram {
+    pub(crate) fn new(lowered: Lowered, info: ProgramInfo) -> Self {
+        Self {
+            closure: lowered.into_closure(),
+            info,
+        }
+    }
+
+    /// Get detailed information about the program, as collected by the VRL
+    /// compiler.
+    #[must_use]
+    pub fn info(&self) -> &ProgramInfo {
+        &self.info
+    }
+
+    /// Resolve the program to its final [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the program resulted in a runtime error.
+    pub fn resolve(&self, ctx: &mut Context) -> Resolved {
+        (self.closure)(ctx)
+    }
+}
+
+impl fmt::Debug for CompiledProgram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompiledProgram")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use vector_common::TimeZone;
+
+    use super::*;
+    use crate::{
+        state::{self, ExternalEnv, LocalEnv},
+        value, Compiler, Program,
+    };
+
+    fn compile(source: &str) -> Program {
+        let ast = parser::parse(source).expect("valid program");
+
+        Compiler::compile(&[], ast, &mut ExternalEnv::default(), LocalEnv::default())
+            .expect("program compiles")
+            .0
+    }
+
+    fn lower(source: &str) -> Lowered {
+        compile(source).expressions.lower()
+    }
+
+    /// Resolve the program with both runtimes, and assert both give the same
+    /// result, and leave the target in the same state.
+    fn resolve(source: &str, target: Value) -> (Resolved, Value) {
+        let program = compile(source);
+        let compiled = program.compile();
+        let timezone = TimeZone::default();
+
+        let mut ast_target = target.clone();
+        let mut ast_state = state::Runtime::default();
+        let mut ctx = Context::new(&mut ast_target, &mut ast_state, &timezone);
+        let ast_result = program.resolve(&mut ctx);
+
+        let mut compiled_target = target;
+        let mut compiled_state = state::Runtime::default();
+        let mut ctx = Context::new(&mut compiled_target, &mut compiled_state, &timezone);
+        let compiled_result = compiled.resolve(&mut ctx);
+
+        assert_eq!(ast_result, compiled_result);
+        assert_eq!(ast_target, compiled_target);
+
+        (compiled_result, compiled_target)
+    }
+
+    #[test]
+    fn folds_constant_expressions() {
+        let cases = [
+            ("1 + 2 * 3", value!(7)),
+            (r#""foo" + "bar""#, value!("foobar")),
+            (r#"[1, { "a": 2 > 1 }]"#, value!([1, { "a": true }])),
+            ("if 1 == 1 { 2 } else { 3 }", value!(2)),
+            ("1\n2\n!true", value!(false)),
+            ("true || .foo", value!(true)),
+            (r#"{ "foo": [1, 2] }.foo[1]"#, value!(2)),
+        ];
+
+        for (source, want) in cases {
+            match lower(source) {
+                Lowered::Constant(got) => assert_eq!(got, want, "{}", source),
+                Lowered::Dynamic(_) => panic!("{} isn't folded", source),
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_dynamic_expressions() {
+        let cases = [
+            ".foo + 1",
+            "x = 1\nx",
+            "1 / 0 ?? 2",
+            "if .foo == true { 1 } else { 2 }",
+        ];
+
+        for source in cases {
+            assert!(lower(source).as_constant().is_none(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn resolves_like_the_ast_runtime() {
+        let target = value!({ "foo": "bar", "numbers": [1, 2, 3] });
+
+        let (result, target) = resolve(
+            indoc::indoc! {r#"
+                x = { "a": { "b": .foo } }
+                .copy = x.a.b
+                .sum = 0
+                for value in [1, 2, 3] {
+                    if value == 2 { continue }
+                    .sum = .sum + value
+                }
+                .quotient, .err = 1 / 0
+                .numbers[1]
+            "#},
+            target,
+        );
+
+        assert_eq!(result, Ok(value!(2)));
+        assert_eq!(
+            target,
+            value!({
+                "foo": "bar",
+                "numbers": [1, 2, 3],
+                "copy": "bar",
+                "sum": 4,
+                "quotient": null,
+                "err": "can't divide by zero",
+            })
+        );
+    }
+
+    #[test]
+    fn resolves_loop_control_like_the_ast_runtime() {
+        let (result, target) = resolve(
+            indoc::indoc! {r#"
+                sum = 0
+                i = 0
+                while true {
+                    i = i + 1
+                    if i == 2 { continue }
+                    if i > 3 {
+                        .last = i
+                        break
+                    }
+                    sum = sum + i
+                }
+                .sum = sum
+                i
+            "#},
+            Value::from(BTreeMap::new()),
+        );
+
+        assert_eq!(result, Ok(value!(4)));
+        assert_eq!(target, value!({ "last": 4, "sum": 4 }));
+    }
+
+    #[test]
+    fn resolves_aborts_like_the_ast_runtime() {
+        let (result, target) = resolve(
+            indoc::indoc! {r#"
+                .foo = 1
+                if .foo == 1 { abort "stop" }
+                .bar = 2
+            "#},
+            Value::from(BTreeMap::new()),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(target, value!({ "foo": 1 }));
+    }
+}
@@ -1,16 +1,26 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
+
 use diagnostic::{DiagnosticList, DiagnosticMessage, Severity, Span};
 use lookup::LookupBuf;
-use parser::ast::{self, Node, QueryTarget};
+use parser::ast::{self, Ident, Node, QueryTarget};
 
 use crate::{
     expression::{
-        assignment, function_call, literal, predicate, query, Abort, Array, Assignment, Block,
-        Container, Error, Expr, Expression, FunctionArgument, FunctionCall, Group, IfStatement,
-        Literal, Noop, Not, Object, Op, Predicate, Query, Target, Unary, Variable,
+        assignment, function_call, literal, loops, predicate, query, user_function, Abort, Array,
+        Assignment, Block, Container, Error, Expr, Expression, FunctionArgument, FunctionCall,
+        Group, IfStatement, Literal, Loop, LoopControl, Noop, Not, Object, Op, Predicate, Query,
+        Target, Unary, UserFunction, UserFunctionCall, Variable,
     },
+    import::{self, ImportRoot},
+    iteration_limit::IterationLimit,
     parser::ast::RootExpr,
     program::ProgramInfo,
     state::{ExternalEnv, LocalEnv},
+    type_info::TypeInfo,
     Function, Program,
 };
 
@@ -38,6 +48,42 @@ pub struct Compiler<'a> {
     /// back to `None`, if the parent expression of a fallible expression
     /// nullifies the fallibility of that expression.
     fallible_expression_error: Option<Box<dyn DiagnosticMessage>>,
+
+    /// The functions defined by the program, or by the programs it imports.
+    user_functions: HashMap<Ident, Arc<UserFunction>>,
+
+    /// Whether the compiler is compiling the body of a function definition.
+    ///
+    /// Function bodies only have access to their parameters, any access to
+    /// the event is rejected.
+    in_function: bool,
+
+    /// The number of loops surrounding the expression that is being compiled.
+    ///
+    /// Closures and function bodies start at zero, as they can't control the
+    /// iteration of a loop they're defined in.
+    loop_depth: usize,
+
+    /// Whether the expression that is being compiled is a statement of a loop
+    /// body, or of a block that is itself such a statement (such as the blocks
+    /// of an `if` statement in the loop body).
+    ///
+    /// Only these expressions can be a `break` or `continue`, as the blocks
+    /// around them are the only expressions that stop resolving when the loop
+    /// is signaled.
+    loop_statement: bool,
+
+    /// The (canonical) paths of the imported programs that are currently being
+    /// compiled, used to resolve nested imports and detect import cycles.
+    import_stack: Vec<PathBuf>,
+
+    /// The (canonical) paths of all programs imported so far. Importing the
+    /// same program twice is a no-op.
+    imported: HashSet<PathBuf>,
+
+    /// The inferred type definitions of the compiled expressions, if they're
+    /// collected.
+    type_info: Option<TypeInfo>,
 }
 
 impl<'a> Compiler<'a> {
@@ -47,7 +93,28 @@ impl<'a> Compiler<'a> {
         external: &mut ExternalEnv,
         local: LocalEnv,
     ) -> Result<(Program, DiagnosticList), DiagnosticList> {
-        let mut compiler = Self {
+        Self::new(fns, local).compile_program(ast, external).0
+    }
+
+    /// Compile the program, and collect the inferred type definitions of its
+    /// expressions.
+    ///
+    /// The type information is returned even if the program fails to compile,
+    /// covering the expressions that did compile.
+    pub fn compile_with_type_info(
+        fns: &'a [Box<dyn Function>],
+        ast: parser::Program,
+        external: &mut ExternalEnv,
+        local: LocalEnv,
+    ) -> (Result<(Program, DiagnosticList), DiagnosticList>, TypeInfo) {
+        let mut compiler = Self::new(fns, local);
+        compiler.type_info = Some(TypeInfo::default());
+
+        compiler.compile_program(ast, external)
+    }
+
+    fn new(fns: &'a [Box<dyn Function>], local: LocalEnv) -> Self {
+        Self {
             fns,
             diagnostics: vec![],
             fallible: false,
@@ -57,37 +124,57 @@ impl<'a> Compiler<'a> {
             external_assignments: vec![],
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
-        };
-        let expressions = compiler.compile_root_exprs(ast, external);
+            user_functions: HashMap::new(),
+            in_function: false,
+            loop_depth: 0,
+            loop_statement: false,
+            import_stack: vec![],
+            imported: HashSet::new(),
+            type_info: None,
+        }
+    }
+
+    fn compile_program(
+        mut self,
+        ast: parser::Program,
+        external: &mut ExternalEnv,
+    ) -> (Result<(Program, DiagnosticList), DiagnosticList>, TypeInfo) {
+        let expressions = self.compile_root_exprs(ast, external);
+        let type_info = self.type_info.take().unwrap_or_default();
 
         let (errors, warnings): (Vec<_>, Vec<_>) =
-            compiler.diagnostics.into_iter().partition(|diagnostic| {
+            self.diagnostics.into_iter().partition(|diagnostic| {
                 matches!(diagnostic.severity(), Severity::Bug | Severity::Error)
             });
 
         if !errors.is_empty() {
-            return Err(errors.into());
+            return (Err(errors.into()), type_info);
         }
 
         let info = ProgramInfo {
-            fallible: compiler.fallible,
-            abortable: compiler.abortable,
-            target_queries: compiler.external_queries,
-            target_assignments: compiler.external_assignments,
+            fallible: self.fallible,
+            abortable: self.abortable,
+            target_queries: self.external_queries,
+            target_assignments: self.external_assignments,
         };
 
-        let expressions = Block::new(expressions, compiler.local);
+        let expressions = Block::new(expressions, self.local);
 
-        Ok((Program { expressions, info }, warnings.into()))
+        (
+            Ok((Program { expressions, info }, warnings.into())),
+            type_info,
+        )
     }
 
     fn compile_exprs(
         &mut self,
         nodes: impl IntoIterator<Item = Node<ast::Expr>>,
+        loop_statements: bool,
         external: &mut ExternalEnv,
     ) -> Option<Vec<Expr>> {
         let mut exprs = vec![];
         for node in nodes {
+            self.loop_statement = loop_statements;
             let expr = self.compile_expr(node, external)?;
             let type_def = expr.type_def((&self.local, external));
             exprs.push(expr);
@@ -104,36 +191,59 @@ impl<'a> Compiler<'a> {
 
     fn compile_expr(&mut self, node: Node<ast::Expr>, external: &mut ExternalEnv) -> Option<Expr> {
         use ast::Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Unary,
-            Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Op, Query, Unary, Variable,
         };
 
         let span = node.span();
 
+        // Only blocks and `if` statements pass the statement position on, to
+        // the statements of their blocks.
+        let loop_statement = std::mem::take(&mut self.loop_statement);
+
         let expr = match node.into_inner() {
             Literal(node) => self.compile_literal(node, external),
-            Container(node) => self.compile_container(node, external).map(Into::into),
-            IfStatement(node) => self.compile_if_statement(node, external).map(Into::into),
+            Container(node) => {
+                self.loop_statement = loop_statement;
+                self.compile_container(node, external).map(Into::into)
+            }
+            IfStatement(node) => {
+                self.loop_statement = loop_statement;
+                self.compile_if_statement(node, external).map(Into::into)
+            }
             Op(node) => self.compile_op(node, external).map(Into::into),
             Assignment(node) => self.compile_assignment(node, external).map(Into::into),
             Query(node) => self.compile_query(node, external).map(Into::into),
+            FunctionCall(node) if self.is_user_function_call(&node) => self
+                .compile_user_function_call(node, external)
+                .map(Into::into),
             FunctionCall(node) => self.compile_function_call(node, external).map(Into::into),
             Variable(node) => self.compile_variable(node, external).map(Into::into),
             Unary(node) => self.compile_unary(node, external).map(Into::into),
             Abort(node) => self.compile_abort(node, external).map(Into::into),
+            Loop(node) => self.compile_loop(node, external).map(Into::into),
+            LoopControl(node) => self
+                .compile_loop_control(node, loop_statement)
+                .map(Into::into),
         }?;
 
+        let type_def = expr.type_def((&self.local, external));
+
         // If the previously compiled expression is fallible, _and_ we are
         // currently not tracking any existing fallible expression in the chain
         // of expressions, then this is the first expression within that chain
         // that can cause the entire chain to be fallible.
-        if expr.type_def((&self.local, external)).is_fallible()
-            && self.fallible_expression_error.is_none()
-        {
+        if type_def.is_fallible() && self.fallible_expression_error.is_none() {
             let error = crate::expression::Error::Fallible { span };
             self.fallible_expression_error = Some(Box::new(error) as _);
         }
 
+        if let Some(type_info) = self.type_info.as_mut() {
+            if self.import_stack.is_empty() {
+                type_info.insert(span, type_def);
+            }
+        }
+
         Some(expr)
     }
 
@@ -193,9 +303,14 @@ impl<'a> Compiler<'a> {
     ) -> Option<Container> {
         use ast::Container::{Array, Block, Group, Object};
 
+        let loop_statement = std::mem::take(&mut self.loop_statement);
+
         let variant = match node.into_inner() {
             Group(node) => self.compile_group(*node, external)?.into(),
-            Block(node) => self.compile_block(node, external)?.into(),
+            Block(node) => {
+                self.loop_statement = loop_statement;
+                self.compile_block(node, external)?.into()
+            }
             Array(node) => self.compile_array(node, external)?.into(),
             Object(node) => self.compile_object(node, external)?.into(),
         };
@@ -244,6 +359,10 @@ impl<'a> Compiler<'a> {
                         }
                     }
                 }
+                RootExpr::FunctionDefinition(node) => {
+                    self.compile_function_definition(node, external);
+                }
+                RootExpr::Import(node) => self.compile_import(node, external),
                 RootExpr::Error(err) => self.handle_parser_error(err),
             }
         }
@@ -269,17 +388,19 @@ impl<'a> Compiler<'a> {
         // scoped to the block, and must not be visible to the rest of the
         // program.
         let local_snapshot = self.local.clone();
+        let loop_statement = std::mem::take(&mut self.loop_statement);
 
         // We can now start compiling the expressions within the block, which
         // will use the existing local state of the compiler, as blocks have
         // access to any state of their parent expressions.
-        let exprs = match self.compile_exprs(node.into_inner().into_iter(), external) {
-            Some(exprs) => exprs,
-            None => {
-                self.local = local_snapshot.apply_child_scope(self.local.clone());
-                return None;
-            }
-        };
+        let exprs =
+            match self.compile_exprs(node.into_inner().into_iter(), loop_statement, external) {
+                Some(exprs) => exprs,
+                None => {
+                    self.local = local_snapshot.apply_child_scope(self.local.clone());
+                    return None;
+                }
+            };
 
         // Now that we've compiled the expressions, we pass them into the block,
         // and also a copy of the local state, which includes any state added by
@@ -300,7 +421,7 @@ impl<'a> Compiler<'a> {
         node: Node<ast::Array>,
         external: &mut ExternalEnv,
     ) -> Option<Array> {
-        let exprs = self.compile_exprs(node.into_inner().into_iter(), external)?;
+        let exprs = self.compile_exprs(node.into_inner().into_iter(), false, external)?;
 
         Some(Array::new(exprs))
     }
@@ -337,6 +458,8 @@ impl<'a> Compiler<'a> {
             alternative,
         } = node.into_inner();
 
+        let loop_statement = std::mem::take(&mut self.loop_statement);
+
         let predicate = self
             .compile_predicate(predicate, external)?
             .map_err(|err| self.diagnostics.push(Box::new(err)))
@@ -345,6 +468,7 @@ impl<'a> Compiler<'a> {
         let original_locals = self.local.clone();
         let original_external = external.target().clone();
 
+        self.loop_statement = loop_statement;
         let consequent = self.compile_block(consequent, external)?;
 
         match alternative {
@@ -355,6 +479,7 @@ impl<'a> Compiler<'a> {
                 self.local = original_locals;
                 external.update_target(original_external);
 
+                self.loop_statement = loop_statement;
                 let else_block = self.compile_block(block, external)?;
 
                 // assignments must be the result of either the if or else block, but not the original value
@@ -403,7 +528,7 @@ impl<'a> Compiler<'a> {
 
         let exprs = match predicate {
             One(node) => vec![self.compile_expr(*node, external)?],
-            Many(nodes) => self.compile_exprs(nodes, external)?,
+            Many(nodes) => self.compile_exprs(nodes, false, external)?,
         };
 
         Some(Predicate::new(
@@ -480,6 +605,7 @@ impl<'a> Compiler<'a> {
         };
         use value::Value;
 
+        let assignment_span = node.span();
         let assignment = node.into_inner();
 
         let node = match assignment {
@@ -563,6 +689,7 @@ impl<'a> Compiler<'a> {
         // potential external optimizations.
         for target in assignment.targets() {
             if let assignment::Target::External(path) = target {
+                self.check_event_access(assignment_span)?;
                 self.external_assignments.push(path);
             }
         }
@@ -585,6 +712,7 @@ impl<'a> Compiler<'a> {
         node: Node<ast::Query>,
         external: &mut ExternalEnv,
     ) -> Option<Query> {
+        let span = node.span();
         let ast::Query { target, path } = node.into_inner();
 
         if self
@@ -602,6 +730,7 @@ impl<'a> Compiler<'a> {
         // This data is exposed to the caller of the compiler, to allow any
         // potential external optimizations.
         if let Target::External = target {
+            self.check_event_access(span)?;
             self.external_queries.push(path.clone());
         }
 
@@ -633,6 +762,12 @@ impl<'a> Compiler<'a> {
                 let container = self.compile_container(Node::new(span, container), external)?;
                 Target::Container(container)
             }
+            FunctionCall(call) if self.is_user_function_call(&call) => {
+                let call = self.compile_user_function_call(Node::new(span, call), external)?;
+                let group = Group::new(call.into());
+
+                Target::Container(Container::new(group.into()))
+            }
             FunctionCall(call) => {
                 let call = self.compile_function_call(Node::new(span, call), external)?;
                 Target::FunctionCall(call)
@@ -713,7 +848,11 @@ impl<'a> Compiler<'a> {
                 None => None,
                 Some(block) => {
                     let span = block.span();
-                    match self.compile_block(block, external) {
+                    let loop_depth = std::mem::take(&mut self.loop_depth);
+                    let block = self.compile_block(block, external);
+                    self.loop_depth = loop_depth;
+
+                    match block {
                         Some(block) => Some(Node::new(span, block)),
                         None => return None,
                     }
@@ -759,6 +898,169 @@ impl<'a> Compiler<'a> {
         None
     }
 
+    fn is_user_function_call(&self, call: &ast::FunctionCall) -> bool {
+        self.user_functions.contains_key(call.ident.inner())
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_user_function_call(
+        &mut self,
+        node: Node<ast::FunctionCall>,
+        external: &mut ExternalEnv,
+    ) -> Option<UserFunctionCall> {
+        let call_span = node.span();
+        let ast::FunctionCall {
+            ident,
+            abort_on_error,
+            arguments,
+            closure,
+        } = node.into_inner();
+
+        let (ident_span, ident) = ident.take();
+        let function = self.user_functions.get(&ident)?.clone();
+
+        let arguments = arguments
+            .into_iter()
+            .map(|node| {
+                Some(Node::new(
+                    node.span(),
+                    self.compile_function_argument(node, external)?,
+                ))
+            })
+            .collect::<Option<_>>()?;
+
+        if abort_on_error {
+            self.fallible = true;
+        }
+
+        UserFunctionCall::new(
+            call_span,
+            ident_span,
+            function,
+            abort_on_error,
+            arguments,
+            closure.as_ref().map(Node::span),
+            (&self.local, external),
+        )
+        .map_err(|err| self.diagnostics.push(Box::new(err)))
+        .ok()
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_user_function_call(
+        &mut self,
+        node: Node<ast::FunctionCall>,
+        _: &mut ExternalEnv,
+    ) -> Option<Noop> {
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+        None
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_function_definition(
+        &mut self,
+        node: Node<ast::FunctionDefinition>,
+        external: &mut ExternalEnv,
+    ) -> Option<()> {
+        use crate::{type_def::Details, value::Kind};
+
+        let ast::FunctionDefinition {
+            ident,
+            parameters,
+            return_type,
+            block,
+        } = node.into_inner();
+
+        let (ident_span, ident) = ident.take();
+
+        let builtin = self.fns.iter().any(|f| f.identifier() == ident.as_ref());
+        if builtin || self.user_functions.contains_key(&ident) {
+            self.diagnostics
+                .push(Box::new(user_function::Error::DuplicateDefinition {
+                    ident_span,
+                    ident,
+                    builtin,
+                }));
+
+            return None;
+        }
+
+        let mut annotation_kind = |annotation: Option<Node<ast::TypeAnnotation>>| match annotation {
+            Some(annotation) => user_function::annotation_kind(annotation.into_inner().0)
+                .map_err(|err| self.diagnostics.push(Box::new(err)))
+                .ok(),
+            None => Some(Kind::any()),
+        };
+
+        // The body of the function only has access to its parameters.
+        let mut local = LocalEnv::default();
+        let mut function_parameters = Vec::with_capacity(parameters.len());
+
+        for node in parameters {
+            let ast::FunctionParameter {
+                ident,
+                type_annotation,
+            } = node.into_inner();
+
+            let kind = annotation_kind(type_annotation)?;
+            let details = Details {
+                type_def: kind.clone().into(),
+                value: None,
+            };
+
+            local.insert_variable(ident.inner().clone(), details);
+            function_parameters.push((ident.into_inner(), kind));
+        }
+
+        let return_kind = match return_type {
+            Some(return_type) => Some(annotation_kind(Some(return_type))?),
+            None => None,
+        };
+
+        // The function body is compiled in isolation of the rest of the
+        // program, after which the state of the compiler is restored.
+        let local = std::mem::replace(&mut self.local, local);
+        let fallible_expression_error = self.fallible_expression_error.take();
+        let in_function = std::mem::replace(&mut self.in_function, true);
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+
+        let block_span = block.span();
+        let block = self.compile_block(block, external);
+
+        self.local = local;
+        self.fallible_expression_error = fallible_expression_error;
+        self.in_function = in_function;
+        self.loop_depth = loop_depth;
+
+        // The function is only visible to the expressions following its
+        // definition, which excludes recursive calls from within its body.
+        let function = UserFunction::new(
+            ident.clone(),
+            function_parameters,
+            Node::new(block_span, block?),
+            return_kind,
+            external,
+        )
+        .map_err(|err| self.diagnostics.push(Box::new(err)))
+        .ok()?;
+
+        self.user_functions.insert(ident, Arc::new(function));
+
+        Some(())
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_function_definition(
+        &mut self,
+        node: Node<ast::FunctionDefinition>,
+        _: &mut ExternalEnv,
+    ) -> Option<()> {
+        let _ = &self.in_function;
+
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+        None
+    }
+
     fn compile_variable(
         &mut self,
         node: Node<ast::Ident>,
@@ -840,6 +1142,351 @@ impl<'a> Compiler<'a> {
         self.handle_missing_feature_error(node.span(), "expr-abort")
     }
 
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop(&mut self, node: Node<ast::Loop>, external: &mut ExternalEnv) -> Option<Loop> {
+        use crate::value::Kind;
+
+        let limit = external
+            .get_external_context::<IterationLimit>()
+            .copied()
+            .unwrap_or_default()
+            .0;
+
+        // Any loop can exceed the iteration limit, which aborts the program.
+        self.abortable = true;
+
+        let (span, node) = node.take();
+
+        match node {
+            ast::Loop::For(ast::ForLoop {
+                variables,
+                expr,
+                block,
+            }) => {
+                let expr_span = expr.span();
+                let expr = self.compile_expr(*expr, external)?;
+                let kind: Kind = expr.type_def((&self.local, external)).into();
+
+                let (key_kind, value_kind) = loops::element_kinds(&kind, expr_span)
+                    .map_err(|err| self.diagnostics.push(Box::new(err)))
+                    .ok()?;
+
+                // The last variable is bound to the value of each element, the
+                // optional first one to its key or index.
+                let mut variables = variables
+                    .into_iter()
+                    .map(|ident| Some(ident.into_inner()).filter(|ident| !ident.is_empty()))
+                    .collect::<Vec<_>>();
+                let value = variables.pop().flatten();
+                let key = variables.pop().flatten();
+
+                let bindings = [(key.clone(), key_kind), (value.clone(), value_kind)]
+                    .into_iter()
+                    .filter_map(|(ident, kind)| Some((ident?, kind)))
+                    .collect::<Vec<_>>();
+
+                let block = self.compile_loop_body(&bindings, external, |compiler, external| {
+                    compiler.loop_statement = true;
+                    compiler.compile_block(block.clone(), external)
+                })?;
+
+                Some(Loop::for_loop(span, key, value, expr, &kind, block, limit))
+            }
+            ast::Loop::While(ast::WhileLoop { predicate, block }) => {
+                let (predicate, block) =
+                    self.compile_loop_body(&[], external, |compiler, external| {
+                        let predicate = compiler
+                            .compile_predicate(predicate.clone(), external)?
+                            .map_err(|err| compiler.diagnostics.push(Box::new(err)))
+                            .ok()?;
+
+                        compiler.loop_statement = true;
+                        let block = compiler.compile_block(block.clone(), external)?;
+
+                        Some((predicate, block))
+                    })?;
+
+                Some(Loop::while_loop(span, predicate, block, limit))
+            }
+        }
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    fn compile_loop(&mut self, node: Node<ast::Loop>, _: &mut ExternalEnv) -> Option<Expr> {
+        let _ = self.loop_depth;
+
+        self.handle_missing_feature_error(node.span(), "expr-loop")
+    }
+
+    /// Compile the body (and predicate) of a loop, with the loop variables
+    /// bound to the given kinds.
+    ///
+    /// The body can run any number of times, so the state at the start of an
+    /// iteration is the union of the state before the loop, and the state at
+    /// the end of any previous iteration. That state is computed first, by
+    /// type-checking the body until the state stops changing, after which the
+    /// body is compiled once, against the final state.
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop_body<T>(
+        &mut self,
+        variables: &[(Ident, crate::value::Kind)],
+        external: &mut ExternalEnv,
+        mut compile: impl FnMut(&mut Self, &mut ExternalEnv) -> Option<T>,
+    ) -> Option<T> {
+        // Bounds the compile time of (nested) loops, whose state keeps changing
+        // between iterations.
+        const MAX_PASSES: usize = 4;
+
+        let mut local = self.local.clone();
+        let mut target = external.target().clone();
+
+        for _ in 0..MAX_PASSES {
+            self.check_loop_iteration(variables, external, &local, &mut compile);
+
+            let next_local = local.clone().merge(self.local.clone());
+            let next_target = target.clone().merge(external.target().clone());
+            let stable = next_local == local && next_target == target;
+
+            local = next_local;
+            target = next_target;
+            self.local = local.clone();
+            external.update_target(target.clone());
+
+            if stable {
+                break;
+            }
+        }
+
+        let compiled = self.compile_loop_iteration(variables, external, &local, &mut compile);
+
+        // The loop might not run at all.
+        self.local = local.merge(self.local.clone());
+        external.update_target(target.merge(external.target().clone()));
+
+        compiled
+    }
+
+    /// Type-check a single iteration of a loop, only keeping the state at the
+    /// end of the iteration.
+    ///
+    /// Any diagnostics, and any other results of compiling the iteration, are
+    /// discarded, so they're only reported once the loop is compiled.
+    #[cfg(feature = "expr-loop")]
+    fn check_loop_iteration<T>(
+        &mut self,
+        variables: &[(Ident, crate::value::Kind)],
+        external: &mut ExternalEnv,
+        local: &LocalEnv,
+        compile: &mut impl FnMut(&mut Self, &mut ExternalEnv) -> Option<T>,
+    ) {
+        let diagnostics = self.diagnostics.len();
+        let external_queries = self.external_queries.len();
+        let external_assignments = self.external_assignments.len();
+        let skip_missing_query_target = self.skip_missing_query_target.len();
+        let fallible_expression_error = self.fallible_expression_error.take();
+        let type_info = self.type_info.take();
+        let (fallible, abortable) = (self.fallible, self.abortable);
+
+        let _ = self.compile_loop_iteration(variables, external, local, compile);
+
+        self.diagnostics.truncate(diagnostics);
+        self.external_queries.truncate(external_queries);
+        self.external_assignments.truncate(external_assignments);
+        self.skip_missing_query_target
+            .truncate(skip_missing_query_target);
+        self.fallible_expression_error = fallible_expression_error;
+        self.type_info = type_info;
+        self.fallible = fallible;
+        self.abortable = abortable;
+    }
+
+    /// Compile a single iteration of a loop, with the loop variables bound to
+    /// the given kinds.
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop_iteration<T>(
+        &mut self,
+        variables: &[(Ident, crate::value::Kind)],
+        external: &mut ExternalEnv,
+        local: &LocalEnv,
+        compile: &mut impl FnMut(&mut Self, &mut ExternalEnv) -> Option<T>,
+    ) -> Option<T> {
+        use crate::type_def::Details;
+
+        for (ident, kind) in variables {
+            let details = Details {
+                type_def: kind.clone().into(),
+                value: None,
+            };
+
+            self.local.insert_variable(ident.clone(), details);
+        }
+
+        self.loop_depth += 1;
+        let compiled = compile(self, external);
+        self.loop_depth -= 1;
+
+        // The loop variables are scoped to the loop, any variable they shadow
+        // is restored.
+        for (ident, _) in variables {
+            match local.variable(ident) {
+                Some(details) => self.local.insert_variable(ident.clone(), details.clone()),
+                None => {
+                    self.local.remove_variable(ident);
+                }
+            }
+        }
+
+        compiled
+    }
+
+    #[cfg(feature = "expr-loop")]
+    fn compile_loop_control(
+        &mut self,
+        node: Node<ast::LoopControl>,
+        loop_statement: bool,
+    ) -> Option<LoopControl> {
+        let (span, control) = node.take();
+
+        if self.loop_depth == 0 {
+            self.diagnostics
+                .push(Box::new(loops::Error::OutsideLoop { control, span }));
+
+            return None;
+        }
+
+        if !loop_statement {
+            self.diagnostics
+                .push(Box::new(loops::Error::NotStatement { control, span }));
+
+            return None;
+        }
+
+        Some(LoopControl::new(span, control))
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    fn compile_loop_control(&mut self, node: Node<ast::LoopControl>, _: bool) -> Option<Expr> {
+        self.handle_missing_feature_error(node.span(), "expr-loop")
+    }
+
+    /// Compile the function definitions of the program at the path of the
+    /// import.
+    ///
+    /// Any errors of the imported program are reported at the location of the
+    /// import.
+    fn compile_import(&mut self, node: Node<ast::Import>, external: &mut ExternalEnv) {
+        let (span, ast::Import(path)) = node.take();
+
+        // Nested imports are resolved relative to the program importing them.
+        let base = match self.import_stack.last() {
+            Some(path) => path.parent().map(Into::into),
+            None => external
+                .get_external_context::<ImportRoot>()
+                .map(|root| root.0.clone()),
+        };
+
+        let path = match base {
+            Some(base) => base.join(path.inner()),
+            None => PathBuf::from(path.inner()),
+        };
+
+        let display = path.display().to_string();
+        let read_error = |error: std::io::Error| import::Error::Read {
+            span,
+            path: display.clone(),
+            error: error.to_string(),
+        };
+
+        let (path, source) = match path
+            .canonicalize()
+            .and_then(|path| std::fs::read_to_string(&path).map(|source| (path, source)))
+        {
+            Ok(read) => read,
+            Err(error) => {
+                self.diagnostics.push(Box::new(read_error(error)));
+                return;
+            }
+        };
+
+        if self.import_stack.contains(&path) {
+            self.diagnostics.push(Box::new(import::Error::Cycle {
+                span,
+                path: display,
+            }));
+            return;
+        }
+
+        if !self.imported.insert(path.clone()) {
+            return;
+        }
+
+        let program = match parser::parse(&source) {
+            Ok(program) => program,
+            Err(error) => {
+                self.diagnostics.push(Box::new(import::Error::Invalid {
+                    span,
+                    errors: vec![import::format_diagnostic(&display, &source, &error)],
+                    path: display,
+                }));
+                return;
+            }
+        };
+
+        // Diagnostics of the imported program point into its own source, so
+        // they're collected separately.
+        let diagnostics = std::mem::take(&mut self.diagnostics);
+        self.import_stack.push(path);
+
+        for root_expr in program {
+            match root_expr.into_inner() {
+                RootExpr::FunctionDefinition(node) => {
+                    self.compile_function_definition(node, external);
+                }
+                RootExpr::Import(node) => self.compile_import(node, external),
+                RootExpr::Expr(node) => {
+                    self.diagnostics
+                        .push(Box::new(import::Error::UnexpectedExpression {
+                            span: node.span(),
+                        }));
+                }
+                RootExpr::Error(err) => self.handle_parser_error(err),
+            }
+        }
+
+        self.import_stack.pop();
+
+        let errors = std::mem::replace(&mut self.diagnostics, diagnostics)
+            .into_iter()
+            .filter(|diagnostic| matches!(diagnostic.severity(), Severity::Bug | Severity::Error))
+            .map(|diagnostic| import::format_diagnostic(&display, &source, diagnostic.as_ref()))
+            .collect::<Vec<_>>();
+
+        if !errors.is_empty() {
+            self.diagnostics.push(Box::new(import::Error::Invalid {
+                span,
+                path: display,
+                errors,
+            }));
+        }
+    }
+
+    /// Reject access to the event from within the body of a function
+    /// definition.
+    fn check_event_access(&mut self, span: Span) -> Option<()> {
+        if !self.in_function {
+            return Some(());
+        }
+
+        #[cfg(feature = "expr-function_call")]
+        self.diagnostics
+            .push(Box::new(user_function::Error::EventAccess { span }));
+
+        #[cfg(not(feature = "expr-function_call"))]
+        let _ = span;
+
+        None
+    }
+
     fn handle_parser_error(&mut self, error: parser::Error) {
         self.diagnostics.push(Box::new(error));
     }
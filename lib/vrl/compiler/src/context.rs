@@ -1,11 +1,23 @@
 use vector_common::TimeZone;
 
-use crate::{state::Runtime, Target};
+#[cfg(feature = "expr-loop")]
+use parser::ast::LoopControl;
+
+use crate::{state::Runtime, Target, Trace};
 
 pub struct Context<'a> {
     target: &'a mut dyn Target,
     state: &'a mut Runtime,
     timezone: &'a TimeZone,
+    trace: Option<&'a mut Trace>,
+
+    /// The `break` or `continue` that ended the current iteration of the
+    /// innermost loop, if any.
+    ///
+    /// Blocks stop resolving their expressions once this is set, until the
+    /// loop takes it.
+    #[cfg(feature = "expr-loop")]
+    loop_control: Option<LoopControl>,
 }
 
 impl<'a> Context<'a> {
@@ -15,9 +27,19 @@ impl<'a> Context<'a> {
             target,
             state,
             timezone,
+            trace: None,
+            #[cfg(feature = "expr-loop")]
+            loop_control: None,
         }
     }
 
+    /// Record the expressions evaluated in this context into the [`Trace`].
+    #[must_use]
+    pub fn with_trace(mut self, trace: &'a mut Trace) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
     /// Get a reference to the [`Target`].
     #[must_use]
     pub fn target(&self) -> &dyn Target {
@@ -45,4 +67,39 @@ impl<'a> Context<'a> {
     pub fn timezone(&self) -> &TimeZone {
         self.timezone
     }
+
+    pub(crate) fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Get the trace, if any, along with the target to record changes of.
+    pub(crate) fn trace_parts(&mut self) -> Option<(&mut Trace, &dyn Target)> {
+        let target = &*self.target;
+        self.trace.as_deref_mut().map(|trace| (trace, target))
+    }
+
+    /// Signal the innermost loop to stop its current iteration.
+    #[cfg(feature = "expr-loop")]
+    pub(crate) fn set_loop_control(&mut self, control: LoopControl) {
+        self.loop_control = Some(control);
+    }
+
+    /// Take the signal to the innermost loop, if any.
+    #[cfg(feature = "expr-loop")]
+    pub(crate) fn take_loop_control(&mut self) -> Option<LoopControl> {
+        self.loop_control.take()
+    }
+
+    /// Whether the current iteration of a loop has ended, in which case the
+    /// remaining expressions of a block must not be resolved.
+    #[cfg(feature = "expr-loop")]
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.loop_control.is_some()
+    }
+
+    #[cfg(not(feature = "expr-loop"))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn is_interrupted(&self) -> bool {
+        false
+    }
 }
@@ -5,6 +5,7 @@ use dyn_clone::{clone_trait_object, DynClone};
 use value::Value;
 
 use crate::{
+    compiled::{Lower, Lowered},
     state::{ExternalEnv, LocalEnv},
     Context, Span, TypeDef,
 };
@@ -35,10 +36,14 @@ pub(crate) mod container;
 pub(crate) mod function_call;
 #[cfg(feature = "expr-literal")]
 pub(crate) mod literal;
+#[cfg(feature = "expr-loop")]
+pub(crate) mod loops;
 #[cfg(feature = "expr-if_statement")]
 pub(crate) mod predicate;
 #[cfg(feature = "expr-query")]
 pub mod query;
+#[cfg(feature = "expr-function_call")]
+pub(crate) mod user_function;
 
 pub use core::{ExpressionError, Resolved};
 
@@ -57,6 +62,8 @@ pub use group::Group;
 pub use if_statement::IfStatement;
 #[cfg(feature = "expr-literal")]
 pub use literal::Literal;
+#[cfg(feature = "expr-loop")]
+pub use loops::{Loop, LoopControl};
 pub use noop::Noop;
 #[cfg(feature = "expr-unary")]
 pub use not::Not;
@@ -69,6 +76,8 @@ pub use predicate::Predicate;
 pub use query::{Query, Target};
 #[cfg(feature = "expr-unary")]
 pub use unary::Unary;
+#[cfg(feature = "expr-function_call")]
+pub use user_function::{UserFunction, UserFunctionCall};
 pub use variable::Variable;
 
 pub trait Expression: Send + Sync + fmt::Debug + DynClone {
@@ -128,20 +137,26 @@ pub enum Expr {
     Query(Query),
     #[cfg(feature = "expr-function_call")]
     FunctionCall(FunctionCall),
+    #[cfg(feature = "expr-function_call")]
+    UserFunctionCall(UserFunctionCall),
     Variable(Variable),
     Noop(Noop),
     #[cfg(feature = "expr-unary")]
     Unary(Unary),
     #[cfg(feature = "expr-abort")]
     Abort(Abort),
+    #[cfg(feature = "expr-loop")]
+    Loop(Loop),
+    #[cfg(feature = "expr-loop")]
+    LoopControl(LoopControl),
 }
 
 impl Expr {
     pub fn as_str(&self) -> &str {
         use container::Variant::{Array, Block, Group, Object};
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Noop, Op, Query, Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -163,12 +178,18 @@ impl Expr {
             Query(..) => "query",
             #[cfg(feature = "expr-function_call")]
             FunctionCall(..) => "function call",
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(..) => "function call",
             Variable(..) => "variable call",
             Noop(..) => "noop",
             #[cfg(feature = "expr-unary")]
             Unary(..) => "unary operation",
             #[cfg(feature = "expr-abort")]
             Abort(..) => "abort operation",
+            #[cfg(feature = "expr-loop")]
+            Loop(..) => "loop",
+            #[cfg(feature = "expr-loop")]
+            LoopControl(..) => "loop control",
         }
     }
 
@@ -227,13 +248,13 @@ impl Expr {
             },
         )
     }
-}
 
-impl Expression for Expr {
-    fn resolve(&self, ctx: &mut Context) -> Resolved {
+    /// Resolve the expression, without recording it into the trace of the
+    /// context.
+    pub(crate) fn resolve_expr(&self, ctx: &mut Context) -> Resolved {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Noop, Op, Query, Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -250,19 +271,35 @@ impl Expression for Expr {
             Query(v) => v.resolve(ctx),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.resolve(ctx),
             Variable(v) => v.resolve(ctx),
             Noop(v) => v.resolve(ctx),
             #[cfg(feature = "expr-unary")]
             Unary(v) => v.resolve(ctx),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => v.resolve(ctx),
         }
     }
+}
+
+impl Expression for Expr {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        if ctx.is_tracing() {
+            return crate::trace::resolve(self, ctx);
+        }
+
+        self.resolve_expr(ctx)
+    }
 
     fn as_value(&self) -> Option<Value> {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Noop, Op, Query, Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -279,19 +316,25 @@ impl Expression for Expr {
             Query(v) => Expression::as_value(v),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => Expression::as_value(v),
             Variable(v) => Expression::as_value(v),
             Noop(v) => Expression::as_value(v),
             #[cfg(feature = "expr-unary")]
             Unary(v) => Expression::as_value(v),
             #[cfg(feature = "expr-abort")]
             Abort(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => Expression::as_value(v),
         }
     }
 
     fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Noop, Op, Query, Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -308,12 +351,55 @@ impl Expression for Expr {
             Query(v) => v.type_def(state),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.type_def(state),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.type_def(state),
             Variable(v) => v.type_def(state),
             Noop(v) => v.type_def(state),
             #[cfg(feature = "expr-unary")]
             Unary(v) => v.type_def(state),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.type_def(state),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.type_def(state),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => v.type_def(state),
+        }
+    }
+}
+
+impl Lower for Expr {
+    fn lower(&self) -> Lowered {
+        use Expr::{
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Noop, Op, Query, Unary, UserFunctionCall, Variable,
+        };
+
+        match self {
+            #[cfg(feature = "expr-literal")]
+            Literal(v) => Lowered::Constant(v.to_value()),
+            Container(v) => v.lower(),
+            #[cfg(feature = "expr-if_statement")]
+            IfStatement(v) => v.lower(),
+            #[cfg(feature = "expr-op")]
+            Op(v) => v.lower(),
+            #[cfg(feature = "expr-assignment")]
+            Assignment(v) => v.lower(),
+            #[cfg(feature = "expr-query")]
+            Query(v) => v.lower(),
+            #[cfg(feature = "expr-function_call")]
+            FunctionCall(v) => crate::compiled::fallback(v),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.lower(),
+            Variable(v) => v.lower(),
+            Noop(_) => Lowered::Constant(Value::Null),
+            #[cfg(feature = "expr-unary")]
+            Unary(v) => v.lower(),
+            #[cfg(feature = "expr-abort")]
+            Abort(v) => v.lower(),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.lower(),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => crate::compiled::fallback(v),
         }
     }
 }
@@ -321,8 +407,8 @@ impl Expression for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Noop, Op, Query, Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -339,12 +425,18 @@ impl fmt::Display for Expr {
             Query(v) => v.fmt(f),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.fmt(f),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.fmt(f),
             Variable(v) => v.fmt(f),
             Noop(v) => v.fmt(f),
             #[cfg(feature = "expr-unary")]
             Unary(v) => v.fmt(f),
             #[cfg(feature = "expr-abort")]
             Abort(v) => v.fmt(f),
+            #[cfg(feature = "expr-loop")]
+            Loop(v) => v.fmt(f),
+            #[cfg(feature = "expr-loop")]
+            LoopControl(v) => v.fmt(f),
         }
     }
 }
@@ -399,6 +491,13 @@ impl From<FunctionCall> for Expr {
     }
 }
 
+#[cfg(feature = "expr-function_call")]
+impl From<UserFunctionCall> for Expr {
+    fn from(function_call: UserFunctionCall) -> Self {
+        Expr::UserFunctionCall(function_call)
+    }
+}
+
 impl From<Variable> for Expr {
     fn from(variable: Variable) -> Self {
         Expr::Variable(variable)
@@ -425,6 +524,20 @@ impl From<Abort> for Expr {
     }
 }
 
+#[cfg(feature = "expr-loop")]
+impl From<Loop> for Expr {
+    fn from(expr: Loop) -> Self {
+        Expr::Loop(expr)
+    }
+}
+
+#[cfg(feature = "expr-loop")]
+impl From<LoopControl> for Expr {
+    fn from(loop_control: LoopControl) -> Self {
+        Expr::LoopControl(loop_control)
+    }
+}
+
 #[cfg(feature = "expr-literal")]
 impl From<Value> for Expr {
     fn from(value: Value) -> Self {
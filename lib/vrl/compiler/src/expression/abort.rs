@@ -5,6 +5,7 @@ use parser::ast::Node;
 
 use super::Expr;
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{ExpressionError, Resolved},
     state::{ExternalEnv, LocalEnv},
     value::{Kind, VrlValueConvert},
@@ -73,6 +74,27 @@ impl Expression for Abort {
     }
 }
 
+impl Lower for Abort {
+    fn lower(&self) -> Lowered {
+        let span = self.span;
+        let message = self
+            .message
+            .as_ref()
+            .map(|expr| expr.lower().into_closure());
+
+        Lowered::dynamic(move |ctx| {
+            let message = message
+                .as_ref()
+                .map::<Result<_, ExpressionError>, _>(|message| {
+                    Ok(message(ctx)?.try_bytes_utf8_lossy()?.to_string())
+                })
+                .transpose()?;
+
+            Err(ExpressionError::Abort { span, message })
+        })
+    }
+}
+
 impl fmt::Display for Abort {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "abort")
@@ -3,6 +3,7 @@ use std::{collections::BTreeMap, fmt, ops::Deref};
 use value::Value;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
     Context, Expression, TypeDef,
@@ -65,6 +66,34 @@ impl Expression for Array {
     }
 }
 
+impl Lower for Array {
+    fn lower(&self) -> Lowered {
+        let items = self.inner.iter().map(Lower::lower).collect::<Vec<_>>();
+
+        if items.iter().all(|item| item.as_constant().is_some()) {
+            return Lowered::Constant(Value::Array(
+                items
+                    .into_iter()
+                    .filter_map(Lowered::into_constant)
+                    .collect(),
+            ));
+        }
+
+        let items = items
+            .into_iter()
+            .map(Lowered::into_closure)
+            .collect::<Vec<_>>();
+
+        Lowered::dynamic(move |ctx| {
+            items
+                .iter()
+                .map(|item| item(ctx))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+        })
+    }
+}
+
 impl fmt::Display for Array {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let exprs = self
@@ -5,6 +5,7 @@ use lookup::{Lookup, LookupBuf, Segment};
 use value::{Kind, Value};
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{assignment::ErrorVariant::InvalidParentPathSegment, Expr, Resolved},
     parser::{
         ast::{self, Ident},
@@ -310,6 +311,50 @@ impl Expression for Assignment {
     }
 }
 
+impl Lower for Assignment {
+    fn lower(&self) -> Lowered {
+        use Variant::{Infallible, Single};
+
+        match &self.variant {
+            Single { target, expr } => {
+                let target = target.clone();
+                let expr = expr.lower().into_closure();
+
+                Lowered::dynamic(move |ctx| {
+                    let value = expr(ctx)?;
+                    target.insert(value.clone(), ctx);
+                    Ok(value)
+                })
+            }
+            Infallible {
+                ok,
+                err,
+                expr,
+                default,
+            } => {
+                let ok = ok.clone();
+                let err = err.clone();
+                let expr = expr.lower().into_closure();
+                let default = default.clone();
+
+                Lowered::dynamic(move |ctx| match expr(ctx) {
+                    Ok(value) => {
+                        ok.insert(value.clone(), ctx);
+                        err.insert(Value::Null, ctx);
+                        Ok(value)
+                    }
+                    Err(error) => {
+                        ok.insert(default.clone(), ctx);
+                        let value = Value::from(error.to_string());
+                        err.insert(value.clone(), ctx);
+                        Ok(value)
+                    }
+                })
+            }
+        }
+    }
+}
+
 impl fmt::Display for Assignment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Variant::{Infallible, Single};
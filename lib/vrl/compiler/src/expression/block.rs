@@ -1,6 +1,9 @@
 use std::fmt;
 
+use value::Value;
+
 use crate::{
+    compiled::{self, Lower, Lowered},
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
     Context, Expression, TypeDef,
@@ -45,9 +48,14 @@ impl Expression for Block {
         // as it uses the same compiler as this AST runtime.
         let (last, other) = self.inner.split_last().expect("at least one expression");
 
-        other
-            .iter()
-            .try_for_each(|expr| expr.resolve(ctx).map(|_| ()))?;
+        for expr in other {
+            expr.resolve(ctx)?;
+
+            // A `break` or `continue` ends the block, up to the enclosing loop.
+            if ctx.is_interrupted() {
+                return Ok(Value::Null);
+            }
+        }
 
         last.resolve(ctx)
     }
@@ -83,6 +91,12 @@ impl Expression for Block {
     }
 }
 
+impl Lower for Block {
+    fn lower(&self) -> Lowered {
+        compiled::sequence(&self.inner)
+    }
+}
+
 impl fmt::Display for Block {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("{\n")?;
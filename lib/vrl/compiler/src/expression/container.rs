@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Array, Block, Group, Object, Resolved, Value},
     state::{ExternalEnv, LocalEnv},
     Context, Expression, TypeDef,
@@ -61,6 +62,19 @@ impl Expression for Container {
     }
 }
 
+impl Lower for Container {
+    fn lower(&self) -> Lowered {
+        use Variant::{Array, Block, Group, Object};
+
+        match &self.variant {
+            Group(v) => v.lower(),
+            Block(v) => v.lower(),
+            Array(v) => v.lower(),
+            Object(v) => v.lower(),
+        }
+    }
+}
+
 impl fmt::Display for Container {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Variant::{Array, Block, Group, Object};
@@ -587,10 +587,10 @@ impl FunctionCall {
 impl Expression for FunctionCall {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         self.expr.resolve(ctx).map_err(|err| match err {
+            // Closures can contain loops, which abort the program when they
+            // exceed their iteration limit.
             #[cfg(feature = "expr-abort")]
-            ExpressionError::Abort { .. } => {
-                panic!("abort errors must only be defined by `abort` statement")
-            }
+            err @ ExpressionError::Abort { .. } => err,
             ExpressionError::Error {
                 message,
                 mut labels,
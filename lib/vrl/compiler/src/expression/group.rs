@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
     Context, Expression, TypeDef,
@@ -29,6 +30,12 @@ impl Expression for Group {
     }
 }
 
+impl Lower for Group {
+    fn lower(&self) -> Lowered {
+        self.inner.lower()
+    }
+}
+
 impl fmt::Display for Group {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, r#"({})"#, self.inner)
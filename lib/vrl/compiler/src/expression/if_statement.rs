@@ -3,6 +3,7 @@ use std::fmt;
 use value::Value;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Block, Predicate, Resolved},
     state::{ExternalEnv, LocalEnv},
     value::VrlValueConvert,
@@ -39,6 +40,30 @@ impl Expression for IfStatement {
     }
 }
 
+impl Lower for IfStatement {
+    fn lower(&self) -> Lowered {
+        let alternative = || {
+            self.alternative
+                .as_ref()
+                .map_or(Lowered::Constant(Value::Null), Lower::lower)
+        };
+
+        let predicate = match self.predicate.lower() {
+            Lowered::Constant(Value::Boolean(true)) => return self.consequent.lower(),
+            Lowered::Constant(Value::Boolean(false)) => return alternative(),
+            predicate => predicate.into_closure(),
+        };
+
+        let consequent = self.consequent.lower().into_closure();
+        let alternative = alternative().into_closure();
+
+        Lowered::dynamic(move |ctx| match predicate(ctx)?.try_boolean()? {
+            true => consequent(ctx),
+            false => alternative(ctx),
+        })
+    }
+}
+
 impl fmt::Display for IfStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("if ")?;
@@ -0,0 +1,402 @@
+use std::fmt;
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+use parser::ast::{self, Ident};
+use value::Value;
+
+use crate::{
+    compiled::{Lower, Lowered},
+    expression::{Block, Expr, ExpressionError, Predicate, Resolved},
+    state::{ExternalEnv, LocalEnv},
+    value::{Collection, Kind, VrlValueConvert},
+    Context, Expression, Span, TypeDef,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loop {
+    span: Span,
+    variant: Variant,
+    block: Block,
+
+    /// The maximum number of iterations of the loop.
+    limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Variant {
+    For {
+        key: Option<Ident>,
+        value: Option<Ident>,
+        expr: Box<Expr>,
+
+        /// Whether the expression can resolve to a value that isn't an array
+        /// or object.
+        fallible: bool,
+    },
+    While {
+        predicate: Predicate,
+    },
+}
+
+impl Loop {
+    pub(crate) fn for_loop(
+        span: Span,
+        key: Option<Ident>,
+        value: Option<Ident>,
+        expr: Expr,
+        kind: &Kind,
+        block: Block,
+        limit: usize,
+    ) -> Self {
+        let fallible = !iterable_kind().is_superset(kind);
+
+        Self {
+            span,
+            variant: Variant::For {
+                key,
+                value,
+                expr: Box::new(expr),
+                fallible,
+            },
+            block,
+            limit,
+        }
+    }
+
+    pub(crate) fn while_loop(span: Span, predicate: Predicate, block: Block, limit: usize) -> Self {
+        Self {
+            span,
+            variant: Variant::While { predicate },
+            block,
+            limit,
+        }
+    }
+
+    /// A loop that exceeds its iteration limit aborts the program, instead of
+    /// failing with an error that could be handled by the program.
+    fn check_limit(&self, iteration: usize) -> Result<(), ExpressionError> {
+        if iteration < self.limit {
+            return Ok(());
+        }
+
+        Err(ExpressionError::Abort {
+            span: self.span,
+            message: Some(format!(
+                "loop exceeded the maximum of {} iterations",
+                self.limit
+            )),
+        })
+    }
+
+    /// Run the loop, using the provided functions to resolve the collection
+    /// (or predicate) of the loop, and its block.
+    fn run(
+        &self,
+        ctx: &mut Context,
+        head: impl Fn(&mut Context) -> Resolved,
+        block: impl Fn(&mut Context) -> Resolved,
+    ) -> Resolved {
+        match &self.variant {
+            Variant::For { key, value, .. } => {
+                // The collection is resolved once, changes made to it by the
+                // body of the loop don't affect the iteration.
+                let items: Box<dyn Iterator<Item = (Value, Value)>> = match head(ctx)? {
+                    Value::Array(array) => Box::new(
+                        array
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, value)| (index.into(), value)),
+                    ),
+                    Value::Object(object) => {
+                        Box::new(object.into_iter().map(|(key, value)| (key.into(), value)))
+                    }
+                    value => {
+                        return Err(crate::value::Error::Expected {
+                            got: value.kind(),
+                            expected: iterable_kind(),
+                        }
+                        .into())
+                    }
+                };
+
+                // Loop variables shadow any existing variable of the same
+                // name, which is restored once the loop ends.
+                let shadowed = [key, value]
+                    .into_iter()
+                    .flatten()
+                    .map(|ident| (ident, ctx.state().variable(ident).cloned()))
+                    .collect::<Vec<_>>();
+
+                let result = self.run_for(ctx, key.as_ref(), value.as_ref(), items, &block);
+
+                for (ident, value) in shadowed {
+                    match value {
+                        Some(value) => ctx.state_mut().insert_variable(ident.clone(), value),
+                        None => ctx.state_mut().remove_variable(ident),
+                    }
+                }
+
+                result?;
+            }
+            Variant::While { .. } => {
+                let mut iteration = 0;
+
+                while head(ctx)?.try_boolean()? {
+                    self.check_limit(iteration)?;
+                    iteration += 1;
+
+                    if !run_block(ctx, &block)? {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(Value::Null)
+    }
+
+    fn run_for(
+        &self,
+        ctx: &mut Context,
+        key: Option<&Ident>,
+        value: Option<&Ident>,
+        items: impl Iterator<Item = (Value, Value)>,
+        block: &impl Fn(&mut Context) -> Resolved,
+    ) -> Result<(), ExpressionError> {
+        for (iteration, (item_key, item_value)) in items.enumerate() {
+            self.check_limit(iteration)?;
+
+            if let Some(ident) = key {
+                ctx.state_mut().insert_variable(ident.clone(), item_key);
+            }
+
+            if let Some(ident) = value {
+                ctx.state_mut().insert_variable(ident.clone(), item_value);
+            }
+
+            if !run_block(ctx, block)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Run a single iteration of a loop, returning whether the loop should
+/// continue.
+fn run_block(
+    ctx: &mut Context,
+    block: &impl Fn(&mut Context) -> Resolved,
+) -> Result<bool, ExpressionError> {
+    block(ctx)?;
+
+    Ok(ctx.take_loop_control() != Some(ast::LoopControl::Break))
+}
+
+impl Expression for Loop {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.run(
+            ctx,
+            |ctx| match &self.variant {
+                Variant::For { expr, .. } => expr.resolve(ctx),
+                Variant::While { predicate } => predicate.resolve(ctx),
+            },
+            |ctx| self.block.resolve(ctx),
+        )
+    }
+
+    fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        let fallible = match &self.variant {
+            Variant::For { expr, fallible, .. } => *fallible || expr.type_def(state).is_fallible(),
+            Variant::While { predicate } => predicate.type_def(state).is_fallible(),
+        };
+
+        TypeDef::null().with_fallibility(fallible || self.block.type_def(state).is_fallible())
+    }
+}
+
+impl Lower for Loop {
+    fn lower(&self) -> Lowered {
+        let head = match &self.variant {
+            Variant::For { expr, .. } => expr.lower(),
+            Variant::While { predicate } => predicate.lower(),
+        }
+        .into_closure();
+        let block = self.block.lower().into_closure();
+        let this = self.clone();
+
+        Lowered::dynamic(move |ctx| this.run(ctx, &head, &block))
+    }
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.variant {
+            Variant::For {
+                key, value, expr, ..
+            } => {
+                f.write_str("for ")?;
+
+                if let Some(key) = key {
+                    write!(f, "{}, ", key)?;
+                }
+
+                match value {
+                    Some(value) => value.fmt(f)?,
+                    None => f.write_str("_")?,
+                }
+
+                write!(f, " in {} ", expr)?;
+            }
+            Variant::While { predicate } => write!(f, "while {} ", predicate)?,
+        }
+
+        self.block.fmt(f)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// A `break` or `continue` expression.
+///
+/// These are only compiled as statements of a loop body (or of the blocks of
+/// an `if` statement in it), so the blocks around them are the only
+/// expressions that have to stop resolving once they signal the loop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopControl {
+    variant: ast::LoopControl,
+    span: Span,
+}
+
+impl LoopControl {
+    pub(crate) fn new(span: Span, variant: ast::LoopControl) -> Self {
+        Self { variant, span }
+    }
+}
+
+impl Expression for LoopControl {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        ctx.set_loop_control(self.variant);
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        TypeDef::never().infallible()
+    }
+}
+
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.variant.fmt(f)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+/// The kinds of values a `for` loop can iterate over.
+fn iterable_kind() -> Kind {
+    Kind::array(Collection::any()).or_object(Collection::any())
+}
+
+/// Get the kinds of the key (or index) and the value of the elements of the
+/// collection a `for` loop iterates over.
+pub(crate) fn element_kinds(kind: &Kind, span: Span) -> Result<(Kind, Kind), Error> {
+    if !kind.contains_array() && !kind.contains_object() {
+        return Err(Error::NonIterable {
+            kind: kind.clone(),
+            span,
+        });
+    }
+
+    let mut key = Kind::never();
+    let mut value = Kind::never();
+
+    if let Some(array) = kind.as_array() {
+        key.add_integer();
+        value.merge_keep(array.reduced_kind(), false);
+    }
+
+    if let Some(object) = kind.as_object() {
+        key.add_bytes();
+        value.merge_keep(object.reduced_kind(), false);
+    }
+
+    Ok((key, value))
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("non-iterable loop expression")]
+    NonIterable { kind: Kind, span: Span },
+
+    #[error("{control} outside of loop")]
+    OutsideLoop {
+        control: ast::LoopControl,
+        span: Span,
+    },
+
+    #[error("{control} used as a value")]
+    NotStatement {
+        control: ast::LoopControl,
+        span: Span,
+    },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::{NonIterable, NotStatement, OutsideLoop};
+
+        match self {
+            NonIterable { .. } => 117,
+            OutsideLoop { .. } => 118,
+            NotStatement { .. } => 119,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::{NonIterable, NotStatement, OutsideLoop};
+
+        match self {
+            NonIterable { kind, span } => vec![
+                Label::primary("this expression must resolve to an array or object", span),
+                Label::context(format!("instead it resolves to {}", kind), span),
+            ],
+            OutsideLoop { control, span } => vec![Label::primary(
+                format!(
+                    "`{}` can only be used inside a `for` or `while` loop",
+                    control
+                ),
+                span,
+            )],
+            NotStatement { control, span } => vec![
+                Label::primary(format!("`{}` can't be used as a value", control), span),
+                Label::context(
+                    "it must be a statement of the loop body, or of an `if` statement in it",
+                    span,
+                ),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::{NonIterable, NotStatement, OutsideLoop};
+
+        match self {
+            NonIterable { .. } => vec![
+                Note::CoerceValue,
+                Note::SeeDocs(
+                    "loop expressions".to_owned(),
+                    Urls::expression_docs_url("#loop"),
+                ),
+            ],
+            OutsideLoop { .. } | NotStatement { .. } => vec![Note::SeeDocs(
+                "loop expressions".to_owned(),
+                Urls::expression_docs_url("#loop"),
+            )],
+        }
+    }
+}
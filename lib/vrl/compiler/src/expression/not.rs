@@ -3,6 +3,7 @@ use std::fmt;
 use diagnostic::{DiagnosticMessage, Label, Note, Urls};
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Expr, Resolved},
     parser::Node,
     state::{ExternalEnv, LocalEnv},
@@ -48,6 +49,20 @@ impl Expression for Not {
     }
 }
 
+impl Lower for Not {
+    fn lower(&self) -> Lowered {
+        match self.inner.lower() {
+            Lowered::Constant(value) => match value.try_boolean() {
+                Ok(boolean) => Lowered::Constant((!boolean).into()),
+                Err(_) => crate::compiled::fallback(self),
+            },
+            Lowered::Dynamic(inner) => {
+                Lowered::dynamic(move |ctx| Ok((!inner(ctx)?.try_boolean()?).into()))
+            }
+        }
+    }
+}
+
 impl fmt::Display for Not {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, r#"!{}"#, self.inner)
@@ -3,6 +3,7 @@ use std::{collections::BTreeMap, fmt, ops::Deref};
 use value::Value;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Expr, Resolved},
     state::{ExternalEnv, LocalEnv},
     Context, Expression, TypeDef,
@@ -65,6 +66,41 @@ impl Expression for Object {
     }
 }
 
+impl Lower for Object {
+    fn lower(&self) -> Lowered {
+        let fields = self
+            .inner
+            .iter()
+            .map(|(key, expr)| (key.clone(), expr.lower()))
+            .collect::<Vec<_>>();
+
+        if fields
+            .iter()
+            .all(|(_, field)| field.as_constant().is_some())
+        {
+            return Lowered::Constant(Value::Object(
+                fields
+                    .into_iter()
+                    .filter_map(|(key, field)| field.into_constant().map(|value| (key, value)))
+                    .collect(),
+            ));
+        }
+
+        let fields = fields
+            .into_iter()
+            .map(|(key, field)| (key, field.into_closure()))
+            .collect::<Vec<_>>();
+
+        Lowered::dynamic(move |ctx| {
+            fields
+                .iter()
+                .map(|(key, field)| field(ctx).map(|value| (key.clone(), value)))
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map(Value::Object)
+        })
+    }
+}
+
 impl fmt::Display for Object {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let exprs = self
@@ -4,6 +4,7 @@ use diagnostic::{DiagnosticMessage, Label, Note, Span, Urls};
 use value::Value;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{self, Expr, Resolved},
     parser::{ast, Node},
     state::{ExternalEnv, LocalEnv},
@@ -75,15 +76,40 @@ impl Op {
             opcode,
         })
     }
+
+    /// Apply the operation to both resolved operands.
+    ///
+    /// This excludes the `??`, `||` and `&&` operations, which only resolve the
+    /// right-hand side operand when needed.
+    fn apply(opcode: ast::Opcode, lhs: Value, rhs: Value) -> Resolved {
+        use ast::Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Rem, Sub};
+
+        match opcode {
+            Mul => lhs.try_mul(rhs),
+            Div => lhs.try_div(rhs),
+            Add => lhs.try_add(rhs),
+            Sub => lhs.try_sub(rhs),
+            Rem => lhs.try_rem(rhs),
+            Eq => Ok(lhs.eq_lossy(&rhs).into()),
+            Ne => Ok((!lhs.eq_lossy(&rhs)).into()),
+            Gt => lhs.try_gt(rhs),
+            Ge => lhs.try_ge(rhs),
+            Lt => lhs.try_lt(rhs),
+            Le => lhs.try_le(rhs),
+            Merge => lhs.try_merge(rhs),
+            And | Or | Err => unreachable!(),
+        }
+        .map_err(Into::into)
+    }
 }
 
 impl Expression for Op {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        use ast::Opcode::{Add, And, Div, Eq, Err, Ge, Gt, Le, Lt, Merge, Mul, Ne, Or, Rem, Sub};
+        use ast::Opcode::{And, Or};
         use value::Value::{Boolean, Null};
 
         match self.opcode {
-            Err => return self.lhs.resolve(ctx).or_else(|_| self.rhs.resolve(ctx)),
+            ast::Opcode::Err => return self.lhs.resolve(ctx).or_else(|_| self.rhs.resolve(ctx)),
             Or => {
                 return self
                     .lhs
@@ -103,22 +129,7 @@ impl Expression for Op {
         let lhs = self.lhs.resolve(ctx)?;
         let rhs = self.rhs.resolve(ctx)?;
 
-        match self.opcode {
-            Mul => lhs.try_mul(rhs),
-            Div => lhs.try_div(rhs),
-            Add => lhs.try_add(rhs),
-            Sub => lhs.try_sub(rhs),
-            Rem => lhs.try_rem(rhs),
-            Eq => Ok(lhs.eq_lossy(&rhs).into()),
-            Ne => Ok((!lhs.eq_lossy(&rhs)).into()),
-            Gt => lhs.try_gt(rhs),
-            Ge => lhs.try_ge(rhs),
-            Lt => lhs.try_lt(rhs),
-            Le => lhs.try_le(rhs),
-            Merge => lhs.try_merge(rhs),
-            And | Or | Err => unreachable!(),
-        }
-        .map_err(Into::into)
+        Self::apply(self.opcode, lhs, rhs)
     }
 
     fn type_def(&self, state: (&LocalEnv, &ExternalEnv)) -> TypeDef {
@@ -273,6 +284,79 @@ impl Expression for Op {
     }
 }
 
+impl Lower for Op {
+    fn lower(&self) -> Lowered {
+        use ast::Opcode::{And, Eq, Ne, Or};
+        use value::Value::{Boolean, Null};
+
+        let opcode = self.opcode;
+        let lhs = self.lhs.lower();
+        let rhs = self.rhs.lower();
+
+        match (opcode, lhs, rhs) {
+            // A constant never fails, so the right-hand side is never used.
+            (ast::Opcode::Err, Lowered::Constant(lhs), _) => Lowered::Constant(lhs),
+            (ast::Opcode::Err, Lowered::Dynamic(lhs), rhs) => {
+                let rhs = rhs.into_closure();
+
+                Lowered::dynamic(move |ctx| lhs(ctx).or_else(|_| rhs(ctx)))
+            }
+
+            (Or, Lowered::Constant(lhs), _) if !matches!(lhs, Null | Boolean(false)) => {
+                Lowered::Constant(lhs)
+            }
+            (Or, lhs, rhs) => {
+                let lhs = lhs.into_closure();
+                let rhs = rhs.into_closure();
+
+                Lowered::dynamic(move |ctx| lhs(ctx)?.try_or(|| rhs(ctx)).map_err(Into::into))
+            }
+
+            (And, Lowered::Constant(Null | Boolean(false)), _) => Lowered::Constant(false.into()),
+            (And, lhs, rhs) => {
+                let lhs = lhs.into_closure();
+                let rhs = rhs.into_closure();
+
+                Lowered::dynamic(move |ctx| match lhs(ctx)? {
+                    Null | Boolean(false) => Ok(false.into()),
+                    value => value.try_and(rhs(ctx)?).map_err(Into::into),
+                })
+            }
+
+            // Operations on constants are folded, unless they fail, in which
+            // case the error is raised at runtime.
+            (opcode, Lowered::Constant(lhs), Lowered::Constant(rhs)) => {
+                match Self::apply(opcode, lhs.clone(), rhs.clone()) {
+                    Ok(value) => Lowered::Constant(value),
+                    Err(_) => {
+                        Lowered::dynamic(move |_| Self::apply(opcode, lhs.clone(), rhs.clone()))
+                    }
+                }
+            }
+
+            // Comparisons against a constant don't need to clone the constant.
+            (Eq | Ne, Lowered::Dynamic(lhs), Lowered::Constant(rhs)) => {
+                Lowered::dynamic(move |ctx| Ok((lhs(ctx)?.eq_lossy(&rhs) == (opcode == Eq)).into()))
+            }
+            (Eq | Ne, Lowered::Constant(lhs), Lowered::Dynamic(rhs)) => {
+                Lowered::dynamic(move |ctx| Ok((lhs.eq_lossy(&rhs(ctx)?) == (opcode == Eq)).into()))
+            }
+
+            (opcode, lhs, rhs) => {
+                let lhs = lhs.into_closure();
+                let rhs = rhs.into_closure();
+
+                Lowered::dynamic(move |ctx| {
+                    let lhs = lhs(ctx)?;
+                    let rhs = rhs(ctx)?;
+
+                    Self::apply(opcode, lhs, rhs)
+                })
+            }
+        }
+    }
+}
+
 impl fmt::Display for Op {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {} {}", self.lhs, self.opcode, self.rhs)
@@ -3,6 +3,7 @@ use std::fmt;
 use diagnostic::{DiagnosticMessage, Label, Note, Urls};
 
 use crate::{
+    compiled::{self, Lower, Lowered},
     expression::{Expr, Resolved},
     parser::Node,
     state::{ExternalEnv, LocalEnv},
@@ -81,6 +82,12 @@ impl Expression for Predicate {
     }
 }
 
+impl Lower for Predicate {
+    fn lower(&self) -> Lowered {
+        compiled::sequence(&self.inner)
+    }
+}
+
 impl fmt::Display for Predicate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.inner.len() > 1 {
@@ -4,6 +4,7 @@ use lookup::LookupBuf;
 use value::{kind::remove, Kind, Value};
 
 use crate::{
+    compiled::{self, Lower, Lowered},
     expression::{Container, Resolved, Variable},
     parser::ast::Ident,
     state::{ExternalEnv, LocalEnv},
@@ -132,6 +133,57 @@ impl Expression for Query {
     }
 }
 
+impl Lower for Query {
+    fn lower(&self) -> Lowered {
+        use Target::{Container, External, FunctionCall, Internal};
+
+        let path = self.path.clone();
+
+        let target = match &self.target {
+            External => {
+                return Lowered::dynamic(move |ctx| {
+                    Ok(ctx
+                        .target()
+                        .target_get(&path)
+                        .ok()
+                        .flatten()
+                        .cloned()
+                        .unwrap_or(Value::Null))
+                })
+            }
+
+            // Only the queried value is cloned, instead of the entire
+            // variable.
+            Internal(variable) => {
+                let ident = variable.ident().clone();
+
+                return Lowered::dynamic(move |ctx| {
+                    Ok(ctx
+                        .state()
+                        .variable(&ident)
+                        .and_then(|value| value.get_by_path(&path))
+                        .cloned()
+                        .unwrap_or(Value::Null))
+                });
+            }
+            FunctionCall(call) => compiled::fallback(call),
+            Container(container) => container.lower(),
+        };
+
+        match target {
+            Lowered::Constant(value) => {
+                Lowered::Constant(value.get_by_path(&path).cloned().unwrap_or(Value::Null))
+            }
+            Lowered::Dynamic(target) => Lowered::dynamic(move |ctx| {
+                Ok(target(ctx)?
+                    .get_by_path(&path)
+                    .cloned()
+                    .unwrap_or(Value::Null))
+            }),
+        }
+    }
+}
+
 impl fmt::Display for Query {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.target {
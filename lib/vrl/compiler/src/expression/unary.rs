@@ -1,6 +1,7 @@
 use std::fmt;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{Not, Resolved},
     state::{ExternalEnv, LocalEnv},
     Context, Expression, TypeDef,
@@ -41,6 +42,16 @@ impl Expression for Unary {
     }
 }
 
+impl Lower for Unary {
+    fn lower(&self) -> Lowered {
+        use Variant::Not;
+
+        match &self.variant {
+            Not(v) => v.lower(),
+        }
+    }
+}
+
 impl fmt::Display for Unary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Variant::Not;
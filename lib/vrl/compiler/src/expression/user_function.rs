@@ -0,0 +1,647 @@
+use std::{fmt, sync::Arc};
+
+use diagnostic::{DiagnosticMessage, Label, Note, Urls};
+
+use crate::{
+    compiled::{Lower, Lowered},
+    expression::{Block, Expr, ExpressionError, FunctionArgument, Resolved},
+    parser::{Ident, Node},
+    state::{ExternalEnv, LocalEnv},
+    value::{self, Kind},
+    Context, Expression, Span, TypeDef,
+};
+
+/// A function defined within a VRL program, using the `fn` keyword.
+///
+/// The body of the function is compiled once, when it is defined. It only has
+/// access to its parameters, so calling it can't observe or mutate any other
+/// state of the program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction {
+    ident: Ident,
+    parameters: Vec<(Ident, Kind)>,
+    block: Block,
+    type_def: TypeDef,
+}
+
+impl UserFunction {
+    pub(crate) fn new(
+        ident: Ident,
+        parameters: Vec<(Ident, Kind)>,
+        block: Node<Block>,
+        return_kind: Option<Kind>,
+        external: &ExternalEnv,
+    ) -> Result<Self, Error> {
+        let (block_span, block) = block.take();
+        let type_def = block.type_def((&block.local_env, external));
+
+        let type_def = match return_kind {
+            Some(expected_kind) => {
+                if !expected_kind.is_superset(type_def.kind()) {
+                    return Err(Error::ReturnTypeMismatch {
+                        block_span,
+                        found_kind: type_def.kind().clone(),
+                        expected_kind,
+                    });
+                }
+
+                TypeDef::from(expected_kind).with_fallibility(type_def.is_fallible())
+            }
+            None => type_def,
+        };
+
+        Ok(Self {
+            ident,
+            parameters,
+            block,
+            type_def,
+        })
+    }
+
+    #[must_use]
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunctionCall {
+    function: Arc<UserFunction>,
+
+    /// The arguments, in the order of the parameters of the function.
+    ///
+    /// Arguments whose type only partially matches the parameter type are
+    /// checked at runtime.
+    arguments: Vec<(Expr, bool)>,
+    abort_on_error: bool,
+    span: Span,
+}
+
+impl UserFunctionCall {
+    pub(crate) fn new(
+        call_span: Span,
+        ident_span: Span,
+        function: Arc<UserFunction>,
+        abort_on_error: bool,
+        arguments: Vec<Node<FunctionArgument>>,
+        closure_span: Option<Span>,
+        state: (&LocalEnv, &ExternalEnv),
+    ) -> Result<Self, Error> {
+        if let Some(closure_span) = closure_span {
+            return Err(Error::UnexpectedClosure {
+                call_span,
+                closure_span,
+            });
+        }
+
+        let parameters = &function.parameters;
+
+        if arguments.len() > parameters.len() {
+            let start = arguments.first().unwrap().span().start();
+            let end = arguments.last().unwrap().span().end();
+
+            return Err(Error::WrongNumberOfArgs {
+                arguments_span: Span::new(start, end),
+                max: parameters.len(),
+            });
+        }
+
+        // Keyword arguments can be used in any order, positional arguments
+        // fill the first parameters that haven't been set by keyword.
+        let mut slots: Vec<Option<(Expr, bool)>> = vec![None; parameters.len()];
+        let mut index = 0;
+
+        for node in arguments {
+            let (argument_span, argument) = node.take();
+
+            let position = match argument.keyword() {
+                None => {
+                    while slots.get(index).map_or(false, Option::is_some) {
+                        index += 1;
+                    }
+                    index
+                }
+                Some(keyword) => parameters
+                    .iter()
+                    .position(|(ident, _)| ident.as_ref() == keyword)
+                    .ok_or_else(|| Error::UnknownKeyword {
+                        keyword_span: argument.keyword_span().expect("exists"),
+                        ident_span,
+                        keywords: parameters.iter().map(|(ident, _)| ident.clone()).collect(),
+                    })?,
+            };
+
+            let (ident, param_kind) = &parameters[position];
+            let type_def = argument.type_def(state);
+
+            if !param_kind.intersects(type_def.kind()) {
+                return Err(Error::InvalidArgumentKind {
+                    parameter: ident.clone(),
+                    expected: param_kind.clone(),
+                    got: type_def.kind().clone(),
+                    expr_span: argument.expr_span(),
+                    argument_span,
+                });
+            }
+
+            if type_def.is_fallible() {
+                return Err(Error::FallibleArgument {
+                    expr_span: argument.expr_span(),
+                });
+            }
+
+            if slots[position].is_some() {
+                return Err(Error::DuplicateArgument {
+                    parameter: ident.clone(),
+                    argument_span,
+                });
+            }
+
+            let checked = !param_kind.is_superset(type_def.kind());
+            slots[position] = Some((argument.into_inner(), checked));
+        }
+
+        let arguments = slots
+            .into_iter()
+            .enumerate()
+            .map(|(position, slot)| {
+                slot.ok_or_else(|| Error::MissingArgument {
+                    call_span,
+                    parameter: parameters[position].0.clone(),
+                    position,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Asking for an infallible function to abort on error makes no sense.
+        if abort_on_error
+            && !function.type_def.is_fallible()
+            && arguments.iter().all(|(_, checked)| !checked)
+        {
+            return Err(Error::AbortInfallible {
+                ident_span,
+                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+            });
+        }
+
+        Ok(Self {
+            function,
+            arguments,
+            abort_on_error,
+            span: call_span,
+        })
+    }
+
+    /// Call the function, using the provided functions to resolve the
+    /// argument at the given position, and the body of the function.
+    fn call(
+        &self,
+        ctx: &mut Context,
+        mut argument: impl FnMut(usize, &mut Context) -> Resolved,
+        body: impl FnOnce(&mut Context) -> Resolved,
+    ) -> Resolved {
+        let mut variables = Vec::with_capacity(self.arguments.len());
+
+        for (position, ((ident, kind), (_, checked))) in self
+            .function
+            .parameters
+            .iter()
+            .zip(&self.arguments)
+            .enumerate()
+        {
+            let argument = argument(position, ctx)?;
+
+            if *checked && !kind.intersects(&argument.kind()) {
+                return Err(value::Error::Expected {
+                    got: argument.kind(),
+                    expected: kind.clone(),
+                }
+                .into());
+            }
+
+            variables.push((ident.clone(), argument));
+        }
+
+        // The function body runs against its own variables, the variables of
+        // the caller are restored once it completes.
+        let caller = std::mem::take(ctx.state_mut());
+        for (ident, value) in variables {
+            ctx.state_mut().insert_variable(ident, value);
+        }
+
+        let resolved = body(ctx);
+        *ctx.state_mut() = caller;
+
+        resolved
+    }
+
+    fn map_error(&self, err: ExpressionError) -> ExpressionError {
+        match err {
+            #[cfg(feature = "expr-abort")]
+            err @ ExpressionError::Abort { .. } => err,
+            ExpressionError::Error {
+                message,
+                mut labels,
+                notes,
+            } => {
+                labels.push(Label::primary(message.clone(), self.span));
+
+                ExpressionError::Error {
+                    message: format!(
+                        r#"function call error for "{}" at ({}:{}): {}"#,
+                        self.function.ident,
+                        self.span.start(),
+                        self.span.end(),
+                        message
+                    ),
+                    labels,
+                    notes,
+                }
+            }
+        }
+    }
+}
+
+impl Expression for UserFunctionCall {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.call(
+            ctx,
+            |position, ctx| self.arguments[position].0.resolve(ctx),
+            |ctx| self.function.block.resolve(ctx),
+        )
+        .map_err(|err| self.map_error(err))
+    }
+
+    fn type_def(&self, _: (&LocalEnv, &ExternalEnv)) -> TypeDef {
+        let mut type_def = self.function.type_def.clone();
+
+        // Arguments that only partially match the parameter type are checked
+        // at runtime, which can fail.
+        if self.arguments.iter().any(|(_, checked)| *checked) {
+            type_def = type_def.with_fallibility(true);
+        }
+
+        if self.abort_on_error {
+            type_def = type_def.with_fallibility(false);
+        }
+
+        type_def
+    }
+}
+
+impl Lower for UserFunctionCall {
+    fn lower(&self) -> Lowered {
+        let call = self.clone();
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|(expr, _)| expr.lower().into_closure())
+            .collect::<Vec<_>>();
+        let body = self.function.block.lower().into_closure();
+
+        Lowered::dynamic(move |ctx| {
+            call.call(ctx, |position, ctx| arguments[position](ctx), &body)
+                .map_err(|err| call.map_error(err))
+        })
+    }
+}
+
+impl fmt::Display for UserFunctionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.function.ident.fmt(f)?;
+
+        if self.abort_on_error {
+            f.write_str("!")?;
+        }
+
+        f.write_str("(")?;
+
+        let mut iter = self.arguments.iter().peekable();
+        while let Some((expr, _)) = iter.next() {
+            expr.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(")")
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("invalid function definition")]
+    DuplicateDefinition {
+        ident_span: Span,
+        ident: Ident,
+        builtin: bool,
+    },
+
+    #[error("unknown type")]
+    UnknownType { span: Span, ident: Ident },
+
+    #[error("type mismatch in function return type")]
+    ReturnTypeMismatch {
+        block_span: Span,
+        found_kind: Kind,
+        expected_kind: Kind,
+    },
+
+    #[error("event access in function definition")]
+    EventAccess { span: Span },
+
+    #[error("wrong number of function arguments")]
+    WrongNumberOfArgs { arguments_span: Span, max: usize },
+
+    #[error("unknown function argument keyword")]
+    UnknownKeyword {
+        keyword_span: Span,
+        ident_span: Span,
+        keywords: Vec<Ident>,
+    },
+
+    #[error("duplicate function argument")]
+    DuplicateArgument {
+        parameter: Ident,
+        argument_span: Span,
+    },
+
+    #[error("missing function argument")]
+    MissingArgument {
+        call_span: Span,
+        parameter: Ident,
+        position: usize,
+    },
+
+    #[error("invalid argument type")]
+    InvalidArgumentKind {
+        parameter: Ident,
+        expected: Kind,
+        got: Kind,
+        expr_span: Span,
+        argument_span: Span,
+    },
+
+    #[error("fallible argument")]
+    FallibleArgument { expr_span: Span },
+
+    #[error("can't abort infallible function")]
+    AbortInfallible { ident_span: Span, abort_span: Span },
+
+    #[error("unexpected closure")]
+    UnexpectedClosure { call_span: Span, closure_span: Span },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::{
+            AbortInfallible, DuplicateArgument, DuplicateDefinition, EventAccess, FallibleArgument,
+            InvalidArgumentKind, MissingArgument, ReturnTypeMismatch, UnexpectedClosure,
+            UnknownKeyword, UnknownType, WrongNumberOfArgs,
+        };
+
+        match self {
+            DuplicateDefinition { .. } => 112,
+            UnknownType { .. } => 113,
+            ReturnTypeMismatch { .. } => 114,
+            EventAccess { .. } => 115,
+            WrongNumberOfArgs { .. } => 106,
+            MissingArgument { .. } => 107,
+            UnknownKeyword { .. } => 108,
+            UnexpectedClosure { .. } => 109,
+            InvalidArgumentKind { .. } => 110,
+            DuplicateArgument { .. } => 123,
+            AbortInfallible { .. } => 620,
+            FallibleArgument { .. } => 630,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::{
+            AbortInfallible, DuplicateArgument, DuplicateDefinition, EventAccess, FallibleArgument,
+            InvalidArgumentKind, MissingArgument, ReturnTypeMismatch, UnexpectedClosure,
+            UnknownKeyword, UnknownType, WrongNumberOfArgs,
+        };
+
+        match self {
+            DuplicateDefinition {
+                ident_span,
+                ident,
+                builtin,
+            } => {
+                let context = if *builtin {
+                    format!(r#"a built-in function named "{}" already exists"#, ident)
+                } else {
+                    format!(r#"a function named "{}" is already defined"#, ident)
+                };
+
+                vec![
+                    Label::primary("function name already in use", ident_span),
+                    Label::context(context, ident_span),
+                ]
+            }
+
+            UnknownType { span, ident } => vec![
+                Label::primary(format!(r#"unknown type "{}""#, ident), span),
+                Label::context(
+                    "expected one of: any, string, integer, float, boolean, null, timestamp, regex, array, object",
+                    span,
+                ),
+            ],
+
+            ReturnTypeMismatch {
+                block_span,
+                found_kind,
+                expected_kind,
+            } => vec![
+                Label::primary("block returns invalid value type", block_span),
+                Label::context(format!("expected: {}", expected_kind), block_span),
+                Label::context(format!("received: {}", found_kind), block_span),
+            ],
+
+            EventAccess { span } => vec![
+                Label::primary("functions can't access the event", span),
+                Label::context("pass the value in as an argument instead", span),
+            ],
+
+            WrongNumberOfArgs {
+                arguments_span,
+                max,
+            } => {
+                let arg = if *max == 1 { "argument" } else { "arguments" };
+
+                vec![
+                    Label::primary("too many function arguments", arguments_span),
+                    Label::context(
+                        format!("this function takes a maximum of {} {}", max, arg),
+                        arguments_span,
+                    ),
+                ]
+            }
+
+            UnknownKeyword {
+                keyword_span,
+                ident_span,
+                keywords,
+            } => vec![
+                Label::primary("unknown keyword", keyword_span),
+                Label::context(
+                    format!(
+                        "this function accepts the following keywords: {}",
+                        keywords
+                            .iter()
+                            .map(|k| format!(r#""{}""#, k))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    ident_span,
+                ),
+            ],
+
+            MissingArgument {
+                call_span,
+                parameter,
+                position,
+            } => vec![Label::primary(
+                format!(
+                    r#"required argument missing: "{}" (position {})"#,
+                    parameter, position
+                ),
+                call_span,
+            )],
+
+            DuplicateArgument {
+                parameter,
+                argument_span,
+            } => vec![
+                Label::primary(
+                    format!(r#"argument "{}" is already set"#, parameter),
+                    argument_span,
+                ),
+                Label::context(
+                    "each parameter can only be passed once",
+                    argument_span,
+                ),
+            ],
+
+            InvalidArgumentKind {
+                parameter,
+                expected,
+                got,
+                expr_span,
+                argument_span,
+            } => {
+                let kind_str = |kind: &Kind| {
+                    if kind.is_any() {
+                        kind.to_string()
+                    } else if kind.is_exact() {
+                        format!(r#"the exact type {}"#, kind)
+                    } else {
+                        format!("one of {}", kind)
+                    }
+                };
+
+                vec![
+                    Label::primary(
+                        format!("this expression resolves to {}", kind_str(got)),
+                        expr_span,
+                    ),
+                    Label::context(
+                        format!(
+                            r#"but the parameter "{}" expects {}"#,
+                            parameter,
+                            kind_str(expected)
+                        ),
+                        argument_span,
+                    ),
+                ]
+            }
+
+            FallibleArgument { expr_span } => vec![
+                Label::primary("this expression can fail", expr_span),
+                Label::context(
+                    "handle the error before passing it in as an argument",
+                    expr_span,
+                ),
+            ],
+
+            AbortInfallible {
+                ident_span,
+                abort_span,
+            } => vec![
+                Label::primary("this function can't fail", ident_span),
+                Label::context("remove this abort-instruction", abort_span),
+            ],
+
+            UnexpectedClosure {
+                call_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("this function does not accept a closure", call_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::{AbortInfallible, FallibleArgument, WrongNumberOfArgs};
+
+        match self {
+            WrongNumberOfArgs { .. } => vec![Note::SeeDocs(
+                "function arguments".to_owned(),
+                Urls::expression_docs_url("#arguments"),
+            )],
+            AbortInfallible { .. } | FallibleArgument { .. } => vec![Note::SeeErrorDocs],
+            _ => vec![],
+        }
+    }
+}
+
+/// Resolve the names of the types in a type annotation, such as
+/// `string | null`, into the [`Kind`] they represent.
+pub(crate) fn annotation_kind(idents: Vec<Node<Ident>>) -> Result<Kind, Error> {
+    use crate::value::Collection;
+
+    let mut kind = Kind::never();
+
+    for node in idents {
+        let (span, ident) = node.take();
+
+        match ident.as_ref() {
+            "any" => kind = Kind::any(),
+            "string" => {
+                kind.add_bytes();
+            }
+            "integer" => {
+                kind.add_integer();
+            }
+            "float" => {
+                kind.add_float();
+            }
+            "boolean" => {
+                kind.add_boolean();
+            }
+            "null" => {
+                kind.add_null();
+            }
+            "timestamp" => {
+                kind.add_timestamp();
+            }
+            "regex" => {
+                kind.add_regex();
+            }
+            "array" => {
+                kind.add_array(Collection::any());
+            }
+            "object" => {
+                kind.add_object(Collection::any());
+            }
+            _ => return Err(Error::UnknownType { span, ident }),
+        }
+    }
+
+    Ok(kind)
+}
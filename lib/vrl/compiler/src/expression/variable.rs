@@ -4,6 +4,7 @@ use diagnostic::{DiagnosticMessage, Label};
 use value::Value;
 
 use crate::{
+    compiled::{Lower, Lowered},
     expression::{levenstein, Resolved},
     parser::ast::Ident,
     state::{ExternalEnv, LocalEnv},
@@ -59,6 +60,16 @@ impl Expression for Variable {
     }
 }
 
+impl Lower for Variable {
+    fn lower(&self) -> Lowered {
+        let ident = self.ident.clone();
+
+        Lowered::dynamic(move |ctx| {
+            Ok(ctx.state().variable(&ident).cloned().unwrap_or(Value::Null))
+        })
+    }
+}
+
 impl fmt::Display for Variable {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.ident.fmt(f)
@@ -132,6 +132,13 @@ impl FunctionCompileContext {
         self.external_context.get_mut::<T>()
     }
 
+    /// Store an external context, replacing any existing context of the same
+    /// type. The context is available to the functions compiled after this
+    /// one.
+    pub fn set_external_context<T: 'static>(&mut self, data: T) {
+        self.external_context.insert::<T>(data);
+    }
+
     /// Consume the `FunctionCompileContext`, returning the (potentially mutated) `AnyMap`.
     #[must_use]
     pub fn into_external_context(self) -> AnyMap {
@@ -0,0 +1,217 @@
+use std::path::PathBuf;
+
+use diagnostic::{DiagnosticMessage, Label};
+
+use crate::Span;
+
+/// The directory relative `import` paths are resolved against.
+///
+/// This is provided to the compiler as external context. If it isn't set,
+/// relative paths are resolved against the current working directory.
+/// Imports within an imported program are resolved relative to the directory
+/// of that program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRoot(pub PathBuf);
+
+impl ImportRoot {
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self(path.into())
+    }
+}
+
+/// Format a diagnostic of an imported program into a single line, pointing at
+/// the location of the error in that program.
+pub(crate) fn format_diagnostic(
+    path: &str,
+    source: &str,
+    diagnostic: &dyn DiagnosticMessage,
+) -> String {
+    let label = diagnostic.labels().into_iter().find(|label| label.primary);
+
+    let location = label.as_ref().map_or_else(String::new, |label| {
+        let start = label.span.start().min(source.len());
+        let preceding = &source[..start];
+        let line = preceding.matches('\n').count() + 1;
+        let column = start - preceding.rfind('\n').map_or(0, |pos| pos + 1) + 1;
+
+        format!(":{}:{}", line, column)
+    });
+
+    match label {
+        Some(label) => format!(
+            "{}{}: error[E{}] {}: {}",
+            path,
+            location,
+            diagnostic.code(),
+            diagnostic.message(),
+            label.message
+        ),
+        None => format!(
+            "{}: error[E{}] {}",
+            path,
+            diagnostic.code(),
+            diagnostic.message()
+        ),
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("unable to read imported program")]
+    Read {
+        span: Span,
+        path: String,
+        error: String,
+    },
+
+    #[error("circular import")]
+    Cycle { span: Span, path: String },
+
+    #[error("invalid imported program")]
+    Invalid {
+        span: Span,
+        path: String,
+        errors: Vec<String>,
+    },
+
+    #[error("unexpected expression in imported program")]
+    UnexpectedExpression { span: Span },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        116
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::{Cycle, Invalid, Read, UnexpectedExpression};
+
+        match self {
+            Read { span, path, error } => vec![
+                Label::primary(format!(r#"unable to read "{}""#, path), span),
+                Label::context(error, span),
+            ],
+            Cycle { span, path } => vec![
+                Label::primary(format!(r#""{}" imports itself"#, path), span),
+                Label::context("remove this import", span),
+            ],
+            Invalid { span, path, errors } => {
+                let mut labels = vec![Label::primary(
+                    format!(r#""{}" failed to compile"#, path),
+                    span,
+                )];
+
+                labels.extend(errors.iter().map(|error| Label::context(error, span)));
+                labels
+            }
+            UnexpectedExpression { span } => vec![Label::primary(
+                "imported programs can only contain function definitions and imports",
+                span,
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use tempfile::{tempdir, TempDir};
+
+    use super::*;
+    use crate::{
+        state::{ExternalEnv, LocalEnv},
+        Compiler,
+    };
+
+    fn compile(root: &Path, source: &str) -> Result<(), Vec<String>> {
+        let mut external = ExternalEnv::default();
+        external.set_external_context(ImportRoot::new(root));
+
+        let ast = parser::parse(source).expect("valid program");
+
+        Compiler::compile(&[], ast, &mut external, LocalEnv::default())
+            .map(|_| ())
+            .map_err(|diagnostics| {
+                diagnostics
+                    .into_iter()
+                    .flat_map(|diagnostic| {
+                        std::iter::once(diagnostic.message().to_owned()).chain(
+                            diagnostic
+                                .labels()
+                                .iter()
+                                .map(|label| label.message.clone())
+                                .collect::<Vec<_>>(),
+                        )
+                    })
+                    .collect()
+            })
+    }
+
+    fn write_programs(programs: &[(&str, &str)]) -> TempDir {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("lib")).unwrap();
+
+        for (path, source) in programs {
+            fs::write(dir.path().join(path), source).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn imports_function_definitions() {
+        let dir = write_programs(&[
+            (
+                "lib/math.vrl",
+                "import \"numbers.vrl\"\nfn add_two(n: integer) -> integer { add_one(add_one(n)) }",
+            ),
+            (
+                "lib/numbers.vrl",
+                "fn add_one(n: integer) -> integer { n + 1 }",
+            ),
+        ]);
+
+        let result = compile(
+            dir.path(),
+            "import \"lib/math.vrl\"\nimport \"lib/numbers.vrl\"\nadd_two(add_one(1))",
+        );
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn rejects_import_cycles() {
+        let dir = write_programs(&[
+            ("lib/a.vrl", "import \"b.vrl\""),
+            ("lib/b.vrl", "import \"a.vrl\""),
+        ]);
+
+        let errors = compile(dir.path(), "import \"lib/a.vrl\"").unwrap_err();
+
+        assert_eq!(errors[0], "invalid imported program");
+        assert!(errors.iter().any(|error| error.contains("circular import")));
+    }
+
+    #[test]
+    fn rejects_expressions_in_imported_programs() {
+        let dir = write_programs(&[("lib/event.vrl", ".foo = true")]);
+
+        let errors = compile(dir.path(), "import \"lib/event.vrl\"").unwrap_err();
+
+        assert_eq!(errors[0], "invalid imported program");
+        assert!(errors[2].ends_with(
+            "lib/event.vrl:1:1: error[E116] unexpected expression in imported program: imported programs can only contain function definitions and imports"
+        ));
+    }
+
+    #[test]
+    fn reports_missing_imports() {
+        let dir = write_programs(&[]);
+
+        let errors = compile(dir.path(), "import \"lib/missing.vrl\"").unwrap_err();
+
+        assert_eq!(errors[0], "unable to read imported program");
+    }
+}
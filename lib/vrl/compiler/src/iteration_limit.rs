@@ -0,0 +1,22 @@
+/// The maximum number of iterations of a single `for` or `while` loop.
+///
+/// This is provided to the compiler as external context. If it isn't set, the
+/// default limit is used. A loop that exceeds the limit fails at runtime,
+/// which guarantees a program always terminates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IterationLimit(pub usize);
+
+impl IterationLimit {
+    pub const DEFAULT: usize = 10_000;
+
+    #[must_use]
+    pub fn new(limit: usize) -> Self {
+        Self(limit)
+    }
+}
+
+impl Default for IterationLimit {
+    fn default() -> Self {
+        Self(Self::DEFAULT)
+    }
+}
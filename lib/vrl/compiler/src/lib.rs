@@ -28,10 +28,16 @@
     clippy::too_many_lines, // allowed in initial deny commit
 )]
 
+mod clock;
+mod compiled;
 mod compiler;
 mod context;
+mod import;
+mod iteration_limit;
 mod program;
 mod test_util;
+mod trace;
+mod type_info;
 
 pub mod expression;
 pub mod function;
@@ -39,6 +45,8 @@ pub mod state;
 pub mod type_def;
 pub mod value;
 
+pub use clock::Clock;
+pub use compiled::CompiledProgram;
 pub use compiler::Compiler;
 pub use core::{
     value, ExpressionError, MetadataTarget, Resolved, SecretTarget, Target, TargetValue,
@@ -51,9 +59,13 @@ use diagnostic::DiagnosticList;
 pub(crate) use diagnostic::Span;
 pub use expression::Expression;
 pub use function::{Function, Parameter};
+pub use import::ImportRoot;
+pub use iteration_limit::IterationLimit;
 pub use paste::paste;
 pub use program::{Program, ProgramInfo};
+pub use trace::{Trace, TraceChange, TraceEntry, TraceLog};
 pub use type_def::TypeDef;
+pub use type_info::TypeInfo;
 use vector_config::configurable_component;
 
 pub type Result<T = (Program, DiagnosticList)> = std::result::Result<T, DiagnosticList>;
@@ -65,8 +77,15 @@ pub type Result<T = (Program, DiagnosticList)> = std::result::Result<T, Diagnost
 pub enum VrlRuntime {
     /// Tree-walking runtime.
     ///
-    /// This is the only, and default, runtime.
+    /// This is the default runtime.
     Ast,
+
+    /// Closure-compiled runtime.
+    ///
+    /// The program is lowered into closures once, folding constant
+    /// expressions, which avoids most of the overhead of walking the
+    /// expression tree for each event.
+    Compiled,
 }
 
 impl Default for VrlRuntime {
@@ -81,7 +100,8 @@ impl FromStr for VrlRuntime {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s {
             "ast" => Ok(Self::Ast),
-            _ => Err("runtime must be ast."),
+            "compiled" => Ok(Self::Compiled),
+            _ => Err("runtime must be ast or compiled."),
         }
     }
 }
@@ -93,6 +113,7 @@ impl Display for VrlRuntime {
             "{}",
             match self {
                 VrlRuntime::Ast => "ast",
+                VrlRuntime::Compiled => "compiled",
             }
         )
     }
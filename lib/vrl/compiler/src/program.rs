@@ -1,6 +1,7 @@
 use lookup::LookupBuf;
 
 use crate::{
+    compiled::{CompiledProgram, Lower},
     expression::{Block, Resolved},
     state::LocalEnv,
     Context, Expression,
@@ -41,6 +42,13 @@ impl Program {
     pub fn resolve(&self, ctx: &mut Context) -> Resolved {
         self.expressions.resolve(ctx)
     }
+
+    /// Lower the program into closures, to be resolved by the compiled
+    /// runtime.
+    #[must_use]
+    pub fn compile(&self) -> CompiledProgram {
+        CompiledProgram::new(self.expressions.lower(), self.info.clone())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
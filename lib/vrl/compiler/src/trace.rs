@@ -0,0 +1,386 @@
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Write as _},
+    sync::{Arc, Mutex},
+};
+
+use lookup::LookupBuf;
+use value::Value;
+
+use crate::{
+    expression::{Expr, Resolved},
+    Context, Expression, Target,
+};
+
+/// A record of the expressions evaluated while resolving a program, in the
+/// order in which their evaluation started.
+///
+/// Only expressions that were evaluated are recorded, so the trace shows which
+/// branches of the program ran for a given target. Tracing is only supported
+/// by the AST runtime.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace {
+    entries: Vec<TraceEntry>,
+
+    /// The expressions currently being evaluated, innermost last.
+    open: Vec<Frame>,
+}
+
+/// An expression evaluated while tracing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// The number of expressions the expression is nested in.
+    pub depth: usize,
+
+    /// The kind of expression, such as "assignment" or "function call".
+    pub kind: &'static str,
+
+    /// The source of the expression, formatted by the compiler.
+    pub expression: String,
+
+    /// The value the expression resolved to, or the error it raised.
+    pub result: Resolved,
+
+    /// The changes the expression made to the target. Changes made by nested
+    /// expressions are recorded in their own entries instead.
+    pub changes: Vec<TraceChange>,
+}
+
+/// A change made to a field of the target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceChange {
+    /// The path of the field, such as `.foo.bar[0]`.
+    pub path: String,
+
+    /// The value of the field before the change, if it existed.
+    pub before: Option<Value>,
+
+    /// The value of the field after the change, if it still exists.
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Frame {
+    index: usize,
+
+    /// The target as last seen by the expression, which is updated whenever
+    /// a nested expression finishes, so that its changes aren't recorded
+    /// twice.
+    target: Option<Value>,
+}
+
+impl Trace {
+    #[must_use]
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn enter(&mut self, expr: &Expr, target: &dyn Target) {
+        self.open.push(Frame {
+            index: self.entries.len(),
+            target: snapshot(target),
+        });
+
+        self.entries.push(TraceEntry {
+            depth: self.open.len() - 1,
+            kind: expr.as_str(),
+            expression: expr.to_string(),
+            result: Ok(Value::Null),
+            changes: vec![],
+        });
+    }
+
+    fn exit(&mut self, result: &Resolved, target: &dyn Target) {
+        let frame = match self.open.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+
+        let after = snapshot(target);
+        let entry = &mut self.entries[frame.index];
+        entry.result = result.clone();
+
+        if frame.target != after {
+            diff(
+                &mut String::new(),
+                frame.target.as_ref(),
+                after.as_ref(),
+                &mut entry.changes,
+            );
+        }
+
+        if let Some(parent) = self.open.last_mut() {
+            parent.target = after;
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            let indent = "  ".repeat(entry.depth);
+
+            // Multi-line expressions, such as blocks, are shortened to their
+            // first line, as their nested expressions follow in the trace.
+            let mut lines = entry.expression.lines();
+            let first = lines.next().unwrap_or_default();
+            let ellipsis = if lines.next().is_some() { " …" } else { "" };
+
+            match &entry.result {
+                Ok(value) => writeln!(f, "{}{}{} => {}", indent, first, ellipsis, value)?,
+                Err(error) => writeln!(f, "{}{}{} => error: {}", indent, first, ellipsis, error)?,
+            }
+
+            for change in &entry.changes {
+                write!(f, "{}  ", indent)?;
+
+                match (&change.before, &change.after) {
+                    (None, Some(after)) => writeln!(f, "+ {} = {}", change.path, after)?,
+                    (Some(before), None) => writeln!(f, "- {} = {}", change.path, before)?,
+                    (Some(before), Some(after)) => {
+                        writeln!(f, "~ {} = {} -> {}", change.path, before, after)?;
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The traces of the programs run by a component, shared with the reader of
+/// the traces, such as a unit test.
+#[derive(Debug, Clone, Default)]
+pub struct TraceLog(Arc<Mutex<Vec<(String, Trace)>>>);
+
+impl TraceLog {
+    /// Record the trace of a program run, labelled with what was run.
+    pub fn push(&self, label: impl Into<String>, trace: Trace) {
+        self.0
+            .lock()
+            .expect("trace log lock poisoned")
+            .push((label.into(), trace));
+    }
+
+    /// Take all traces recorded so far.
+    #[must_use]
+    pub fn take(&self) -> Vec<(String, Trace)> {
+        std::mem::take(&mut *self.0.lock().expect("trace log lock poisoned"))
+    }
+}
+
+/// Resolve the expression, recording it into the trace of the context.
+pub(crate) fn resolve(expr: &Expr, ctx: &mut Context) -> Resolved {
+    if let Some((trace, target)) = ctx.trace_parts() {
+        trace.enter(expr, target);
+    }
+
+    let result = expr.resolve_expr(ctx);
+
+    if let Some((trace, target)) = ctx.trace_parts() {
+        trace.exit(&result, target);
+    }
+
+    result
+}
+
+fn snapshot(target: &dyn Target) -> Option<Value> {
+    target
+        .target_get(&LookupBuf::root())
+        .ok()
+        .flatten()
+        .cloned()
+}
+
+/// Record the fields that differ between the two values, nested at the given
+/// path.
+fn diff(
+    path: &mut String,
+    before: Option<&Value>,
+    after: Option<&Value>,
+    changes: &mut Vec<TraceChange>,
+) {
+    match (before, after) {
+        (Some(Value::Object(before)), Some(Value::Object(after))) => {
+            let keys = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+
+            for key in keys {
+                let len = path.len();
+                push_field(path, key);
+                diff(path, before.get(key), after.get(key), changes);
+                path.truncate(len);
+            }
+        }
+        (Some(Value::Array(before)), Some(Value::Array(after))) => {
+            for index in 0..before.len().max(after.len()) {
+                let len = path.len();
+                write!(path, "[{}]", index).expect("write to String never fails");
+                diff(path, before.get(index), after.get(index), changes);
+                path.truncate(len);
+            }
+        }
+        (before, after) if before != after => changes.push(TraceChange {
+            path: if path.is_empty() {
+                ".".to_owned()
+            } else {
+                path.clone()
+            },
+            before: before.cloned(),
+            after: after.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+fn push_field(path: &mut String, key: &str) {
+    path.push('.');
+
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    {
+        path.push_str(key);
+    } else {
+        path.push('"');
+        path.push_str(&key.replace('\\', "\\\\").replace('"', "\\\""));
+        path.push('"');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ::value::Secrets;
+    use indoc::indoc;
+
+    use super::*;
+    use crate::{
+        state::{self, ExternalEnv, LocalEnv},
+        value, Compiler, TargetValue,
+    };
+
+    fn trace(source: &str, event: Value) -> (Resolved, Trace) {
+        let ast = parser::parse(source).expect("valid program");
+        let (program, _) =
+            Compiler::compile(&[], ast, &mut ExternalEnv::default(), LocalEnv::default())
+                .expect("program compiles");
+
+        let mut target = TargetValue {
+            value: event,
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Secrets::new(),
+        };
+        let mut state = state::Runtime::default();
+        let timezone = vector_common::TimeZone::default();
+        let mut trace = Trace::default();
+
+        let result = {
+            let mut ctx = Context::new(&mut target, &mut state, &timezone).with_trace(&mut trace);
+            program.resolve(&mut ctx)
+        };
+
+        (result, trace)
+    }
+
+    #[test]
+    fn records_evaluated_branch() {
+        let (result, trace) = trace(
+            indoc! {r#"
+                if .status == 200 {
+                    .ok = true
+                } else {
+                    .ok = false
+                }
+            "#},
+            value!({ "status": 500 }),
+        );
+
+        assert_eq!(result, Ok(false.into()));
+
+        let expressions = trace
+            .entries()
+            .iter()
+            .map(|entry| entry.expression.as_str())
+            .collect::<Vec<_>>();
+
+        assert!(expressions.contains(&".ok = false"), "{:?}", expressions);
+        assert!(!expressions.contains(&".ok = true"), "{:?}", expressions);
+    }
+
+    #[test]
+    fn records_changes_once() {
+        let (_, trace) = trace(".b = if true { .a = 2 } else { 3 }", value!({}));
+
+        let changes = |expression: &str| {
+            trace
+                .entries()
+                .iter()
+                .find(|entry| entry.expression == expression)
+                .map(|entry| entry.changes.clone())
+                .expect("expression traced")
+        };
+
+        assert_eq!(trace.entries()[0].kind, "assignment");
+        assert_eq!(
+            trace.entries()[0].changes,
+            vec![TraceChange {
+                path: ".b".to_owned(),
+                before: None,
+                after: Some(2.into()),
+            }]
+        );
+        assert_eq!(
+            changes(".a = 2"),
+            vec![TraceChange {
+                path: ".a".to_owned(),
+                before: None,
+                after: Some(2.into()),
+            }]
+        );
+        assert!(changes("2").is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let (_, trace) = trace(".a = 1", value!({ "a": 0 }));
+
+        assert_eq!(
+            trace.to_string(),
+            indoc! {"
+                .a = 1 => 1
+                  ~ .a = 0 -> 1
+                  1 => 1
+            "}
+        );
+    }
+
+    #[test]
+    fn formats_paths() {
+        let mut changes = vec![];
+        diff(
+            &mut String::new(),
+            Some(&value!({ "a b": [1, 2], "c": 1 })),
+            Some(&value!({ "a b": [1], "c": "x" })),
+            &mut changes,
+        );
+
+        let paths = changes
+            .into_iter()
+            .map(|change| change.path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, [r#"."a b"[1]"#, ".c"]);
+
+        let mut changes = vec![];
+        diff(&mut String::new(), Some(&value!(1)), None, &mut changes);
+        assert_eq!(changes[0].path, ".");
+    }
+}
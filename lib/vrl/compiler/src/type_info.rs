@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use crate::{Span, TypeDef};
+
+/// The type definitions of the expressions of a program, as inferred by the
+/// compiler, keyed by the span of each expression.
+///
+/// This is only collected when compiling through
+/// [`Compiler::compile_with_type_info`](crate::Compiler::compile_with_type_info),
+/// for tooling such as editor integrations. Expressions of imported programs
+/// aren't included, as their spans point into a different source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TypeInfo(BTreeMap<Span, TypeDef>);
+
+impl TypeInfo {
+    pub(crate) fn insert(&mut self, span: Span, type_def: TypeDef) {
+        // Expressions compiled more than once (such as the body of a loop)
+        // keep the type definition of the last pass.
+        self.0.insert(span, type_def);
+    }
+
+    /// Get the innermost expression containing the given byte offset, and its
+    /// type definition.
+    #[must_use]
+    pub fn at(&self, offset: usize) -> Option<(Span, &TypeDef)> {
+        self.0
+            .iter()
+            .filter(|(span, _)| span.start() <= offset && offset < span.end())
+            .min_by_key(|(span, _)| span.end() - span.start())
+            .map(|(span, type_def)| (*span, type_def))
+    }
+
+    /// Iterate over all expressions, and their type definitions, ordered by
+    /// span.
+    pub fn iter(&self) -> impl Iterator<Item = (Span, &TypeDef)> {
+        self.0.iter().map(|(span, type_def)| (*span, type_def))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::{ExternalEnv, LocalEnv},
+        value::Kind,
+        Compiler,
+    };
+
+    fn compile(source: &str) -> (bool, TypeInfo) {
+        let ast = parser::parse(source).expect("valid program");
+        let (result, type_info) = Compiler::compile_with_type_info(
+            &[],
+            ast,
+            &mut ExternalEnv::default(),
+            LocalEnv::default(),
+        );
+
+        (result.is_ok(), type_info)
+    }
+
+    #[test]
+    fn innermost_expression() {
+        let (ok, type_info) = compile(r#"x = 1 + "a""#);
+
+        assert!(!ok);
+
+        let (span, type_def) = type_info.at(4).expect("literal");
+        assert_eq!(span, Span::new(4, 5));
+        assert_eq!(type_def.kind(), &Kind::integer());
+
+        let (span, type_def) = type_info.at(6).expect("operation");
+        assert_eq!(span, Span::new(4, 11));
+        assert!(type_def.is_fallible());
+
+        assert!(type_info.at(11).is_none());
+    }
+
+    #[test]
+    fn partial_program() {
+        let (ok, type_info) = compile(".foo = true\nx");
+
+        assert!(!ok);
+
+        let (_, type_def) = type_info.at(0).expect("assignment");
+        assert_eq!(type_def.kind(), &Kind::boolean());
+    }
+}
@@ -65,6 +65,11 @@ impl Diagnostic {
         self.severity
     }
 
+    #[must_use]
+    pub fn code(&self) -> usize {
+        self.code
+    }
+
     #[must_use]
     pub fn message(&self) -> &str {
         &self.message
@@ -0,0 +1,507 @@
+use std::borrow::Cow;
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Documentation, MarkupContent, MarkupKind,
+    ParameterInformation, ParameterLabel, SignatureHelp, SignatureInformation,
+};
+use value::{kind::Field, Kind};
+use vrl::{
+    diagnostic::{DiagnosticList, Span, Urls},
+    function::Parameter,
+    state::{ExternalEnv, LocalEnv},
+    Function, TypeInfo,
+};
+
+/// The keywords completed alongside the functions.
+const KEYWORDS: &[&str] = &[
+    "abort", "break", "continue", "else", "false", "fn", "for", "if", "import", "in", "null",
+    "true", "while",
+];
+
+/// The schema of the events a program handles, such as the input schema
+/// definition of a `remap` transform.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub event: Kind,
+    pub metadata: Kind,
+}
+
+/// Compiles documents, and answers questions about their source.
+pub(crate) struct Analyzer {
+    functions: Vec<Box<dyn Function>>,
+    schema: Option<Schema>,
+}
+
+/// The result of compiling a document.
+#[derive(Debug, Clone)]
+pub(crate) struct Analysis {
+    pub(crate) diagnostics: DiagnosticList,
+    pub(crate) type_info: TypeInfo,
+
+    /// The kind of the event once the program ran, used to complete paths.
+    event: Kind,
+}
+
+impl Analyzer {
+    pub(crate) fn new(functions: Vec<Box<dyn Function>>, schema: Option<Schema>) -> Self {
+        Self { functions, schema }
+    }
+
+    pub(crate) fn analyze(&self, source: &str) -> Analysis {
+        let mut external = match &self.schema {
+            Some(schema) => {
+                ExternalEnv::new_with_kind(schema.event.clone(), schema.metadata.clone())
+            }
+            None => ExternalEnv::default(),
+        };
+
+        let (result, type_info) = vrl::compile_with_type_info(
+            source,
+            &self.functions,
+            &mut external,
+            LocalEnv::default(),
+        );
+
+        let diagnostics = match result {
+            Ok((_, warnings)) => warnings,
+            Err(errors) => errors,
+        };
+
+        Analysis {
+            diagnostics,
+            type_info,
+            event: external.target_kind().clone(),
+        }
+    }
+
+    /// Describe the source at the offset: the documentation of the function
+    /// called there, or the inferred type of the innermost expression.
+    pub(crate) fn hover(
+        &self,
+        source: &str,
+        analysis: &Analysis,
+        offset: usize,
+    ) -> Option<(Span, String)> {
+        if let Some((span, function)) = self.called_function_at(source, offset) {
+            return Some((span, function_docs(function)));
+        }
+
+        let (span, type_def) = analysis.type_info.at(offset)?;
+        let mut docs = format!("```text\n{}\n```", type_def.kind());
+        if type_def.is_fallible() {
+            docs.push_str("\n\nThis expression can fail at runtime.");
+        }
+
+        Some((span, docs))
+    }
+
+    /// Complete the path, function or keyword being typed at the offset.
+    pub(crate) fn completions(
+        &self,
+        source: &str,
+        analysis: &Analysis,
+        offset: usize,
+    ) -> Vec<CompletionItem> {
+        let prefix = &source[..offset];
+        let word_start = prefix
+            .rfind(|ch: char| !is_ident_char(ch) && ch != '.')
+            .map_or(0, |index| index + 1);
+        let word = &prefix[word_start..];
+
+        if let Some(path) = word.strip_prefix('.') {
+            return path_completions(&analysis.event, path);
+        }
+
+        if word.contains('.') {
+            return vec![];
+        }
+
+        let functions = self
+            .functions
+            .iter()
+            .filter(|function| function.identifier().starts_with(word))
+            .map(|function| CompletionItem {
+                label: function.identifier().to_owned(),
+                kind: Some(CompletionItemKind::FUNCTION),
+                detail: Some(signature(function.as_ref())),
+                documentation: Some(markdown(function_docs(function.as_ref()))),
+                ..Default::default()
+            });
+
+        let keywords = KEYWORDS
+            .iter()
+            .filter(|keyword| keyword.starts_with(word))
+            .map(|keyword| CompletionItem {
+                label: (*keyword).to_owned(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                ..Default::default()
+            });
+
+        functions.chain(keywords).collect()
+    }
+
+    /// Describe the parameters of the function call surrounding the offset.
+    pub(crate) fn signature_help(&self, source: &str, offset: usize) -> Option<SignatureHelp> {
+        let call = enclosing_call(&source[..offset])?;
+        let function = self.function(call.ident)?;
+        let parameters = function.parameters();
+
+        // An argument passed by keyword activates its parameter, otherwise
+        // arguments map to parameters by position.
+        let active = call
+            .argument
+            .split_once(':')
+            .and_then(|(keyword, _)| {
+                parameters
+                    .iter()
+                    .position(|parameter| parameter.keyword == keyword.trim())
+            })
+            .unwrap_or(call.index);
+
+        let information = SignatureInformation {
+            label: signature(function),
+            documentation: Some(markdown(function.summary().to_owned())),
+            parameters: Some(
+                parameters
+                    .iter()
+                    .map(|parameter| ParameterInformation {
+                        label: ParameterLabel::Simple(parameter_label(parameter)),
+                        documentation: None,
+                    })
+                    .collect(),
+            ),
+            active_parameter: None,
+        };
+
+        Some(SignatureHelp {
+            signatures: vec![information],
+            active_signature: Some(0),
+            active_parameter: u32::try_from(active).ok(),
+        })
+    }
+
+    fn function(&self, ident: &str) -> Option<&dyn Function> {
+        self.functions
+            .iter()
+            .find(|function| function.identifier() == ident)
+            .map(AsRef::as_ref)
+    }
+
+    /// Get the function called by the identifier at the offset, if any.
+    fn called_function_at(&self, source: &str, offset: usize) -> Option<(Span, &dyn Function)> {
+        if !source.is_char_boundary(offset) {
+            return None;
+        }
+
+        let start = source[..offset]
+            .rfind(|ch: char| !is_ident_char(ch))
+            .map_or(0, |index| index + 1);
+        let end = source[offset..]
+            .find(|ch: char| !is_ident_char(ch))
+            .map_or(source.len(), |index| offset + index);
+
+        let rest = &source[end..];
+        if !rest.starts_with('(') && !rest.starts_with("!(") {
+            return None;
+        }
+
+        let function = self.function(&source[start..end])?;
+
+        Some((Span::new(start, end), function))
+    }
+}
+
+fn is_ident_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+fn markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
+
+fn parameter_label(parameter: &Parameter) -> String {
+    let label = format!("{}: <{}>", parameter.keyword, parameter.kind());
+
+    if parameter.required {
+        label
+    } else {
+        format!("[{}]", label)
+    }
+}
+
+fn signature(function: &dyn Function) -> String {
+    let parameters = function
+        .parameters()
+        .iter()
+        .map(parameter_label)
+        .collect::<Vec<_>>();
+
+    format!("{}({})", function.identifier(), parameters.join(", "))
+}
+
+fn function_docs(function: &dyn Function) -> String {
+    format!(
+        "```text\n{}\n```\n\n{}\n\n{}\n\n[Documentation]({})",
+        signature(function),
+        function.summary(),
+        function.usage(),
+        Urls::func_docs(function.identifier()),
+    )
+}
+
+/// Complete the last segment of a path into the event, using the known
+/// fields of the objects along the path.
+fn path_completions(event: &Kind, path: &str) -> Vec<CompletionItem> {
+    let (parents, partial) = match path.rsplit_once('.') {
+        Some((parents, partial)) => (parents, partial),
+        None => ("", path),
+    };
+
+    let mut kind = Cow::Borrowed(event);
+    for segment in parents.split('.').filter(|segment| !segment.is_empty()) {
+        let field = Field::from(segment);
+
+        kind = match kind
+            .as_object()
+            .and_then(|object| object.known().get(&field))
+        {
+            Some(kind) => Cow::Owned(kind.clone()),
+            None => return vec![],
+        };
+    }
+
+    let object = match kind.as_object() {
+        Some(object) => object,
+        None => return vec![],
+    };
+
+    object
+        .known()
+        .iter()
+        .filter(|(field, _)| field.as_str().starts_with(partial))
+        .map(|(field, kind)| CompletionItem {
+            label: field.as_str().to_owned(),
+            kind: Some(CompletionItemKind::FIELD),
+            detail: Some(kind.to_string()),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// A function call the cursor is within.
+#[derive(Debug, PartialEq)]
+struct Call<'a> {
+    ident: &'a str,
+
+    /// The position of the argument being typed.
+    index: usize,
+
+    /// The source of the argument being typed, up to the cursor.
+    argument: &'a str,
+}
+
+/// Find the innermost function call the end of the source is within, by
+/// tracking the delimiters opened in the source.
+fn enclosing_call(source: &str) -> Option<Call<'_>> {
+    struct Open {
+        delimiter: char,
+        start: usize,
+        index: usize,
+        argument_start: usize,
+    }
+
+    let mut stack: Vec<Open> = vec![];
+    let mut chars = source.char_indices();
+
+    while let Some((offset, ch)) = chars.next() {
+        match ch {
+            // Comments run until the end of the line.
+            '#' => {
+                for (_, ch) in chars.by_ref() {
+                    if ch == '\n' {
+                        break;
+                    }
+                }
+            }
+            // Delimiters in string literals are skipped.
+            '"' | '\'' => {
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+                        }
+                        next if next == ch => break,
+                        _ => {}
+                    }
+                }
+            }
+            '(' | '[' | '{' => stack.push(Open {
+                delimiter: ch,
+                start: offset,
+                index: 0,
+                argument_start: offset + 1,
+            }),
+            ')' | ']' | '}' => {
+                stack.pop();
+            }
+            ',' => {
+                if let Some(open) = stack.last_mut() {
+                    open.index += 1;
+                    open.argument_start = offset + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let open = stack.iter().rev().find(|open| open.delimiter == '(')?;
+
+    // Functions that abort on errors are called as `ident!(...)`.
+    let before = &source[..open.start];
+    let before = before.strip_suffix('!').unwrap_or(before);
+    let ident_start = before
+        .rfind(|ch: char| !is_ident_char(ch))
+        .map_or(0, |index| index + 1);
+    let ident = &before[ident_start..];
+
+    if ident.is_empty() {
+        return None;
+    }
+
+    Some(Call {
+        ident,
+        index: open.index,
+        argument: &source[open.argument_start..],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use value::kind::Collection;
+
+    use super::*;
+
+    fn analyzer(schema: Option<Schema>) -> Analyzer {
+        Analyzer::new(stdlib::all(), schema)
+    }
+
+    /// Split the source at the cursor, marked by `|`.
+    fn cursor(source: &str) -> (String, usize) {
+        let offset = source.find('|').expect("cursor");
+
+        (source.replacen('|', "", 1), offset)
+    }
+
+    #[test]
+    fn diagnostics() {
+        let analyzer = analyzer(None);
+
+        let analysis = analyzer.analyze(".foo = parse_json(.bar)");
+        assert_eq!(analysis.diagnostics.len(), 1);
+        assert_eq!(analysis.diagnostics[0].code(), 103);
+
+        let analysis = analyzer.analyze(".foo = parse_json!(.bar)");
+        assert!(analysis.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn hover() {
+        let analyzer = analyzer(None);
+
+        let (source, offset) = cursor(r#".foo = upc|ase("bar")"#);
+        let analysis = analyzer.analyze(&source);
+        let (span, docs) = analyzer.hover(&source, &analysis, offset).unwrap();
+        assert_eq!(span, Span::new(7, 13));
+        assert!(docs.contains("upcase(value: <string>)"), "{}", docs);
+
+        let (source, offset) = cursor(r#".foo = upcase(|"bar")"#);
+        let analysis = analyzer.analyze(&source);
+        let (span, docs) = analyzer.hover(&source, &analysis, offset).unwrap();
+        assert_eq!(span, Span::new(14, 19));
+        assert_eq!(docs, "```text\nstring\n```");
+
+        let (source, offset) = cursor(".foo = to_int(.bar) ?? |0");
+        let analysis = analyzer.analyze(&source);
+        let (span, docs) = analyzer.hover(&source, &analysis, offset).unwrap();
+        assert_eq!(span, Span::new(23, 24));
+        assert_eq!(docs, "```text\ninteger\n```");
+    }
+
+    #[test]
+    fn completions() {
+        let schema = Schema {
+            event: Kind::object(BTreeMap::from([(
+                "nested".into(),
+                Kind::object(BTreeMap::from([
+                    ("message".into(), Kind::bytes()),
+                    ("method".into(), Kind::bytes()),
+                    ("status".into(), Kind::integer()),
+                ])),
+            )])),
+            metadata: Kind::object(Collection::any()),
+        };
+        let analyzer = analyzer(Some(schema));
+
+        let labels = |source: &str| {
+            let (source, offset) = cursor(source);
+            let analysis = analyzer.analyze(&source);
+
+            analyzer
+                .completions(&source, &analysis, offset)
+                .into_iter()
+                .map(|item| item.label)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(labels(".nested.me|"), ["message", "method"]);
+        assert_eq!(labels(".|"), ["nested"]);
+        assert!(labels(".unknown.|").is_empty());
+        assert_eq!(labels("x = upca|"), ["upcase"]);
+        assert_eq!(labels("whi|"), ["while"]);
+    }
+
+    #[test]
+    fn signature_help() {
+        let analyzer = analyzer(None);
+
+        let active = |source: &str| {
+            let (source, offset) = cursor(source);
+            analyzer.signature_help(&source, offset)?.active_parameter
+        };
+
+        assert_eq!(active("parse_json(|"), Some(0));
+        assert_eq!(active(r#"parse_json!(.foo, |"#), Some(1));
+        assert_eq!(active(r#"parse_json(.foo, max_depth: |"#), Some(1));
+        assert_eq!(active(r#"parse_json(max_depth: 1, value: |"#), Some(0));
+        assert_eq!(active(r#"parse_json([1, 2], "(, #", |"#), Some(2));
+        assert_eq!(active(r#"parse_json(.foo)|"#), None);
+        assert_eq!(active(r#"unknown(|"#), None);
+    }
+
+    #[test]
+    fn finds_enclosing_call() {
+        assert_eq!(
+            enclosing_call("upcase(downcase(.foo), "),
+            Some(Call {
+                ident: "upcase",
+                index: 1,
+                argument: " ",
+            })
+        );
+        assert_eq!(
+            enclosing_call("upcase(downcase(.foo"),
+            Some(Call {
+                ident: "downcase",
+                index: 0,
+                argument: ".foo",
+            })
+        );
+        assert_eq!(enclosing_call("(1 + "), None);
+        assert_eq!(enclosing_call("upcase(.foo) # (\n"), None);
+    }
+}
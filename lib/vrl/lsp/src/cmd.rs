@@ -0,0 +1,36 @@
+use clap::Parser;
+use vrl::Function;
+
+use super::run;
+
+#[derive(Parser, Debug)]
+#[clap(
+    name = "VRL LSP",
+    about = "Vector Remap Language language server, communicating over stdin and stdout"
+)]
+pub struct Opts {}
+
+#[must_use]
+pub fn cmd(_opts: &Opts) -> exitcode::ExitCode {
+    match run(functions(), None) {
+        Ok(_) => exitcode::OK,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// The functions available to programs run by Vector.
+///
+/// Enrichment table functions are left out, as they can only be compiled with
+/// the tables of a loaded configuration.
+#[must_use]
+pub fn functions() -> Vec<Box<dyn Function>> {
+    let mut functions = stdlib::all();
+    functions.extend(vector_vrl_functions::vrl_functions());
+    functions
+}
@@ -0,0 +1,189 @@
+use lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+use vrl::diagnostic::Span;
+
+/// A text document opened in the editor.
+///
+/// Positions sent by the client are line and (UTF-16) character based, while
+/// the compiler works with byte offsets, the document converts between both.
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    text: String,
+    version: i32,
+
+    /// The byte offset at which each line starts.
+    line_starts: Vec<usize>,
+}
+
+impl Document {
+    pub(crate) fn new(text: String, version: i32) -> Self {
+        let line_starts = line_starts(&text);
+
+        Self {
+            text,
+            version,
+            line_starts,
+        }
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Apply the changes made in the editor. A change without a range
+    /// replaces the entire text.
+    ///
+    /// A change with a range that ends before it starts is ignored, as it
+    /// can't be applied to the text.
+    pub(crate) fn apply(&mut self, changes: Vec<TextDocumentContentChangeEvent>, version: i32) {
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let (start, end) = (self.offset(range.start), self.offset(range.end));
+                    if start > end {
+                        continue;
+                    }
+
+                    self.text.replace_range(start..end, &change.text);
+                }
+                None => self.text = change.text,
+            }
+
+            self.line_starts = line_starts(&self.text);
+        }
+
+        self.version = version;
+    }
+
+    /// Get the byte offset of the position. Positions past the end of a line
+    /// are clamped to the end of the line.
+    pub(crate) fn offset(&self, position: Position) -> usize {
+        let line = position.line as usize;
+        let start = match self.line_starts.get(line) {
+            Some(start) => *start,
+            None => return self.text.len(),
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or(self.text.len());
+
+        let mut character = 0;
+        for (offset, ch) in self.text[start..end].char_indices() {
+            if character >= position.character as usize || ch == '\n' {
+                return start + offset;
+            }
+
+            character += ch.len_utf16();
+        }
+
+        end
+    }
+
+    /// Get the position of the byte offset.
+    pub(crate) fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        // The first line always starts at offset zero.
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let character = self.text[self.line_starts[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+
+        Position::new(to_u32(line), to_u32(character))
+    }
+
+    pub(crate) fn range(&self, span: Span) -> Range {
+        Range::new(self.position(span.start()), self.position(span.end()))
+    }
+}
+
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+        .collect()
+}
+
+fn to_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_positions() {
+        let document = Document::new(".foo = \"é😀\"\n.bar = 1\n".to_owned(), 1);
+
+        // The emoji is two UTF-16 code units, and four bytes.
+        let cases = [
+            (Position::new(0, 0), 0),
+            (Position::new(0, 8), 8),
+            (Position::new(0, 9), 10),
+            (Position::new(0, 11), 14),
+            (Position::new(1, 1), 17),
+            (Position::new(2, 0), 25),
+        ];
+
+        for (position, offset) in cases {
+            assert_eq!(document.offset(position), offset, "{:?}", position);
+            assert_eq!(document.position(offset), position, "{}", offset);
+        }
+
+        // Positions past the end of a line are clamped.
+        assert_eq!(document.offset(Position::new(1, 100)), 24);
+        assert_eq!(document.offset(Position::new(5, 0)), 25);
+    }
+
+    #[test]
+    fn applies_changes() {
+        let mut document = Document::new(".foo = 1\n.bar = 2".to_owned(), 1);
+
+        document.apply(
+            vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 7), Position::new(1, 8))),
+                range_length: None,
+                text: "\"baz\"".to_owned(),
+            }],
+            2,
+        );
+
+        assert_eq!(document.text(), ".foo = 1\n.bar = \"baz\"");
+        assert_eq!(document.version(), 2);
+        assert_eq!(document.position(20), Position::new(1, 11));
+    }
+
+    #[test]
+    fn ignores_invalid_changes() {
+        let mut document = Document::new(".foo = 1\n.bar = 2".to_owned(), 1);
+
+        document.apply(
+            vec![
+                TextDocumentContentChangeEvent {
+                    range: Some(Range::new(Position::new(1, 4), Position::new(0, 1))),
+                    range_length: None,
+                    text: "nope".to_owned(),
+                },
+                TextDocumentContentChangeEvent {
+                    range: Some(Range::new(Position::new(1, 7), Position::new(7, 7))),
+                    range_length: None,
+                    text: "3".to_owned(),
+                },
+            ],
+            2,
+        );
+
+        // Positions past the end of the text are clamped to its end.
+        assert_eq!(document.text(), ".foo = 1\n.bar = 3");
+        assert_eq!(document.version(), 2);
+    }
+}
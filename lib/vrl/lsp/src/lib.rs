@@ -0,0 +1,34 @@
+#![deny(
+    warnings,
+    clippy::all,
+    clippy::pedantic,
+    unreachable_pub,
+    unused_allocation,
+    unused_extern_crates,
+    unused_assignments,
+    unused_comparisons
+)]
+
+mod analysis;
+pub mod cmd;
+mod document;
+mod server;
+
+pub use analysis::Schema;
+pub use cmd::{cmd, Opts};
+pub use server::run;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("io error: {}", .0)]
+    Io(#[from] std::io::Error),
+
+    #[error("protocol error: {}", .0)]
+    Protocol(#[from] lsp_server::ProtocolError),
+
+    #[error("json error: {}", .0)]
+    Json(#[from] serde_json::Error),
+
+    #[error("client disconnected before shutting down the server")]
+    Disconnected,
+}
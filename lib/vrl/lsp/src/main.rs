@@ -0,0 +1,8 @@
+extern crate vrl_lsp;
+
+use clap::Parser;
+use vrl_lsp::{cmd::cmd, Opts};
+
+fn main() {
+    std::process::exit(cmd(&Opts::parse()));
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{Completion, HoverRequest, Request as RequestTrait, SignatureHelpRequest},
+    CompletionOptions, CompletionResponse, DiagnosticRelatedInformation, DiagnosticSeverity, Hover,
+    HoverContents, HoverProviderCapability, InitializeParams, Location, MarkupContent, MarkupKind,
+    NumberOrString, PublishDiagnosticsParams, ServerCapabilities, SignatureHelpOptions,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use serde_json::Value as JsonValue;
+use vrl::{
+    diagnostic::{Diagnostic, Severity},
+    Function,
+};
+
+use crate::{
+    analysis::{Analysis, Analyzer, Schema},
+    document::Document,
+    Error,
+};
+
+/// Run the language server over stdin and stdout, until the client shuts it
+/// down.
+///
+/// # Errors
+///
+/// Returns an error if the client disconnects without shutting the server
+/// down, or if it sends malformed messages.
+pub fn run(functions: Vec<Box<dyn Function>>, schema: Option<Schema>) -> Result<(), Error> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(capabilities())?;
+    let params = connection.initialize(capabilities)?;
+    let _params: InitializeParams = serde_json::from_value(params)?;
+
+    Server {
+        connection,
+        analyzer: Analyzer::new(functions, schema),
+        documents: HashMap::new(),
+    }
+    .main_loop()?;
+
+    io_threads.join()?;
+
+    Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![".".to_owned()]),
+            ..Default::default()
+        }),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+struct Server {
+    connection: Connection,
+    analyzer: Analyzer,
+
+    /// The open documents, and the result of their last compilation.
+    documents: HashMap<Url, (Document, Analysis)>,
+}
+
+impl Server {
+    fn main_loop(mut self) -> Result<(), Error> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+
+                    let response = self.handle_request(request);
+                    self.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Err(Error::Disconnected)
+    }
+
+    fn send(&self, message: Message) -> Result<(), Error> {
+        self.connection
+            .sender
+            .send(message)
+            .map_err(|_| Error::Disconnected)
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            HoverRequest::METHOD => respond::<HoverRequest>(request, |params| {
+                self.hover(&params.text_document_position_params)
+            }),
+            Completion::METHOD => respond::<Completion>(request, |params| {
+                self.with_document(
+                    &params.text_document_position,
+                    |document, analysis, offset| {
+                        CompletionResponse::Array(self.analyzer.completions(
+                            document.text(),
+                            analysis,
+                            offset,
+                        ))
+                    },
+                )
+            }),
+            SignatureHelpRequest::METHOD => respond::<SignatureHelpRequest>(request, |params| {
+                self.with_document(
+                    &params.text_document_position_params,
+                    |document, _, offset| self.analyzer.signature_help(document.text(), offset),
+                )
+                .flatten()
+            }),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request: {}", method),
+            ),
+        }
+    }
+
+    fn with_document<T>(
+        &self,
+        params: &TextDocumentPositionParams,
+        f: impl FnOnce(&Document, &Analysis, usize) -> T,
+    ) -> Option<T> {
+        let (document, analysis) = self.documents.get(&params.text_document.uri)?;
+        let offset = document.offset(params.position);
+
+        Some(f(document, analysis, offset))
+    }
+
+    fn hover(&self, params: &TextDocumentPositionParams) -> Option<Hover> {
+        self.with_document(params, |document, analysis, offset| {
+            let (span, value) = self.analyzer.hover(document.text(), analysis, offset)?;
+
+            Some(Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value,
+                }),
+                range: Some(document.range(span)),
+            })
+        })
+        .flatten()
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), Error> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = cast_notification::<DidOpenTextDocument>(notification.params)?;
+                let document = params.text_document;
+
+                self.update(document.uri, Document::new(document.text, document.version))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = cast_notification::<DidChangeTextDocument>(notification.params)?;
+                let uri = params.text_document.uri;
+
+                let mut document = match self.documents.remove(&uri) {
+                    Some((document, _)) => document,
+                    None => return Ok(()),
+                };
+                document.apply(params.content_changes, params.text_document.version);
+
+                self.update(uri, document)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = cast_notification::<DidCloseTextDocument>(notification.params)?;
+                let uri = params.text_document.uri;
+
+                self.documents.remove(&uri);
+                self.publish_diagnostics(uri, vec![], None)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Compile the document, and publish its diagnostics.
+    fn update(&mut self, uri: Url, document: Document) -> Result<(), Error> {
+        let analysis = self.analyzer.analyze(document.text());
+        let diagnostics = analysis
+            .diagnostics
+            .iter()
+            .map(|diagnostic| to_lsp_diagnostic(&uri, &document, diagnostic))
+            .collect();
+        let version = document.version();
+
+        self.documents.insert(uri.clone(), (document, analysis));
+        self.publish_diagnostics(uri, diagnostics, Some(version))
+    }
+
+    fn publish_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+        version: Option<i32>,
+    ) -> Result<(), Error> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+
+        self.send(Notification::new(PublishDiagnostics::METHOD.to_owned(), params).into())
+    }
+}
+
+/// Deserialize the parameters of the request, and serialize the result of
+/// the handler.
+fn respond<R>(request: Request, handler: impl FnOnce(R::Params) -> R::Result) -> Response
+where
+    R: RequestTrait,
+{
+    let id = request.id;
+
+    match serde_json::from_value::<R::Params>(request.params) {
+        Ok(params) => match serde_json::to_value(handler(params)) {
+            Ok(result) => Response::new_ok(id, result),
+            Err(error) => Response::new_err(id, ErrorCode::InternalError as i32, error.to_string()),
+        },
+        Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+    }
+}
+
+fn cast_notification<N>(params: JsonValue) -> Result<N::Params, Error>
+where
+    N: NotificationTrait,
+{
+    Ok(serde_json::from_value(params)?)
+}
+
+fn to_lsp_diagnostic(
+    uri: &Url,
+    document: &Document,
+    diagnostic: &Diagnostic,
+) -> lsp_types::Diagnostic {
+    let labels = diagnostic.labels();
+    let primary = labels
+        .iter()
+        .find(|label| label.primary)
+        .or_else(|| labels.first());
+
+    let mut message = diagnostic.message().to_owned();
+    if let Some(label) = primary {
+        if !label.message.is_empty() {
+            message.push_str("\n\n");
+            message.push_str(&label.message);
+        }
+    }
+    for note in diagnostic.notes() {
+        message.push('\n');
+        message.push_str(&note.to_string());
+    }
+
+    let related_information = labels
+        .iter()
+        .filter(|label| primary.map_or(true, |primary| !std::ptr::eq(*label, primary)))
+        .map(|label| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), document.range(label.span)),
+            message: label.message.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let severity = match diagnostic.severity() {
+        Severity::Bug | Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Note => DiagnosticSeverity::INFORMATION,
+    };
+
+    lsp_types::Diagnostic {
+        range: primary.map_or_else(Default::default, |label| document.range(label.span)),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(format!("E{:03}", diagnostic.code()))),
+        source: Some("vrl".to_owned()),
+        message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        ..Default::default()
+    }
+}
@@ -187,6 +187,12 @@ impl IntoIterator for Program {
 pub enum RootExpr {
     Expr(Node<Expr>),
 
+    /// The definition of a function, callable by the expressions following it.
+    FunctionDefinition(Node<FunctionDefinition>),
+
+    /// An import of the function definitions of another program.
+    Import(Node<Import>),
+
     /// A special expression that is returned if a given expression could not be
     /// parsed. This allows the parser to continue on to the next expression.
     Error(Error),
@@ -194,10 +200,12 @@ pub enum RootExpr {
 
 impl fmt::Debug for RootExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RootExpr::{Error, Expr};
+        use RootExpr::{Error, Expr, FunctionDefinition, Import};
 
         let value = match self {
             Expr(v) => format!("{:?}", v),
+            FunctionDefinition(v) => format!("{:?}", v),
+            Import(v) => format!("{:?}", v),
             Error(v) => format!("{:?}", v),
         };
 
@@ -207,15 +215,142 @@ impl fmt::Debug for RootExpr {
 
 impl fmt::Display for RootExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RootExpr::{Error, Expr};
+        use RootExpr::{Error, Expr, FunctionDefinition, Import};
 
         match self {
             Expr(v) => v.fmt(f),
+            FunctionDefinition(v) => v.fmt(f),
+            Import(v) => v.fmt(f),
             Error(v) => v.fmt(f),
         }
     }
 }
 
+// -----------------------------------------------------------------------------
+// function definition
+// -----------------------------------------------------------------------------
+
+/// The definition of a user-defined function.
+///
+/// It contains the identifier of the function, its parameters, the optional
+/// type of its return value, and the block evaluated when it is called.
+#[derive(Clone, PartialEq)]
+pub struct FunctionDefinition {
+    pub ident: Node<Ident>,
+    pub parameters: Vec<Node<FunctionParameter>>,
+    pub return_type: Option<Node<TypeAnnotation>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}(", self.ident)?;
+
+        let mut iter = self.parameters.iter().peekable();
+        while let Some(parameter) = iter.next() {
+            parameter.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(")")?;
+
+        if let Some(return_type) = &self.return_type {
+            write!(f, " -> {}", return_type)?;
+        }
+
+        write!(f, " {}", self.block)
+    }
+}
+
+impl fmt::Debug for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FunctionDefinition({:?}, {:?}, {:?}, {:?})",
+            self.ident, self.parameters, self.return_type, self.block
+        )
+    }
+}
+
+/// A parameter of a user-defined function, with the types of the values it
+/// accepts, if any are specified.
+#[derive(Clone, PartialEq)]
+pub struct FunctionParameter {
+    pub ident: Node<Ident>,
+    pub type_annotation: Option<Node<TypeAnnotation>>,
+}
+
+impl fmt::Display for FunctionParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.ident.fmt(f)?;
+
+        if let Some(type_annotation) = &self.type_annotation {
+            write!(f, ": {}", type_annotation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FunctionParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.type_annotation {
+            Some(type_annotation) => {
+                write!(f, "Parameter({:?}: {:?})", self.ident, type_annotation)
+            }
+            None => write!(f, "Parameter({:?})", self.ident),
+        }
+    }
+}
+
+/// The names of the types a value may have, such as `string | integer`.
+#[derive(Clone, PartialEq)]
+pub struct TypeAnnotation(pub Vec<Node<Ident>>);
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.0.iter().peekable();
+        while let Some(ident) = iter.next() {
+            ident.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(" | ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypeAnnotation({})", self)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// import
+// -----------------------------------------------------------------------------
+
+/// An import of the function definitions of the program at the given path.
+#[derive(Clone, PartialEq)]
+pub struct Import(pub Node<String>);
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#"import "{}""#, self.0)
+    }
+}
+
+impl fmt::Debug for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Import({:?})", self.0)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // expression
 // -----------------------------------------------------------------------------
@@ -233,13 +368,15 @@ pub enum Expr {
     Variable(Node<Ident>),
     Unary(Node<Unary>),
     Abort(Node<Abort>),
+    Loop(Node<Loop>),
+    LoopControl(Node<LoopControl>),
 }
 
 impl fmt::Debug for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Unary,
-            Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Op, Query, Unary, Variable,
         };
 
         let value = match self {
@@ -253,6 +390,8 @@ impl fmt::Debug for Expr {
             Variable(v) => format!("{:?}", v),
             Unary(v) => format!("{:?}", v),
             Abort(v) => format!("{:?}", v),
+            Loop(v) => format!("{:?}", v),
+            LoopControl(v) => format!("{:?}", v),
         };
 
         write!(f, "Expr({})", value)
@@ -262,8 +401,8 @@ impl fmt::Debug for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Op, Query, Unary,
-            Variable,
+            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Loop, LoopControl,
+            Op, Query, Unary, Variable,
         };
 
         match self {
@@ -277,6 +416,8 @@ impl fmt::Display for Expr {
             Variable(v) => v.fmt(f),
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
+            Loop(v) => v.fmt(f),
+            LoopControl(v) => v.fmt(f),
         }
     }
 }
@@ -667,6 +808,111 @@ impl fmt::Debug for Predicate {
     }
 }
 
+// -----------------------------------------------------------------------------
+// loop
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, PartialEq)]
+pub enum Loop {
+    For(ForLoop),
+    While(WhileLoop),
+}
+
+impl fmt::Display for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loop::For(v) => v.fmt(f),
+            Loop::While(v) => v.fmt(f),
+        }
+    }
+}
+
+impl fmt::Debug for Loop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loop::For(v) => v.fmt(f),
+            Loop::While(v) => v.fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ForLoop {
+    /// Either the value, or the key (or index) and the value of each element.
+    pub variables: Vec<Node<Ident>>,
+    pub expr: Box<Node<Expr>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for ForLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("for ")?;
+
+        let mut iter = self.variables.iter().peekable();
+        while let Some(var) = iter.next() {
+            var.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        write!(f, " in {} {}", self.expr, self.block)
+    }
+}
+
+impl fmt::Debug for ForLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "For({:?} in {:?} {:?})",
+            self.variables, self.expr, self.block
+        )
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct WhileLoop {
+    pub predicate: Node<Predicate>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for WhileLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "while {} {}", self.predicate, self.block)
+    }
+}
+
+impl fmt::Debug for WhileLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "While({:?} {:?})", self.predicate, self.block)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoopControl {
+    Break,
+    Continue,
+}
+
+impl fmt::Display for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopControl::Break => f.write_str("break"),
+            LoopControl::Continue => f.write_str("continue"),
+        }
+    }
+}
+
+impl fmt::Debug for LoopControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopControl::Break => f.write_str("Break"),
+            LoopControl::Continue => f.write_str("Continue"),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // operation
 // -----------------------------------------------------------------------------
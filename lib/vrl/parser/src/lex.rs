@@ -116,6 +116,13 @@ impl DiagnosticMessage for Error {
                         r#""null""#,
                         r#""true""#,
                         r#""if""#,
+                        r#""fn""#,
+                        r#""import""#,
+                        r#""for""#,
+                        r#""in""#,
+                        r#""while""#,
+                        r#""break""#,
+                        r#""continue""#,
                     ];
                     let is_any_ident = any_ident
                         .iter()
@@ -253,6 +260,13 @@ pub enum Token<S> {
     False,
     True,
     Abort,
+    Fn,
+    Import,
+    For,
+    In,
+    While,
+    Break,
+    Continue,
 
     // tokens
     Colon,
@@ -312,11 +326,12 @@ pub enum Token<S> {
 impl<S> Token<S> {
     pub(crate) fn map<R>(self, f: impl Fn(S) -> R) -> Token<R> {
         use self::Token::{
-            Abort, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False, FloatLiteral,
-            FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace, LBracket, LParen,
-            LQuery, MergeEquals, Newline, Null, Operator, PathField, Question, RBrace, RBracket,
-            RParen, RQuery, RawStringLiteral, RegexLiteral, ReservedIdentifier, SemiColon,
-            StringLiteral, TimestampLiteral, True, Underscore,
+            Abort, Arrow, Bang, Break, Colon, Comma, Continue, Dot, Else, Equals, Escape, False,
+            FloatLiteral, Fn, For, FunctionCall, Identifier, If, Import, In, IntegerLiteral,
+            InvalidToken, LBrace, LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator,
+            PathField, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
+            ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
+            While,
         };
 
         match self {
@@ -345,6 +360,13 @@ impl<S> Token<S> {
             Null => Null,
             True => True,
             Abort => Abort,
+            Fn => Fn,
+            Import => Import,
+            For => For,
+            In => In,
+            While => While,
+            Break => Break,
+            Continue => Continue,
 
             // tokens
             Colon => Colon,
@@ -379,11 +401,12 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Token::{
-            Abort, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False, FloatLiteral,
-            FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace, LBracket, LParen,
-            LQuery, MergeEquals, Newline, Null, Operator, PathField, Question, RBrace, RBracket,
-            RParen, RQuery, RawStringLiteral, RegexLiteral, ReservedIdentifier, SemiColon,
-            StringLiteral, TimestampLiteral, True, Underscore,
+            Abort, Arrow, Bang, Break, Colon, Comma, Continue, Dot, Else, Equals, Escape, False,
+            FloatLiteral, Fn, For, FunctionCall, Identifier, If, Import, In, IntegerLiteral,
+            InvalidToken, LBrace, LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator,
+            PathField, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
+            ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
+            While,
         };
 
         let s = match *self {
@@ -406,6 +429,13 @@ where
             Null => "Null",
             True => "True",
             Abort => "Abort",
+            Fn => "Fn",
+            Import => "Import",
+            For => "For",
+            In => "In",
+            While => "While",
+            Break => "Break",
+            Continue => "Continue",
 
             // tokens
             Colon => "Colon",
@@ -440,7 +470,8 @@ impl<'input> Token<&'input str> {
     /// Returns either a literal, reserved, or generic identifier.
     fn ident(s: &'input str) -> Self {
         use Token::{
-            Abort, Else, False, Identifier, If, Null, PathField, ReservedIdentifier, True,
+            Abort, Break, Continue, Else, False, Fn, For, Identifier, If, Import, In, Null,
+            PathField, ReservedIdentifier, True, While,
         };
 
         match s {
@@ -450,14 +481,19 @@ impl<'input> Token<&'input str> {
             "false" => False,
             "null" => Null,
             "abort" => Abort,
+            "fn" => Fn,
+            "import" => Import,
+            "for" => For,
+            "in" => In,
+            "while" => While,
+            "break" => Break,
+            "continue" => Continue,
 
             // reserved identifiers
-            "array" | "bool" | "boolean" | "break" | "continue" | "do" | "emit" | "float"
-            | "for" | "forall" | "foreach" | "all" | "each" | "any" | "try" | "undefined"
-            | "int" | "integer" | "iter" | "object" | "regex" | "return" | "string"
-            | "traverse" | "timestamp" | "duration" | "unless" | "walk" | "while" | "loop" => {
-                ReservedIdentifier(s)
-            }
+            "array" | "bool" | "boolean" | "do" | "emit" | "float" | "forall" | "foreach"
+            | "all" | "each" | "any" | "try" | "undefined" | "int" | "integer" | "iter"
+            | "object" | "regex" | "return" | "string" | "traverse" | "timestamp" | "duration"
+            | "unless" | "walk" | "loop" => ReservedIdentifier(s),
 
             _ if s.contains('@') => PathField(s),
 
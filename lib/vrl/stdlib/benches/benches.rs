@@ -15,10 +15,15 @@ criterion_group!(
               assert_eq,
               r#bool,
               ceil,
+              community_id,
               compact,
               contains,
+              crc32,
               decode_base64,
+              decode_gzip,
               decode_percent,
+              decode_snappy,
+              decode_zstd,
               decrypt,
               // TODO: Cannot pass a Path to bench_function
               //del,
@@ -42,10 +47,12 @@ criterion_group!(
               get,
               get_env_var,
               get_hostname,
+              hmac,
               includes,
               int,
               ip_aton,
               ip_cidr_contains,
+              ip_classify,
               ip_ntoa,
               ip_ntop,
               ip_pton,
@@ -80,14 +87,17 @@ criterion_group!(
               parse_aws_alb_log,
               parse_aws_cloudwatch_log_subscription_message,
               parse_aws_vpc_flow_log,
+              parse_cef,
               parse_common_log,
               parse_csv,
               parse_duration,
+              parse_etld,
               parse_glog,
               parse_grok,
               parse_groks,
               parse_key_value,
               parse_klog,
+              parse_leef,
               parse_int,
               parse_json,
               parse_nginx_log,
@@ -100,6 +110,7 @@ criterion_group!(
               parse_tokens,
               parse_url,
               parse_user_agent,
+              parse_windows_event_xml,
               parse_xml,
               push,
               redact,
@@ -107,6 +118,7 @@ criterion_group!(
               replace,
               reverse_dns,
               round,
+              seahash,
               set,
               sha1,
               sha2,
@@ -138,9 +150,55 @@ criterion_group!(
               // TODO: value is dynamic so we cannot assert equality
               //uuidv4,
               upcase,
+              xxhash,
 );
 criterion_main!(benches);
 
+bench_function! {
+    community_id => vrl_stdlib::CommunityId;
+
+    tcp {
+        args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80],
+        want: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg=")
+    }
+}
+
+bench_function! {
+    crc32 => vrl_stdlib::Crc32;
+
+    default {
+        args: func_args![value: "foobar"],
+        want: Ok(2_666_930_069_i64)
+    }
+}
+
+bench_function! {
+    decode_gzip => vrl_stdlib::DecodeGzip;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x2b\xc8\x49\x4d\x2c\x4e\x55\x48\x49\x4d\xce\x4f\x49\x55\xc8\x4d\x05\x00\x9b\x19\xf3\xbd\x10\x00\x00\x00")],
+        want: Ok("please decode me")
+    }
+}
+
+bench_function! {
+    decode_snappy => vrl_stdlib::DecodeSnappy;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x10\x3cplease decode me")],
+        want: Ok("please decode me")
+    }
+}
+
+bench_function! {
+    decode_zstd => vrl_stdlib::DecodeZstd;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x28\xb5\x2f\xfd\x24\x10\x81\x00\x00\x70\x6c\x65\x61\x73\x65\x20\x64\x65\x63\x6f\x64\x65\x20\x6d\x65\xe2\xce\x0f\xa5")],
+        want: Ok("please decode me")
+    }
+}
+
 bench_function! {
     encrypt => vrl_stdlib::Encrypt;
 
@@ -519,6 +577,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    hmac => vrl_stdlib::Hmac;
+
+    default {
+        args: func_args![value: "Hello there", key: "super-secret-key"],
+        want: Ok("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662")
+    }
+}
+
 bench_function! {
     includes => vrl_stdlib::Includes;
 
@@ -528,6 +595,47 @@ bench_function! {
     }
 }
 
+bench_function! {
+    ip_classify => vrl_stdlib::IpClassify;
+
+    ipv4 {
+        args: func_args![value: "192.168.10.32"],
+        want: Ok(value!({
+            "version": 4,
+            "unspecified": false,
+            "loopback": false,
+            "private": true,
+            "link_local": false,
+            "multicast": false,
+            "reserved": false,
+            "global": false,
+        }))
+    }
+}
+
+bench_function! {
+    parse_etld => vrl_stdlib::ParseEtld;
+
+    literal {
+        args: func_args![value: "www.example.co.uk"],
+        want: Ok(value!({
+            "subdomain": "www",
+            "registered_domain": "example.co.uk",
+            "public_suffix": "co.uk",
+            "known_suffix": true,
+        }))
+    }
+}
+
+bench_function! {
+    seahash => vrl_stdlib::Seahash;
+
+    default {
+        args: func_args![value: "to be or not to be"],
+        want: Ok(1_988_685_042_348_123_509_i64)
+    }
+}
+
 bench_function! {
     set => vrl_stdlib::Set;
 
@@ -1316,6 +1424,27 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_cef => vrl_stdlib::ParseCef;
+
+    literal {
+        args: func_args![value: "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed."],
+        want: Ok(value!({
+            "cefVersion": 0,
+            "deviceVendor": "Security",
+            "deviceProduct": "threatmanager",
+            "deviceVersion": "1.0",
+            "deviceEventClassId": "100",
+            "name": "worm successfully stopped",
+            "severity": 10,
+            "src": "10.0.0.1",
+            "dst": "2.1.2.2",
+            "spt": "1232",
+            "msg": "Detected a threat. No action needed.",
+        })),
+    }
+}
+
 bench_function! {
     parse_common_log => vrl_stdlib::ParseCommonLog;
 
@@ -1507,6 +1636,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_leef => vrl_stdlib::ParseLeef;
+
+    literal {
+        args: func_args![value: "LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^usrName=joe"],
+        want: Ok(value!({
+            "leefVersion": "2.0",
+            "vendor": "Lancope",
+            "productName": "StealthWatch",
+            "productVersion": "1.0",
+            "eventId": "41",
+            "src": "10.0.1.8",
+            "dst": "10.0.0.5",
+            "usrName": "joe",
+        })),
+    }
+}
+
 bench_function! {
     parse_nginx_log => vrl_stdlib::ParseNginxLog;
 
@@ -1783,6 +1930,23 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_windows_event_xml => vrl_stdlib::ParseWindowsEventXml;
+
+    literal {
+        args: func_args![value: r#"<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Microsoft-Windows-Security-Auditing"/><EventID>4624</EventID><Level>0</Level><EventRecordID>8242</EventRecordID><Channel>Security</Channel><Computer>dc01.example.com</Computer></System><EventData><Data Name="TargetUserName">alice</Data><Data Name="LogonType">3</Data></EventData></Event>"#],
+        want: Ok(value!({
+            "provider": { "name": "Microsoft-Windows-Security-Auditing" },
+            "event_id": 4624,
+            "level": 0,
+            "event_record_id": 8242,
+            "channel": "Security",
+            "computer": "dc01.example.com",
+            "event_data": { "TargetUserName": "alice", "LogonType": "3" },
+        })),
+    }
+}
+
 bench_function! {
     parse_xml => vrl_stdlib::ParseXml;
 
@@ -2437,3 +2601,12 @@ bench_function! {
         want: Ok("FOO")
     }
 }
+
+bench_function! {
+    xxhash => vrl_stdlib::Xxhash;
+
+    default {
+        args: func_args![value: "foo"],
+        want: Ok(3_728_699_739_546_630_719_i64)
+    }
+}
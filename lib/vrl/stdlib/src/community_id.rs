@@ -0,0 +1,344 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use sha_1::{Digest, Sha1};
+use vrl::prelude::*;
+
+const ICMP: u8 = 1;
+const ICMPV6: u8 = 58;
+
+/// The ICMP message types with a counterpart in the opposite direction, such
+/// as an echo request and its reply.
+const ICMP_COUNTERPARTS: [(u16, u16); 10] = [
+    (8, 0),
+    (0, 8),
+    (13, 14),
+    (14, 13),
+    (15, 16),
+    (16, 15),
+    (10, 9),
+    (9, 10),
+    (17, 18),
+    (18, 17),
+];
+
+const ICMPV6_COUNTERPARTS: [(u16, u16); 12] = [
+    (128, 129),
+    (129, 128),
+    (133, 134),
+    (134, 133),
+    (135, 136),
+    (136, 135),
+    (130, 131),
+    (131, 130),
+    (139, 140),
+    (140, 139),
+    (144, 145),
+    (145, 144),
+];
+
+fn community_id(
+    source_ip: Value,
+    destination_ip: Value,
+    protocol: Value,
+    ports: Option<(Value, Value)>,
+    seed: Option<Value>,
+) -> Resolved {
+    let source_ip = parse_ip(source_ip)?;
+    let destination_ip = parse_ip(destination_ip)?;
+    let protocol =
+        u8::try_from(protocol.try_integer()?).map_err(|_| "protocol must be between 0 and 255")?;
+    let ports = match ports {
+        Some((source, destination)) => Some((port(source)?, port(destination)?)),
+        None => None,
+    };
+    let seed = match seed {
+        Some(seed) => {
+            u16::try_from(seed.try_integer()?).map_err(|_| "seed must be between 0 and 65535")?
+        }
+        None => 0,
+    };
+
+    let (source, destination) = match (source_ip, destination_ip) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            (source.octets().to_vec(), destination.octets().to_vec())
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination)) => {
+            (source.octets().to_vec(), destination.octets().to_vec())
+        }
+        _ => return Err("source and destination IP addresses must be of the same version".into()),
+    };
+
+    Ok(hash(seed, source, destination, protocol, ports).into())
+}
+
+fn hash(
+    seed: u16,
+    mut source: Vec<u8>,
+    mut destination: Vec<u8>,
+    protocol: u8,
+    mut ports: Option<(u16, u16)>,
+) -> String {
+    // The ports of ICMP flows are the message type and code. Messages with a
+    // counterpart use the type of the counterpart as the destination port, so
+    // that both directions of the flow hash the same. Other messages are
+    // one-way, and hashed in the given order.
+    let counterparts = match protocol {
+        ICMP => Some(&ICMP_COUNTERPARTS[..]),
+        ICMPV6 => Some(&ICMPV6_COUNTERPARTS[..]),
+        _ => None,
+    };
+    let mut one_way = false;
+    if let (Some(counterparts), Some((message_type, code))) = (counterparts, ports.as_mut()) {
+        match counterparts.iter().find(|(from, _)| from == message_type) {
+            Some((_, counterpart)) => *code = *counterpart,
+            None => one_way = true,
+        }
+    }
+
+    // The flow is hashed in a canonical order of its endpoints, so that both
+    // directions have the same ID.
+    let ordered = source < destination
+        || (source == destination
+            && ports.map_or(true, |(source, destination)| source < destination));
+    if !one_way && !ordered {
+        std::mem::swap(&mut source, &mut destination);
+        ports = ports.map(|(source, destination)| (destination, source));
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(seed.to_be_bytes());
+    hasher.update(source);
+    hasher.update(destination);
+    hasher.update([protocol, 0]);
+    if let Some((source, destination)) = ports {
+        hasher.update(source.to_be_bytes());
+        hasher.update(destination.to_be_bytes());
+    }
+
+    format!("1:{}", base64::encode(hasher.finalize()))
+}
+
+fn parse_ip(value: Value) -> Result<IpAddr> {
+    let ip = value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse IP address: {}", err))?;
+
+    Ok(ip)
+}
+
+fn port(value: Value) -> Result<u16> {
+    let port =
+        u16::try_from(value.try_integer()?).map_err(|_| "port must be between 0 and 65535")?;
+
+    Ok(port)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CommunityId;
+
+impl Function for CommunityId {
+    fn identifier(&self) -> &'static str {
+        "community_id"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "source_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "destination_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "protocol",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "source_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "destination_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "TCP",
+                source: r#"community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6)"#,
+                result: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            },
+            Example {
+                title: "with seed",
+                source: r#"community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6, seed: 1)"#,
+                result: Ok("1:3V71V58M3Ksw/yuFALMcW0LAHvc="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let source_ip = arguments.required("source_ip");
+        let destination_ip = arguments.required("destination_ip");
+        let protocol = arguments.required("protocol");
+        let source_port = arguments.optional("source_port");
+        let destination_port = arguments.optional("destination_port");
+        let seed = arguments.optional("seed");
+
+        Ok(Box::new(CommunityIdFn {
+            source_ip,
+            destination_ip,
+            protocol,
+            source_port,
+            destination_port,
+            seed,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CommunityIdFn {
+    source_ip: Box<dyn Expression>,
+    destination_ip: Box<dyn Expression>,
+    protocol: Box<dyn Expression>,
+    source_port: Option<Box<dyn Expression>>,
+    destination_port: Option<Box<dyn Expression>>,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl Expression for CommunityIdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let source_ip = self.source_ip.resolve(ctx)?;
+        let destination_ip = self.destination_ip.resolve(ctx)?;
+        let protocol = self.protocol.resolve(ctx)?;
+        let source_port = self
+            .source_port
+            .as_ref()
+            .map(|port| port.resolve(ctx))
+            .transpose()?;
+        let destination_port = self
+            .destination_port
+            .as_ref()
+            .map(|port| port.resolve(ctx))
+            .transpose()?;
+        let seed = self
+            .seed
+            .as_ref()
+            .map(|seed| seed.resolve(ctx))
+            .transpose()?;
+
+        let ports = match (source_port, destination_port) {
+            (Some(source), Some(destination)) => Some((source, destination)),
+            (None, None) => None,
+            _ => return Err("source_port and destination_port must be given together".into()),
+        };
+
+        community_id(source_ip, destination_ip, protocol, ports, seed)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        community_id => CommunityId;
+
+        tcp {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80],
+            want: Ok(value!("1:LQU9qZlK+B5F3KDmev6m5PMibrg=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        tcp_reverse {
+            args: func_args![source_ip: "66.35.250.204", destination_ip: "128.232.110.120", protocol: 6, source_port: 80, destination_port: 34855],
+            want: Ok(value!("1:LQU9qZlK+B5F3KDmev6m5PMibrg=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        seed {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", protocol: 6, source_port: 34855, destination_port: 80, seed: 1],
+            want: Ok(value!("1:3V71V58M3Ksw/yuFALMcW0LAHvc=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        udp_same_address {
+            args: func_args![source_ip: "10.0.0.1", destination_ip: "10.0.0.2", protocol: 17, source_port: 53, destination_port: 53],
+            want: Ok(value!("1:ioul/+8C+vRgpVEpDJl8kvsmTcQ=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_echo_request {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 1, source_port: 8, destination_port: 0],
+            want: Ok(value!("1:crodRHL2FEsHjbv3UkRrfbs4bZ0=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_echo_reply {
+            args: func_args![source_ip: "5.6.7.8", destination_ip: "1.2.3.4", protocol: 1, source_port: 0, destination_port: 0],
+            want: Ok(value!("1:crodRHL2FEsHjbv3UkRrfbs4bZ0=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_one_way {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 1, source_port: 3, destination_port: 1],
+            want: Ok(value!("1:W4aFotGvd33My5d10oV5afM5O+E=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6 {
+            args: func_args![source_ip: "fe80::1", destination_ip: "fe80::2", protocol: 58, source_port: 128, destination_port: 0],
+            want: Ok(value!("1:3H+V/JVcqh/phPel5utt4dOKqOU=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        without_ports {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 47],
+            want: Ok(value!("1:+mluqz76jObh6dJLwnhX08n5St4=")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mixed_versions {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "::1", protocol: 6, source_port: 1, destination_port: 2],
+            want: Err("source and destination IP addresses must be of the same version"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        missing_port {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 1],
+            want: Err("source_port and destination_port must be given together"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_port {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", protocol: 6, source_port: 1, destination_port: 65536],
+            want: Err("port must be between 0 and 65535"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
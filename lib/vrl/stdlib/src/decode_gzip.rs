@@ -0,0 +1,120 @@
+use std::io::Read;
+
+use ::value::Value;
+use flate2::read::MultiGzDecoder;
+use vrl::prelude::*;
+
+use crate::util::{check_decompressed_size, MAX_DECOMPRESSED_SIZE};
+
+fn decode_gzip(value: Value, max_size: usize) -> Resolved {
+    let value = value.try_bytes()?;
+    let mut decompressed = Vec::new();
+
+    // Concatenated gzip members, such as in batched CloudWatch Logs payloads,
+    // are decompressed into a single value. Reading stops one byte past the
+    // maximum size, which is enough to tell the value is too large.
+    MultiGzDecoder::new(&value[..])
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|err| format!("unable to decode value with gzip decoder: {}", err))?;
+    check_decompressed_size(decompressed.len(), max_size)?;
+
+    Ok(Value::Bytes(decompressed.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeGzip;
+
+impl Function for DecodeGzip {
+    fn identifier(&self) -> &'static str {
+        "decode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeGzipFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_gzip!(decode_base64!("H4sIAAAAAAACAyvISU0sTlVISU3OT0lVyE0FAJsZ870QAAAA"))"#,
+            result: Ok("please decode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeGzipFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_gzip(value, MAX_DECOMPRESSED_SIZE)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid() {
+        let error = decode_gzip(value!("please decode me"), MAX_DECOMPRESSED_SIZE).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("unable to decode value with gzip decoder: "));
+    }
+
+    #[test]
+    fn too_large() {
+        let value = Value::from(Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x2b\xc8\x49\x4d\x2c\x4e\x55\x48\x49\x4d\xce\x4f\x49\x55\xc8\x4d\x05\x00\x9b\x19\xf3\xbd\x10\x00\x00\x00"));
+
+        assert_eq!(
+            decode_gzip(value.clone(), 16),
+            Ok(value!("please decode me"))
+        );
+        assert_eq!(
+            decode_gzip(value, 15).unwrap_err().to_string(),
+            "decompressed value exceeds the maximum size of 15 bytes"
+        );
+    }
+
+    test_function![
+        decode_gzip => DecodeGzip;
+
+        single_member {
+            args: func_args![value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x2b\xc8\x49\x4d\x2c\x4e\x55\x48\x49\x4d\xce\x4f\x49\x55\xc8\x4d\x05\x00\x9b\x19\xf3\xbd\x10\x00\x00\x00")],
+            want: Ok(value!("please decode me")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multiple_members {
+            args: func_args![value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcb\x48\xcd\xc9\xc9\x57\x00\x00\xf6\xf9\x81\xed\x06\x00\x00\x00\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x2b\xcf\x2f\xca\x49\x01\x00\x43\x11\x77\x3a\x05\x00\x00\x00")],
+            want: Ok(value!("hello world")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,107 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{check_decompressed_size, MAX_DECOMPRESSED_SIZE};
+
+fn decode_snappy(value: Value, max_size: usize) -> Resolved {
+    let value = value.try_bytes()?;
+    let error = |err| format!("unable to decode value with snappy decoder: {}", err);
+
+    // The raw format, such as in Prometheus remote write requests, rather than
+    // the framed format. It starts with the decompressed size, which is checked
+    // before anything is allocated.
+    check_decompressed_size(snap::raw::decompress_len(&value).map_err(error)?, max_size)?;
+    let decompressed = snap::raw::Decoder::new()
+        .decompress_vec(&value)
+        .map_err(error)?;
+
+    Ok(Value::Bytes(decompressed.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeSnappy;
+
+impl Function for DecodeSnappy {
+    fn identifier(&self) -> &'static str {
+        "decode_snappy"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeSnappyFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_snappy!(decode_base64!("EDxwbGVhc2UgZGVjb2RlIG1l"))"#,
+            result: Ok("please decode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeSnappyFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeSnappyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_snappy(value, MAX_DECOMPRESSED_SIZE)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid() {
+        let error = decode_snappy(value!(""), MAX_DECOMPRESSED_SIZE).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("unable to decode value with snappy decoder: "));
+    }
+
+    #[test]
+    fn too_large() {
+        let value = Value::from(Bytes::from_static(b"\x05\x10hello"));
+
+        assert_eq!(decode_snappy(value.clone(), 5), Ok(value!("hello")));
+        assert_eq!(
+            decode_snappy(value, 4).unwrap_err().to_string(),
+            "decompressed value exceeds the maximum size of 4 bytes"
+        );
+    }
+
+    test_function![
+        decode_snappy => DecodeSnappy;
+
+        raw {
+            args: func_args![value: Bytes::from_static(b"\x05\x10hello")],
+            want: Ok(value!("hello")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
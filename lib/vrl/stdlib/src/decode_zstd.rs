@@ -0,0 +1,115 @@
+use std::io::Read;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{check_decompressed_size, MAX_DECOMPRESSED_SIZE};
+
+fn decode_zstd(value: Value, max_size: usize) -> Resolved {
+    let value = value.try_bytes()?;
+    let mut decompressed = Vec::new();
+
+    // Reading stops one byte past the maximum size, which is enough to tell
+    // the value is too large.
+    zstd::stream::read::Decoder::new(&value[..])
+        .and_then(|decoder| {
+            decoder
+                .take(max_size as u64 + 1)
+                .read_to_end(&mut decompressed)
+        })
+        .map_err(|err| format!("unable to decode value with zstd decoder: {}", err))?;
+    check_decompressed_size(decompressed.len(), max_size)?;
+
+    Ok(Value::Bytes(decompressed.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZstd;
+
+impl Function for DecodeZstd {
+    fn identifier(&self) -> &'static str {
+        "decode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(DecodeZstdFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zstd!(decode_base64!("KLUv/SQQgQAAcGxlYXNlIGRlY29kZSBtZeLOD6U="))"#,
+            result: Ok("please decode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZstdFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for DecodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        decode_zstd(value, MAX_DECOMPRESSED_SIZE)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn invalid() {
+        let error = decode_zstd(value!("please decode me"), MAX_DECOMPRESSED_SIZE).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("unable to decode value with zstd decoder: "));
+    }
+
+    #[test]
+    fn too_large() {
+        let value = Value::from(Bytes::from_static(b"\x28\xb5\x2f\xfd\x24\x10\x81\x00\x00\x70\x6c\x65\x61\x73\x65\x20\x64\x65\x63\x6f\x64\x65\x20\x6d\x65\xe2\xce\x0f\xa5"));
+
+        assert_eq!(
+            decode_zstd(value.clone(), 16),
+            Ok(value!("please decode me"))
+        );
+        assert_eq!(
+            decode_zstd(value, 15).unwrap_err().to_string(),
+            "decompressed value exceeds the maximum size of 15 bytes"
+        );
+    }
+
+    test_function![
+        decode_zstd => DecodeZstd;
+
+        frame {
+            args: func_args![value: Bytes::from_static(b"\x28\xb5\x2f\xfd\x24\x10\x81\x00\x00\x70\x6c\x65\x61\x73\x65\x20\x64\x65\x63\x6f\x64\x65\x20\x6d\x65\xe2\xce\x0f\xa5")],
+            want: Ok(value!("please decode me")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,141 @@
+use std::io::Write;
+
+use ::value::Value;
+use flate2::{write::GzEncoder, Compression};
+use vrl::prelude::*;
+
+const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+const MAX_COMPRESSION_LEVEL: u32 = 9;
+
+fn encode_gzip(value: Value, compression_level: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let level = match compression_level {
+        Some(level) => u32::try_from(level.try_integer()?)
+            .ok()
+            .filter(|level| *level <= MAX_COMPRESSION_LEVEL)
+            .ok_or_else(|| {
+                format!(
+                    "compression level must be between 0 and {}",
+                    MAX_COMPRESSION_LEVEL
+                )
+            })?,
+        None => DEFAULT_COMPRESSION_LEVEL,
+    };
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
+    encoder
+        .write_all(&value)
+        .expect("compressing into memory can't fail");
+    let compressed = encoder
+        .finish()
+        .expect("compressing into memory can't fail");
+
+    Ok(Value::Bytes(compressed.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeGzip;
+
+impl Function for EncodeGzip {
+    fn identifier(&self) -> &'static str {
+        "encode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        Ok(Box::new(EncodeGzipFn {
+            value,
+            compression_level,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "round trip",
+            source: r#"decode_gzip!(encode_gzip!("please encode me", compression_level: 9))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeGzipFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl Expression for EncodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|level| level.resolve(ctx))
+            .transpose()?;
+
+        encode_gzip(value, compression_level)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Only an invalid compression level makes compression fail.
+        TypeDef::bytes().with_fallibility(self.compression_level.is_some())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    fn decompress(value: Value) -> String {
+        let mut decompressed = String::new();
+        GzDecoder::new(&value.try_bytes().unwrap()[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        decompressed
+    }
+
+    #[test]
+    fn round_trip() {
+        for level in [None, Some(value!(0)), Some(value!(9))] {
+            let compressed = encode_gzip(value!("please encode me"), level).unwrap();
+
+            assert_eq!(decompress(compressed), "please encode me");
+        }
+    }
+
+    test_function![
+        encode_gzip => EncodeGzip;
+
+        invalid_compression_level {
+            args: func_args![value: value!("please encode me"), compression_level: value!(10)],
+            want: Err("compression level must be between 0 and 9"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,124 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+const MIN_COMPRESSION_LEVEL: i64 = 1;
+const MAX_COMPRESSION_LEVEL: i64 = 22;
+
+fn encode_zstd(value: Value, compression_level: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let level = match compression_level {
+        Some(level) => {
+            let level = level.try_integer()?;
+            if !(MIN_COMPRESSION_LEVEL..=MAX_COMPRESSION_LEVEL).contains(&level) {
+                return Err(format!(
+                    "compression level must be between {} and {}",
+                    MIN_COMPRESSION_LEVEL, MAX_COMPRESSION_LEVEL
+                )
+                .into());
+            }
+            level as i32
+        }
+        None => zstd::DEFAULT_COMPRESSION_LEVEL,
+    };
+
+    let compressed =
+        zstd::stream::encode_all(&value[..], level).expect("compressing into memory can't fail");
+
+    Ok(Value::Bytes(compressed.into()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeZstd;
+
+impl Function for EncodeZstd {
+    fn identifier(&self) -> &'static str {
+        "encode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        Ok(Box::new(EncodeZstdFn {
+            value,
+            compression_level,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "round trip",
+            source: r#"decode_zstd!(encode_zstd!("please encode me", compression_level: 9))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeZstdFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl Expression for EncodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|level| level.resolve(ctx))
+            .transpose()?;
+
+        encode_zstd(value, compression_level)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        // Only an invalid compression level makes compression fail.
+        TypeDef::bytes().with_fallibility(self.compression_level.is_some())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for level in [None, Some(value!(1)), Some(value!(22))] {
+            let compressed = encode_zstd(value!("please encode me"), level).unwrap();
+            let decompressed = zstd::stream::decode_all(&compressed.try_bytes().unwrap()[..]);
+
+            assert_eq!(decompressed.unwrap(), b"please encode me");
+        }
+    }
+
+    test_function![
+        encode_zstd => EncodeZstd;
+
+        invalid_compression_level {
+            args: func_args![value: value!("please encode me"), compression_level: value!(0)],
+            want: Err("compression level must be between 1 and 22"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
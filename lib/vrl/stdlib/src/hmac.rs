@@ -0,0 +1,138 @@
+use ::value::Value;
+use hmac_lib::{digest::KeyInit, Hmac as HmacImpl, Mac};
+use sha_2::{Sha256, Sha512};
+use vrl::prelude::*;
+
+fn hmac(value: Value, key: Value, algorithm: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+    let mac = match algorithm.as_ref() {
+        b"SHA-256" => encode::<HmacImpl<Sha256>>(&value, &key),
+        b"SHA-512" => encode::<HmacImpl<Sha512>>(&value, &key),
+        _ => unreachable!("enum invariant"),
+    };
+    Ok(mac.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hmac;
+
+fn algorithms() -> Vec<Value> {
+    vec![value!("SHA-256"), value!("SHA-512")]
+}
+
+impl Function for Hmac {
+    fn identifier(&self) -> &'static str {
+        "hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default algorithm",
+                source: r#"hmac("Hello there", "super-secret-key")"#,
+                result: Ok("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"),
+            },
+            Example {
+                title: "custom algorithm",
+                source: r#"hmac("Hello there", "super-secret-key", algorithm: "SHA-512")"#,
+                result: Ok("20c92a076b22f3432bfe918dfe4314d0243c8508643ab1d7d779a5658481ce2fd40821044010e978c136517f58ffc8e6c1f25830738858303cf0a71073c60e96"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("SHA-256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+
+        Ok(Box::new(HmacFn {
+            value,
+            key,
+            algorithm,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Bytes,
+}
+
+impl Expression for HmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+
+        hmac(value, key, &self.algorithm)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[inline]
+fn encode<T: Mac + KeyInit>(value: &[u8], key: &[u8]) -> String {
+    let mut mac = <T as Mac>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(value);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        hmac => Hmac;
+
+        hmac_sha256 {
+            args: func_args![value: "Hello there", key: "super-secret-key"],
+            want: Ok("78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha512 {
+            args: func_args![value: "Hello there", key: "super-secret-key", algorithm: "SHA-512"],
+            want: Ok("20c92a076b22f3432bfe918dfe4314d0243c8508643ab1d7d779a5658481ce2fd40821044010e978c136517f58ffc8e6c1f25830738858303cf0a71073c60e96"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        rfc_4231_long_key {
+            args: func_args![value: "Test Using Larger Than Block-Size Key - Hash Key First", key: Bytes::from(vec![0xaa; 131])],
+            want: Ok("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use ::value::Value;
+use vrl::prelude::*;
+
+fn ip_classify(value: Value) -> Resolved {
+    let ip: IpAddr = value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse IP address: {}", err))?;
+
+    let (version, class) = match ip {
+        IpAddr::V4(ip) => (4, classify_ipv4(ip)),
+        // IPv4 addresses mapped to IPv6 are classified as the IPv4 address.
+        IpAddr::V6(ip) => (
+            6,
+            ipv4_mapped(ip).map_or_else(|| classify_ipv6(ip), classify_ipv4),
+        ),
+    };
+
+    Ok(classified(version, class))
+}
+
+/// The classification of an address, with a flag per class, and whether the
+/// address is globally routable, that is in none of the classes.
+fn classified(version: i64, class: Option<&str>) -> Value {
+    let mut map = BTreeMap::from([("version".to_owned(), Value::from(version))]);
+    for name in CLASSES {
+        map.insert(name.to_owned(), (class == Some(name)).into());
+    }
+    map.insert("global".to_owned(), class.is_none().into());
+
+    map.into()
+}
+
+/// The classes of IP addresses that aren't globally routable. An address is
+/// in at most one of them.
+const CLASSES: [&str; 6] = [
+    "unspecified",
+    "loopback",
+    "private",
+    "link_local",
+    "multicast",
+    "reserved",
+];
+
+fn classify_ipv4(ip: Ipv4Addr) -> Option<&'static str> {
+    let octets = ip.octets();
+
+    if ip.is_unspecified() {
+        Some("unspecified")
+    } else if ip.is_loopback() {
+        Some("loopback")
+    } else if ip.is_private() {
+        Some("private")
+    } else if ip.is_link_local() {
+        Some("link_local")
+    } else if ip.is_multicast() {
+        Some("multicast")
+    } else if matches!(
+        octets,
+        // "This network", and the future use and limited broadcast ranges.
+        [0 | 240..=255, ..]
+        // Shared address space for carrier-grade NAT.
+        | [100, 64..=127, ..]
+        // IETF protocol assignments, and the documentation ranges.
+        | [192, 0, 0 | 2, _]
+        | [198, 51, 100, _]
+        | [203, 0, 113, _]
+        // Benchmarking.
+        | [198, 18..=19, ..]
+    ) {
+        Some("reserved")
+    } else {
+        None
+    }
+}
+
+fn classify_ipv6(ip: Ipv6Addr) -> Option<&'static str> {
+    let segments = ip.segments();
+
+    if ip.is_unspecified() {
+        Some("unspecified")
+    } else if ip.is_loopback() {
+        Some("loopback")
+    } else if segments[0] & 0xfe00 == 0xfc00 {
+        // Unique local addresses.
+        Some("private")
+    } else if segments[0] & 0xffc0 == 0xfe80 {
+        Some("link_local")
+    } else if ip.is_multicast() {
+        Some("multicast")
+    } else if matches!(
+        segments,
+        // The discard prefix.
+        [0x0100, 0, 0, 0, ..]
+        // IETF protocol assignments, and the documentation range.
+        | [0x2001, 0..=0x01ff, ..]
+        | [0x2001, 0x0db8, ..]
+    ) {
+        Some("reserved")
+    } else {
+        None
+    }
+}
+
+fn ipv4_mapped(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    match ip.octets() {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => Some(Ipv4Addr::new(a, b, c, d)),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpClassify;
+
+impl Function for IpClassify {
+    fn identifier(&self) -> &'static str {
+        "ip_classify"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "private IPv4 address",
+                source: r#"ip_classify!("192.168.10.32")"#,
+                result: Ok(indoc! {r#"{
+                    "version": 4,
+                    "unspecified": false,
+                    "loopback": false,
+                    "private": true,
+                    "link_local": false,
+                    "multicast": false,
+                    "reserved": false,
+                    "global": false
+                }"#}),
+            },
+            Example {
+                title: "global IPv6 address",
+                source: r#"ip_classify!("2606:4700:4700::1111")"#,
+                result: Ok(indoc! {r#"{
+                    "version": 6,
+                    "unspecified": false,
+                    "loopback": false,
+                    "private": false,
+                    "link_local": false,
+                    "multicast": false,
+                    "reserved": false,
+                    "global": true
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(IpClassifyFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpClassifyFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for IpClassifyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        ip_classify(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("version".into(), Kind::integer()),
+        ("unspecified".into(), Kind::boolean()),
+        ("loopback".into(), Kind::boolean()),
+        ("private".into(), Kind::boolean()),
+        ("link_local".into(), Kind::boolean()),
+        ("multicast".into(), Kind::boolean()),
+        ("reserved".into(), Kind::boolean()),
+        ("global".into(), Kind::boolean()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_classify => IpClassify;
+
+        ipv4_private {
+            args: func_args![value: "172.16.4.1"],
+            want: Ok(classified(4, Some("private"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv4_loopback {
+            args: func_args![value: "127.0.0.53"],
+            want: Ok(classified(4, Some("loopback"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv4_link_local {
+            args: func_args![value: "169.254.169.254"],
+            want: Ok(classified(4, Some("link_local"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv4_multicast {
+            args: func_args![value: "239.255.255.250"],
+            want: Ok(classified(4, Some("multicast"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv4_reserved {
+            args: func_args![value: "198.51.100.7"],
+            want: Ok(classified(4, Some("reserved"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv4_broadcast {
+            args: func_args![value: "255.255.255.255"],
+            want: Ok(classified(4, Some("reserved"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv4_global {
+            args: func_args![value: "8.8.8.8"],
+            want: Ok(classified(4, None)),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv6_unspecified {
+            args: func_args![value: "::"],
+            want: Ok(classified(6, Some("unspecified"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv6_unique_local {
+            args: func_args![value: "fd12:3456:789a::1"],
+            want: Ok(classified(6, Some("private"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv6_link_local {
+            args: func_args![value: "fe80::1"],
+            want: Ok(classified(6, Some("link_local"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv6_documentation {
+            args: func_args![value: "2001:db8::1"],
+            want: Ok(classified(6, Some("reserved"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv6_mapped_ipv4 {
+            args: func_args![value: "::ffff:10.0.0.1"],
+            want: Ok(classified(6, Some("private"))),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        ipv6_global {
+            args: func_args![value: "2606:4700:4700::1111"],
+            want: Ok(classified(6, None)),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "192.168.0"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -40,14 +40,24 @@ mod assert_eq;
 mod boolean;
 #[cfg(feature = "ceil")]
 mod ceil;
+#[cfg(feature = "community_id")]
+mod community_id;
 #[cfg(feature = "compact")]
 mod compact;
 #[cfg(feature = "contains")]
 mod contains;
+#[cfg(feature = "crc32")]
+mod crc32;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
+#[cfg(feature = "decode_gzip")]
+mod decode_gzip;
 #[cfg(feature = "decode_percent")]
 mod decode_percent;
+#[cfg(feature = "decode_snappy")]
+mod decode_snappy;
+#[cfg(feature = "decode_zstd")]
+mod decode_zstd;
 #[cfg(feature = "decrypt")]
 mod decrypt;
 #[cfg(feature = "del")]
@@ -56,6 +66,8 @@ mod del;
 mod downcase;
 #[cfg(feature = "encode_base64")]
 mod encode_base64;
+#[cfg(feature = "encode_gzip")]
+mod encode_gzip;
 #[cfg(feature = "encode_json")]
 mod encode_json;
 #[cfg(feature = "encode_key_value")]
@@ -64,6 +76,8 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_zstd")]
+mod encode_zstd;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
@@ -94,6 +108,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "hmac")]
+mod hmac;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -102,6 +118,8 @@ mod integer;
 mod ip_aton;
 #[cfg(feature = "ip_cidr_contains")]
 mod ip_cidr_contains;
+#[cfg(feature = "ip_classify")]
+mod ip_classify;
 #[cfg(feature = "ip_ntoa")]
 mod ip_ntoa;
 #[cfg(feature = "ip_ntop")]
@@ -180,12 +198,16 @@ mod parse_aws_alb_log;
 mod parse_aws_cloudwatch_log_subscription_message;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
+#[cfg(feature = "parse_cef")]
+mod parse_cef;
 #[cfg(feature = "parse_common_log")]
 mod parse_common_log;
 #[cfg(feature = "parse_csv")]
 mod parse_csv;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
+#[cfg(feature = "parse_etld")]
+mod parse_etld;
 #[cfg(feature = "parse_glog")]
 mod parse_glog;
 #[cfg(feature = "parse_grok")]
@@ -200,6 +222,8 @@ mod parse_json;
 mod parse_key_value;
 #[cfg(feature = "parse_klog")]
 mod parse_klog;
+#[cfg(feature = "parse_leef")]
+mod parse_leef;
 #[cfg(feature = "parse_linux_authorization")]
 mod parse_linux_authorization;
 #[cfg(feature = "parse_logfmt")]
@@ -224,6 +248,8 @@ mod parse_tokens;
 mod parse_url;
 #[cfg(feature = "parse_user_agent")]
 mod parse_user_agent;
+#[cfg(feature = "parse_windows_event_xml")]
+mod parse_windows_event_xml;
 #[cfg(feature = "parse_xml")]
 mod parse_xml;
 #[cfg(feature = "push")]
@@ -240,6 +266,8 @@ mod replace;
 mod reverse_dns;
 #[cfg(feature = "round")]
 mod round;
+#[cfg(feature = "seahash")]
+mod seahash;
 #[cfg(feature = "set")]
 mod set;
 #[cfg(feature = "sha1")]
@@ -302,6 +330,8 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "xxhash")]
+mod xxhash;
 
 // -----------------------------------------------------------------------------
 
@@ -315,14 +345,24 @@ pub use assert_eq::AssertEq;
 pub use boolean::Boolean;
 #[cfg(feature = "ceil")]
 pub use ceil::Ceil;
+#[cfg(feature = "community_id")]
+pub use community_id::CommunityId;
 #[cfg(feature = "compact")]
 pub use compact::Compact;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
+#[cfg(feature = "crc32")]
+pub use crc32::Crc32;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
+#[cfg(feature = "decode_gzip")]
+pub use decode_gzip::DecodeGzip;
 #[cfg(feature = "decode_percent")]
 pub use decode_percent::DecodePercent;
+#[cfg(feature = "decode_snappy")]
+pub use decode_snappy::DecodeSnappy;
+#[cfg(feature = "decode_zstd")]
+pub use decode_zstd::DecodeZstd;
 #[cfg(feature = "decrypt")]
 pub use decrypt::Decrypt;
 #[cfg(feature = "del")]
@@ -331,6 +371,8 @@ pub use del::Del;
 pub use downcase::Downcase;
 #[cfg(feature = "encode_base64")]
 pub use encode_base64::EncodeBase64;
+#[cfg(feature = "encode_gzip")]
+pub use encode_gzip::EncodeGzip;
 #[cfg(feature = "encode_json")]
 pub use encode_json::EncodeJson;
 #[cfg(feature = "encode_key_value")]
@@ -339,6 +381,8 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_zstd")]
+pub use encode_zstd::EncodeZstd;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
@@ -369,6 +413,8 @@ pub use get::Get;
 pub use get_env_var::GetEnvVar;
 #[cfg(feature = "get_hostname")]
 pub use get_hostname::GetHostname;
+#[cfg(feature = "hmac")]
+pub use hmac::Hmac;
 #[cfg(feature = "includes")]
 pub use includes::Includes;
 #[cfg(feature = "integer")]
@@ -377,6 +423,8 @@ pub use integer::Integer;
 pub use ip_aton::IpAton;
 #[cfg(feature = "ip_cidr_contains")]
 pub use ip_cidr_contains::IpCidrContains;
+#[cfg(feature = "ip_classify")]
+pub use ip_classify::IpClassify;
 #[cfg(feature = "ip_ntoa")]
 pub use ip_ntoa::IpNtoa;
 #[cfg(feature = "ip_ntop")]
@@ -445,12 +493,16 @@ pub use parse_aws_alb_log::ParseAwsAlbLog;
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
+#[cfg(feature = "parse_cef")]
+pub use parse_cef::ParseCef;
 #[cfg(feature = "parse_common_log")]
 pub use parse_common_log::ParseCommonLog;
 #[cfg(feature = "parse_csv")]
 pub use parse_csv::ParseCsv;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
+#[cfg(feature = "parse_etld")]
+pub use parse_etld::ParseEtld;
 #[cfg(feature = "parse_glog")]
 pub use parse_glog::ParseGlog;
 #[cfg(feature = "parse_grok")]
@@ -465,6 +517,8 @@ pub use parse_json::ParseJson;
 pub use parse_key_value::ParseKeyValue;
 #[cfg(feature = "parse_klog")]
 pub use parse_klog::ParseKlog;
+#[cfg(feature = "parse_leef")]
+pub use parse_leef::ParseLeef;
 #[cfg(feature = "parse_linux_authorization")]
 pub use parse_linux_authorization::ParseLinuxAuthorization;
 #[cfg(feature = "parse_logfmt")]
@@ -489,6 +543,8 @@ pub use parse_tokens::ParseTokens;
 pub use parse_url::ParseUrl;
 #[cfg(feature = "parse_user_agent")]
 pub use parse_user_agent::ParseUserAgent;
+#[cfg(feature = "parse_windows_event_xml")]
+pub use parse_windows_event_xml::ParseWindowsEventXml;
 #[cfg(feature = "parse_xml")]
 pub use parse_xml::ParseXml;
 #[cfg(feature = "push")]
@@ -507,6 +563,8 @@ pub use replace::Replace;
 pub use reverse_dns::ReverseDns;
 #[cfg(feature = "round")]
 pub use round::Round;
+#[cfg(feature = "seahash")]
+pub use seahash::Seahash;
 #[cfg(feature = "set")]
 pub use set::Set;
 #[cfg(feature = "sha2")]
@@ -567,6 +625,8 @@ pub use unnest::Unnest;
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "xxhash")]
+pub use xxhash::Xxhash;
 
 #[cfg(feature = "array")]
 pub use crate::array::Array;
@@ -590,14 +650,24 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Boolean),
         #[cfg(feature = "ceil")]
         Box::new(Ceil),
+        #[cfg(feature = "community_id")]
+        Box::new(CommunityId),
         #[cfg(feature = "compact")]
         Box::new(Compact),
         #[cfg(feature = "contains")]
         Box::new(Contains),
+        #[cfg(feature = "crc32")]
+        Box::new(Crc32),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
+        #[cfg(feature = "decode_gzip")]
+        Box::new(DecodeGzip),
         #[cfg(feature = "decode_percent")]
         Box::new(DecodePercent),
+        #[cfg(feature = "decode_snappy")]
+        Box::new(DecodeSnappy),
+        #[cfg(feature = "decode_zstd")]
+        Box::new(DecodeZstd),
         #[cfg(feature = "decrypt")]
         Box::new(Decrypt),
         #[cfg(feature = "del")]
@@ -606,6 +676,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Downcase),
         #[cfg(feature = "encode_base64")]
         Box::new(EncodeBase64),
+        #[cfg(feature = "encode_gzip")]
+        Box::new(EncodeGzip),
         #[cfg(feature = "encode_json")]
         Box::new(EncodeJson),
         #[cfg(feature = "encode_key_value")]
@@ -614,6 +686,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_zstd")]
+        Box::new(EncodeZstd),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
@@ -644,6 +718,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "hmac")]
+        Box::new(Hmac),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -652,6 +728,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IpAton),
         #[cfg(feature = "ip_cidr_contains")]
         Box::new(IpCidrContains),
+        #[cfg(feature = "ip_classify")]
+        Box::new(IpClassify),
         #[cfg(feature = "ip_ntoa")]
         Box::new(IpNtoa),
         #[cfg(feature = "ip_ntop")]
@@ -726,12 +804,16 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_cef")]
+        Box::new(ParseCef),
         #[cfg(feature = "parse_common_log")]
         Box::new(ParseCommonLog),
         #[cfg(feature = "parse_csv")]
         Box::new(ParseCsv),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
+        #[cfg(feature = "parse_etld")]
+        Box::new(ParseEtld),
         #[cfg(feature = "parse_glog")]
         Box::new(ParseGlog),
         #[cfg(feature = "parse_grok")]
@@ -746,6 +828,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseKeyValue),
         #[cfg(feature = "parse_klog")]
         Box::new(ParseKlog),
+        #[cfg(feature = "parse_leef")]
+        Box::new(ParseLeef),
         #[cfg(feature = "parse_linux_authorization")]
         Box::new(ParseLinuxAuthorization),
         #[cfg(feature = "parse_logfmt")]
@@ -770,6 +854,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseUrl),
         #[cfg(feature = "parse_user_agent")]
         Box::new(ParseUserAgent),
+        #[cfg(feature = "parse_windows_event_xml")]
+        Box::new(ParseWindowsEventXml),
         #[cfg(feature = "parse_xml")]
         Box::new(ParseXml),
         #[cfg(feature = "push")]
@@ -786,6 +872,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ReverseDns),
         #[cfg(feature = "round")]
         Box::new(Round),
+        #[cfg(feature = "seahash")]
+        Box::new(Seahash),
         #[cfg(feature = "set")]
         Box::new(Set),
         #[cfg(feature = "sha1")]
@@ -848,5 +936,7 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "xxhash")]
+        Box::new(Xxhash),
     ]
 }
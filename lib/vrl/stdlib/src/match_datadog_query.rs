@@ -13,7 +13,8 @@ use vrl::prelude::*;
 #[derive(Clone, Copy, Debug)]
 pub struct MatchDatadogQuery;
 
-struct DynMatcher(Box<dyn Matcher<Value>>);
+/// The matchers of a value as a log, and as a metric.
+struct DynMatcher(Box<dyn Matcher<Value>>, Box<dyn Matcher<Value>>);
 
 impl Function for MatchDatadogQuery {
     fn identifier(&self) -> &'static str {
@@ -42,6 +43,11 @@ impl Function for MatchDatadogQuery {
                 source: r#"match_datadog_query({"tags": ["a:x", "b:y", "c:z"]}, s'b:["x" TO "z"]')"#,
                 result: Ok("true"),
             },
+            Example {
+                title: "Metric",
+                source: r#"match_datadog_query({"name": "cpu.usage", "kind": "absolute", "type": "gauge", "tags": {"host": "web-1"}}, "cpu host:web*", metric: true)"#,
+                result: Ok("true"),
+            },
         ]
     }
 
@@ -53,6 +59,7 @@ impl Function for MatchDatadogQuery {
     ) -> Compiled {
         let value = arguments.required("value");
         let query_value = arguments.required_literal("query")?.to_value();
+        let metric = arguments.optional("metric").unwrap_or_else(|| expr!(false));
 
         // Query should always be a string.
         let query = query_value
@@ -64,12 +71,18 @@ impl Function for MatchDatadogQuery {
             Box::new(ExpressionError::from(e.to_string())) as Box<dyn DiagnosticMessage>
         })?;
 
-        // Build the matcher function that accepts a VRL event value. This will parse the `node`
-        // at boot-time and return a boxed func that contains just the logic required to match a
-        // VRL `Value` against the Datadog Search Syntax literal.
-        let filter = build_matcher(&node, &VrlFilter::default());
-
-        Ok(Box::new(MatchDatadogQueryFn { value, filter }))
+        // Build the matcher functions that accept a VRL event value. This will parse the `node`
+        // at boot-time and return boxed funcs that contain just the logic required to match a
+        // VRL `Value` against the Datadog Search Syntax literal, as a log or as a metric.
+        let log_filter = build_matcher(&node, &VrlFilter { metric: false });
+        let metric_filter = build_matcher(&node, &VrlFilter { metric: true });
+
+        Ok(Box::new(MatchDatadogQueryFn {
+            value,
+            metric,
+            log_filter,
+            metric_filter,
+        }))
     }
 
     fn compile_argument(
@@ -98,13 +111,16 @@ impl Function for MatchDatadogQuery {
                     Box::new(ExpressionError::from(e.to_string())) as Box<dyn DiagnosticMessage>
                 })?;
 
-                // Build the matcher function that accepts a VRL event value. This will parse the `node`
-                // at boot-time and return a boxed func that contains just the logic required to match a
-                // VRL `Value` against the Datadog Search Syntax literal.
-                let filter = build_matcher(&node, &VrlFilter::default());
+                // Build the matcher functions that accept a VRL event value. This will parse the `node`
+                // at boot-time and return boxed funcs that contain just the logic required to match a
+                // VRL `Value` against the Datadog Search Syntax literal, as a log or as a metric.
+                let matcher = DynMatcher(
+                    build_matcher(&node, &VrlFilter { metric: false }),
+                    build_matcher(&node, &VrlFilter { metric: true }),
+                );
 
                 Ok(Some(
-                    Box::new(DynMatcher(filter)) as Box<dyn std::any::Any + Send + Sync>
+                    Box::new(matcher) as Box<dyn std::any::Any + Send + Sync>
                 ))
             }
             _ => Ok(None),
@@ -123,6 +139,11 @@ impl Function for MatchDatadogQuery {
                 kind: kind::BYTES,
                 required: true,
             },
+            Parameter {
+                keyword: "metric",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 }
@@ -130,16 +151,23 @@ impl Function for MatchDatadogQuery {
 #[derive(Debug, Clone)]
 struct MatchDatadogQueryFn {
     value: Box<dyn Expression>,
-    filter: Box<dyn Matcher<Value>>,
+    metric: Box<dyn Expression>,
+    log_filter: Box<dyn Matcher<Value>>,
+    metric_filter: Box<dyn Matcher<Value>>,
 }
 
 impl Expression for MatchDatadogQueryFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
+        let filter = if self.metric.resolve(ctx)?.try_boolean()? {
+            &self.metric_filter
+        } else {
+            &self.log_filter
+        };
 
         // Provide the current VRL event `Value` to the matcher function to determine
         // whether the data matches the given Datadog Search syntax literal.
-        Ok(self.filter.run(&value).into())
+        Ok(filter.run(&value).into())
     }
 
     fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
@@ -151,8 +179,11 @@ fn type_def() -> TypeDef {
     TypeDef::boolean().infallible()
 }
 
-#[derive(Default, Clone)]
-struct VrlFilter;
+#[derive(Clone)]
+struct VrlFilter {
+    /// Whether the value is matched as a metric, as seen by a remap program run on metric events.
+    metric: bool,
+}
 
 /// Implements `Resolver`, which translates Datadog Search Syntax literal names into
 /// fields.
@@ -161,7 +192,7 @@ impl Resolver for VrlFilter {}
 /// Implements `Filter`, which provides methods for matching against (in this case) VRL values.
 impl Filter<Value> for VrlFilter {
     fn exists(&self, field: Field) -> Box<dyn Matcher<Value>> {
-        let buf = lookup_field(&field);
+        let buf = self.lookup_field(&field);
 
         match field {
             // Tags need to check the element value.
@@ -170,14 +201,11 @@ impl Filter<Value> for VrlFilter {
 
                 resolve_value(
                     buf,
-                    Run::boxed(move |value| match value {
-                        Value::Array(v) => v.iter().any(|v| {
-                            let str_value = string_value(v);
-
+                    Run::boxed(move |value| {
+                        tag_values(value).iter().any(|v| {
                             // The tag matches using either 'key' or 'key:value' syntax.
-                            str_value == tag || str_value.starts_with(&starts_with)
-                        }),
-                        _ => false,
+                            *v == tag || v.starts_with(&starts_with)
+                        })
                     }),
                 )
             }
@@ -196,7 +224,7 @@ impl Filter<Value> for VrlFilter {
     }
 
     fn equals(&self, field: Field, to_match: &str) -> Box<dyn Matcher<Value>> {
-        let buf = lookup_field(&field);
+        let buf = self.lookup_field(&field);
 
         match field {
             // Default fields are compared by word boundary.
@@ -217,24 +245,16 @@ impl Filter<Value> for VrlFilter {
 
                 resolve_value(
                     buf,
-                    Run::boxed(move |value| match value {
-                        Value::Array(v) => {
-                            v.contains(&Value::Bytes(Bytes::copy_from_slice(to_match.as_bytes())))
-                        }
-                        _ => false,
-                    }),
+                    Run::boxed(move |value| tag_values(value).iter().any(|v| *v == to_match)),
                 )
             }
             // Individual tags are compared by element key:value.
             Field::Tag(tag) => {
-                let value_bytes = Value::Bytes(format!("{}:{}", tag, to_match).into());
+                let to_match = format!("{}:{}", tag, to_match);
 
                 resolve_value(
                     buf,
-                    Run::boxed(move |value| match value {
-                        Value::Array(v) => v.contains(&value_bytes),
-                        _ => false,
-                    }),
+                    Run::boxed(move |value| tag_values(value).iter().any(|v| *v == to_match)),
                 )
             }
             // Everything else is matched by string equality.
@@ -250,7 +270,7 @@ impl Filter<Value> for VrlFilter {
     }
 
     fn prefix(&self, field: Field, prefix: &str) -> Box<dyn Matcher<Value>> {
-        let buf = lookup_field(&field);
+        let buf = self.lookup_field(&field);
 
         match field {
             // Default fields are matched by word boundary.
@@ -268,11 +288,10 @@ impl Filter<Value> for VrlFilter {
 
                 resolve_value(
                     buf,
-                    Run::boxed(move |value| match value {
-                        Value::Array(v) => {
-                            v.iter().any(|v| string_value(v).starts_with(&starts_with))
-                        }
-                        _ => false,
+                    Run::boxed(move |value| {
+                        tag_values(value)
+                            .iter()
+                            .any(|v| v.starts_with(&starts_with))
                     }),
                 )
             }
@@ -289,7 +308,7 @@ impl Filter<Value> for VrlFilter {
     }
 
     fn wildcard(&self, field: Field, wildcard: &str) -> Box<dyn Matcher<Value>> {
-        let buf = lookup_field(&field);
+        let buf = self.lookup_field(&field);
 
         match field {
             Field::Default(_) => {
@@ -305,10 +324,7 @@ impl Filter<Value> for VrlFilter {
 
                 resolve_value(
                     buf,
-                    Run::boxed(move |value| match value {
-                        Value::Array(v) => v.iter().any(|v| re.is_match(&string_value(v))),
-                        _ => false,
-                    }),
+                    Run::boxed(move |value| tag_values(value).iter().any(|v| re.is_match(v))),
                 )
             }
             _ => {
@@ -328,7 +344,7 @@ impl Filter<Value> for VrlFilter {
         comparator: Comparison,
         comparison_value: ComparisonValue,
     ) -> Box<dyn Matcher<Value>> {
-        let buf = lookup_field(&field);
+        let buf = self.lookup_field(&field);
         let rhs = Cow::from(comparison_value.to_string());
 
         match field {
@@ -400,8 +416,8 @@ impl Filter<Value> for VrlFilter {
             // Tag values need extracting by "key:value" to be compared.
             Field::Tag(_) => resolve_value(
                 buf,
-                Run::boxed(move |value| match value {
-                    Value::Array(v) => v.iter().any(|v| match string_value(v).split_once(':') {
+                Run::boxed(move |value| {
+                    tag_values(value).iter().any(|v| match v.split_once(':') {
                         Some((_, lhs)) => {
                             let lhs = Cow::from(lhs);
 
@@ -413,8 +429,7 @@ impl Filter<Value> for VrlFilter {
                             }
                         }
                         _ => false,
-                    }),
-                    _ => false,
+                    })
                 }),
             ),
             // All other tag types are compared by string.
@@ -435,10 +450,15 @@ impl Filter<Value> for VrlFilter {
     }
 }
 
-fn resolve_value(buf: LookupBuf, match_fn: Box<dyn Matcher<Value>>) -> Box<dyn Matcher<Value>> {
+/// Returns a matcher running `match_fn` against the value of the first of the `paths` that
+/// exists, or returning `false` if none of them do.
+fn resolve_value(
+    paths: Vec<LookupBuf>,
+    match_fn: Box<dyn Matcher<Value>>,
+) -> Box<dyn Matcher<Value>> {
     let func = move |obj: &Value| {
         // Get the value by path, or return early with `false` if it doesn't exist.
-        let value = match obj.get_by_path(&buf) {
+        let value = match paths.iter().find_map(|path| obj.get_by_path(path)) {
             Some(v) => v,
             _ => return false,
         };
@@ -449,14 +469,74 @@ fn resolve_value(buf: LookupBuf, match_fn: Box<dyn Matcher<Value>>) -> Box<dyn M
     Run::boxed(func)
 }
 
-/// If the provided field is a `Field::Tag`, will return a "tags" lookup buf. Otherwise,
-/// parses the field and returns a lookup buf is the lookup itself is valid.
-fn lookup_field(field: &Field) -> LookupBuf {
-    match field {
-        Field::Default(p) | Field::Reserved(p) | Field::Facet(p) => parse_lookup(p.as_str())
-            .expect("should parse lookup buf")
-            .into_buf(),
+impl VrlFilter {
+    /// Returns the paths a field may resolve to. Metrics have no `message` or `custom`
+    /// attributes, so their fields resolve to the metric's own properties instead.
+    fn lookup_field(&self, field: &Field) -> Vec<LookupBuf> {
+        if self.metric {
+            return lookup_metric_field(field);
+        }
+
+        let buf = match field {
+            Field::Default(p) | Field::Reserved(p) | Field::Facet(p) => parse_lookup(p.as_str())
+                .expect("should parse lookup buf")
+                .into_buf(),
+            Field::Tag(_) => LookupBuf::from("tags"),
+        };
+
+        vec![buf]
+    }
+}
+
+/// Returns the paths of a field in a metric, if the metric has such a field. The metric
+/// name is the default field, the `@name`, `@namespace`, `@kind` and `@type` facets are the
+/// metric's own properties, `@value` is the value of a counter or a gauge, and the reserved
+/// `host`, `source`, `status` and `service` attributes are read from the tags of the same name.
+fn lookup_metric_field(field: &Field) -> Vec<LookupBuf> {
+    let buf = match field {
+        Field::Default(p) if p == "message" => LookupBuf::from("name"),
+        Field::Facet(p) => match p.strip_prefix("custom.") {
+            Some(p @ ("name" | "namespace" | "kind" | "type")) => LookupBuf::from(p),
+            // Only metrics with a single value can be compared by it.
+            Some("value") => {
+                return ["counter", "gauge"]
+                    .into_iter()
+                    .map(|kind| {
+                        let mut buf = LookupBuf::from(kind);
+                        buf.push_back("value");
+                        buf
+                    })
+                    .collect()
+            }
+            _ => return Vec::new(),
+        },
+        Field::Reserved(p) if matches!(p.as_str(), "host" | "source" | "status" | "service") => {
+            let mut buf = LookupBuf::from("tags");
+            buf.push_back(p.as_str());
+            buf
+        }
+        Field::Reserved(p) if p == "timestamp" || p == "tags" => LookupBuf::from(p.as_str()),
         Field::Tag(_) => LookupBuf::from("tags"),
+        _ => return Vec::new(),
+    };
+
+    vec![buf]
+}
+
+/// Returns the tags in a VRL `Value` as "key:value" strings. Tags are usually an array of
+/// such strings, but metrics and traces carry them as an object, in which a tag with an
+/// empty value is returned as its key alone.
+fn tag_values(value: &Value) -> Vec<Cow<str>> {
+    match value {
+        Value::Array(v) => v.iter().map(string_value).collect(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| match string_value(value) {
+                v if v.is_empty() => Cow::from(key.as_str()),
+                v => Cow::from(format!("{}:{}", key, v)),
+            })
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
@@ -2000,5 +2080,83 @@ mod test {
             want: Ok(false),
             tdef: type_def(),
         }
+
+        tag_object {
+            args: func_args![value: value!({"tags": {"env": "prod"}}), query: "env:prod"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        tag_object_no_match {
+            args: func_args![value: value!({"tags": {"env": "staging"}}), query: "env:prod"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        tag_object_without_value {
+            args: func_args![value: value!({"tags": {"canary": ""}}), query: "tags:canary"],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        metric_name {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "type": "gauge"}), query: "cpu", metric: true],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        metric_name_no_match {
+            args: func_args![value: value!({"name": "memory.usage", "kind": "absolute", "type": "gauge"}), query: "cpu", metric: true],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        metric_facet {
+            args: func_args![value: value!({"name": "cpu.usage", "namespace": "host", "kind": "incremental", "type": "counter"}), query: "@namespace:host @kind:incremental @type:counter", metric: true],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        metric_facet_no_match {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "type": "gauge"}), query: "@kind:incremental", metric: true],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        metric_tag {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "type": "gauge", "tags": {"env": "prod"}}), query: "env:prod", metric: true],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        metric_reserved_from_tag {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "type": "gauge", "tags": {"host": "web-1"}}), query: "host:web*", metric: true],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        metric_reserved_from_tag_no_match {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "type": "gauge", "host": "web-1"}), query: "host:web*", metric: true],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        metric_value {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "gauge": {"value": 0.8}}), query: "@value:>0.5", metric: true],
+            want: Ok(true),
+            tdef: type_def(),
+        }
+
+        metric_counter_value_no_match {
+            args: func_args![value: value!({"name": "requests", "kind": "incremental", "counter": {"value": 2.0}}), query: "@value:>5", metric: true],
+            want: Ok(false),
+            tdef: type_def(),
+        }
+
+        metric_shaped_log {
+            args: func_args![value: value!({"name": "cpu.usage", "kind": "absolute", "type": "gauge"}), query: "cpu"],
+            want: Ok(false),
+            tdef: type_def(),
+        }
     ];
 }
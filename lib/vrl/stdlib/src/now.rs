@@ -1,5 +1,5 @@
 use chrono::Utc;
-use vrl::prelude::*;
+use vrl::{prelude::*, Clock};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Now;
@@ -20,19 +20,25 @@ impl Function for Now {
     fn compile(
         &self,
         _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         _: ArgumentList,
     ) -> Compiled {
-        Ok(Box::new(NowFn))
+        let clock = ctx.get_external_context::<Clock>().cloned();
+
+        Ok(Box::new(NowFn { clock }))
     }
 }
 
 #[derive(Debug, Clone)]
-struct NowFn;
+struct NowFn {
+    clock: Option<Clock>,
+}
 
 impl Expression for NowFn {
     fn resolve(&self, _: &mut Context) -> Resolved {
-        Ok(Utc::now().into())
+        let now = self.clock.as_ref().map_or_else(Utc::now, Clock::now);
+
+        Ok(now.into())
     }
 
     fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
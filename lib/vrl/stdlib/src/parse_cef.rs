@@ -0,0 +1,387 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::split_header;
+
+/// The header fields following the `CEF:` prefix, in order.
+const HEADER_FIELDS: [&str; 7] = [
+    "cefVersion",
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "deviceEventClassId",
+    "name",
+    "severity",
+];
+
+fn parse_cef(value: Value, translate_custom_fields: bool) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let message = String::from_utf8_lossy(&bytes);
+
+    // The message may be prefixed, such as by a syslog header.
+    let start = message
+        .find("CEF:")
+        .ok_or("unable to parse CEF: missing CEF prefix")?;
+
+    let (header, extension) = split_header(&message[start + 4..], HEADER_FIELDS.len())
+        .ok_or("unable to parse CEF: missing header fields")?;
+
+    let mut log = parse_extension(extension)?;
+
+    if translate_custom_fields {
+        translate(&mut log);
+    }
+
+    for (key, field) in HEADER_FIELDS.iter().zip(header) {
+        let value = match *key {
+            "cefVersion" => field
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| format!(r#"unable to parse CEF: invalid version "{}""#, field))?
+                .into(),
+            // Severity is either an integer from 0 to 10, or a name such as "High".
+            "severity" => match field.trim().parse::<i64>() {
+                Ok(severity) => severity.into(),
+                Err(_) => field.into(),
+            },
+            _ => field.into(),
+        };
+
+        log.insert((*key).to_owned(), value);
+    }
+
+    Ok(log.into())
+}
+
+/// Parse the space-separated `key=value` pairs of the extension. Values may
+/// contain spaces, so a value ends where the next key starts.
+fn parse_extension(extension: &str) -> std::result::Result<BTreeMap<String, Value>, String> {
+    let extension = extension.trim();
+    let bytes = extension.as_bytes();
+
+    // The start and end of each key.
+    let mut keys = vec![];
+
+    // The start of the key characters preceding the current one, if any.
+    let mut key_start = None;
+
+    // The number of consecutive backslashes preceding the current character,
+    // which escape it if odd.
+    let mut backslashes = 0;
+
+    for (index, ch) in extension.char_indices() {
+        let escaped = backslashes % 2 == 1;
+        backslashes = if ch == '\\' { backslashes + 1 } else { 0 };
+
+        match ch {
+            '=' if !escaped => {
+                if let Some(start) = key_start {
+                    if start == 0 || bytes[start - 1] == b' ' {
+                        keys.push((start, index));
+                    }
+                }
+                key_start = None;
+            }
+            ch if !escaped && is_key_char(ch) => {
+                key_start.get_or_insert(index);
+            }
+            _ => key_start = None,
+        }
+    }
+
+    match keys.first() {
+        None if extension.is_empty() => return Ok(BTreeMap::new()),
+        Some((0, _)) => {}
+        _ => return Err("unable to parse CEF: invalid extension".to_owned()),
+    }
+
+    let mut fields = BTreeMap::new();
+
+    for (position, &(start, end)) in keys.iter().enumerate() {
+        let value_end = keys
+            .get(position + 1)
+            .map_or(extension.len(), |&(next, _)| next);
+        let value = extension[end + 1..value_end].trim_end_matches(' ');
+
+        fields.insert(extension[start..end].to_owned(), unescape(value).into());
+    }
+
+    Ok(fields)
+}
+
+fn is_key_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '[' | ']' | '-')
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(next @ ('\\' | '=' | '|')) => unescaped.push(next),
+            Some(next) => {
+                unescaped.push(ch);
+                unescaped.push(next);
+            }
+            None => unescaped.push(ch),
+        }
+    }
+
+    unescaped
+}
+
+/// Replace custom fields, such as `cs1`, with fields named after their label,
+/// such as `cs1Label`.
+fn translate(log: &mut BTreeMap<String, Value>) {
+    let labelled = log
+        .keys()
+        .filter_map(|key| {
+            let field = key.strip_suffix("Label")?;
+            log.contains_key(field).then(|| field.to_owned())
+        })
+        .collect::<Vec<_>>();
+
+    for field in labelled {
+        let label = log.remove(&format!("{}Label", field));
+        let value = log.remove(&field);
+
+        if let (Some(label), Some(value)) = (label, value) {
+            log.insert(label.to_string_lossy(), value);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCef;
+
+impl Function for ParseCef {
+    fn identifier(&self) -> &'static str {
+        "parse_cef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "translate_custom_fields",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse CEF",
+                source: r#"parse_cef!(s'CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed.')"#,
+                result: Ok(indoc! {r#"{
+                    "cefVersion": 0,
+                    "deviceVendor": "Security",
+                    "deviceProduct": "threatmanager",
+                    "deviceVersion": "1.0",
+                    "deviceEventClassId": "100",
+                    "name": "worm successfully stopped",
+                    "severity": 10,
+                    "src": "10.0.0.1",
+                    "dst": "2.1.2.2",
+                    "spt": "1232",
+                    "msg": "Detected a threat. No action needed."
+                }"#}),
+            },
+            Example {
+                title: "parse CEF with custom fields",
+                source: r#"parse_cef!(s'CEF:0|Vendor|Product|1|42|Blocked|High|cs1Label=policy cs1=default deny', translate_custom_fields: true)"#,
+                result: Ok(indoc! {r#"{
+                    "cefVersion": 0,
+                    "deviceVendor": "Vendor",
+                    "deviceProduct": "Product",
+                    "deviceVersion": "1",
+                    "deviceEventClassId": "42",
+                    "name": "Blocked",
+                    "severity": "High",
+                    "policy": "default deny"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let translate_custom_fields = arguments.optional("translate_custom_fields");
+
+        Ok(Box::new(ParseCefFn {
+            value,
+            translate_custom_fields,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCefFn {
+    value: Box<dyn Expression>,
+    translate_custom_fields: Option<Box<dyn Expression>>,
+}
+
+impl Expression for ParseCefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let translate_custom_fields = match &self.translate_custom_fields {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => false,
+        };
+
+        parse_cef(value, translate_custom_fields)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    let mut collection = Collection::from(BTreeMap::from([
+        (Field::from("cefVersion"), Kind::integer()),
+        (Field::from("deviceVendor"), Kind::bytes()),
+        (Field::from("deviceProduct"), Kind::bytes()),
+        (Field::from("deviceVersion"), Kind::bytes()),
+        (Field::from("deviceEventClassId"), Kind::bytes()),
+        (Field::from("name"), Kind::bytes()),
+        (Field::from("severity"), Kind::integer().or_bytes()),
+    ]));
+    collection.set_unknown(Kind::bytes());
+    collection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_cef => ParseCef;
+
+        header_and_extension {
+            args: func_args![value: "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232"],
+            want: Ok(value!({
+                "cefVersion": 0,
+                "deviceVendor": "Security",
+                "deviceProduct": "threatmanager",
+                "deviceVersion": "1.0",
+                "deviceEventClassId": "100",
+                "name": "worm successfully stopped",
+                "severity": 10,
+                "src": "10.0.0.1",
+                "dst": "2.1.2.2",
+                "spt": "1232",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        syslog_prefix {
+            args: func_args![value: "<134>Sep 19 08:26:10 host CEF:1|Vendor|Product|2|7|Login|Low|"],
+            want: Ok(value!({
+                "cefVersion": 1,
+                "deviceVendor": "Vendor",
+                "deviceProduct": "Product",
+                "deviceVersion": "2",
+                "deviceEventClassId": "7",
+                "name": "Login",
+                "severity": "Low",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        escapes {
+            args: func_args![value: r#"CEF:0|Ven\|dor|Pro\\duct|1|1|Name|5|msg=a\=b c\\d\nnext request=https://example.com/?q=1 act=blocked  "#],
+            want: Ok(value!({
+                "cefVersion": 0,
+                "deviceVendor": "Ven|dor",
+                "deviceProduct": r#"Pro\duct"#,
+                "deviceVersion": "1",
+                "deviceEventClassId": "1",
+                "name": "Name",
+                "severity": 5,
+                "msg": "a=b c\\d\nnext",
+                "request": "https://example.com/?q=1",
+                "act": "blocked",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        translate_custom_fields {
+            args: func_args![value: "CEF:0|V|P|1|1|N|5|cs1=default deny cs1Label=policy cn1Label=count", translate_custom_fields: true],
+            want: Ok(value!({
+                "cefVersion": 0,
+                "deviceVendor": "V",
+                "deviceProduct": "P",
+                "deviceVersion": "1",
+                "deviceEventClassId": "1",
+                "name": "N",
+                "severity": 5,
+                "policy": "default deny",
+                "cn1Label": "count",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "0|Security|threatmanager|1.0|100|worm|10|"],
+            want: Err("unable to parse CEF: missing CEF prefix"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_header_fields {
+            args: func_args![value: "CEF:0|Security|threatmanager|1.0"],
+            want: Err("unable to parse CEF: missing header fields"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_version {
+            args: func_args![value: "CEF:x|Security|threatmanager|1.0|100|worm|10|"],
+            want: Err(r#"unable to parse CEF: invalid version "x""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        escaped_backslash {
+            args: func_args![value: r#"CEF:0|V|P|1|1|N|5|share=C:\\=files\\ dst=2.1.2.2 msg=a\\\=b"#],
+            want: Ok(value!({
+                "cefVersion": 0,
+                "deviceVendor": "V",
+                "deviceProduct": "P",
+                "deviceVersion": "1",
+                "deviceEventClassId": "1",
+                "name": "N",
+                "severity": 5,
+                "share": r#"C:\=files\"#,
+                "dst": "2.1.2.2",
+                "msg": r#"a\=b"#,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_extension {
+            args: func_args![value: "CEF:0|Security|threatmanager|1.0|100|worm|10|not an extension"],
+            want: Err("unable to parse CEF: invalid extension"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
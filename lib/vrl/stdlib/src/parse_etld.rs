@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+fn parse_etld(value: Value) -> Resolved {
+    let value = value.try_bytes_utf8_lossy()?;
+    // Hostnames are case-insensitive, and may be fully qualified.
+    let host = value.trim_end_matches('.').to_lowercase();
+
+    let suffix = psl::suffix(host.as_bytes())
+        .ok_or_else(|| format!(r#"unable to parse eTLD: invalid hostname "{}""#, value))?;
+    let known_suffix = suffix.is_known();
+    let public_suffix = &host[host.len() - suffix.as_bytes().len()..];
+
+    // The registered domain is the public suffix and the label before it, also
+    // known as eTLD+1. A hostname that is a public suffix has none.
+    let registered_domain =
+        psl::domain(host.as_bytes()).map(|domain| &host[host.len() - domain.as_bytes().len()..]);
+    let subdomain = registered_domain
+        .and_then(|domain| host.strip_suffix(domain)?.strip_suffix('.'))
+        .filter(|subdomain| !subdomain.is_empty());
+
+    Ok(BTreeMap::from([
+        ("subdomain".to_owned(), subdomain.into()),
+        ("registered_domain".to_owned(), registered_domain.into()),
+        ("public_suffix".to_owned(), public_suffix.into()),
+        ("known_suffix".to_owned(), known_suffix.into()),
+    ])
+    .into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseEtld;
+
+impl Function for ParseEtld {
+    fn identifier(&self) -> &'static str {
+        "parse_etld"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse eTLD",
+                source: r#"parse_etld!("www.vector.dev")"#,
+                result: Ok(indoc! {r#"{
+                    "subdomain": "www",
+                    "registered_domain": "vector.dev",
+                    "public_suffix": "dev",
+                    "known_suffix": true
+                }"#}),
+            },
+            Example {
+                title: "parse eTLD with a multi-label suffix",
+                source: r#"parse_etld!("mail.eu.example.co.uk")"#,
+                result: Ok(indoc! {r#"{
+                    "subdomain": "mail.eu",
+                    "registered_domain": "example.co.uk",
+                    "public_suffix": "co.uk",
+                    "known_suffix": true
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseEtldFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseEtldFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseEtldFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_etld(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    BTreeMap::from([
+        ("subdomain".into(), Kind::bytes().or_null()),
+        ("registered_domain".into(), Kind::bytes().or_null()),
+        ("public_suffix".into(), Kind::bytes()),
+        ("known_suffix".into(), Kind::boolean()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_etld => ParseEtld;
+
+        registered_domain {
+            args: func_args![value: "example.com"],
+            want: Ok(value!({
+                "subdomain": null,
+                "registered_domain": "example.com",
+                "public_suffix": "com",
+                "known_suffix": true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        subdomain {
+            args: func_args![value: "a.b.example.co.uk"],
+            want: Ok(value!({
+                "subdomain": "a.b",
+                "registered_domain": "example.co.uk",
+                "public_suffix": "co.uk",
+                "known_suffix": true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        fully_qualified {
+            args: func_args![value: "WWW.Example.COM."],
+            want: Ok(value!({
+                "subdomain": "www",
+                "registered_domain": "example.com",
+                "public_suffix": "com",
+                "known_suffix": true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        public_suffix {
+            args: func_args![value: "co.uk"],
+            want: Ok(value!({
+                "subdomain": null,
+                "registered_domain": null,
+                "public_suffix": "co.uk",
+                "known_suffix": true,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unknown_suffix {
+            args: func_args![value: "printer.corp.internal-tld"],
+            want: Ok(value!({
+                "subdomain": "printer",
+                "registered_domain": "corp.internal-tld",
+                "public_suffix": "internal-tld",
+                "known_suffix": false,
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid {
+            args: func_args![value: ""],
+            want: Err(r#"unable to parse eTLD: invalid hostname """#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::split_header;
+
+/// The header fields following the `LEEF:` prefix, in order. LEEF 2.0 adds a
+/// field with the delimiter of the attributes, which isn't part of the result.
+const HEADER_FIELDS: [&str; 5] = [
+    "leefVersion",
+    "vendor",
+    "productName",
+    "productVersion",
+    "eventId",
+];
+
+fn parse_leef(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let message = String::from_utf8_lossy(&bytes);
+
+    // The message may be prefixed, such as by a syslog header.
+    let start = message
+        .find("LEEF:")
+        .ok_or("unable to parse LEEF: missing LEEF prefix")?;
+    let message = &message[start + 5..];
+
+    let version = message.split('|').next().unwrap_or_default().trim();
+    let (version, header, attributes, delimiter) = match version {
+        "1.0" | "1" => {
+            let (header, attributes) = split_header(message, HEADER_FIELDS.len())
+                .ok_or("unable to parse LEEF: missing header fields")?;
+
+            (1.0, header, attributes, '\t')
+        }
+        "2.0" | "2" => {
+            let (mut header, attributes) = split_header(message, HEADER_FIELDS.len() + 1)
+                .ok_or("unable to parse LEEF: missing header fields")?;
+            let delimiter = header.pop().unwrap_or_default();
+
+            (2.0, header, attributes, parse_delimiter(&delimiter)?)
+        }
+        version => {
+            return Err(
+                format!(r#"unable to parse LEEF: unsupported version "{}""#, version).into(),
+            )
+        }
+    };
+
+    let mut log = parse_attributes(attributes, delimiter)?;
+
+    for (key, field) in HEADER_FIELDS.iter().zip(header) {
+        let value = match *key {
+            "leefVersion" => Value::from(version),
+            _ => field.into(),
+        };
+
+        log.insert((*key).to_owned(), value);
+    }
+
+    Ok(log.into())
+}
+
+/// Parse the delimiter of LEEF 2.0 attributes, which is either a single
+/// character, or its code point in hexadecimal, such as `x09` or `0x09`. It
+/// defaults to a tab.
+fn parse_delimiter(delimiter: &str) -> std::result::Result<char, String> {
+    let invalid = || format!(r#"unable to parse LEEF: invalid delimiter "{}""#, delimiter);
+
+    let mut chars = delimiter.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => return Ok('\t'),
+        (Some(ch), None) => return Ok(ch),
+        _ => {}
+    }
+
+    let hex = delimiter
+        .strip_prefix("0x")
+        .or_else(|| delimiter.strip_prefix('x'))
+        .ok_or_else(invalid)?;
+
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(invalid)
+}
+
+/// Parse the delimited `key=value` attributes. A backslash escapes the
+/// delimiter, `=` or another backslash.
+fn parse_attributes(
+    attributes: &str,
+    delimiter: char,
+) -> std::result::Result<BTreeMap<String, Value>, String> {
+    let mut fields = BTreeMap::new();
+    let mut chars = attributes.chars();
+    let mut key = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+
+    loop {
+        let next = chars.next();
+        let current = if in_value { &mut value } else { &mut key };
+
+        match next {
+            Some('\\') => match chars.next() {
+                Some(ch) if ch == delimiter || ch == '=' || ch == '\\' => current.push(ch),
+                Some(ch) => {
+                    current.push('\\');
+                    current.push(ch);
+                }
+                None => current.push('\\'),
+            },
+            Some('=') if !in_value => in_value = true,
+            Some(ch) if ch != delimiter => current.push(ch),
+            _ => {
+                let name = key.trim();
+
+                if in_value && !name.is_empty() {
+                    fields.insert(name.to_owned(), std::mem::take(&mut value).into());
+                } else if in_value || !name.is_empty() {
+                    return Err(format!(
+                        r#"unable to parse LEEF: invalid attribute "{}""#,
+                        name
+                    ));
+                }
+
+                key.clear();
+                in_value = false;
+
+                if next.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseLeef;
+
+impl Function for ParseLeef {
+    fn identifier(&self) -> &'static str {
+        "parse_leef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse LEEF 1.0",
+                source: r#"parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5")"#,
+                result: Ok(indoc! {r#"{
+                    "leefVersion": 1.0,
+                    "vendor": "Microsoft",
+                    "productName": "MSExchange",
+                    "productVersion": "4.0 SP1",
+                    "eventId": "15345",
+                    "src": "192.0.2.0",
+                    "dst": "172.50.123.1",
+                    "sev": "5"
+                }"#}),
+            },
+            Example {
+                title: "parse LEEF 2.0 with a custom delimiter",
+                source: r#"parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^usrName=joe")"#,
+                result: Ok(indoc! {r#"{
+                    "leefVersion": 2.0,
+                    "vendor": "Lancope",
+                    "productName": "StealthWatch",
+                    "productVersion": "1.0",
+                    "eventId": "41",
+                    "src": "10.0.1.8",
+                    "dst": "10.0.0.5",
+                    "usrName": "joe"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseLeefFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseLeefFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseLeefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_leef(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    let mut collection = Collection::from(BTreeMap::from([
+        (Field::from("leefVersion"), Kind::float()),
+        (Field::from("vendor"), Kind::bytes()),
+        (Field::from("productName"), Kind::bytes()),
+        (Field::from("productVersion"), Kind::bytes()),
+        (Field::from("eventId"), Kind::bytes()),
+    ]));
+    collection.set_unknown(Kind::bytes());
+    collection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_leef => ParseLeef;
+
+        leef_1 {
+            args: func_args![value: "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tmsg=a=b c"],
+            want: Ok(value!({
+                "leefVersion": 1.0,
+                "vendor": "Microsoft",
+                "productName": "MSExchange",
+                "productVersion": "4.0 SP1",
+                "eventId": "15345",
+                "src": "192.0.2.0",
+                "dst": "172.50.123.1",
+                "msg": "a=b c",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_hex_delimiter {
+            args: func_args![value: "<13>Jan 18 11:07:53 host LEEF:2.0|Vendor|Product|1.0|login|x7C|usrName=joe|msg=a\\|b|"],
+            want: Ok(value!({
+                "leefVersion": 2.0,
+                "vendor": "Vendor",
+                "productName": "Product",
+                "productVersion": "1.0",
+                "eventId": "login",
+                "usrName": "joe",
+                "msg": "a|b",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_default_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|login||key\\=name=a\\=b\tempty="],
+            want: Ok(value!({
+                "leefVersion": 2.0,
+                "vendor": "Vendor",
+                "productName": "Product",
+                "productVersion": "1.0",
+                "eventId": "login",
+                "key=name": "a=b",
+                "empty": "",
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        unsupported_version {
+            args: func_args![value: "LEEF:3.0|Vendor|Product|1.0|login|"],
+            want: Err(r#"unable to parse LEEF: unsupported version "3.0""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|login|tab|a=b"],
+            want: Err(r#"unable to parse LEEF: invalid delimiter "tab""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_attribute {
+            args: func_args![value: "LEEF:1.0|Vendor|Product|1.0|login|a=b\tnot an attribute"],
+            want: Err(r#"unable to parse LEEF: invalid attribute "not an attribute""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
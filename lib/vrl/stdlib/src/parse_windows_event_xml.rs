@@ -0,0 +1,460 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+use vrl::prelude::*;
+
+use crate::parse_xml::{process_node, trim_xml, ParseXmlConfig};
+
+type Fields = BTreeMap<String, Value>;
+
+fn parse_windows_event_xml(value: Value) -> Resolved {
+    let string = value.try_bytes_utf8_lossy()?;
+    let xml = trim_xml(&string);
+    let doc = Document::parse(&xml).map_err(|e| format!("unable to parse xml: {}", e))?;
+
+    let event = doc.root_element();
+    if event.tag_name().name() != "Event" {
+        return Err("unable to parse Windows event: root element isn't an Event".into());
+    }
+
+    let mut log = Fields::new();
+
+    for node in event.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "System" => system(node, &mut log)?,
+            "EventData" => {
+                log.insert("event_data".to_owned(), event_data(node).into());
+            }
+            "UserData" => {
+                log.insert(
+                    "user_data".to_owned(),
+                    process_node(node, &ParseXmlConfig::default()),
+                );
+            }
+            "RenderingInfo" => {
+                log.insert("rendering_info".to_owned(), rendering_info(node).into());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(log.into())
+}
+
+/// Insert the fields of the `System` element, which are the same for all
+/// events.
+fn system(system: Node, log: &mut Fields) -> std::result::Result<(), ExpressionError> {
+    for node in system.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Provider" => {
+                let provider = attributes(
+                    node,
+                    &[
+                        ("Name", "name"),
+                        ("Guid", "guid"),
+                        ("EventSourceName", "event_source_name"),
+                    ],
+                );
+                log.insert("provider".to_owned(), provider.into());
+            }
+            "EventID" => {
+                log.insert("event_id".to_owned(), integer(node.text()));
+
+                if let Some(qualifiers) = node.attribute("Qualifiers") {
+                    log.insert("qualifiers".to_owned(), integer(Some(qualifiers)));
+                }
+            }
+            "TimeCreated" => {
+                if let Some(time) = node.attribute("SystemTime") {
+                    let timestamp = DateTime::parse_from_rfc3339(time)
+                        .map_err(|e| format!(r#"unable to parse SystemTime "{}": {}"#, time, e))?
+                        .with_timezone(&Utc);
+
+                    log.insert("time_created".to_owned(), timestamp.into());
+                }
+            }
+            "Correlation" => {
+                let correlation = attributes(
+                    node,
+                    &[
+                        ("ActivityID", "activity_id"),
+                        ("RelatedActivityID", "related_activity_id"),
+                    ],
+                );
+                log.insert("correlation".to_owned(), correlation.into());
+            }
+            "Execution" => {
+                let execution = node
+                    .attributes()
+                    .iter()
+                    .filter_map(|attribute| {
+                        let key = match attribute.name() {
+                            "ProcessID" => "process_id",
+                            "ThreadID" => "thread_id",
+                            _ => return None,
+                        };
+
+                        Some((key.to_owned(), integer(Some(attribute.value()))))
+                    })
+                    .collect::<Fields>();
+                log.insert("execution".to_owned(), execution.into());
+            }
+            "Security" => {
+                let security = attributes(node, &[("UserID", "user_id")]);
+                log.insert("security".to_owned(), security.into());
+            }
+            name => {
+                let (key, value) = match name {
+                    "Version" => ("version", integer(node.text())),
+                    "Level" => ("level", integer(node.text())),
+                    "Task" => ("task", integer(node.text())),
+                    "Opcode" => ("opcode", integer(node.text())),
+                    "EventRecordID" => ("event_record_id", integer(node.text())),
+                    "Keywords" => ("keywords", text(node)),
+                    "Channel" => ("channel", text(node)),
+                    "Computer" => ("computer", text(node)),
+                    _ => continue,
+                };
+
+                log.insert(key.to_owned(), value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The named `Data` elements of the event data. Events of classic event logs
+/// have unnamed data, which is named `param1`, `param2` and so on instead.
+fn event_data(event_data: Node) -> Fields {
+    let mut fields = Fields::new();
+    let mut unnamed = 0;
+
+    for node in event_data.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Data" => {
+                let name = match node.attribute("Name") {
+                    Some(name) => name.to_owned(),
+                    None => {
+                        unnamed += 1;
+                        format!("param{}", unnamed)
+                    }
+                };
+
+                fields.insert(name, text(node));
+            }
+            "Binary" => {
+                fields.insert("binary".to_owned(), text(node));
+            }
+            _ => {}
+        }
+    }
+
+    fields
+}
+
+/// The messages rendered from the event, in the language of the culture.
+fn rendering_info(rendering_info: Node) -> Fields {
+    let mut fields = Fields::new();
+
+    if let Some(culture) = rendering_info.attribute("Culture") {
+        fields.insert("culture".to_owned(), culture.into());
+    }
+
+    for node in rendering_info.children().filter(Node::is_element) {
+        let key = match node.tag_name().name() {
+            "Keywords" => {
+                let keywords = node
+                    .children()
+                    .filter(Node::is_element)
+                    .map(text)
+                    .collect::<Vec<_>>();
+
+                fields.insert("keywords".to_owned(), keywords.into());
+                continue;
+            }
+            "Message" => "message",
+            "Level" => "level",
+            "Task" => "task",
+            "Opcode" => "opcode",
+            "Channel" => "channel",
+            "Provider" => "provider",
+            _ => continue,
+        };
+
+        fields.insert(key.to_owned(), text(node));
+    }
+
+    fields
+}
+
+/// The given attributes of the node, renamed to the given keys.
+fn attributes(node: Node, names: &[(&str, &str)]) -> Fields {
+    names
+        .iter()
+        .filter_map(|(name, key)| Some(((*key).to_owned(), node.attribute(*name)?.into())))
+        .collect()
+}
+
+fn text(node: Node) -> Value {
+    node.text().map_or(Value::Null, Into::into)
+}
+
+/// Parse the text as an integer, keeping it as a string if it isn't one.
+fn integer(text: Option<&str>) -> Value {
+    match text {
+        Some(text) => text
+            .trim()
+            .parse::<i64>()
+            .map_or_else(|_| text.into(), Into::into),
+        None => Value::Null,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseWindowsEventXml;
+
+impl Function for ParseWindowsEventXml {
+    fn identifier(&self) -> &'static str {
+        "parse_windows_event_xml"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse Windows event XML",
+            source: indoc! {r#"
+                value = s'<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Microsoft-Windows-Security-Auditing"/><EventID>4624</EventID><Level>0</Level><TimeCreated SystemTime="2022-10-03T12:00:00.123Z"/><EventRecordID>8242</EventRecordID><Channel>Security</Channel><Computer>dc01.example.com</Computer></System><EventData><Data Name="TargetUserName">alice</Data><Data Name="LogonType">3</Data></EventData></Event>'
+
+                parse_windows_event_xml!(value)
+            "#},
+            result: Ok(indoc! {r#"{
+                "provider": { "name": "Microsoft-Windows-Security-Auditing" },
+                "event_id": 4624,
+                "level": 0,
+                "time_created": "2022-10-03T12:00:00.123Z",
+                "event_record_id": 8242,
+                "channel": "Security",
+                "computer": "dc01.example.com",
+                "event_data": { "TargetUserName": "alice", "LogonType": "3" }
+            }"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseWindowsEventXmlFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseWindowsEventXmlFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseWindowsEventXmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_windows_event_xml(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> BTreeMap<Field, Kind> {
+    let integer = || Kind::integer().or_bytes().or_null();
+    let bytes = || Kind::bytes().or_null();
+    let object = |fields: &[&str]| {
+        Kind::object(
+            fields
+                .iter()
+                .map(|field| (Field::from(*field), Kind::bytes()))
+                .collect::<BTreeMap<_, _>>(),
+        )
+        .or_null()
+    };
+
+    BTreeMap::from([
+        (
+            "provider".into(),
+            object(&["name", "guid", "event_source_name"]),
+        ),
+        ("event_id".into(), integer()),
+        ("qualifiers".into(), integer()),
+        ("version".into(), integer()),
+        ("level".into(), integer()),
+        ("task".into(), integer()),
+        ("opcode".into(), integer()),
+        ("keywords".into(), bytes()),
+        ("time_created".into(), Kind::timestamp().or_null()),
+        ("event_record_id".into(), integer()),
+        (
+            "correlation".into(),
+            object(&["activity_id", "related_activity_id"]),
+        ),
+        (
+            "execution".into(),
+            Kind::object(BTreeMap::from([
+                ("process_id".into(), Kind::integer().or_bytes()),
+                ("thread_id".into(), Kind::integer().or_bytes()),
+            ]))
+            .or_null(),
+        ),
+        ("channel".into(), bytes()),
+        ("computer".into(), bytes()),
+        ("security".into(), object(&["user_id"])),
+        (
+            "event_data".into(),
+            Kind::object(Collection::from_unknown(Kind::bytes().or_null())).or_null(),
+        ),
+        (
+            "user_data".into(),
+            Kind::object(Collection::any()).or_null(),
+        ),
+        (
+            "rendering_info".into(),
+            Kind::object(BTreeMap::from([
+                ("culture".into(), Kind::bytes().or_null()),
+                ("message".into(), Kind::bytes().or_null()),
+                ("level".into(), Kind::bytes().or_null()),
+                ("task".into(), Kind::bytes().or_null()),
+                ("opcode".into(), Kind::bytes().or_null()),
+                ("channel".into(), Kind::bytes().or_null()),
+                ("provider".into(), Kind::bytes().or_null()),
+                (
+                    "keywords".into(),
+                    Kind::array(Collection::from_unknown(Kind::bytes().or_null())).or_null(),
+                ),
+            ]))
+            .or_null(),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    test_function![
+        parse_windows_event_xml => ParseWindowsEventXml;
+
+        security_event {
+            args: func_args![value: indoc! {r#"
+                <Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+                  <System>
+                    <Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625-5478-4994-A5BA-3E3B0328C30D}"/>
+                    <EventID>4625</EventID>
+                    <Version>0</Version>
+                    <Level>0</Level>
+                    <Task>12544</Task>
+                    <Opcode>0</Opcode>
+                    <Keywords>0x8010000000000000</Keywords>
+                    <TimeCreated SystemTime="2022-10-03T12:00:00Z"/>
+                    <EventRecordID>8243</EventRecordID>
+                    <Correlation/>
+                    <Execution ProcessID="660" ThreadID="712"/>
+                    <Channel>Security</Channel>
+                    <Computer>dc01.example.com</Computer>
+                    <Security/>
+                  </System>
+                  <EventData>
+                    <Data Name="TargetUserName">bob</Data>
+                    <Data Name="IpAddress">192.0.2.1</Data>
+                    <Data Name="SubjectLogonId"></Data>
+                  </EventData>
+                  <RenderingInfo Culture="en-US">
+                    <Message>An account failed to log on.</Message>
+                    <Level>Information</Level>
+                    <Task>Logon</Task>
+                    <Keywords>
+                      <Keyword>Audit Failure</Keyword>
+                    </Keywords>
+                  </RenderingInfo>
+                </Event>
+            "#}],
+            want: Ok(value!({
+                "provider": {
+                    "name": "Microsoft-Windows-Security-Auditing",
+                    "guid": "{54849625-5478-4994-A5BA-3E3B0328C30D}",
+                },
+                "event_id": 4625,
+                "version": 0,
+                "level": 0,
+                "task": 12544,
+                "opcode": 0,
+                "keywords": "0x8010000000000000",
+                "time_created": (Utc.ymd(2022, 10, 3).and_hms(12, 0, 0)),
+                "event_record_id": 8243,
+                "correlation": {},
+                "execution": { "process_id": 660, "thread_id": 712 },
+                "channel": "Security",
+                "computer": "dc01.example.com",
+                "security": {},
+                "event_data": {
+                    "TargetUserName": "bob",
+                    "IpAddress": "192.0.2.1",
+                    "SubjectLogonId": null,
+                },
+                "rendering_info": {
+                    "culture": "en-US",
+                    "message": "An account failed to log on.",
+                    "level": "Information",
+                    "task": "Logon",
+                    "keywords": ["Audit Failure"],
+                },
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        classic_event {
+            args: func_args![value: r#"<Event><System><Provider Name="Service Control Manager" EventSourceName="Service Control Manager"/><EventID Qualifiers="16384">7036</EventID></System><EventData><Data>Windows Update</Data><Data>running</Data><Binary>7700</Binary></EventData><UserData><Log><Entry>1</Entry></Log></UserData></Event>"#],
+            want: Ok(value!({
+                "provider": {
+                    "name": "Service Control Manager",
+                    "event_source_name": "Service Control Manager",
+                },
+                "event_id": 7036,
+                "qualifiers": 16384,
+                "event_data": {
+                    "param1": "Windows Update",
+                    "param2": "running",
+                    "binary": "7700",
+                },
+                "user_data": { "Log": { "Entry": 1 } },
+            })),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        not_an_event {
+            args: func_args![value: "<a>test</a>"],
+            want: Err("unable to parse Windows event: root element isn't an Event"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_time {
+            args: func_args![value: r#"<Event><System><TimeCreated SystemTime="yesterday"/></System></Event>"#],
+            want: Err(r#"unable to parse SystemTime "yesterday": input contains invalid characters"#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -72,7 +72,7 @@ fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
     Ok(value)
 }
 
-struct ParseXmlConfig<'a> {
+pub(crate) struct ParseXmlConfig<'a> {
     /// Include XML attributes. Default: true,
     include_attr: bool,
     /// XML attribute prefix, e.g. `<a href="test">` -> `{a: { "@href": "test }}`. Default: "@".
@@ -89,6 +89,20 @@ struct ParseXmlConfig<'a> {
     parse_number: bool,
 }
 
+impl Default for ParseXmlConfig<'_> {
+    fn default() -> Self {
+        Self {
+            include_attr: true,
+            attr_prefix: Cow::from("@"),
+            text_key: Cow::from("text"),
+            always_use_text_key: false,
+            parse_bool: true,
+            parse_null: true,
+            parse_number: true,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct ParseXml;
 
@@ -279,7 +293,7 @@ fn inner_kind() -> Kind {
 }
 
 /// Process an XML node, and return a VRL `Value`.
-fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
+pub(crate) fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
     // Helper to recurse over a `Node`s children, and build an object.
     let recurse = |node: Node| -> BTreeMap<String, Value> {
         let mut map = BTreeMap::new();
@@ -410,7 +424,7 @@ static XML_RE: Lazy<Regex> = Lazy::new(|| {
 });
 
 #[inline]
-fn trim_xml(xml: &str) -> Cow<str> {
+pub(crate) fn trim_xml(xml: &str) -> Cow<str> {
     XML_RE.replace_all(xml, "><")
 }
 
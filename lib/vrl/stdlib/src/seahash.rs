@@ -0,0 +1,78 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+#[allow(clippy::cast_possible_wrap)]
+fn seahash(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+
+    // VRL integers are signed, so hashes above `i64::MAX` wrap around to
+    // negative integers.
+    Ok((seahash_lib::hash(&value) as i64).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Seahash;
+
+impl Function for Seahash {
+    fn identifier(&self) -> &'static str {
+        "seahash"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "seahash",
+            source: r#"seahash("to be or not to be")"#,
+            result: Ok("1988685042348123509"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(SeahashFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SeahashFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for SeahashFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        seahash(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        seahash => Seahash;
+
+        seahash {
+            args: func_args![value: "to be or not to be"],
+            want: Ok(value!(1_988_685_042_348_123_509_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
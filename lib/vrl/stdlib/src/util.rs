@@ -122,3 +122,71 @@ impl std::str::FromStr for Base64Charset {
         }
     }
 }
+
+/// Splits the given number of `|`-delimited header fields off the start of a
+/// CEF or LEEF message, returning the unescaped fields and the remainder of
+/// the message after the last field's delimiter.
+///
+/// Within header fields, `\|` escapes a pipe and `\\` a backslash. A message
+/// that ends right after the last field, without a delimiter, has an empty
+/// remainder.
+#[cfg(any(feature = "parse_cef", feature = "parse_leef"))]
+pub(crate) fn split_header(input: &str, count: usize) -> Option<(Vec<String>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut field = String::new();
+    let mut chars = input.char_indices();
+
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '\\' => match chars.clone().next() {
+                Some((_, next @ ('|' | '\\'))) => {
+                    field.push(next);
+                    chars.next();
+                }
+                _ => field.push(ch),
+            },
+            '|' => {
+                fields.push(std::mem::take(&mut field));
+
+                if fields.len() == count {
+                    return Some((fields, &input[index + 1..]));
+                }
+            }
+            _ => field.push(ch),
+        }
+    }
+
+    fields.push(field);
+    (fields.len() == count).then_some((fields, ""))
+}
+
+/// The maximum size of a decompressed value.
+///
+/// This guards against decompression bombs, small values that decompress into
+/// more data than can be held in memory.
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_snappy",
+    feature = "decode_zstd"
+))]
+pub(crate) const MAX_DECOMPRESSED_SIZE: usize = 100 * 1024 * 1024;
+
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_snappy",
+    feature = "decode_zstd"
+))]
+pub(crate) fn check_decompressed_size(
+    size: usize,
+    max_size: usize,
+) -> Result<(), vrl::prelude::ExpressionError> {
+    if size > max_size {
+        return Err(format!(
+            "decompressed value exceeds the maximum size of {} bytes",
+            max_size
+        )
+        .into());
+    }
+
+    Ok(())
+}
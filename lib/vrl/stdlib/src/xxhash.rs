@@ -0,0 +1,129 @@
+use std::hash::Hasher;
+
+use ::value::Value;
+use twox_hash::{XxHash32, XxHash64};
+use vrl::prelude::*;
+
+#[allow(clippy::cast_possible_wrap)]
+fn xxhash(value: Value, variant: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let digest = match variant.as_ref() {
+        b"XXH32" => hash(XxHash32::with_seed(0), &value),
+        b"XXH64" => hash(XxHash64::with_seed(0), &value),
+        _ => unreachable!("enum invariant"),
+    };
+
+    // VRL integers are signed, so 64-bit hashes above `i64::MAX` wrap around
+    // to negative integers.
+    Ok((digest as i64).into())
+}
+
+#[inline]
+fn hash(mut hasher: impl Hasher, value: &[u8]) -> u64 {
+    hasher.write(value);
+    hasher.finish()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Xxhash;
+
+fn variants() -> Vec<Value> {
+    vec![value!("XXH32"), value!("XXH64")]
+}
+
+impl Function for Xxhash {
+    fn identifier(&self) -> &'static str {
+        "xxhash"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default variant",
+                source: r#"xxhash("foo")"#,
+                result: Ok("3728699739546630719"),
+            },
+            Example {
+                title: "custom variant",
+                source: r#"xxhash("foo", variant: "XXH32")"#,
+                result: Ok("3792637401"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variant = arguments
+            .optional_enum("variant", &variants())?
+            .unwrap_or_else(|| value!("XXH64"))
+            .try_bytes()
+            .expect("variant not bytes");
+
+        Ok(Box::new(XxhashFn { value, variant }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct XxhashFn {
+    value: Box<dyn Expression>,
+    variant: Bytes,
+}
+
+impl Expression for XxhashFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        xxhash(value, &self.variant)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        xxhash => Xxhash;
+
+        xxh64 {
+            args: func_args![value: "foo"],
+            want: Ok(value!(3_728_699_739_546_630_719_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh64_wraps {
+            args: func_args![value: "foobar", variant: "XXH64"],
+            want: Ok(value!(-6_725_556_575_634_347_271_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh32 {
+            args: func_args![value: "foo", variant: "XXH32"],
+            want: Ok(value!(3_792_637_401_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
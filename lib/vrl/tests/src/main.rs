@@ -51,7 +51,8 @@ pub struct Cmd {
     #[clap(short = 'z', long)]
     timezone: Option<String>,
 
-    /// Should we use the VM to evaluate the VRL
+    /// The runtime used to evaluate the VRL. The compiled runtime is checked
+    /// against the ast runtime, and fails any test for which they diverge.
     #[clap(short, long = "runtime", default_value_t)]
     runtime: VrlRuntime,
 
@@ -396,23 +397,78 @@ fn run_vrl(
     vrl_runtime: VrlRuntime,
     test_enrichment: enrichment::TableRegistry,
 ) -> Result<Value, Terminate> {
+    test_enrichment.finish_load();
+
+    match vrl_runtime {
+        VrlRuntime::Ast => {
+            resolve_target(&mut test.object, |target| {
+                runtime.resolve(target, &program, &timezone)
+            })
+            .0
+        }
+        VrlRuntime::Compiled => {
+            let compiled = program.compile();
+
+            // The program is resolved by the tree-walking runtime as well, so
+            // any divergence of the compiled runtime fails the test.
+            let mut ast_object = test.object.clone();
+            let (ast_result, ast_metadata) = resolve_target(&mut ast_object, |target| {
+                Runtime::default().resolve(target, &program, &timezone)
+            });
+            let (result, metadata) = resolve_target(&mut test.object, |target| {
+                runtime.resolve_compiled(target, &compiled, &timezone)
+            });
+
+            if test.skip {
+                return result;
+            }
+
+            if result != ast_result {
+                return Err(Terminate::Error(
+                    format!(
+                        "compiled runtime resolved to {:?}, ast runtime resolved to {:?}",
+                        result, ast_result
+                    )
+                    .into(),
+                ));
+            }
+
+            if test.object != ast_object || metadata != ast_metadata {
+                return Err(Terminate::Error(
+                    format!(
+                        "compiled runtime left object {} and metadata {}, ast runtime {} and {}",
+                        test.object, metadata, ast_object, ast_metadata
+                    )
+                    .into(),
+                ));
+            }
+
+            result
+        }
+    }
+}
+
+/// Resolve a program against the object, returning the result and the final
+/// metadata of the target.
+fn resolve_target(
+    object: &mut Value,
+    resolve: impl FnOnce(&mut TargetValueRef<'_>) -> Result<Value, Terminate>,
+) -> (Result<Value, Terminate>, Value) {
     let mut metadata = Value::from(BTreeMap::new());
+    let mut secrets = Secrets::new();
     let mut target = TargetValueRef {
-        value: &mut test.object,
+        value: object,
         metadata: &mut metadata,
-        secrets: &mut Secrets::new(),
+        secrets: &mut secrets,
     };
 
     // Insert a dummy secret for examples to use
     target.insert_secret("my_secret", "secret value");
     target.insert_secret("datadog_api_key", "secret value");
 
-    match vrl_runtime {
-        VrlRuntime::Ast => {
-            test_enrichment.finish_load();
-            runtime.resolve(&mut target, &program, &timezone)
-        }
-    }
+    let result = resolve(&mut target);
+
+    (result, metadata)
 }
 
 fn compare_partial_diagnostic(got: &str, want: &str) -> bool {
@@ -113,6 +113,23 @@ fn benchmark_vrl_runtimes(c: &mut Criterion) {
                 },
             )
         });
+
+        let compiled = program.compile();
+
+        group.bench_with_input(BenchmarkId::new(source.name, "compiled"), &(), |b, _| {
+            let state = state::Runtime::default();
+            let mut runtime = Runtime::new(state);
+            let target: Value = serde_json::from_str(source.target).expect("valid json");
+
+            b.iter_with_setup(
+                || target.clone(),
+                |mut obj| {
+                    let _ = black_box(runtime.resolve_compiled(&mut obj, &compiled, &tz));
+                    runtime.clear();
+                    obj
+                },
+            )
+        });
     }
 }
 
@@ -12,8 +12,9 @@ mod runtime;
 
 use compiler::Compiler;
 pub use compiler::{
-    function, state, value, Context, Expression, Function, MetadataTarget, Program, ProgramInfo,
-    SecretTarget, Target, TargetValue, TargetValueRef, VrlRuntime,
+    function, state, value, Clock, CompiledProgram, Context, Expression, Function, ImportRoot,
+    IterationLimit, MetadataTarget, Program, ProgramInfo, SecretTarget, Target, TargetValue,
+    TargetValueRef, Trace, TraceChange, TraceEntry, TraceLog, TypeInfo, VrlRuntime,
 };
 pub use diagnostic;
 pub use runtime::{Runtime, RuntimeResult, Terminate};
@@ -46,3 +47,21 @@ pub fn compile_with_state(
         .map_err(|err| diagnostic::DiagnosticList::from(vec![Box::new(err) as Box<_>]))?;
     Compiler::compile(fns, ast, external, local)
 }
+
+/// Compile a given source, and collect the inferred type definitions of its
+/// expressions, for as much of the source as compiles.
+pub fn compile_with_type_info(
+    source: &str,
+    fns: &[Box<dyn Function>],
+    external: &mut state::ExternalEnv,
+    local: state::LocalEnv,
+) -> (compiler::Result, TypeInfo) {
+    match parser::parse(source) {
+        Ok(ast) => Compiler::compile_with_type_info(fns, ast, external, local),
+        Err(err) => {
+            let diagnostics = diagnostic::DiagnosticList::from(vec![Box::new(err) as Box<_>]);
+
+            (Err(diagnostics), TypeInfo::default())
+        }
+    }
+}
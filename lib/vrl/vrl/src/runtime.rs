@@ -1,10 +1,10 @@
 use std::{error::Error, fmt};
 
-use compiler::ExpressionError;
+use compiler::{ExpressionError, Resolved};
 use lookup::LookupBuf;
 use value::Value;
 
-use crate::{state, Context, Program, Target, TimeZone};
+use crate::{state, CompiledProgram, Context, Program, Target, TimeZone, Trace};
 
 pub type RuntimeResult = Result<Value, Terminate>;
 
@@ -71,6 +71,39 @@ impl Runtime {
         target: &mut dyn Target,
         program: &Program,
         timezone: &TimeZone,
+    ) -> RuntimeResult {
+        self.run(target, timezone, None, |ctx| program.resolve(ctx))
+    }
+
+    /// Given the provided [`Target`], resolve the provided [`Program`] to
+    /// completion, recording each evaluated expression into the [`Trace`].
+    pub fn resolve_traced(
+        &mut self,
+        target: &mut dyn Target,
+        program: &Program,
+        timezone: &TimeZone,
+        trace: &mut Trace,
+    ) -> RuntimeResult {
+        self.run(target, timezone, Some(trace), |ctx| program.resolve(ctx))
+    }
+
+    /// Given the provided [`Target`], resolve the provided [`CompiledProgram`]
+    /// to completion.
+    pub fn resolve_compiled(
+        &mut self,
+        target: &mut dyn Target,
+        program: &CompiledProgram,
+        timezone: &TimeZone,
+    ) -> RuntimeResult {
+        self.run(target, timezone, None, |ctx| program.resolve(ctx))
+    }
+
+    fn run(
+        &mut self,
+        target: &mut dyn Target,
+        timezone: &TimeZone,
+        trace: Option<&mut Trace>,
+        resolve: impl FnOnce(&mut Context) -> Resolved,
     ) -> RuntimeResult {
         // Validate that the path is a value.
         match target.target_get(&self.root_lookup) {
@@ -88,8 +121,11 @@ impl Runtime {
         };
 
         let mut ctx = Context::new(target, &mut self.state, timezone);
+        if let Some(trace) = trace {
+            ctx = ctx.with_trace(trace);
+        }
 
-        program.resolve(&mut ctx).map_err(|err| match err {
+        resolve(&mut ctx).map_err(|err| match err {
             #[cfg(feature = "expr-abort")]
             ExpressionError::Abort { .. } => Terminate::Abort(err),
             err @ ExpressionError::Error { .. } => Terminate::Error(err),
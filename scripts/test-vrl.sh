@@ -11,4 +11,5 @@ set -euo pipefail
   cd "$(dirname "${BASH_SOURCE[0]}")/../lib/vrl/tests"
 
   cargo run -- --runtime=ast
+  cargo run -- --runtime=compiled
 )
@@ -15,6 +15,8 @@ use crate::config::enterprise::{
     attach_enterprise_components, report_configuration, report_on_reload, EnterpriseError,
     EnterpriseMetadata, EnterpriseReporter,
 };
+#[cfg(feature = "vrl-cli")]
+use crate::language_server;
 #[cfg(not(feature = "enterprise-tests"))]
 use crate::metrics;
 #[cfg(windows)]
@@ -51,6 +53,8 @@ pub struct ApplicationConfig {
     pub enterprise: Option<EnterpriseReporter<BoxFuture<'static, ()>>>,
     pub signal_handler: signal::SignalHandler,
     pub signal_rx: signal::SignalRx,
+    /// The paths watched for changes, when the config is watched.
+    pub watched_paths: Option<config::watcher::WatchedPaths>,
 }
 
 pub struct Application {
@@ -159,6 +163,8 @@ impl Application {
                         SubCommand::Validate(v) => validate::validate(&v, color).await,
                         #[cfg(feature = "vrl-cli")]
                         SubCommand::Vrl(s) => vrl_cli::cmd::cmd(&s),
+                        #[cfg(feature = "vrl-cli")]
+                        SubCommand::VrlLsp(l) => language_server::cmd(&l),
                     };
 
                     return Err(code);
@@ -168,14 +174,18 @@ impl Application {
 
                 let config_paths = config::process_paths(&config_paths).ok_or(exitcode::CONFIG)?;
 
-                if watch_config {
+                let watched_paths = if watch_config {
                     // Start listening for config changes immediately.
-                    config::watcher::spawn_thread(config_paths.iter().map(Into::into), None)
-                        .map_err(|error| {
-                            error!(message = "Unable to start config watcher.", %error);
-                            exitcode::CONFIG
-                        })?;
-                }
+                    let watched_paths =
+                        config::watcher::spawn_thread(&paths_to_watch(&config_paths), None)
+                            .map_err(|error| {
+                                error!(message = "Unable to start config watcher.", %error);
+                                exitcode::CONFIG
+                            })?;
+                    Some(watched_paths)
+                } else {
+                    None
+                };
 
                 info!(
                     message = "Loading configs.",
@@ -236,6 +246,7 @@ impl Application {
                     enterprise,
                     signal_handler,
                     signal_rx,
+                    watched_paths,
                 })
             })
         }?;
@@ -265,6 +276,7 @@ impl Application {
 
         let mut signal_handler = self.config.signal_handler;
         let mut signal_rx = self.config.signal_rx;
+        let watched_paths = self.config.watched_paths;
 
         // Any internal_logs sources will have grabbed a copy of the
         // early buffer by this point and set up a subscriber.
@@ -348,6 +360,9 @@ impl Application {
                             Ok(SignalTo::ReloadFromDisk) => {
                                 // Reload paths
                                 config_paths = config::process_paths(&opts.config_paths_with_formats()).unwrap_or(config_paths);
+                                if let Some(watched_paths) = &watched_paths {
+                                    watched_paths.set(paths_to_watch(&config_paths));
+                                }
 
                                 // Reload config
                                 let new_config = config::load_from_paths_with_provider_and_secrets(&config_paths, &mut signal_handler)
@@ -432,3 +447,18 @@ impl Application {
         });
     }
 }
+
+/// Returns the config paths along with the paths secrets are read from, so that rotated secrets
+/// are picked up by reloading the config.
+fn paths_to_watch(config_paths: &[config::ConfigPath]) -> Vec<PathBuf> {
+    config_paths
+        .iter()
+        .map(<&PathBuf>::from)
+        .cloned()
+        .chain(
+            config::load_secret_backends_from_paths(config_paths)
+                .map(|(backends, _)| backends.watched_paths())
+                .unwrap_or_default(),
+        )
+        .collect()
+}
@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use clap::{AppSettings, FromArgMatches, IntoApp, Parser};
 
+#[cfg(feature = "vrl-cli")]
+use crate::language_server;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
@@ -208,6 +210,10 @@ pub enum SubCommand {
     /// Vector Remap Language CLI
     #[cfg(feature = "vrl-cli")]
     Vrl(vrl_cli::Opts),
+
+    /// Vector Remap Language language server, for editor integrations
+    #[cfg(feature = "vrl-cli")]
+    VrlLsp(language_server::Opts),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -91,6 +91,11 @@ impl EncodingConfigWithFraming {
         (&self.framing, &self.encoding.encoding)
     }
 
+    /// Get the encoding configuration, without the framing.
+    pub const fn encoding(&self) -> &EncodingConfig {
+        &self.encoding
+    }
+
     /// Build the `Framer` and `Serializer` for this config.
     pub fn build(&self, sink_type: SinkType) -> crate::Result<(Framer, Serializer)> {
         let framer = self.framing.as_ref().map(|framing| framing.build());
@@ -111,6 +116,7 @@ impl EncodingConfigWithFraming {
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
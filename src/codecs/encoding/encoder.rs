@@ -121,6 +121,7 @@ impl Encoder<Framer> {
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
                 _,
             ) => "text/plain",
@@ -4,6 +4,9 @@
 #![allow(unreachable_pub)]
 use serde::{Deserialize, Serialize};
 
+#[cfg(any(feature = "sources-datadog_agent", feature = "sinks-datadog_traces"))]
+pub(crate) mod apm_stats;
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub(crate) struct DatadogSeriesMetric {
     pub(crate) metric: String,
@@ -0,0 +1,325 @@
+//! APM stats payloads, as sent by the Datadog trace-agent to the `/api/v0.2/stats` route.
+//!
+//! On the agent side APM Stats payload are encoded into the messagepack format using this
+//! go code https://github.com/DataDog/datadog-agent/blob/b5bed4d/pkg/trace/pb/stats_gen.go.
+//! Note that this code is generated from code itself generate from this .proto file
+//! https://github.com/DataDog/datadog-agent/blob/dc2f202/pkg/trace/pb/stats.proto.
+//! All the subsequent struct are dedicated to be used with rmp_serde and the fields names
+//! exactly match the ones of the go code.
+//!
+//! Within Vector, the client stats payloads are carried by a `TraceEvent` as an array of objects
+//! whose fields are the snake cased names of the go fields, so that a payload received by the
+//! `datadog_agent` source can be relayed as is by the `datadog_traces` sink.
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use serde_bytes;
+
+use crate::event::Value;
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct StatsPayload {
+    pub(crate) agent_hostname: String,
+    pub(crate) agent_env: String,
+    pub(crate) stats: Vec<ClientStatsPayload>,
+    pub(crate) agent_version: String,
+    pub(crate) client_computed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ClientStatsPayload {
+    pub(crate) hostname: String,
+    pub(crate) env: String,
+    pub(crate) version: String,
+    pub(crate) stats: Vec<ClientStatsBucket>,
+    pub(crate) lang: String,
+    pub(crate) tracer_version: String,
+    #[serde(rename = "RuntimeID")]
+    pub(crate) runtime_id: String,
+    pub(crate) sequence: u64,
+    pub(crate) agent_aggregation: String,
+    pub(crate) service: String,
+    #[serde(rename = "ContainerID")]
+    pub(crate) container_id: String,
+    pub(crate) tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ClientStatsBucket {
+    pub(crate) start: u64,
+    pub(crate) duration: u64,
+    pub(crate) stats: Vec<ClientGroupedStats>,
+    pub(crate) agent_time_shift: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ClientGroupedStats {
+    pub(crate) service: String,
+    pub(crate) name: String,
+    pub(crate) resource: String,
+    #[serde(rename = "HTTPStatusCode")]
+    pub(crate) http_status_code: u32,
+    pub(crate) r#type: String,
+    #[serde(rename = "DBType")]
+    pub(crate) db_type: String,
+    pub(crate) hits: u64,
+    pub(crate) errors: u64,
+    pub(crate) duration: u64,
+    #[serde(with = "serde_bytes")]
+    pub(crate) ok_summary: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub(crate) error_summary: Vec<u8>,
+    pub(crate) synthetics: bool,
+    pub(crate) top_level_hits: u64,
+}
+
+impl From<ClientStatsPayload> for Value {
+    fn from(payload: ClientStatsPayload) -> Self {
+        Value::from(BTreeMap::from([
+            ("hostname".to_string(), Value::from(payload.hostname)),
+            ("env".to_string(), Value::from(payload.env)),
+            ("version".to_string(), Value::from(payload.version)),
+            (
+                "stats".to_string(),
+                Value::from(
+                    payload
+                        .stats
+                        .into_iter()
+                        .map(Value::from)
+                        .collect::<Vec<Value>>(),
+                ),
+            ),
+            ("lang".to_string(), Value::from(payload.lang)),
+            (
+                "tracer_version".to_string(),
+                Value::from(payload.tracer_version),
+            ),
+            ("runtime_id".to_string(), Value::from(payload.runtime_id)),
+            (
+                "sequence".to_string(),
+                Value::Integer(payload.sequence as i64),
+            ),
+            (
+                "agent_aggregation".to_string(),
+                Value::from(payload.agent_aggregation),
+            ),
+            ("service".to_string(), Value::from(payload.service)),
+            (
+                "container_id".to_string(),
+                Value::from(payload.container_id),
+            ),
+            (
+                "tags".to_string(),
+                Value::from(
+                    payload
+                        .tags
+                        .into_iter()
+                        .map(Value::from)
+                        .collect::<Vec<Value>>(),
+                ),
+            ),
+        ]))
+    }
+}
+
+impl From<ClientStatsBucket> for Value {
+    fn from(bucket: ClientStatsBucket) -> Self {
+        Value::from(BTreeMap::from([
+            ("start".to_string(), Value::Integer(bucket.start as i64)),
+            (
+                "duration".to_string(),
+                Value::Integer(bucket.duration as i64),
+            ),
+            (
+                "stats".to_string(),
+                Value::from(
+                    bucket
+                        .stats
+                        .into_iter()
+                        .map(Value::from)
+                        .collect::<Vec<Value>>(),
+                ),
+            ),
+            (
+                "agent_time_shift".to_string(),
+                Value::Integer(bucket.agent_time_shift),
+            ),
+        ]))
+    }
+}
+
+impl From<ClientGroupedStats> for Value {
+    fn from(stats: ClientGroupedStats) -> Self {
+        Value::from(BTreeMap::from([
+            ("service".to_string(), Value::from(stats.service)),
+            ("name".to_string(), Value::from(stats.name)),
+            ("resource".to_string(), Value::from(stats.resource)),
+            (
+                "http_status_code".to_string(),
+                Value::Integer(stats.http_status_code.into()),
+            ),
+            ("type".to_string(), Value::from(stats.r#type)),
+            ("db_type".to_string(), Value::from(stats.db_type)),
+            ("hits".to_string(), Value::Integer(stats.hits as i64)),
+            ("errors".to_string(), Value::Integer(stats.errors as i64)),
+            (
+                "duration".to_string(),
+                Value::Integer(stats.duration as i64),
+            ),
+            (
+                "ok_summary".to_string(),
+                Value::from(Bytes::from(stats.ok_summary)),
+            ),
+            (
+                "error_summary".to_string(),
+                Value::from(Bytes::from(stats.error_summary)),
+            ),
+            ("synthetics".to_string(), Value::from(stats.synthetics)),
+            (
+                "top_level_hits".to_string(),
+                Value::Integer(stats.top_level_hits as i64),
+            ),
+        ]))
+    }
+}
+
+impl From<&BTreeMap<String, Value>> for ClientStatsPayload {
+    fn from(payload: &BTreeMap<String, Value>) -> Self {
+        ClientStatsPayload {
+            hostname: string(payload, "hostname"),
+            env: string(payload, "env"),
+            version: string(payload, "version"),
+            stats: objects(payload, "stats")
+                .map(ClientStatsBucket::from)
+                .collect(),
+            lang: string(payload, "lang"),
+            tracer_version: string(payload, "tracer_version"),
+            runtime_id: string(payload, "runtime_id"),
+            sequence: integer(payload, "sequence") as u64,
+            agent_aggregation: string(payload, "agent_aggregation"),
+            service: string(payload, "service"),
+            container_id: string(payload, "container_id"),
+            tags: match payload.get("tags") {
+                Some(Value::Array(tags)) => tags.iter().map(|tag| tag.to_string_lossy()).collect(),
+                _ => vec![],
+            },
+        }
+    }
+}
+
+impl From<&BTreeMap<String, Value>> for ClientStatsBucket {
+    fn from(bucket: &BTreeMap<String, Value>) -> Self {
+        ClientStatsBucket {
+            start: integer(bucket, "start") as u64,
+            duration: integer(bucket, "duration") as u64,
+            stats: objects(bucket, "stats")
+                .map(ClientGroupedStats::from)
+                .collect(),
+            agent_time_shift: integer(bucket, "agent_time_shift"),
+        }
+    }
+}
+
+impl From<&BTreeMap<String, Value>> for ClientGroupedStats {
+    fn from(stats: &BTreeMap<String, Value>) -> Self {
+        ClientGroupedStats {
+            service: string(stats, "service"),
+            name: string(stats, "name"),
+            resource: string(stats, "resource"),
+            http_status_code: integer(stats, "http_status_code") as u32,
+            r#type: string(stats, "type"),
+            db_type: string(stats, "db_type"),
+            hits: integer(stats, "hits") as u64,
+            errors: integer(stats, "errors") as u64,
+            duration: integer(stats, "duration") as u64,
+            ok_summary: bytes(stats, "ok_summary"),
+            error_summary: bytes(stats, "error_summary"),
+            synthetics: matches!(stats.get("synthetics"), Some(Value::Boolean(true))),
+            top_level_hits: integer(stats, "top_level_hits") as u64,
+        }
+    }
+}
+
+fn string(map: &BTreeMap<String, Value>, key: &str) -> String {
+    map.get(key)
+        .map(|v| v.to_string_lossy())
+        .unwrap_or_default()
+}
+
+fn integer(map: &BTreeMap<String, Value>, key: &str) -> i64 {
+    match map.get(key) {
+        Some(Value::Integer(val)) => *val,
+        _ => 0,
+    }
+}
+
+fn bytes(map: &BTreeMap<String, Value>, key: &str) -> Vec<u8> {
+    match map.get(key) {
+        Some(Value::Bytes(val)) => val.to_vec(),
+        _ => vec![],
+    }
+}
+
+fn objects<'a>(
+    map: &'a BTreeMap<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = &'a BTreeMap<String, Value>> {
+    map.get(key)
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_object())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_stats_payload_round_trips_through_value() {
+        let payload = ClientStatsPayload {
+            hostname: "a_host".to_string(),
+            env: "an_env".to_string(),
+            version: "v1.2".to_string(),
+            stats: vec![ClientStatsBucket {
+                start: 1_431_648_000_000_000_000,
+                duration: 10_000_000_000,
+                stats: vec![ClientGroupedStats {
+                    service: "a_service".to_string(),
+                    name: "a_name".to_string(),
+                    resource: "a_resource".to_string(),
+                    http_status_code: 200,
+                    r#type: "web".to_string(),
+                    db_type: "".to_string(),
+                    hits: 10,
+                    errors: 1,
+                    duration: 123_456,
+                    ok_summary: vec![1, 2, 3],
+                    error_summary: vec![0xff, 0],
+                    synthetics: true,
+                    top_level_hits: 9,
+                }],
+                agent_time_shift: -42,
+            }],
+            lang: "go".to_string(),
+            tracer_version: "v1.38.0".to_string(),
+            runtime_id: "a_runtime".to_string(),
+            sequence: 7,
+            agent_aggregation: "distributions".to_string(),
+            service: "a_service".to_string(),
+            container_id: "a_container".to_string(),
+            tags: vec!["a:tag".to_string(), "another:tag".to_string()],
+        };
+
+        let value = Value::from(payload.clone());
+        assert_eq!(
+            ClientStatsPayload::from(value.as_object().unwrap()),
+            payload
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::SystemTime;
+
+use aws_sigv4::http_request::{SignableRequest, SigningSettings};
+use aws_sigv4::SigningParams;
+use aws_types::credentials::{ProvideCredentials, SharedCredentialsProvider};
+use aws_types::region::Region;
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use crate::aws::AwsAuthentication;
+
+/// Authentication strategies.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum ElasticsearchAuth {
+    /// HTTP Basic Authentication.
+    Basic {
+        /// Basic authentication username.
+        user: String,
+
+        /// Basic authentication password.
+        password: String,
+    },
+
+    /// Amazon OpenSearch Service-specific authentication.
+    Aws(#[configurable(derived)] AwsAuthentication),
+}
+
+pub async fn sign_request(
+    request: &mut http::Request<Bytes>,
+    credentials_provider: &SharedCredentialsProvider,
+    region: &Option<Region>,
+) -> crate::Result<()> {
+    let signable_request = SignableRequest::from(&*request);
+    let credentials = credentials_provider.provide_credentials().await?;
+    let mut signing_params_builder = SigningParams::builder()
+        .access_key(credentials.access_key_id())
+        .secret_key(credentials.secret_access_key())
+        .region(region.as_ref().map(|r| r.as_ref()).unwrap_or(""))
+        .service_name("es")
+        .time(SystemTime::now())
+        .settings(SigningSettings::default());
+
+    signing_params_builder.set_security_token(credentials.session_token());
+
+    let (signing_instructions, _signature) =
+        aws_sigv4::http_request::sign(signable_request, &signing_params_builder.build()?)?
+            .into_parts();
+    signing_instructions.apply_to_request(request);
+
+    Ok(())
+}
@@ -9,6 +9,15 @@
 ))]
 pub(crate) mod datadog;
 
+#[cfg(any(feature = "sources-elasticsearch", feature = "sinks-elasticsearch"))]
+pub(crate) mod elasticsearch;
+
+#[cfg(any(
+    feature = "sources-aws_kinesis_streams",
+    feature = "sinks-aws_kinesis_streams"
+))]
+pub(crate) mod kinesis;
+
 #[cfg(any(
     feature = "sources-aws_sqs",
     feature = "sinks-aws_sqs",
@@ -1,6 +1,5 @@
 use std::borrow::Cow;
 
-use bytes::Bytes;
 use datadog_filter::{
     build_matcher,
     regex::{wildcard_regex, word_regex},
@@ -8,8 +7,9 @@ use datadog_filter::{
 };
 use datadog_search_syntax::parse;
 use datadog_search_syntax::{Comparison, ComparisonValue, Field};
+use ordered_float::NotNan;
 use vector_config::configurable_component;
-use vector_core::event::{Event, LogEvent, Value};
+use vector_core::event::{Event, Metric, MetricValue, Value};
 
 use crate::conditions::{Condition, Conditional, ConditionalConfig};
 
@@ -40,47 +40,35 @@ impl Conditional for DatadogSearchRunner {
 impl ConditionalConfig for DatadogSearchConfig {
     fn build(&self, _enrichment_tables: &enrichment::TableRegistry) -> crate::Result<Condition> {
         let node = parse(&self.source)?;
-        let matcher = as_log(build_matcher(&node, &EventFilter::default()));
+        let matcher = build_matcher(&node, &EventFilter::default());
 
         Ok(Condition::DatadogSearch(DatadogSearchRunner { matcher }))
     }
 }
 
-/// Run the provided `Matcher` when we're dealing with `LogEvent`s. Otherwise, return false.
-fn as_log(matcher: Box<dyn Matcher<LogEvent>>) -> Box<dyn Matcher<Event>> {
-    Run::boxed(move |ev| match ev {
-        Event::Log(log) => matcher.run(log),
-        _ => false,
-    })
-}
-
 #[derive(Default, Clone)]
 struct EventFilter;
 
 /// Uses the default `Resolver`, to build a `Vec<Field>`.
 impl Resolver for EventFilter {}
 
-impl Filter<LogEvent> for EventFilter {
-    fn exists(&self, field: Field) -> Box<dyn Matcher<LogEvent>> {
+impl Filter<Event> for EventFilter {
+    fn exists(&self, field: Field) -> Box<dyn Matcher<Event>> {
         match field {
             Field::Tag(tag) => {
                 let starts_with = format!("{}:", tag);
 
-                any_string_match("tags", move |value| {
-                    value == tag || value.starts_with(&starts_with)
-                })
+                any_tag_match(move |value| value == tag || value.starts_with(&starts_with))
             }
             // Literal field 'tags' needs to be compared by key.
-            Field::Reserved(field) if field == "tags" => {
-                any_string_match("tags", move |value| value == field)
-            }
+            Field::Reserved(field) if field == "tags" => any_tag_match(move |value| value == field),
             Field::Default(f) | Field::Facet(f) | Field::Reserved(f) => {
-                Run::boxed(move |log: &LogEvent| log.get(f.as_str()).is_some())
+                Run::boxed(move |event: &Event| field_value(event, &f).is_some())
             }
         }
     }
 
-    fn equals(&self, field: Field, to_match: &str) -> Box<dyn Matcher<LogEvent>> {
+    fn equals(&self, field: Field, to_match: &str) -> Box<dyn Matcher<Event>> {
         match field {
             // Default fields are compared by word boundary.
             Field::Default(field) => {
@@ -92,15 +80,13 @@ impl Filter<LogEvent> for EventFilter {
             Field::Reserved(field) if field == "tags" => {
                 let to_match = to_match.to_owned();
 
-                array_match(field, move |values| {
-                    values.contains(&Value::Bytes(Bytes::copy_from_slice(to_match.as_bytes())))
-                })
+                any_tag_match(move |value| value == to_match)
             }
             // Individual tags are compared by element key:value.
             Field::Tag(tag) => {
-                let value_bytes = Value::Bytes(format!("{}:{}", tag, to_match).into());
+                let to_match = format!("{}:{}", tag, to_match);
 
-                array_match("tags", move |values| values.contains(&value_bytes))
+                any_tag_match(move |value| value == to_match)
             }
             // Everything else is matched by string equality.
             Field::Reserved(field) | Field::Facet(field) => {
@@ -111,7 +97,7 @@ impl Filter<LogEvent> for EventFilter {
         }
     }
 
-    fn prefix(&self, field: Field, prefix: &str) -> Box<dyn Matcher<LogEvent>> {
+    fn prefix(&self, field: Field, prefix: &str) -> Box<dyn Matcher<Event>> {
         match field {
             // Default fields are matched by word boundary.
             Field::Default(field) => {
@@ -123,7 +109,7 @@ impl Filter<LogEvent> for EventFilter {
             Field::Tag(tag) => {
                 let starts_with = format!("{}:{}", tag, prefix);
 
-                any_string_match("tags", move |value| value.starts_with(&starts_with))
+                any_tag_match(move |value| value.starts_with(&starts_with))
             }
             // All other field types are compared by complete value.
             Field::Reserved(field) | Field::Facet(field) => {
@@ -134,7 +120,7 @@ impl Filter<LogEvent> for EventFilter {
         }
     }
 
-    fn wildcard(&self, field: Field, wildcard: &str) -> Box<dyn Matcher<LogEvent>> {
+    fn wildcard(&self, field: Field, wildcard: &str) -> Box<dyn Matcher<Event>> {
         match field {
             Field::Default(field) => {
                 let re = word_regex(wildcard);
@@ -144,7 +130,7 @@ impl Filter<LogEvent> for EventFilter {
             Field::Tag(tag) => {
                 let re = wildcard_regex(&format!("{}:{}", tag, wildcard));
 
-                any_string_match("tags", move |value| re.is_match(&value))
+                any_tag_match(move |value| re.is_match(&value))
             }
             Field::Reserved(field) | Field::Facet(field) => {
                 let re = wildcard_regex(wildcard);
@@ -159,79 +145,71 @@ impl Filter<LogEvent> for EventFilter {
         field: Field,
         comparator: Comparison,
         comparison_value: ComparisonValue,
-    ) -> Box<dyn Matcher<LogEvent>> {
+    ) -> Box<dyn Matcher<Event>> {
         let rhs = Cow::from(comparison_value.to_string());
 
         match field {
             // Facets are compared numerically if the value is numeric, or as strings otherwise.
-            Field::Facet(f) => {
-                Run::boxed(
-                    move |log: &LogEvent| match (log.get(f.as_str()), &comparison_value) {
-                        // Integers.
-                        (Some(Value::Integer(lhs)), ComparisonValue::Integer(rhs)) => {
-                            match comparator {
-                                Comparison::Lt => lhs < rhs,
-                                Comparison::Lte => lhs <= rhs,
-                                Comparison::Gt => lhs > rhs,
-                                Comparison::Gte => lhs >= rhs,
-                            }
-                        }
-                        // Integer value - Float boundary
-                        (Some(Value::Integer(lhs)), ComparisonValue::Float(rhs)) => {
-                            match comparator {
-                                Comparison::Lt => (*lhs as f64) < *rhs,
-                                Comparison::Lte => *lhs as f64 <= *rhs,
-                                Comparison::Gt => *lhs as f64 > *rhs,
-                                Comparison::Gte => *lhs as f64 >= *rhs,
-                            }
-                        }
-                        // Floats.
-                        (Some(Value::Float(lhs)), ComparisonValue::Float(rhs)) => {
-                            match comparator {
-                                Comparison::Lt => lhs.into_inner() < *rhs,
-                                Comparison::Lte => lhs.into_inner() <= *rhs,
-                                Comparison::Gt => lhs.into_inner() > *rhs,
-                                Comparison::Gte => lhs.into_inner() >= *rhs,
-                            }
+            Field::Facet(f) => Run::boxed(move |event: &Event| {
+                match (field_value(event, &f).as_deref(), &comparison_value) {
+                    // Integers.
+                    (Some(Value::Integer(lhs)), ComparisonValue::Integer(rhs)) => {
+                        match comparator {
+                            Comparison::Lt => lhs < rhs,
+                            Comparison::Lte => lhs <= rhs,
+                            Comparison::Gt => lhs > rhs,
+                            Comparison::Gte => lhs >= rhs,
                         }
-                        // Float value - Integer boundary
-                        (Some(Value::Float(lhs)), ComparisonValue::Integer(rhs)) => {
-                            match comparator {
-                                Comparison::Lt => lhs.into_inner() < *rhs as f64,
-                                Comparison::Lte => lhs.into_inner() <= *rhs as f64,
-                                Comparison::Gt => lhs.into_inner() > *rhs as f64,
-                                Comparison::Gte => lhs.into_inner() >= *rhs as f64,
-                            }
-                        }
-                        // Where the rhs is a string ref, the lhs is coerced into a string.
-                        (Some(Value::Bytes(v)), ComparisonValue::String(rhs)) => {
-                            let lhs = String::from_utf8_lossy(v);
-                            let rhs = Cow::from(rhs);
+                    }
+                    // Integer value - Float boundary
+                    (Some(Value::Integer(lhs)), ComparisonValue::Float(rhs)) => match comparator {
+                        Comparison::Lt => (*lhs as f64) < *rhs,
+                        Comparison::Lte => *lhs as f64 <= *rhs,
+                        Comparison::Gt => *lhs as f64 > *rhs,
+                        Comparison::Gte => *lhs as f64 >= *rhs,
+                    },
+                    // Floats.
+                    (Some(Value::Float(lhs)), ComparisonValue::Float(rhs)) => match comparator {
+                        Comparison::Lt => lhs.into_inner() < *rhs,
+                        Comparison::Lte => lhs.into_inner() <= *rhs,
+                        Comparison::Gt => lhs.into_inner() > *rhs,
+                        Comparison::Gte => lhs.into_inner() >= *rhs,
+                    },
+                    // Float value - Integer boundary
+                    (Some(Value::Float(lhs)), ComparisonValue::Integer(rhs)) => match comparator {
+                        Comparison::Lt => lhs.into_inner() < *rhs as f64,
+                        Comparison::Lte => lhs.into_inner() <= *rhs as f64,
+                        Comparison::Gt => lhs.into_inner() > *rhs as f64,
+                        Comparison::Gte => lhs.into_inner() >= *rhs as f64,
+                    },
+                    // Where the rhs is a string ref, the lhs is coerced into a string.
+                    (Some(Value::Bytes(v)), ComparisonValue::String(rhs)) => {
+                        let lhs = String::from_utf8_lossy(v);
+                        let rhs = Cow::from(rhs);
 
-                            match comparator {
-                                Comparison::Lt => lhs < rhs,
-                                Comparison::Lte => lhs <= rhs,
-                                Comparison::Gt => lhs > rhs,
-                                Comparison::Gte => lhs >= rhs,
-                            }
+                        match comparator {
+                            Comparison::Lt => lhs < rhs,
+                            Comparison::Lte => lhs <= rhs,
+                            Comparison::Gt => lhs > rhs,
+                            Comparison::Gte => lhs >= rhs,
                         }
-                        // Otherwise, compare directly as strings.
-                        (Some(Value::Bytes(v)), _) => {
-                            let lhs = String::from_utf8_lossy(v);
+                    }
+                    // Otherwise, compare directly as strings.
+                    (Some(Value::Bytes(v)), _) => {
+                        let lhs = String::from_utf8_lossy(v);
 
-                            match comparator {
-                                Comparison::Lt => lhs < rhs,
-                                Comparison::Lte => lhs <= rhs,
-                                Comparison::Gt => lhs > rhs,
-                                Comparison::Gte => lhs >= rhs,
-                            }
+                        match comparator {
+                            Comparison::Lt => lhs < rhs,
+                            Comparison::Lte => lhs <= rhs,
+                            Comparison::Gt => lhs > rhs,
+                            Comparison::Gte => lhs >= rhs,
                         }
-                        _ => false,
-                    },
-                )
-            }
+                    }
+                    _ => false,
+                }
+            }),
             // Tag values need extracting by "key:value" to be compared.
-            Field::Tag(tag) => any_string_match("tags", move |value| match value.split_once(':') {
+            Field::Tag(tag) => any_tag_match(move |value| match value.split_once(':') {
                 Some((t, lhs)) if t == tag => {
                     let lhs = Cow::from(lhs);
 
@@ -257,57 +235,103 @@ impl Filter<LogEvent> for EventFilter {
     }
 }
 
-/// Returns a `Matcher` that returns true if the log event resolves to a string which
-/// matches the provided `func`.
-fn string_match<S, F>(field: S, func: F) -> Box<dyn Matcher<LogEvent>>
-where
-    S: Into<String>,
-    F: Fn(Cow<str>) -> bool + Send + Sync + Clone + 'static,
-{
-    let field = field.into();
+/// Returns the value of a field in an event. Log and trace fields are looked up by path.
+/// Metrics have no paths, so their properties are mapped onto fields instead: the metric
+/// name is the default field, the `@name`, `@namespace`, `@kind`, `@type` and `@value`
+/// facets are the metric's own properties, and the reserved `host`, `source`, `status` and `service`
+/// attributes are read from the tags of the same name.
+fn field_value<'a>(event: &'a Event, field: &str) -> Option<Cow<'a, Value>> {
+    match event {
+        Event::Log(log) => log.get(field).map(Cow::Borrowed),
+        Event::Trace(trace) => trace.get(field).map(Cow::Borrowed),
+        Event::Metric(metric) => metric_field_value(metric, field).map(Cow::Owned),
+    }
+}
 
-    Run::boxed(move |log: &LogEvent| match log.get(field.as_str()) {
-        Some(Value::Bytes(v)) => func(String::from_utf8_lossy(v)),
-        _ => false,
-    })
+fn metric_field_value(metric: &Metric, field: &str) -> Option<Value> {
+    match field {
+        "message" | "custom.name" => Some(metric.name().into()),
+        "custom.namespace" => metric.namespace().map(Into::into),
+        "custom.kind" => Some(metric.kind().into()),
+        "custom.type" => Some(metric.value().as_name().into()),
+        // Only metrics with a single value can be compared by it.
+        "custom.value" => match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => {
+                NotNan::new(*value).ok().map(Into::into)
+            }
+            _ => None,
+        },
+        "timestamp" => metric.timestamp().map(Into::into),
+        "host" | "source" | "status" | "service" => metric.tag_value(field).map(Into::into),
+        _ => None,
+    }
 }
 
-/// Returns a `Matcher` that returns true if the log event resolves to an array, where
-/// the vector of `Value`s the array contains matches the provided `func`.
-fn array_match<S, F>(field: S, func: F) -> Box<dyn Matcher<LogEvent>>
-where
-    S: Into<String>,
-    F: Fn(&Vec<Value>) -> bool + Send + Sync + Clone + 'static,
-{
-    let field = field.into();
+/// Returns the tags of an event as "key:value" strings. Logs usually carry their tags as
+/// an array of such strings, whereas metrics, and traces from the Datadog Agent, carry
+/// them as a map. A tag with an empty value is returned as its key alone.
+fn tags(event: &Event) -> Vec<Cow<'_, str>> {
+    let tags = match event {
+        Event::Log(log) => log.get("tags"),
+        Event::Trace(trace) => trace.get("tags"),
+        Event::Metric(metric) => {
+            return metric
+                .tags()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| tag(key, value))
+                .collect()
+        }
+    };
+
+    match tags {
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| match value {
+                Value::Bytes(v) => String::from_utf8_lossy(v),
+                value => Cow::from(String::from_utf8_lossy(&value.coerce_to_bytes()).into_owned()),
+            })
+            .collect(),
+        Some(Value::Object(map)) => map
+            .iter()
+            .map(|(key, value)| tag(key, &String::from_utf8_lossy(&value.coerce_to_bytes())))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
 
-    Run::boxed(move |log: &LogEvent| match log.get(field.as_str()) {
-        Some(Value::Array(values)) => func(values),
-        _ => false,
-    })
+fn tag<'a>(key: &'a str, value: &str) -> Cow<'a, str> {
+    if value.is_empty() {
+        Cow::from(key)
+    } else {
+        Cow::from(format!("{}:{}", key, value))
+    }
 }
 
-/// Returns a `Matcher` that returns true if the log event resolves to an array, where
-/// at least one `Value` it contains matches the provided `func`.
-fn any_match<S, F>(field: S, func: F) -> Box<dyn Matcher<LogEvent>>
+/// Returns a `Matcher` that returns true if the event resolves to a string which
+/// matches the provided `func`.
+fn string_match<S, F>(field: S, func: F) -> Box<dyn Matcher<Event>>
 where
     S: Into<String>,
-    F: Fn(&Value) -> bool + Send + Sync + Clone + 'static,
+    F: Fn(Cow<str>) -> bool + Send + Sync + Clone + 'static,
 {
-    array_match(field, move |values| values.iter().any(&func))
+    let field = field.into();
+
+    Run::boxed(
+        move |event: &Event| match field_value(event, &field).as_deref() {
+            Some(Value::Bytes(v)) => func(String::from_utf8_lossy(v)),
+            _ => false,
+        },
+    )
 }
 
-/// Retrns a `Matcher` that returns true if the log event resolves to an array of strings,
-/// where at least one string matches the provided `func`.
-fn any_string_match<S, F>(field: S, func: F) -> Box<dyn Matcher<LogEvent>>
+/// Returns a `Matcher` that returns true if at least one of the event's tags, as a
+/// "key:value" string, matches the provided `func`.
+fn any_tag_match<F>(func: F) -> Box<dyn Matcher<Event>>
 where
-    S: Into<String>,
     F: Fn(Cow<str>) -> bool + Send + Sync + Clone + 'static,
 {
-    any_match(field, move |value| {
-        let bytes = value.coerce_to_bytes();
-        func(String::from_utf8_lossy(&bytes))
-    })
+    Run::boxed(move |event: &Event| tags(event).into_iter().any(&func))
 }
 
 #[cfg(test)]
@@ -315,7 +339,7 @@ mod test {
     use datadog_filter::{build_matcher, Filter, Resolver};
     use datadog_search_syntax::parse;
     use serde_json::json;
-    use vector_core::event::Event;
+    use vector_core::event::{Event, LogEvent, MetricKind, TraceEvent};
 
     use super::*;
     use crate::log_event;
@@ -1013,9 +1037,119 @@ mod test {
                 log_event!["custom" => json!({"f": 100})],
                 log_event!["custom" => json!({"f": 50})],
             ),
+            // Metric name, as the default field.
+            (
+                "cpu",
+                Event::from(metric("cpu.usage", &[])),
+                Event::from(metric("memory.usage", &[])),
+            ),
+            // Metric name, as a facet.
+            (
+                "@name:cpu.*",
+                Event::from(metric("cpu.usage", &[])),
+                Event::from(metric("memory.usage", &[])),
+            ),
+            // Metric namespace.
+            (
+                "@namespace:host",
+                Event::from(metric("cpu.usage", &[]).with_namespace(Some("host"))),
+                Event::from(metric("cpu.usage", &[]).with_namespace(Some("process"))),
+            ),
+            // Metric kind.
+            (
+                "@kind:absolute",
+                Event::from(metric("cpu.usage", &[])),
+                Event::from(Metric::new(
+                    "cpu.usage",
+                    MetricKind::Incremental,
+                    MetricValue::Gauge { value: 50.0 },
+                )),
+            ),
+            // Metric type.
+            (
+                "@type:gauge",
+                Event::from(metric("cpu.usage", &[])),
+                Event::from(Metric::new(
+                    "cpu.usage",
+                    MetricKind::Absolute,
+                    MetricValue::Counter { value: 50.0 },
+                )),
+            ),
+            // Metric value.
+            (
+                "@value:>10",
+                Event::from(metric("cpu.usage", &[])),
+                Event::from(Metric::new(
+                    "cpu.usage",
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value: 5.0 },
+                )),
+            ),
+            // Metric value, of a metric without a single value.
+            (
+                "-@value:[* TO *]",
+                Event::from(Metric::new(
+                    "cpu.usage",
+                    MetricKind::Absolute,
+                    MetricValue::Set {
+                        values: ["a".to_owned()].into(),
+                    },
+                )),
+                Event::from(metric("cpu.usage", &[])),
+            ),
+            // Metric tag.
+            (
+                "env:prod",
+                Event::from(metric("cpu.usage", &[("env", "prod")])),
+                Event::from(metric("cpu.usage", &[("env", "staging")])),
+            ),
+            // Metric tag without a value.
+            (
+                "tags:canary",
+                Event::from(metric("cpu.usage", &[("canary", "")])),
+                Event::from(metric("cpu.usage", &[("env", "prod")])),
+            ),
+            // Metric reserved attribute, from its tag.
+            (
+                "host:web*",
+                Event::from(metric("cpu.usage", &[("host", "web-1")])),
+                Event::from(metric("cpu.usage", &[("host", "db-1")])),
+            ),
+            // Trace field.
+            (
+                "service:checkout",
+                trace(json!({"service": "checkout"})),
+                trace(json!({"service": "billing"})),
+            ),
+            // Trace tag, from a map.
+            (
+                "env:prod",
+                trace(json!({"tags": {"env": "prod"}})),
+                trace(json!({"tags": {"env": "staging"}})),
+            ),
         ]
     }
 
+    fn metric(name: &str, tags: &[(&str, &str)]) -> Metric {
+        let tags = tags
+            .iter()
+            .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+            .collect();
+
+        Metric::new(
+            name,
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 50.0 },
+        )
+        .with_tags(Some(tags))
+    }
+
+    fn trace(fields: serde_json::Value) -> Event {
+        let log = LogEvent::try_from(fields).unwrap();
+
+        Event::from(TraceEvent::from(log))
+    }
+
     /// Test a `Matcher` by providing a `Filter<V>` and a processor that receives an
     /// `Event`, and returns a `V`. This allows testing against the pass/fail events that are returned
     /// from `get_checks()` and modifying into a type that allows for their processing.
@@ -1039,7 +1173,7 @@ mod test {
     #[test]
     /// Parse each Datadog Search Syntax query and check that it passes/fails.
     fn event_filter() {
-        test_filter(EventFilter::default(), |ev| ev.into_log())
+        test_filter(EventFilter::default(), |ev| ev)
     }
 
     #[test]
@@ -1063,14 +1197,14 @@ mod test {
                 cond.check_with_context(pass.clone()).0.is_ok(),
                 "should pass: {}\nevent: {:?}",
                 source,
-                pass.as_log()
+                pass
             );
 
             assert!(
                 cond.check_with_context(fail.clone()).0.is_err(),
                 "should fail: {}\nevent: {:?}",
                 source,
-                fail.as_log()
+                fail
             );
         }
     }
@@ -1,9 +1,11 @@
+use std::sync::Arc;
+
 use value::Value;
 use vector_common::TimeZone;
 use vector_config::configurable_component;
 use vector_core::compile_vrl;
 use vrl::state::LocalEnv;
-use vrl::{diagnostic::Formatter, Program, Runtime, VrlRuntime};
+use vrl::{diagnostic::Formatter, CompiledProgram, Program, Runtime, VrlRuntime};
 
 use crate::event::TargetEvents;
 use crate::{
@@ -62,18 +64,25 @@ impl ConditionalConfig for VrlConfig {
             warn!(message = "VRL compilation warning.", %warnings);
         }
 
-        match self.runtime {
-            VrlRuntime::Ast => Ok(Condition::Vrl(Vrl {
-                program,
-                source: self.source.clone(),
-            })),
-        }
+        let compiled = match self.runtime {
+            VrlRuntime::Ast => None,
+            VrlRuntime::Compiled => Some(Arc::new(program.compile())),
+        };
+
+        Ok(Condition::Vrl(Vrl {
+            program,
+            compiled,
+            source: self.source.clone(),
+        }))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Vrl {
     pub(super) program: Program,
+
+    /// The compiled program, if the condition uses the compiled runtime.
+    pub(super) compiled: Option<Arc<CompiledProgram>>,
     pub(super) source: String,
 }
 
@@ -83,7 +92,11 @@ impl Vrl {
         // TODO: use timezone from remap config
         let timezone = TimeZone::default();
 
-        let result = Runtime::default().resolve(&mut target, &self.program, &timezone);
+        let mut runtime = Runtime::default();
+        let result = match &self.compiled {
+            Some(compiled) => runtime.resolve_compiled(&mut target, compiled, &timezone),
+            None => runtime.resolve(&mut target, &self.program, &timezone),
+        };
         let original_event = match target.into_events() {
             TargetEvents::One(event) => event,
             _ => panic!("Event was modified in a condition. This is an internal compiler error."),
@@ -230,25 +243,26 @@ mod test {
         ];
 
         for (event, source, build, check) in checks {
-            let source = source.to_owned();
-            let config = VrlConfig {
-                source,
-                runtime: Default::default(),
-            };
-
-            assert_eq!(
-                config
-                    .build(&Default::default())
-                    .map(|_| ())
-                    .map_err(|e| e.to_string()),
-                build
-            );
-
-            if let Ok(cond) = config.build(&Default::default()) {
+            for runtime in [VrlRuntime::Ast, VrlRuntime::Compiled] {
+                let config = VrlConfig {
+                    source: source.to_owned(),
+                    runtime,
+                };
+
                 assert_eq!(
-                    cond.check_with_context(event.clone()).0,
-                    check.map_err(|e| e.to_string())
+                    config
+                        .build(&Default::default())
+                        .map(|_| ())
+                        .map_err(|e| e.to_string()),
+                    build.clone()
                 );
+
+                if let Ok(cond) = config.build(&Default::default()) {
+                    assert_eq!(
+                        cond.check_with_context(event.clone()).0,
+                        check.map_err(|e| e.to_string())
+                    );
+                }
             }
         }
     }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use indexmap::{IndexMap, IndexSet};
 
@@ -93,6 +93,14 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.push(e);
     }
 
+    let sink_inputs = sinks
+        .iter()
+        .map(|(key, sink)| {
+            let inputs = sink.inner.encoding().map(|_| sink.inputs.clone());
+            (key.clone(), inputs)
+        })
+        .collect::<HashMap<_, _>>();
+
     // Inputs are resolved from string into OutputIds as part of graph construction, so update them
     // here before adding to the final config (the types require this).
     let sinks = sinks
@@ -111,7 +119,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         .collect();
     let tests = tests
         .into_iter()
-        .map(|test| test.resolve_outputs(&graph, &str_expansions))
+        .map(|test| {
+            test.resolve_sink_outputs(&sink_inputs)?
+                .resolve_outputs(&graph, &str_expansions)
+        })
         .collect::<Result<Vec<_>, Vec<_>>>()?;
 
     if errors.is_empty() {
@@ -131,6 +142,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             tests,
             expansions,
             secret,
+            vrl_clock: None,
+            vrl_traces: None,
         };
 
         config.propagate_acknowledgements()?;
@@ -174,7 +174,18 @@ pub(super) mod process {
         ) -> Result<Option<(String, Table, Vec<String>)>, Vec<String>> {
             if let (Ok(name), Some(file)) = (component_name(path), open_file(path)) {
                 self.load(file, format)
-                    .map(|(value, warnings)| Some((name, value, warnings)))
+                    .map(|(mut value, warnings): (Table, _)| {
+                        if let (Some(Value::Array(tests)), Some(dir)) =
+                            (value.get_mut("tests"), path.parent())
+                        {
+                            for test in tests {
+                                if let Value::Table(test) = test {
+                                    resolve_test_fixtures(test, dir);
+                                }
+                            }
+                        }
+                        Some((name, value, warnings))
+                    })
             } else {
                 Ok(None)
             }
@@ -277,9 +288,18 @@ where
             if path.exists() && path.is_dir() {
                 // Transforms are treated differently from other component types; they can be
                 // arbitrarily nested.
-                let (table, warns) =
+                let (mut table, warns) =
                     self.load_dir(&path, matches!(hint, ComponentHint::Transform))?;
 
+                // Each file of the tests folder holds a single test.
+                if matches!(hint, ComponentHint::Test) {
+                    for (_, test) in table.iter_mut() {
+                        if let Value::Table(test) = test {
+                            resolve_test_fixtures(test, &path);
+                        }
+                    }
+                }
+
                 self.merge(table, Some(hint))?;
 
                 warnings.extend(warns);
@@ -290,6 +310,30 @@ where
     }
 }
 
+/// Resolves the relative `fixture` paths of the inputs and outputs of a test against the directory
+/// of the file defining it, so that they don't depend on the working directory of Vector.
+fn resolve_test_fixtures(test: &mut Table, dir: &Path) {
+    for key in ["input", "inputs", "outputs"] {
+        match test.get_mut(key) {
+            Some(Value::Array(entries)) => {
+                for entry in entries {
+                    resolve_fixture(entry, dir);
+                }
+            }
+            Some(entry) => resolve_fixture(entry, dir),
+            None => {}
+        }
+    }
+}
+
+fn resolve_fixture(entry: &mut Value, dir: &Path) {
+    if let Some(Value::String(fixture)) = entry.get_mut("fixture") {
+        if Path::new(fixture.as_str()).is_relative() {
+            *fixture = dir.join(fixture.as_str()).to_string_lossy().into_owned();
+        }
+    }
+}
+
 /// Merge two TOML `Value`s, returning a new `Value`.
 fn merge_values(value: toml::Value, other: toml::Value) -> Result<toml::Value, Vec<String>> {
     serde_toml_merge::merge(value, other).map_err(|e| vec![e.to_string()])
@@ -390,4 +390,61 @@ mod tests {
         let (_, warnings) = load_builder_from_paths(&configs).unwrap();
         assert!(warnings.is_empty());
     }
+    #[test]
+    fn resolves_test_fixtures_relative_to_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("vector.toml"),
+            indoc::indoc! {r#"
+                [[tests]]
+                  name = "root"
+
+                  [[tests.inputs]]
+                    insert_at = "foo"
+                    fixture = "fixtures/input.ndjson"
+
+                  [[tests.outputs]]
+                    extract_from = "foo"
+                    fixture = "/absolute/output.ndjson"
+            "#},
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("tests")).unwrap();
+        std::fs::write(
+            dir.path().join("tests").join("nested.toml"),
+            indoc::indoc! {r#"
+                name = "nested"
+
+                [[inputs]]
+                  insert_at = "foo"
+                  fixture = "input.ndjson"
+            "#},
+        )
+        .unwrap();
+
+        let configs = vec![ConfigPath::Dir(dir.path().to_path_buf())];
+        let (builder, _) = load_builder_from_paths(&configs).unwrap();
+        let fixture = |name: &str| {
+            let test = builder.tests.iter().find(|test| test.name == name).unwrap();
+            test.inputs[0].fixture.clone().unwrap()
+        };
+        assert_eq!(
+            fixture("root"),
+            dir.path().join("fixtures").join("input.ndjson")
+        );
+        assert_eq!(
+            fixture("nested"),
+            dir.path().join("tests").join("input.ndjson")
+        );
+
+        let root = builder
+            .tests
+            .iter()
+            .find(|test| test.name == "root")
+            .unwrap();
+        assert_eq!(
+            root.outputs[0].fixture,
+            Some(PathBuf::from("/absolute/output.ndjson"))
+        );
+    }
 }
@@ -1,12 +1,23 @@
-use std::{collections::HashMap, io::Read};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    io::Read,
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+};
 
 use bytes::BytesMut;
 use futures::{executor, StreamExt};
+use hyper::Body;
 use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command, time};
+use tokio::{
+    io::AsyncWriteExt,
+    process::Command,
+    time::{self, Instant},
+};
 use tokio_util::codec;
 use toml::value::Table;
 use typetag::serde;
@@ -15,9 +26,11 @@ use super::{loader, prepare_input};
 use crate::{
     config::{
         loading::{deserialize_table, ComponentHint, Process},
-        ComponentKey,
+        ComponentKey, ProxyConfig,
     },
+    http::HttpClient,
     signal,
+    tls::{TlsConfig, TlsSettings},
 };
 
 // The following regex aims to extract a pair of strings, the first being the secret backend name
@@ -37,8 +50,16 @@ pub trait SecretBackend: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
         secret_keys: Vec<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// Returns the local paths secrets are read from. When the configuration is watched, changes
+    /// to these paths trigger a reload, retrieving the secrets again.
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
 
+dyn_clone::clone_trait_object!(SecretBackend);
+
 #[derive(Deserialize, Serialize, Debug, Default)]
 pub struct SecretBackendLoader {
     backends: IndexMap<ComponentKey, Box<dyn SecretBackend>>,
@@ -84,6 +105,13 @@ impl SecretBackendLoader {
     pub(crate) fn has_secrets_to_retrieve(&self) -> bool {
         !self.secret_keys.is_empty()
     }
+
+    pub(crate) fn watched_paths(&self) -> Vec<PathBuf> {
+        self.backends
+            .values()
+            .flat_map(|backend| backend.watched_paths())
+            .collect()
+    }
 }
 
 impl Process for SecretBackendLoader {
@@ -288,6 +316,464 @@ async fn query_backend(
     Ok(response)
 }
 
+/// Checks that a value was retrieved for every key, and that none of them is empty.
+fn collect_secrets(
+    secret_keys: Vec<String>,
+    mut retrieve: impl FnMut(&str) -> crate::Result<Option<String>>,
+) -> crate::Result<HashMap<String, String>> {
+    let mut secrets = HashMap::new();
+    for k in secret_keys.into_iter() {
+        match retrieve(&k)? {
+            Some(v) if v.is_empty() => {
+                return Err(format!("secret for key '{}' was empty", k).into());
+            }
+            Some(v) => {
+                secrets.insert(k, v);
+            }
+            None => return Err(format!("secret for key '{}' was not retrieved", k).into()),
+        }
+    }
+    Ok(secrets)
+}
+
+/// Reads secrets from a JSON or YAML file containing a map of secret keys to values.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct FileBackend {
+    pub path: PathBuf,
+}
+
+impl FileBackend {
+    fn read(&self) -> crate::Result<HashMap<String, String>> {
+        let contents = fs::read(&self.path)
+            .map_err(|e| format!("unable to read file {:?}: {}", self.path, e))?;
+        let secrets = match self.path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_slice(&contents)
+                .map_err(|e| format!("unable to parse file {:?}: {}", self.path, e))?,
+            _ => serde_yaml::from_slice(&contents)
+                .map_err(|e| format!("unable to parse file {:?}: {}", self.path, e))?,
+        };
+        Ok(secrets)
+    }
+}
+
+#[typetag::serde(name = "file")]
+impl SecretBackend for FileBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let mut output = self.read()?;
+        collect_secrets(secret_keys, |k| Ok(output.remove(k)))
+    }
+
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+/// Reads secrets from a directory containing one file per secret, named after the secret key,
+/// such as a Kubernetes secret mounted as a volume.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct DirectoryBackend {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub remove_trailing_whitespace: bool,
+}
+
+impl DirectoryBackend {
+    fn read(&self, key: &str) -> crate::Result<Option<String>> {
+        // Keys must name a file within the directory.
+        let key_path = Path::new(key);
+        if !key_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(format!("secret key '{}' is not a valid file name", key).into());
+        }
+
+        let path = self.path.join(key_path);
+        match fs::read_to_string(&path) {
+            Ok(mut secret) => {
+                if self.remove_trailing_whitespace {
+                    secret.truncate(secret.trim_end().len());
+                }
+                Ok(Some(secret))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("unable to read file {:?}: {}", path, e).into()),
+        }
+    }
+}
+
+#[typetag::serde(name = "directory")]
+impl SecretBackend for DirectoryBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        collect_secrets(secret_keys, |k| self.read(k))
+    }
+
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        vec![self.path.clone()]
+    }
+}
+
+/// Reads secrets from environment variables named after the secret key, preceded by an optional
+/// prefix.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct EnvironmentBackend {
+    #[serde(default)]
+    pub prefix: String,
+}
+
+#[typetag::serde(name = "environment")]
+impl SecretBackend for EnvironmentBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        collect_secrets(secret_keys, |k| {
+            let name = format!("{}{}", self.prefix, k);
+            match std::env::var(&name) {
+                Ok(v) => Ok(Some(v)),
+                Err(std::env::VarError::NotPresent) => Ok(None),
+                Err(e) => {
+                    Err(format!("unable to read environment variable {:?}: {}", name, e).into())
+                }
+            }
+        })
+    }
+}
+
+/// Reads secrets from the KV secrets engine of a HashiCorp Vault server.
+///
+/// Secret keys are made of the path of the secret within the secrets engine, with `.` in place of
+/// `/`, followed by the name of the field to read: `SECRET[vault.app.database.password]` reads the
+/// `password` field of the secret at `app/database`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct VaultBackend {
+    pub address: String,
+    pub auth: VaultAuth,
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+    #[serde(default)]
+    pub kv_version: VaultKvVersion,
+    pub namespace: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout: u64,
+    pub tls: Option<TlsConfig>,
+    /// The cache used while retrieving secrets, which is taken from [`VAULT_CACHES`] and returned
+    /// to it once the secrets are retrieved.
+    #[serde(skip)]
+    cache: VaultCache,
+}
+
+fn default_vault_mount() -> String {
+    "secret".to_string()
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+enum VaultAuth {
+    Token {
+        token: String,
+    },
+    Approle {
+        role_id: String,
+        secret_id: String,
+        #[serde(default = "default_approle_mount")]
+        mount: String,
+    },
+}
+
+fn default_approle_mount() -> String {
+    "approle".to_string()
+}
+
+impl fmt::Debug for VaultAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VaultAuth::Token { .. } => f
+                .debug_struct("Token")
+                .field("token", &"<redacted secret>")
+                .finish(),
+            VaultAuth::Approle { role_id, mount, .. } => f
+                .debug_struct("Approle")
+                .field("role_id", role_id)
+                .field("secret_id", &"<redacted secret>")
+                .field("mount", mount)
+                .finish(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+enum VaultKvVersion {
+    V1,
+    #[default]
+    V2,
+}
+
+/// A value retrieved from Vault, along with the time it expires at when it is leased.
+#[derive(Debug, Clone)]
+struct VaultLease<T> {
+    value: T,
+    expires_at: Option<Instant>,
+}
+
+impl<T> VaultLease<T> {
+    fn new(value: T, lease_duration: u64) -> Self {
+        Self {
+            value,
+            expires_at: (lease_duration > 0)
+                .then(|| Instant::now() + time::Duration::from_secs(lease_duration)),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.expires_at
+            .map_or(true, |expires_at| Instant::now() < expires_at)
+    }
+}
+
+/// Caches the client token and secrets read by a backend, so that secrets sharing a path are
+/// read once.
+#[derive(Clone, Default)]
+struct VaultCache {
+    token: Option<VaultLease<String>>,
+    secrets: HashMap<String, HashMap<String, serde_json::Value>>,
+}
+
+impl VaultCache {
+    /// Drops everything that mustn't outlive the current load of the configuration: the secrets,
+    /// which are read again on reload so that rotated secrets are picked up whatever their lease,
+    /// and the client token once its lease expired.
+    fn retain_token(&mut self) {
+        self.token = self.token.take().filter(VaultLease::is_valid);
+        self.secrets.clear();
+    }
+}
+
+impl fmt::Debug for VaultCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultCache")
+            .field("token", &self.token.as_ref().map(|_| "<redacted secret>"))
+            .field("secrets", &self.secrets.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Identifies the secrets engine a backend reads from, and the credentials it reads with.
+type VaultCacheKey = (String, Option<String>, String, VaultKvVersion, VaultAuth);
+
+/// The caches of the Vault backends, kept across reloads of the configuration so that a reload
+/// reuses the client token instead of logging in again.
+static VAULT_CACHES: Lazy<Mutex<HashMap<VaultCacheKey, VaultCache>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Deserialize, Debug)]
+struct VaultLoginResponse {
+    auth: VaultLoginAuth,
+}
+
+#[derive(Deserialize, Debug)]
+struct VaultLoginAuth {
+    client_token: String,
+    #[serde(default)]
+    lease_duration: u64,
+}
+
+#[derive(Deserialize, Debug)]
+struct VaultSecretResponse {
+    data: serde_json::Value,
+}
+
+/// Splits a secret key into the path of the secret and the field to read.
+fn vault_secret_path(key: &str) -> crate::Result<(String, &str)> {
+    match key.rsplit_once('.') {
+        Some((path, field)) if !path.is_empty() && !field.is_empty() => {
+            Ok((path.replace('.', "/"), field))
+        }
+        _ => Err(format!(
+            "secret key '{}' must be made of a path and a field, as in 'path.to.secret.field'",
+            key
+        )
+        .into()),
+    }
+}
+
+impl VaultBackend {
+    fn cache_key(&self) -> VaultCacheKey {
+        (
+            self.address.clone(),
+            self.namespace.clone(),
+            self.mount.clone(),
+            self.kv_version,
+            self.auth.clone(),
+        )
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/v1/{}", self.address.trim_end_matches('/'), path)
+    }
+
+    async fn request(
+        &self,
+        client: &HttpClient,
+        request: http::request::Builder,
+        body: Body,
+    ) -> crate::Result<bytes::Bytes> {
+        let request = match &self.namespace {
+            Some(namespace) => request.header("X-Vault-Namespace", namespace),
+            None => request,
+        }
+        .body(body)?;
+        let uri = request.uri().clone();
+
+        let response = client.send(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(format!(
+                "request to {} failed with status {}: {}",
+                uri,
+                status,
+                String::from_utf8_lossy(&body).trim()
+            )
+            .into());
+        }
+        Ok(body)
+    }
+
+    async fn token(&mut self, client: &HttpClient) -> crate::Result<String> {
+        if let Some(token) = self.cache.token.as_ref().filter(|token| token.is_valid()) {
+            return Ok(token.value.clone());
+        }
+
+        let token = match &self.auth {
+            VaultAuth::Token { token } => VaultLease::new(token.clone(), 0),
+            VaultAuth::Approle {
+                role_id,
+                secret_id,
+                mount,
+            } => {
+                let body = serde_json::to_vec(&serde_json::json!({
+                    "role_id": role_id,
+                    "secret_id": secret_id,
+                }))?;
+                let request = http::Request::post(self.url(&format!("auth/{}/login", mount)))
+                    .header("Content-Type", "application/json");
+                let response = self.request(client, request, Body::from(body)).await?;
+                let VaultLoginResponse { auth } = serde_json::from_slice(&response)?;
+                VaultLease::new(auth.client_token, auth.lease_duration)
+            }
+        };
+        let value = token.value.clone();
+        self.cache.token = Some(token);
+        Ok(value)
+    }
+
+    async fn read_secret(
+        &mut self,
+        client: &HttpClient,
+        path: &str,
+    ) -> crate::Result<HashMap<String, serde_json::Value>> {
+        if let Some(secret) = self.cache.secrets.get(path) {
+            return Ok(secret.clone());
+        }
+
+        let token = self.token(client).await?;
+        let url = match self.kv_version {
+            VaultKvVersion::V1 => self.url(&format!("{}/{}", self.mount, path)),
+            VaultKvVersion::V2 => self.url(&format!("{}/data/{}", self.mount, path)),
+        };
+        let request = http::Request::get(url).header("X-Vault-Token", token);
+        let response = self.request(client, request, Body::empty()).await?;
+        let VaultSecretResponse { data } = serde_json::from_slice(&response)?;
+
+        // KV v2 nests the secret within the data, along with its metadata.
+        let data = match self.kv_version {
+            VaultKvVersion::V1 => Some(data),
+            VaultKvVersion::V2 => data.get("data").cloned(),
+        };
+        let data = match data {
+            Some(serde_json::Value::Object(data)) => data.into_iter().collect::<HashMap<_, _>>(),
+            _ => return Err(format!("secret at path '{}' has no data", path).into()),
+        };
+
+        self.cache.secrets.insert(path.to_string(), data.clone());
+        Ok(data)
+    }
+
+    async fn retrieve_secrets(
+        &mut self,
+        secret_keys: Vec<String>,
+    ) -> crate::Result<HashMap<String, String>> {
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &ProxyConfig::from_env())?;
+
+        let mut values = HashMap::new();
+        for key in &secret_keys {
+            let (path, field) = vault_secret_path(key)?;
+            let value = self
+                .read_secret(&client, &path)
+                .await
+                .map_err(|e| format!("unable to read secret for key '{}': {}", key, e))?
+                .get(field)
+                .map(|value| match value {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                });
+            values.insert(key.clone(), value);
+        }
+        collect_secrets(secret_keys, |k| Ok(values.remove(k).flatten()))
+    }
+}
+
+#[typetag::serde(name = "vault")]
+impl SecretBackend for VaultBackend {
+    fn retrieve(
+        &mut self,
+        secret_keys: Vec<String>,
+        signal_rx: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let key = self.cache_key();
+        self.cache = VAULT_CACHES
+            .lock()
+            .expect("Vault caches mutex poisoned")
+            .remove(&key)
+            .unwrap_or_default();
+
+        let timeout = self.timeout;
+        let result = executor::block_on(async {
+            tokio::select! {
+                biased;
+                Ok(signal::SignalTo::Shutdown | signal::SignalTo::Quit) = signal_rx.recv() => {
+                    Err("Secret retrieval was interrupted.".into())
+                }
+                result = time::timeout(time::Duration::from_secs(timeout), self.retrieve_secrets(secret_keys)) => {
+                    result.map_err(|_| "Secret retrieval timed-out")?
+                }
+            }
+        });
+
+        let mut cache = std::mem::take(&mut self.cache);
+        cache.retain_token();
+        VAULT_CACHES
+            .lock()
+            .expect("Vault caches mutex poisoned")
+            .insert(key, cache);
+
+        result
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct TestBackend {
     pub replacement: String,
@@ -309,11 +795,29 @@ impl SecretBackend for TestBackend {
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
 
     use indoc::indoc;
 
-    use super::{collect_secret_keys, interpolate};
+    use super::{
+        collect_secret_keys, interpolate, vault_secret_path, DirectoryBackend, EnvironmentBackend,
+        FileBackend, SecretBackend, SecretBackendLoader, VaultBackend,
+    };
+    use crate::{config::loading::Process, signal};
+
+    fn retrieve(
+        backend: &mut dyn SecretBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let (_, mut signal_rx) = signal::SignalHandler::new();
+        backend.retrieve(keys.iter().map(|k| k.to_string()).collect(), &mut signal_rx)
+    }
 
     #[test]
     fn replacement() {
@@ -385,4 +889,303 @@ mod test {
         assert!(second_backend_keys.contains(&"secret_key".into()));
         assert!(second_backend_keys.contains(&"secret.key".into()));
     }
+
+    #[test]
+    fn file_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("secrets.json");
+        std::fs::write(&json_path, r#"{"username": "user", "password": "pass"}"#).unwrap();
+        let yaml_path = dir.path().join("secrets.yaml");
+        std::fs::write(&yaml_path, "username: user\ndb.password: pass\nempty: ''\n").unwrap();
+
+        let mut json = FileBackend { path: json_path };
+        let secrets = retrieve(&mut json, &["username", "password"]).unwrap();
+        assert_eq!(secrets.get("username").unwrap(), "user");
+        assert_eq!(secrets.get("password").unwrap(), "pass");
+
+        let mut yaml = FileBackend { path: yaml_path };
+        let secrets = retrieve(&mut yaml, &["db.password"]).unwrap();
+        assert_eq!(secrets.get("db.password").unwrap(), "pass");
+        assert_eq!(
+            retrieve(&mut yaml, &["missing"]).unwrap_err().to_string(),
+            "secret for key 'missing' was not retrieved"
+        );
+        assert_eq!(
+            retrieve(&mut yaml, &["empty"]).unwrap_err().to_string(),
+            "secret for key 'empty' was empty"
+        );
+    }
+
+    #[test]
+    fn directory_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("password"), "pass\n").unwrap();
+
+        let mut backend = DirectoryBackend {
+            path: dir.path().to_path_buf(),
+            remove_trailing_whitespace: true,
+        };
+        let secrets = retrieve(&mut backend, &["password"]).unwrap();
+        assert_eq!(secrets.get("password").unwrap(), "pass");
+
+        // Rotated secrets are picked up on the next retrieval.
+        std::fs::write(dir.path().join("password"), "rotated\n").unwrap();
+        let secrets = retrieve(&mut backend, &["password"]).unwrap();
+        assert_eq!(secrets.get("password").unwrap(), "rotated");
+
+        assert_eq!(
+            retrieve(&mut backend, &["missing"])
+                .unwrap_err()
+                .to_string(),
+            "secret for key 'missing' was not retrieved"
+        );
+        assert_eq!(
+            retrieve(&mut backend, &[".."]).unwrap_err().to_string(),
+            "secret key '..' is not a valid file name"
+        );
+    }
+
+    #[test]
+    fn environment_backend() {
+        std::env::set_var("VECTOR_TEST_SECRET_password", "pass");
+        std::env::set_var("VECTOR_TEST_SECRET_empty", "");
+
+        let mut backend = EnvironmentBackend {
+            prefix: "VECTOR_TEST_SECRET_".to_string(),
+        };
+        let secrets = retrieve(&mut backend, &["password"]).unwrap();
+        assert_eq!(secrets.get("password").unwrap(), "pass");
+
+        assert_eq!(
+            retrieve(&mut backend, &["missing"])
+                .unwrap_err()
+                .to_string(),
+            "secret for key 'missing' was not retrieved"
+        );
+        assert_eq!(
+            retrieve(&mut backend, &["empty"]).unwrap_err().to_string(),
+            "secret for key 'empty' was empty"
+        );
+    }
+
+    #[test]
+    fn watched_paths() {
+        let mut loader = SecretBackendLoader::new();
+        loader
+            .merge(
+                toml::from_str(indoc! {r#"
+                    [secret.file_backend]
+                    type = "file"
+                    path = "/etc/vector/secrets.json"
+
+                    [secret.directory_backend]
+                    type = "directory"
+                    path = "/var/run/secrets/vector"
+
+                    [secret.exec_backend]
+                    type = "exec"
+                    command = ["/path/to/cmd"]
+                "#})
+                .unwrap(),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(
+            loader.watched_paths(),
+            vec![
+                std::path::PathBuf::from("/etc/vector/secrets.json"),
+                std::path::PathBuf::from("/var/run/secrets/vector"),
+            ]
+        );
+    }
+
+    #[test]
+    fn vault_secret_paths() {
+        assert_eq!(
+            vault_secret_path("app.database.password").unwrap(),
+            ("app/database".to_string(), "password")
+        );
+        assert_eq!(
+            vault_secret_path("app.password").unwrap(),
+            ("app".to_string(), "password")
+        );
+        assert!(vault_secret_path("password").is_err());
+        assert!(vault_secret_path("app.").is_err());
+    }
+
+    /// Spawns a stub of a Vault server, returning its address and the number of requests it
+    /// received.
+    async fn spawn_vault_stub() -> (String, Arc<AtomicUsize>) {
+        use hyper::{Body, Request, Response};
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        let uri = crate::test_util::http::spawn_blackhole_http_server(
+            move |request: Request<Body>| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    let token = request
+                        .headers()
+                        .get("X-Vault-Token")
+                        .and_then(|token| token.to_str().ok())
+                        .map(ToOwned::to_owned);
+                    let body = match (request.uri().path(), token.as_deref()) {
+                        ("/v1/auth/approle/login", None) => {
+                            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                            assert_eq!(body["role_id"], "role");
+                            assert_eq!(body["secret_id"], "secret");
+                            Some(r#"{"auth": {"client_token": "approle-token", "lease_duration": 3600}}"#)
+                        }
+                        ("/v1/secret/data/app/database", Some("root")) => Some(
+                            r#"{"data": {"data": {"username": "user", "password": "pass", "port": 5432}, "metadata": {"version": 3}}}"#,
+                        ),
+                        ("/v1/kv/app", Some("approle-token")) => {
+                            Some(r#"{"data": {"api_key": "key"}, "lease_duration": 60}"#)
+                        }
+                        _ => None,
+                    };
+                    Ok::<_, std::convert::Infallible>(match body {
+                        Some(body) => Response::new(Body::from(body)),
+                        None => Response::builder()
+                            .status(403)
+                            .body(Body::from(r#"{"errors": ["permission denied"]}"#))
+                            .unwrap(),
+                    })
+                }
+            },
+        )
+        .await;
+        (uri.to_string(), requests)
+    }
+
+    fn vault_backend(config: &str) -> Box<dyn SecretBackend> {
+        toml::from_str(config).unwrap()
+    }
+
+    async fn retrieve_blocking(
+        mut backend: Box<dyn SecretBackend>,
+        keys: &'static [&'static str],
+    ) -> crate::Result<HashMap<String, String>> {
+        // Backends block until the secrets are retrieved, so they must not run on the runtime
+        // serving the stub.
+        tokio::task::spawn_blocking(move || retrieve(backend.as_mut(), keys))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn vault_backend_token_kv_v2() {
+        let (address, _) = spawn_vault_stub().await;
+        let backend = vault_backend(&format!(
+            indoc! {r#"
+                type = "vault"
+                address = "{}"
+                auth.strategy = "token"
+                auth.token = "root"
+            "#},
+            address
+        ));
+
+        let secrets = retrieve_blocking(
+            backend.clone(),
+            &[
+                "app.database.username",
+                "app.database.password",
+                "app.database.port",
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(secrets.get("app.database.username").unwrap(), "user");
+        assert_eq!(secrets.get("app.database.password").unwrap(), "pass");
+        assert_eq!(secrets.get("app.database.port").unwrap(), "5432");
+
+        assert_eq!(
+            retrieve_blocking(backend.clone(), &["app.database.missing"])
+                .await
+                .unwrap_err()
+                .to_string(),
+            "secret for key 'app.database.missing' was not retrieved"
+        );
+        assert!(retrieve_blocking(backend, &["app.other.password"])
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("failed with status 403 Forbidden"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn vault_backend_approle_kv_v1() {
+        let (address, _) = spawn_vault_stub().await;
+        let backend = vault_backend(&format!(
+            indoc! {r#"
+                type = "vault"
+                address = "{}"
+                mount = "kv"
+                kv_version = "v1"
+                auth.strategy = "approle"
+                auth.role_id = "role"
+                auth.secret_id = "secret"
+            "#},
+            address
+        ));
+
+        let secrets = retrieve_blocking(backend, &["app.api_key"]).await.unwrap();
+        assert_eq!(secrets.get("app.api_key").unwrap(), "key");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn vault_backend_keeps_token_across_reloads() {
+        let (address, requests) = spawn_vault_stub().await;
+        let config = format!(
+            indoc! {r#"
+                type = "vault"
+                address = "{}"
+                mount = "kv"
+                kv_version = "v1"
+                auth.strategy = "approle"
+                auth.role_id = "role"
+                auth.secret_id = "secret"
+            "#},
+            address
+        );
+
+        // Logs in, then reads the secret.
+        retrieve_blocking(vault_backend(&config), &["app.api_key"])
+            .await
+            .unwrap();
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+
+        // A reload creates a new backend, which reuses the token, but reads the secret again even
+        // though its lease hasn't expired, so that a rotated secret is picked up.
+        let secrets = retrieve_blocking(vault_backend(&config), &["app.api_key"])
+            .await
+            .unwrap();
+        assert_eq!(secrets.get("app.api_key").unwrap(), "key");
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn vault_backend_redacts_credentials() {
+        let backend = vault_backend(indoc! {r#"
+            type = "vault"
+            address = "http://127.0.0.1:8200"
+            auth.strategy = "approle"
+            auth.role_id = "role"
+            auth.secret_id = "approle-credential"
+        "#});
+        let debug = format!("{:?}", backend);
+        assert!(debug.contains(r#"role_id: "role""#));
+        assert!(!debug.contains("approle-credential"));
+
+        let backend = vault_backend(indoc! {r#"
+            type = "vault"
+            address = "http://127.0.0.1:8200"
+            auth.strategy = "token"
+            auth.token = "token-credential"
+        "#});
+        assert!(!format!("{:?}", backend).contains("token-credential"));
+    }
 }
@@ -7,6 +7,7 @@ use std::{
 };
 
 use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
 use component::ComponentDescription;
 use indexmap::IndexMap; // IndexMap preserves insertion order, allowing us to output errors in the same order they are present in the file
 use serde::{Deserialize, Serialize};
@@ -44,13 +45,15 @@ pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, OutputId};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, SecretBackend,
-    CONFIG_PATHS,
+    load_from_str, load_secret_backends_from_paths, load_source_from_paths, merge_path_lists,
+    process_paths, SecretBackend, CONFIG_PATHS,
 };
 pub use sink::{SinkConfig, SinkContext, SinkDescription, SinkHealthcheckOptions, SinkOuter};
 pub use source::{SourceConfig, SourceContext, SourceDescription, SourceOuter};
 pub use transform::{TransformDescription, TransformOuter};
-pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
+pub use unit_test::{
+    build_unit_tests, build_unit_tests_main, build_unit_tests_with_tracing, UnitTestResult,
+};
 pub use validation::warnings;
 pub use vector_core::config::{log_schema, proxy::ProxyConfig, LogSchema};
 
@@ -109,6 +112,10 @@ pub struct Config {
     tests: Vec<TestDefinition>,
     expansions: IndexMap<ComponentKey, Vec<ComponentKey>>,
     secret: IndexMap<ComponentKey, Box<dyn SecretBackend>>,
+    /// The clock used by VRL programs in place of the system clock, if any.
+    pub vrl_clock: Option<vrl::Clock>,
+    /// The log VRL programs record their traces into, if tracing is enabled.
+    pub vrl_traces: Option<vrl::TraceLog>,
 }
 
 impl Config {
@@ -380,9 +387,75 @@ pub struct TestDefinition<T = OutputId> {
     pub outputs: Vec<TestOutput<T>>,
     #[serde(default)]
     pub no_outputs_from: Vec<T>,
+    /// Runs the test against a virtual clock rather than the system clock.
+    pub clock: Option<TestClock>,
+}
+
+/// A virtual clock for a unit test.
+///
+/// Inputs are inserted once the clock reaches the time they advance it to using `advance_secs`,
+/// and timers of the components under test, such as flush intervals, fire as the clock passes
+/// them. The clock skips ahead to the next of these instead of waiting in real time.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TestClock {
+    /// The wall clock time at which the test starts.
+    #[serde(default = "default_test_clock_start_time")]
+    pub start_time: DateTime<Utc>,
+}
+
+fn default_test_clock_start_time() -> DateTime<Utc> {
+    Utc.timestamp(0, 0)
 }
 
 impl TestDefinition<String> {
+    /// Replaces `extract_from` targets naming a sink with the inputs of that sink, so that the
+    /// outputs can be resolved like any other. The sink is recorded in `encoded_by`.
+    ///
+    /// Sinks map to `None` when they don't have a configurable encoding, and can't be targeted.
+    fn resolve_sink_outputs(
+        mut self,
+        sink_inputs: &HashMap<ComponentKey, Option<Vec<String>>>,
+    ) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+
+        for output in self.outputs.iter_mut() {
+            match &output.extract_from {
+                OneOrMany::One(from) => {
+                    let key = ComponentKey::from(from.as_str());
+                    match sink_inputs.get(&key) {
+                        Some(Some(inputs)) => {
+                            output.extract_from = inputs.clone().into();
+                            output.encoded_by = Some(key);
+                        }
+                        Some(None) => errors.push(format!(
+                            "Invalid extract_from target in test '{}': sink '{}' does not have a configurable `encoding`",
+                            self.name, key
+                        )),
+                        None => {}
+                    }
+                }
+                OneOrMany::Many(froms) => {
+                    if let Some(from) = froms
+                        .iter()
+                        .find(|from| sink_inputs.contains_key(&ComponentKey::from(from.as_str())))
+                    {
+                        errors.push(format!(
+                            r#"Invalid extract_from target in test '{}': sink '{}' must be the only target"#,
+                            self.name, from
+                        ));
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
     fn resolve_outputs(
         self,
         graph: &graph::Graph,
@@ -394,6 +467,7 @@ impl TestDefinition<String> {
             inputs,
             outputs,
             no_outputs_from,
+            clock,
         } = self;
         let mut errors = Vec::new();
 
@@ -405,6 +479,8 @@ impl TestDefinition<String> {
                 let TestOutput {
                     extract_from,
                     conditions,
+                    fixture,
+                    encoded_by,
                 } = old;
 
                 let extract_from = extract_from
@@ -419,9 +495,9 @@ impl TestDefinition<String> {
                     })
                     .collect::<Vec<_>>();
 
-                (extract_from, conditions)
+                (extract_from, conditions, fixture, encoded_by)
             })
-            .filter_map(|(extract_from, conditions)| {
+            .filter_map(|(extract_from, conditions, fixture, encoded_by)| {
                 let mut outputs = Vec::new();
                 for from in extract_from {
                     if let Some(output_id) = output_map.get(&from) {
@@ -439,6 +515,8 @@ impl TestDefinition<String> {
                     Some(TestOutput {
                         extract_from: outputs.into(),
                         conditions,
+                        fixture,
+                        encoded_by,
                     })
                 }
             })
@@ -466,6 +544,7 @@ impl TestDefinition<String> {
                 inputs,
                 outputs,
                 no_outputs_from,
+                clock,
             })
         } else {
             Err(errors)
@@ -481,6 +560,7 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            clock,
         } = self;
 
         let outputs = outputs
@@ -495,6 +575,8 @@ impl TestDefinition<OutputId> {
                         .into(),
                 },
                 conditions: old.conditions,
+                fixture: old.fixture,
+                encoded_by: old.encoded_by,
             })
             .collect();
 
@@ -506,6 +588,7 @@ impl TestDefinition<OutputId> {
             inputs,
             outputs,
             no_outputs_from,
+            clock,
         }
     }
 }
@@ -528,6 +611,11 @@ pub struct TestInput {
     pub value: Option<String>,
     pub log_fields: Option<IndexMap<String, TestInputValue>>,
     pub metric: Option<Metric>,
+    /// A file of input events, one per line. Lines are raw values for `raw` inputs, and JSON
+    /// encoded events for `log` and `metric` inputs.
+    pub fixture: Option<PathBuf>,
+    /// The number of seconds to advance the test clock by before sending this input.
+    pub advance_secs: Option<f64>,
 }
 
 fn default_test_input_type() -> String {
@@ -539,6 +627,13 @@ fn default_test_input_type() -> String {
 pub struct TestOutput<T = OutputId> {
     pub extract_from: OneOrMany<T>,
     pub conditions: Option<Vec<conditions::AnyCondition>>,
+    /// A file of expected events, one JSON encoded event per line. The output must contain as
+    /// many events as the file, each including the fields of the corresponding expected event.
+    pub fixture: Option<PathBuf>,
+    /// The sink whose encoding is applied to the extracted events before the conditions are
+    /// checked. This is set when `extract_from` names a sink rather than a transform.
+    #[serde(skip)]
+    pub encoded_by: Option<ComponentKey>,
 }
 
 #[cfg(all(test, feature = "sources-file", feature = "sinks-console"))]
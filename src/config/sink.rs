@@ -5,7 +5,10 @@ use vector_buffers::{BufferConfig, BufferType};
 use vector_core::config::{AcknowledgementsConfig, GlobalOptions, Input};
 
 use super::{component, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{self, util::UriSerde};
+use crate::{
+    codecs::EncodingConfig,
+    sinks::{self, util::UriSerde},
+};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter<T> {
@@ -145,6 +148,11 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
     }
 
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig>;
+
+    /// The encoding applied to the events the sink sends, if it has a configurable codec.
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
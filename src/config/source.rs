@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use vector_core::config::{AcknowledgementsConfig, GlobalOptions, LogNamespace, Output};
 
 use super::{component, schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, sources, SourceSender};
+use crate::{codecs::DecodingConfig, shutdown::ShutdownSignal, sources, SourceSender};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SourceOuter {
@@ -46,6 +46,11 @@ pub trait SourceConfig: core::fmt::Debug + Send + Sync {
     }
 
     fn can_acknowledge(&self) -> bool;
+
+    /// The decoding applied to the data the source receives, if it has configurable codecs.
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        None
+    }
 }
 
 pub struct SourceContext {
@@ -4,16 +4,26 @@ mod unit_test_components;
 
 use std::{
     collections::{HashMap, HashSet},
+    fs,
+    path::Path,
     sync::Arc,
+    time::Duration,
 };
 
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use indexmap::IndexMap;
+use once_cell::sync::OnceCell;
 use ordered_float::NotNan;
-use tokio::sync::{
-    oneshot::{self, Receiver},
-    Mutex,
+use tokio::{
+    sync::{
+        oneshot::{self, Receiver},
+        Mutex,
+    },
+    time::Instant,
 };
+use tokio_util::codec::Decoder as _;
 use uuid::Uuid;
 use value::Kind;
 use vector_core::config::LogNamespace;
@@ -23,12 +33,14 @@ use self::unit_test_components::{
 };
 use super::{compiler::expand_globs, graph::Graph, OutputId};
 use crate::{
+    codecs::{DecodingConfig, EncodingConfig},
     conditions::Condition,
     config::{
-        self, compiler::expand_macros, loading, ComponentKey, Config, ConfigBuilder, ConfigPath,
-        SinkOuter, SourceOuter, TestDefinition, TestInput, TestInputValue, TestOutput,
+        self, compiler::expand_macros, loading, log_schema, ComponentKey, Config, ConfigBuilder,
+        ConfigPath, SinkOuter, SourceOuter, TestClock, TestDefinition, TestInput, TestInputValue,
+        TestOutput,
     },
-    event::{Event, LogEvent, Value},
+    event::{Event, LogEvent, Metric, Value},
     schema,
     serde::OneOrMany,
     signal,
@@ -43,14 +55,47 @@ pub struct UnitTest {
     config: Config,
     pieces: Pieces,
     test_result_rxs: Vec<Receiver<UnitTestSinkResult>>,
+    clock: Option<VirtualClock>,
+    traces: Option<vrl::TraceLog>,
 }
 
 pub struct UnitTestResult {
     pub errors: Vec<String>,
+    /// The traces of the VRL programs run by the test, if tracing is enabled.
+    pub traces: Vec<String>,
 }
 
 impl UnitTest {
     pub async fn run(self) -> UnitTestResult {
+        match self.clock.clone() {
+            #[cfg(feature = "unit-test-clock")]
+            Some(clock) => {
+                // Time can only be paused on a current thread runtime, so tests
+                // using a virtual clock run on a dedicated one.
+                let (tx, rx) = oneshot::channel();
+                std::thread::spawn(move || {
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .start_paused(true)
+                        .build()
+                        .expect("Unable to create unit test runtime");
+                    let result = runtime.block_on(async move {
+                        clock.start();
+                        self.run_topology().await
+                    });
+                    let _ = tx.send(result);
+                });
+                rx.await.expect(
+                    "An unexpected error occurred while executing unit tests. Please try again.",
+                )
+            }
+            #[cfg(not(feature = "unit-test-clock"))]
+            Some(_) => unreachable!("tests with a clock are rejected when they're built"),
+            None => self.run_topology().await,
+        }
+    }
+
+    async fn run_topology(self) -> UnitTestResult {
         let diff = config::ConfigDiff::initial(&self.config);
         let (topology, _) = topology::start_validated(self.config, diff, self.pieces)
             .await
@@ -71,13 +116,58 @@ impl UnitTest {
             errors.extend(partial_result.test_errors);
         }
 
-        UnitTestResult { errors }
+        let traces = self
+            .traces
+            .map(|traces| {
+                traces
+                    .take()
+                    .into_iter()
+                    .map(|(label, trace)| format!("{}:\n{}", label, trace))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        UnitTestResult { errors, traces }
+    }
+}
+
+/// The clock of a test configured with a [`TestClock`].
+///
+/// Wall clock time is derived from tokio's clock, which is paused while the
+/// test runs. A paused clock jumps to the next timer whenever the runtime is
+/// idle, so inputs are sent and timers fire in order, at their virtual time,
+/// without the test waiting in real time.
+#[derive(Clone, Debug)]
+struct VirtualClock {
+    start_time: DateTime<Utc>,
+    origin: Arc<OnceCell<Instant>>,
+}
+
+impl VirtualClock {
+    fn new(clock: &TestClock) -> Self {
+        Self {
+            start_time: clock.start_time,
+            origin: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Starts the clock at the current instant of the test runtime.
+    #[cfg(feature = "unit-test-clock")]
+    fn start(&self) {
+        let _ = self.origin.set(Instant::now());
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = self.origin.get().map(Instant::elapsed).unwrap_or_default();
+        self.start_time
+            + chrono::Duration::from_std(elapsed).unwrap_or_else(|_| chrono::Duration::zero())
     }
 }
 
 pub async fn build_unit_tests_main(
     paths: &[ConfigPath],
     signal_handler: &mut signal::SignalHandler,
+    trace_vrl: bool,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
     config::init_log_schema(paths, false)?;
     let (mut secrets_backends_loader, _) = loading::load_secret_backends_from_paths(paths)?;
@@ -90,20 +180,28 @@ pub async fn build_unit_tests_main(
         loading::load_builder_from_paths(paths)?
     };
 
-    build_unit_tests(config_builder).await
+    build_unit_tests_with_tracing(config_builder, trace_vrl).await
+}
+
+pub async fn build_unit_tests(config_builder: ConfigBuilder) -> Result<Vec<UnitTest>, Vec<String>> {
+    build_unit_tests_with_tracing(config_builder, false).await
 }
 
-pub async fn build_unit_tests(
+/// Builds the unit tests of the config. If `trace_vrl` is set, the `remap` transforms under test
+/// record a trace of each run of their program, which is returned with the result of the test.
+pub async fn build_unit_tests_with_tracing(
     mut config_builder: ConfigBuilder,
+    trace_vrl: bool,
 ) -> Result<Vec<UnitTest>, Vec<String>> {
-    // Sanitize config by removing existing sources and sinks
-    config_builder.sources = Default::default();
-    config_builder.sinks = Default::default();
+    // Sanitize config by removing existing sources and sinks. Their codecs are
+    // kept around so that tests can target their decoding and encoding.
+    let sources = std::mem::take(&mut config_builder.sources);
+    let sinks = std::mem::take(&mut config_builder.sinks);
 
     let test_definitions = std::mem::take(&mut config_builder.tests);
     let mut tests = Vec::new();
     let mut build_errors = Vec::new();
-    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder)?;
+    let metadata = UnitTestBuildMetadata::initialize(&mut config_builder, sources, sinks)?;
 
     for mut test_definition in test_definitions {
         let test_name = test_definition.name.clone();
@@ -112,7 +210,14 @@ pub async fn build_unit_tests(
         if let Some(input) = legacy_input {
             test_definition.inputs.push(input);
         }
-        match build_unit_test(&metadata, test_definition, config_builder.clone()).await {
+        match build_unit_test(
+            &metadata,
+            test_definition,
+            config_builder.clone(),
+            trace_vrl,
+        )
+        .await
+        {
             Ok(test) => tests.push(test),
             Err(errors) => {
                 let mut test_error = errors.join("\n");
@@ -141,20 +246,51 @@ pub struct UnitTestBuildMetadata {
     template_sources: IndexMap<ComponentKey, UnitTestSourceConfig>,
     // A mapping from transform name to unit test sink name.
     sink_ids: HashMap<OutputId, String>,
+    // A mapping from source name to the decoding configured on that source.
+    source_decoders: HashMap<ComponentKey, DecodingConfig>,
+    // A mapping from sink name to the sink inputs, with globs expanded. Sinks
+    // without a configurable encoding have no inputs to extract from.
+    sink_inputs: HashMap<ComponentKey, Option<Vec<String>>>,
+    // A mapping from sink name to the encoding used when a test extracts
+    // outputs from a sink.
+    sink_targets: HashMap<ComponentKey, UnitTestSinkTarget>,
+}
+
+struct UnitTestSinkTarget {
+    // The encoding configured on the sink
+    encoding: EncodingConfig,
+    // The name of the unit test sink standing in for the sink
+    test_sink_id: String,
 }
 
 impl UnitTestBuildMetadata {
-    pub fn initialize(config_builder: &mut ConfigBuilder) -> Result<Self, Vec<String>> {
+    pub fn initialize(
+        config_builder: &mut ConfigBuilder,
+        sources: IndexMap<ComponentKey, SourceOuter>,
+        sinks: IndexMap<ComponentKey, SinkOuter<String>>,
+    ) -> Result<Self, Vec<String>> {
         // A unique id used to name test sources and sinks to avoid name clashes
         let random_id = Uuid::new_v4().to_string();
 
-        let available_insert_targets = config_builder
+        // Sources with a configurable decoding can be used as insert targets, in
+        // which case raw inputs are decoded the same way the source would.
+        let source_decoders = sources
+            .iter()
+            .filter_map(|(key, source)| {
+                source
+                    .inner
+                    .decoding(config_builder.schema.log_namespace())
+                    .map(|decoding| (key.clone(), decoding))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut available_insert_targets = config_builder
             .transforms
             .keys()
             .cloned()
             .collect::<HashSet<_>>();
 
-        let source_ids = available_insert_targets
+        let mut source_ids = available_insert_targets
             .iter()
             .map(|key| (key.clone(), format!("{}-{}-{}", key, "source", random_id)))
             .collect::<HashMap<_, _>>();
@@ -171,6 +307,14 @@ impl UnitTestBuildMetadata {
             template_sources.insert(key.clone(), UnitTestSourceConfig::default());
         }
 
+        // Test sources inserting at a source replace it under its own name, so
+        // that transforms consuming the source receive the decoded events.
+        for key in source_decoders.keys() {
+            available_insert_targets.insert(key.clone());
+            source_ids.insert(key.clone(), key.to_string());
+            template_sources.insert(key.clone(), UnitTestSourceConfig::default());
+        }
+
         // In order to attach a sink to any valid extraction point, we need to
         // expand relevant transforms
         let mut builder = config_builder.clone();
@@ -205,11 +349,43 @@ impl UnitTestBuildMetadata {
             })
             .collect::<HashMap<_, _>>();
 
+        // Sink inputs may contain globs, so expand them against the transforms
+        // that are available for extraction.
+        let mut builder = config_builder.clone();
+        builder.sinks = sinks;
+        expand_globs(&mut builder);
+        let sink_inputs = builder
+            .sinks
+            .iter()
+            .map(|(key, sink)| {
+                let inputs = sink.inner.encoding().map(|_| sink.inputs.clone());
+                (key.clone(), inputs)
+            })
+            .collect::<HashMap<_, _>>();
+        let sink_targets = builder
+            .sinks
+            .iter()
+            .filter_map(|(key, sink)| {
+                let encoding = sink.inner.encoding()?.clone();
+                let test_sink_id = format!("{}-{}-{}", key, "encoded-sink", random_id);
+                Some((
+                    key.clone(),
+                    UnitTestSinkTarget {
+                        encoding,
+                        test_sink_id,
+                    },
+                ))
+            })
+            .collect::<HashMap<_, _>>();
+
         Ok(Self {
             available_insert_targets,
             source_ids,
             template_sources,
             sink_ids,
+            source_decoders,
+            sink_inputs,
+            sink_targets,
         })
     }
 
@@ -217,8 +393,14 @@ impl UnitTestBuildMetadata {
     pub fn hydrate_into_sources(
         &self,
         inputs: &[TestInput],
+        clock: Option<&TestClock>,
     ) -> Result<IndexMap<ComponentKey, SourceOuter>, Vec<String>> {
-        let inputs = build_and_validate_inputs(inputs, &self.available_insert_targets)?;
+        let inputs = build_and_validate_inputs(
+            inputs,
+            &self.available_insert_targets,
+            &self.source_decoders,
+            clock,
+        )?;
         let mut template_sources = self.template_sources.clone();
         Ok(inputs
             .into_iter()
@@ -267,18 +449,24 @@ impl UnitTestBuildMetadata {
         let mut template_sinks = IndexMap::new();
         let mut test_result_rxs = Vec::new();
         // Add sinks with checks
-        for (ids, checks) in outputs {
+        for ((ids, encoded_by), (checks, expected_events)) in outputs {
             let (tx, rx) = oneshot::channel();
-            let sink_ids = ids.clone();
+            let encoding = encoded_by.as_ref().and_then(|key| {
+                self.sink_targets
+                    .get(key)
+                    .map(|target| target.encoding.clone())
+            });
             let sink_config = UnitTestSinkConfig {
                 test_name: test_name.to_string(),
-                transform_ids: ids.stringify().into_vec(),
+                transform_ids: ids.clone().stringify().into_vec(),
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::Checks(checks),
+                encoding,
+                expected_events,
             };
 
             test_result_rxs.push(rx);
-            template_sinks.insert(sink_ids, sink_config);
+            template_sinks.insert((ids, encoded_by), sink_config);
         }
 
         // Add sinks with no outputs check
@@ -289,27 +477,37 @@ impl UnitTestBuildMetadata {
                 transform_ids: vec![id.to_string()],
                 result_tx: Arc::new(Mutex::new(Some(tx))),
                 check: UnitTestSinkCheck::NoOutputs,
+                encoding: None,
+                expected_events: Vec::new(),
             };
 
             test_result_rxs.push(rx);
-            template_sinks.insert(id.clone().into(), sink_config);
+            template_sinks.insert((id.clone().into(), None), sink_config);
         }
 
         let sinks = template_sinks
             .into_iter()
-            .map(|(transform_ids, sink_config)| {
+            .map(|((transform_ids, encoded_by), sink_config)| {
                 let transform_ids_str = transform_ids.stringify().into_vec();
-                let sink_ids = transform_ids
-                    .into_vec()
-                    .iter()
-                    .map(|transform_id| {
-                        self.sink_ids
-                            .get(transform_id)
-                            .expect("Sink does not exist")
-                            .as_str()
-                    })
-                    .collect::<Vec<_>>();
-                let sink_id = sink_ids.join(",");
+                let sink_id = match encoded_by {
+                    Some(key) => self
+                        .sink_targets
+                        .get(&key)
+                        .expect("Sink does not exist")
+                        .test_sink_id
+                        .clone(),
+                    None => transform_ids
+                        .into_vec()
+                        .iter()
+                        .map(|transform_id| {
+                            self.sink_ids
+                                .get(transform_id)
+                                .expect("Sink does not exist")
+                                .as_str()
+                        })
+                        .collect::<Vec<_>>()
+                        .join(","),
+                };
                 (
                     ComponentKey::from(sink_id),
                     SinkOuter::new(transform_ids_str, Box::new(sink_config)),
@@ -332,13 +530,12 @@ fn get_relevant_test_components(
     for source in sources {
         let paths = graph.paths_to_sink_from(source);
         if paths.is_empty() {
+            let source = source.to_string();
             errors.push(format!(
                 "Unable to complete topology between input target '{}' and output target(s)",
                 source
-                    .to_string()
                     .rsplit_once("-source-")
-                    .unwrap_or(("", ""))
-                    .0
+                    .map_or(source.as_str(), |(name, _)| name)
             ));
         } else {
             for path in paths {
@@ -358,6 +555,7 @@ async fn build_unit_test(
     metadata: &UnitTestBuildMetadata,
     test: TestDefinition<String>,
     mut config_builder: ConfigBuilder,
+    trace_vrl: bool,
 ) -> Result<UnitTest, Vec<String>> {
     let mut transform_only_config = config_builder.clone();
     let expansions = expand_macros(&mut transform_only_config)?;
@@ -369,9 +567,19 @@ async fn build_unit_test(
         &expansions,
         transform_only_config.schema,
     );
-    let test = test.resolve_outputs(&transform_only_graph, &expansions)?;
+    let test = test
+        .resolve_sink_outputs(&metadata.sink_inputs)?
+        .resolve_outputs(&transform_only_graph, &expansions)?;
+
+    #[cfg(not(feature = "unit-test-clock"))]
+    if test.clock.is_some() {
+        return Err(vec![format!(
+            "Test '{}': `clock` requires Vector to be built with the `unit-test-clock` feature",
+            test.name
+        )]);
+    }
 
-    let sources = metadata.hydrate_into_sources(&test.inputs)?;
+    let sources = metadata.hydrate_into_sources(&test.inputs, test.clock.as_ref())?;
     let (test_result_rxs, sinks) =
         metadata.hydrate_into_sinks(&test.name, &test.outputs, &test.no_outputs_from)?;
 
@@ -438,7 +646,13 @@ async fn build_unit_test(
             .sinks
             .insert(ComponentKey::from(Uuid::new_v4().to_string()), sink);
     }
-    let config = config_builder.build()?;
+    let clock = test.clock.as_ref().map(VirtualClock::new);
+    let mut config = config_builder.build()?;
+    config.vrl_clock = clock
+        .clone()
+        .map(|clock| vrl::Clock::new(move || clock.now()));
+    let traces = trace_vrl.then(vrl::TraceLog::default);
+    config.vrl_traces = traces.clone();
     let diff = config::ConfigDiff::initial(&config);
     let pieces = builder::build_pieces(&config, &diff, HashMap::new()).await?;
 
@@ -447,6 +661,8 @@ async fn build_unit_test(
         config,
         pieces,
         test_result_rxs,
+        clock,
+        traces,
     })
 }
 
@@ -500,6 +716,8 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
             transform_ids: vec![],
             result_tx: Arc::new(Mutex::new(None)),
             check: UnitTestSinkCheck::NoOp,
+            encoding: None,
+            expected_events: Vec::new(),
         };
         Some(SinkOuter::new(loose_end_outputs, Box::new(noop_sink)))
     }
@@ -508,7 +726,9 @@ fn get_loose_end_outputs_sink(config: &ConfigBuilder) -> Option<SinkOuter<String
 fn build_and_validate_inputs(
     test_inputs: &[TestInput],
     available_insert_targets: &HashSet<ComponentKey>,
-) -> Result<HashMap<ComponentKey, Vec<Event>>, Vec<String>> {
+    source_decoders: &HashMap<ComponentKey, DecodingConfig>,
+    clock: Option<&TestClock>,
+) -> Result<HashMap<ComponentKey, Vec<(Duration, Event)>>, Vec<String>> {
     let mut inputs = HashMap::new();
     let mut errors = Vec::new();
     if test_inputs.is_empty() {
@@ -516,16 +736,37 @@ fn build_and_validate_inputs(
         return Err(errors);
     }
 
+    // The offset of the inputs from the start of the test, advanced by the
+    // `advance_secs` of each input
+    let mut offset = Duration::ZERO;
     for (index, input) in test_inputs.iter().enumerate() {
+        if let Some(advance_secs) = input.advance_secs {
+            if clock.is_none() {
+                errors.push(format!(
+                    "inputs[{}]: `advance_secs` requires the test to have a `clock`",
+                    index
+                ));
+            } else if !advance_secs.is_finite() || advance_secs <= 0.0 {
+                errors.push(format!(
+                    "inputs[{}]: `advance_secs` must be a positive number",
+                    index
+                ));
+            } else {
+                offset += Duration::from_secs_f64(advance_secs);
+            }
+        }
+        let timestamp = clock.map(|clock| {
+            clock.start_time
+                + chrono::Duration::from_std(offset).unwrap_or_else(|_| chrono::Duration::zero())
+        });
+
         if available_insert_targets.contains(&input.insert_at) {
-            match build_input_event(input) {
-                Ok(input_event) => {
+            match build_input_events(input, source_decoders.get(&input.insert_at), timestamp) {
+                Ok(input_events) => {
                     inputs
                         .entry(input.insert_at.clone())
-                        .and_modify(|events: &mut Vec<Event>| {
-                            events.push(input_event.clone());
-                        })
-                        .or_insert_with(|| vec![input_event]);
+                        .or_insert_with(Vec::new)
+                        .extend(input_events.into_iter().map(|event| (offset, event)));
                 }
                 Err(error) => errors.push(error),
             }
@@ -544,10 +785,13 @@ fn build_and_validate_inputs(
     }
 }
 
+type TestOutputTarget = (OneOrMany<OutputId>, Option<ComponentKey>);
+type TestOutputChecks = (Vec<Vec<Condition>>, Vec<Vec<serde_json::Value>>);
+
 fn build_outputs(
     test_outputs: &[TestOutput],
-) -> Result<IndexMap<OneOrMany<OutputId>, Vec<Vec<Condition>>>, Vec<String>> {
-    let mut outputs: IndexMap<OneOrMany<OutputId>, Vec<Vec<Condition>>> = IndexMap::new();
+) -> Result<IndexMap<TestOutputTarget, TestOutputChecks>, Vec<String>> {
+    let mut outputs: IndexMap<TestOutputTarget, TestOutputChecks> = IndexMap::new();
     let mut errors = Vec::new();
 
     for output in test_outputs {
@@ -568,10 +812,31 @@ fn build_outputs(
             }
         }
 
-        outputs
-            .entry(output.extract_from.clone())
-            .and_modify(|existing_conditions| existing_conditions.push(conditions.clone()))
-            .or_insert(vec![conditions.clone()]);
+        let (existing_conditions, existing_expected_events) = outputs
+            .entry((output.extract_from.clone(), output.encoded_by.clone()))
+            .or_default();
+        existing_conditions.push(conditions);
+
+        if let Some(path) = &output.fixture {
+            match read_fixture(path).and_then(|lines| {
+                lines
+                    .into_iter()
+                    .map(|(line_number, line)| {
+                        serde_json::from_str(&line).map_err(|error| {
+                            format!(
+                                "fixture '{}' line {}: invalid JSON: {}",
+                                path.display(),
+                                line_number,
+                                error
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            }) {
+                Ok(expected_events) => existing_expected_events.push(expected_events),
+                Err(error) => errors.push(error),
+            }
+        }
     }
 
     if errors.is_empty() {
@@ -581,15 +846,38 @@ fn build_outputs(
     }
 }
 
-fn build_input_event(input: &TestInput) -> Result<Event, String> {
+fn build_input_events(
+    input: &TestInput,
+    decoding: Option<&DecodingConfig>,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<Vec<Event>, String> {
+    match (input.type_str.as_ref(), decoding, &input.fixture) {
+        ("raw", Some(decoding), Some(path)) => read_fixture(path)?
+            .into_iter()
+            .map(|(_, line)| decode_raw_value(&line, decoding))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|events| events.into_iter().flatten().collect()),
+        ("raw", Some(decoding), None) => match input.value.as_ref() {
+            Some(value) => decode_raw_value(value, decoding),
+            None => Err("input type 'raw' requires the field 'value'".to_string()),
+        },
+        (_, _, Some(path)) => build_fixture_events(input, path, timestamp),
+        (_, _, None) => build_input_event(input, timestamp).map(|event| vec![event]),
+    }
+}
+
+fn build_input_event(input: &TestInput, timestamp: Option<DateTime<Utc>>) -> Result<Event, String> {
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
-            Some(v) => Ok(Event::Log(LogEvent::from_str_legacy(v.clone()))),
+            Some(v) => Ok(build_raw_event(v.clone(), timestamp)),
             None => Err("input type 'raw' requires the field 'value'".to_string()),
         },
         "log" => {
             if let Some(log_fields) = &input.log_fields {
                 let mut event = LogEvent::from_str_legacy("");
+                if let Some(timestamp) = timestamp {
+                    event.insert(log_schema().timestamp_key(), timestamp);
+                }
                 for (path, value) in log_fields {
                     let value: Value = match value {
                         TestInputValue::String(s) => Value::from(s.to_owned()),
@@ -619,3 +907,84 @@ fn build_input_event(input: &TestInput) -> Result<Event, String> {
         )),
     }
 }
+
+fn build_raw_event(value: String, timestamp: Option<DateTime<Utc>>) -> Event {
+    let mut event = LogEvent::from_str_legacy(value);
+    if let Some(timestamp) = timestamp {
+        event.insert(log_schema().timestamp_key(), timestamp);
+    }
+    event.into()
+}
+
+/// Builds one event per line of the fixture of a test input.
+fn build_fixture_events(
+    input: &TestInput,
+    path: &Path,
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<Vec<Event>, String> {
+    let lines = read_fixture(path)?;
+    lines
+        .into_iter()
+        .map(|(line_number, line)| {
+            let invalid = |error: String| {
+                format!(
+                    "fixture '{}' line {}: {}",
+                    path.display(),
+                    line_number,
+                    error
+                )
+            };
+            match input.type_str.as_ref() {
+                "raw" => Ok(build_raw_event(line, timestamp)),
+                "log" => {
+                    let value = serde_json::from_str::<serde_json::Value>(&line)
+                        .map_err(|error| invalid(format!("invalid JSON: {}", error)))?;
+                    let mut event =
+                        LogEvent::try_from(value).map_err(|error| invalid(error.to_string()))?;
+                    if let Some(timestamp) = timestamp {
+                        if !event.contains(log_schema().timestamp_key()) {
+                            event.insert(log_schema().timestamp_key(), timestamp);
+                        }
+                    }
+                    Ok(event.into())
+                }
+                "metric" => serde_json::from_str::<Metric>(&line)
+                    .map(Event::Metric)
+                    .map_err(|error| invalid(format!("invalid metric: {}", error))),
+                _ => Err(format!(
+                    "unrecognized input type '{}', expected one of: 'raw', 'log' or 'metric'",
+                    input.type_str
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Reads the non-empty lines of a fixture file, along with their line numbers.
+fn read_fixture(path: &Path) -> Result<Vec<(usize, String)>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("failed to read fixture '{}': {}", path.display(), error))?;
+    Ok(contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| (index + 1, line.to_string()))
+        .collect())
+}
+
+/// Decodes a raw test input with the decoding of the source it is inserted at.
+///
+/// Only the codecs of the source are applied: fields the source would add
+/// itself, such as `source_type` or `host`, are not present on the events.
+fn decode_raw_value(value: &str, decoding: &DecodingConfig) -> Result<Vec<Event>, String> {
+    let mut decoder = decoding.build();
+    let mut buffer = BytesMut::from(value);
+    let mut events = Vec::new();
+    while let Some((decoded, _)) = decoder
+        .decode_eof(&mut buffer)
+        .map_err(|error| format!("failed to decode input: {}", error))?
+    {
+        events.extend(decoded);
+    }
+    Ok(events)
+}
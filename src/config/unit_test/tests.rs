@@ -502,6 +502,52 @@ async fn test_success() {
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
 
+#[tokio::test]
+async fn test_trace_remap() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.foo]
+          inputs = ["ignored"]
+          type = "remap"
+          source = '''
+          if .message == "skip" {
+            .skipped = true
+          } else {
+            .new_field = "string value"
+          }
+          '''
+
+        [[tests]]
+          name = "traced test"
+
+          [tests.input]
+            insert_at = "foo"
+            value = "nah this doesnt matter"
+
+          [[tests.outputs]]
+            extract_from = "foo"
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = """
+                assert_eq!(.new_field, "string value")
+              """
+    "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests_with_tracing(config, true).await.unwrap();
+    let result = tests.remove(0).run().await;
+    assert!(result.errors.is_empty());
+    assert_eq!(result.traces.len(), 1);
+
+    let trace = &result.traces[0];
+    assert!(trace.starts_with("foo:\n"), "{}", trace);
+    assert!(
+        trace.contains(r#"+ .new_field = "string value""#),
+        "{}",
+        trace
+    );
+    assert!(!trace.contains(".skipped = true"), "{}", trace);
+}
+
 #[tokio::test]
 async fn test_route() {
     let config: ConfigBuilder = toml::from_str(indoc! {r#"
@@ -1235,3 +1281,228 @@ async fn test_glob_input() {
     let mut tests = build_unit_tests(config).await.unwrap();
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
+
+#[tokio::test]
+async fn test_source_decoding_and_sink_encoding() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [sources.in]
+          type = "demo_logs"
+          format = "shuffle"
+          lines = ["ignored"]
+          framing.method = "newline_delimited"
+          decoding.codec = "json"
+
+        [transforms.add_field]
+          type = "remap"
+          inputs = ["in"]
+          source = '.new_field = "value"'
+
+        [sinks.out]
+          type = "console"
+          inputs = ["add_*"]
+          encoding.codec = "json"
+          encoding.except_fields = ["timestamp"]
+
+        [[tests]]
+          name = "decoding and encoding test"
+
+          [[tests.inputs]]
+            insert_at = "in"
+            value = '''
+            {"message": "test1"}
+            {"message": "test2"}'''
+
+          [[tests.outputs]]
+            extract_from = "add_field"
+
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = """
+                assert_eq!(.message, "test1", "incorrect message")
+                assert_eq!(.new_field, "value", "incorrect value")
+              """
+
+          [[tests.outputs]]
+            extract_from = "out"
+
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = """
+                assert_eq!(.message, s'{"message":"test2","new_field":"value"}', "incorrect encoding")
+              """
+    "#})
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.len(), 1);
+    let errors = tests.remove(0).run().await.errors;
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[tokio::test]
+async fn test_clock_and_fixtures() {
+    let dir = crate::test_util::temp_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    let input_fixture = dir.join("input.ndjson");
+    let output_fixture = dir.join("output.ndjson");
+    std::fs::write(
+        &input_fixture,
+        indoc! {r#"
+            {"message": "first"}
+            {"message": "second"}
+        "#},
+    )
+    .unwrap();
+    std::fs::write(
+        &output_fixture,
+        indoc! {r#"
+            {"message": "first", "seen_at": 1640995200}
+            {"message": "second", "seen_at": 1640995200}
+            {"message": "third", "seen_at": 1640995230}
+        "#},
+    )
+    .unwrap();
+
+    let config: ConfigBuilder = toml::from_str(&format!(
+        indoc! {r#"
+            [transforms.foo]
+              type = "remap"
+              inputs = ["ignored"]
+              source = ".seen_at = to_unix_timestamp(now())"
+
+            [[tests]]
+              name = "clock test"
+              clock.start_time = "2022-01-01T00:00:00Z"
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "log"
+                fixture = "{}"
+
+              [[tests.inputs]]
+                insert_at = "foo"
+                type = "log"
+                advance_secs = 30
+                log_fields.message = "third"
+
+              [[tests.outputs]]
+                extract_from = "foo"
+                fixture = "{}"
+        "#},
+        input_fixture.display(),
+        output_fixture.display()
+    ))
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert_eq!(tests.len(), 1);
+    let errors = tests.remove(0).run().await.errors;
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[tokio::test]
+async fn test_advance_secs_without_clock() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.foo]
+          type = "remap"
+          inputs = ["ignored"]
+          source = '.new_field = "value"'
+
+        [[tests]]
+          name = "broken test"
+
+          [[tests.inputs]]
+            insert_at = "foo"
+            value = "nah this doesnt matter"
+            advance_secs = 10
+
+          [[tests.outputs]]
+            extract_from = "foo"
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = ""
+    "#})
+    .unwrap();
+
+    let errors = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errors,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              inputs[0]: `advance_secs` requires the test to have a `clock`"#}
+        .to_owned(),]
+    );
+}
+
+#[tokio::test]
+async fn test_advance_secs_must_be_positive() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.foo]
+          type = "remap"
+          inputs = ["ignored"]
+          source = '.new_field = "value"'
+
+        [[tests]]
+          name = "broken test"
+          clock.start_time = "2022-01-01T00:00:00Z"
+
+          [[tests.inputs]]
+            insert_at = "foo"
+            value = "nah this doesnt matter"
+            advance_secs = 0
+
+          [[tests.outputs]]
+            extract_from = "foo"
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = ""
+    "#})
+    .unwrap();
+
+    let errors = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errors,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              inputs[0]: `advance_secs` must be a positive number"#}
+        .to_owned(),]
+    );
+}
+
+#[tokio::test]
+async fn test_sink_without_encoding() {
+    let config: ConfigBuilder = toml::from_str(indoc! {r#"
+        [transforms.foo]
+          type = "remap"
+          inputs = ["ignored"]
+          source = '.new_field = "value"'
+
+        [sinks.out]
+          type = "blackhole"
+          inputs = ["foo"]
+
+        [[tests]]
+          name = "broken test"
+
+          [[tests.inputs]]
+            insert_at = "foo"
+            value = "test"
+
+          [[tests.outputs]]
+            extract_from = "out"
+
+            [[tests.outputs.conditions]]
+              type = "vrl"
+              source = ""
+    "#})
+    .unwrap();
+
+    let errs = build_unit_tests(config).await.err().unwrap();
+    assert_eq!(
+        errs,
+        vec![indoc! {r#"
+            Failed to build test 'broken test':
+              Invalid extract_from target in test 'broken test': sink 'out' does not have a configurable `encoding`"#}
+        .to_owned(),]
+    );
+}
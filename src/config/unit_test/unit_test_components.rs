@@ -1,40 +1,60 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
+use bytes::BytesMut;
+use codecs::encoding::Serializer;
 use futures_util::{future, stream::BoxStream, FutureExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{oneshot, Mutex};
+use tokio::{
+    sync::{oneshot, Mutex},
+    time::Instant,
+};
+use tokio_util::codec::Encoder as _;
 use vector_core::config::LogNamespace;
 use vector_core::{
     config::{DataType, Input, Output},
-    event::Event,
+    event::{Event, LogEvent},
     sink::{StreamSink, VectorSink},
 };
 
 use crate::{
+    codecs::{EncodingConfig, Transformer},
     conditions::Condition,
-    config::{AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext},
+    config::{
+        log_schema, AcknowledgementsConfig, SinkConfig, SinkContext, SourceConfig, SourceContext,
+    },
     sinks::Healthcheck,
     sources,
 };
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct UnitTestSourceConfig {
+    // Events to send, along with their offset from the start of the test
     #[serde(skip)]
-    pub events: Vec<Event>,
+    pub events: Vec<(Duration, Event)>,
 }
 
 #[async_trait::async_trait]
 #[typetag::serde(name = "unit_test")]
 impl SourceConfig for UnitTestSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
-        let events = self.events.clone().into_iter();
+        let mut events = self.events.clone().into_iter().peekable();
 
         Ok(Box::pin(async move {
             let mut out = cx.out;
             // To appropriately shut down the topology after the source is done
             // sending events, we need to hold on to this shutdown trigger.
             let _shutdown = cx.shutdown;
-            out.send_batch(events).await.map_err(|_| ())?;
+            let start = Instant::now();
+            // Events sharing the same offset are sent as a single batch once the
+            // test clock reaches that offset.
+            while let Some((offset, event)) = events.next() {
+                let mut batch = vec![event];
+                while let Some((_, event)) = events.next_if(|(next, _)| *next == offset) {
+                    batch.push(event);
+                }
+                tokio::time::sleep_until(start + offset).await;
+                out.send_batch(batch).await.map_err(|_| ())?;
+            }
             Ok(())
         }))
     }
@@ -88,6 +108,12 @@ pub struct UnitTestSinkConfig {
     #[derivative(Debug = "ignore")]
     // Check applied to incoming events
     pub check: UnitTestSinkCheck,
+    #[serde(skip)]
+    // Encoding of the sink under test, applied to incoming events before the check
+    pub encoding: Option<EncodingConfig>,
+    #[serde(skip)]
+    // Sets of expected events, loaded from fixtures, that incoming events must match
+    pub expected_events: Vec<Vec<serde_json::Value>>,
 }
 
 #[async_trait::async_trait]
@@ -95,11 +121,22 @@ pub struct UnitTestSinkConfig {
 impl SinkConfig for UnitTestSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let tx = self.result_tx.lock().await.take();
+        let encoder = self
+            .encoding
+            .as_ref()
+            .map(|encoding| {
+                encoding
+                    .build()
+                    .map(|serializer| (encoding.transformer(), serializer))
+            })
+            .transpose()?;
         let sink = UnitTestSink {
             test_name: self.test_name.clone(),
             transform_ids: self.transform_ids.clone(),
             result_tx: tx,
             check: self.check.clone(),
+            encoder,
+            expected_events: self.expected_events.clone(),
         };
         let healthcheck = future::ok(()).boxed();
 
@@ -125,6 +162,9 @@ pub struct UnitTestSink {
     // None for NoOp test sinks
     pub result_tx: Option<oneshot::Sender<UnitTestSinkResult>>,
     pub check: UnitTestSinkCheck,
+    // Set when checks are run against the encoded output of a sink
+    pub encoder: Option<(Transformer, Serializer)>,
+    pub expected_events: Vec<Vec<serde_json::Value>>,
 }
 
 #[async_trait::async_trait]
@@ -140,6 +180,27 @@ impl StreamSink<Event> for UnitTestSink {
             output_events.push(event);
         }
 
+        if let Some((transformer, mut serializer)) = self.encoder {
+            let mut encoded_events = Vec::with_capacity(output_events.len());
+            for mut event in output_events {
+                transformer.transform(&mut event);
+                let mut bytes = BytesMut::new();
+                match serializer.encode(event, &mut bytes) {
+                    // The payload is kept as bytes, as binary encodings aren't valid UTF-8.
+                    Ok(()) => {
+                        let mut log = LogEvent::default();
+                        log.insert(log_schema().message_key(), bytes.freeze());
+                        encoded_events.push(Event::Log(log));
+                    }
+                    Err(error) => result.test_errors.push(format!(
+                        "failed to encode output from {:?}: {}",
+                        self.transform_ids, error
+                    )),
+                }
+            }
+            output_events = encoded_events;
+        }
+
         match self.check {
             UnitTestSinkCheck::Checks(checks) => {
                 if output_events.is_empty() {
@@ -179,6 +240,15 @@ impl StreamSink<Event> for UnitTestSink {
                         result.test_errors.extend(check_errors);
                     }
 
+                    for (i, expected_events) in self.expected_events.iter().enumerate() {
+                        result.test_errors.extend(check_expected_events(
+                            i,
+                            &self.transform_ids,
+                            expected_events,
+                            &output_events,
+                        ));
+                    }
+
                     // If there are errors, add a summary of events received
                     if !result.test_errors.is_empty() {
                         result.test_errors.push(format!(
@@ -224,3 +294,57 @@ fn events_to_string(events: &[Event]) -> String {
         .collect::<Vec<_>>()
         .join("\n  ")
 }
+
+fn check_expected_events(
+    index: usize,
+    transform_ids: &[String],
+    expected_events: &[serde_json::Value],
+    output_events: &[Event],
+) -> Vec<String> {
+    if expected_events.len() != output_events.len() {
+        return vec![format!(
+            "fixture[{}] for transforms {:?} failed: expected {} events, received {}",
+            index,
+            transform_ids,
+            expected_events.len(),
+            output_events.len()
+        )];
+    }
+
+    expected_events
+        .iter()
+        .zip(output_events.iter())
+        .enumerate()
+        .filter(|(_, (expected, event))| !json_contains(&event_to_json(event), expected))
+        .map(|(i, (expected, _))| {
+            format!(
+                "fixture[{}] for transforms {:?} failed: event[{}] does not match {}",
+                index, transform_ids, i, expected
+            )
+        })
+        .collect()
+}
+
+fn event_to_json(event: &Event) -> serde_json::Value {
+    match event {
+        Event::Log(log) => serde_json::to_value(log),
+        Event::Metric(metric) => serde_json::to_value(metric),
+        Event::Trace(trace) => serde_json::to_value(trace),
+    }
+    .unwrap_or(serde_json::Value::Null)
+}
+
+/// Checks that `actual` includes all of `expected`. Objects may contain more
+/// fields than the expected ones, every other value must be equal.
+fn json_contains(actual: &serde_json::Value, expected: &serde_json::Value) -> bool {
+    match (actual, expected) {
+        (serde_json::Value::Object(actual), serde_json::Value::Object(expected)) => {
+            expected.iter().all(|(key, expected)| {
+                actual
+                    .get(key)
+                    .map_or(false, |actual| json_contains(actual, expected))
+            })
+        }
+        _ => actual == expected,
+    }
+}
@@ -1,7 +1,11 @@
-use std::{path::PathBuf, time::Duration};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 #[cfg(unix)]
 use std::{
-    sync::mpsc::{channel, Receiver},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
     thread,
 };
 
@@ -23,27 +27,80 @@ const CONFIG_WATCH_DELAY: std::time::Duration = std::time::Duration::from_secs(1
 #[cfg(unix)]
 const RETRY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
+/// How often the watcher checks whether the paths to watch were updated.
+#[cfg(unix)]
+const PATHS_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// The paths watched for changes, which can be updated while the watcher runs, such as when a
+/// reloaded config reads secrets from other paths.
+#[derive(Clone, Debug, Default)]
+pub struct WatchedPaths(Arc<Mutex<Vec<PathBuf>>>);
+
+impl WatchedPaths {
+    pub fn set(&self, paths: Vec<PathBuf>) {
+        *self.0.lock().expect("watched paths lock poisoned") = paths;
+    }
+
+    #[cfg(unix)]
+    fn get(&self) -> Vec<PathBuf> {
+        self.0.lock().expect("watched paths lock poisoned").clone()
+    }
+}
+
 /// Triggers SIGHUP when file on config_path changes.
 /// Accumulates file changes until no change for given duration has occurred.
 /// Has best effort guarantee of detecting all file changes from the end of
 /// this function until the main thread stops.
+///
+/// Returns the watched paths, which can be updated once the thread is running.
 #[cfg(unix)]
 pub fn spawn_thread<'a>(
     config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
     delay: impl Into<Option<Duration>>,
-) -> Result<(), Error> {
-    let config_paths: Vec<_> = config_paths.into_iter().cloned().collect();
+) -> Result<WatchedPaths, Error> {
+    let paths = WatchedPaths::default();
+    paths.set(config_paths.into_iter().cloned().collect());
     let delay = delay.into().unwrap_or(CONFIG_WATCH_DELAY);
 
     // Create watcher now so not to miss any changes happening between
     // returning from this function and the thread starting.
+    let mut config_paths = paths.get();
     let mut watcher = Some(create_watcher(&config_paths)?);
 
     info!("Watching configuration files.");
 
+    let watched_paths = paths.clone();
     thread::spawn(move || loop {
-        if let Some((mut watcher, receiver)) = watcher.take() {
-            while let Ok(RawEvent { op: Ok(event), .. }) = receiver.recv() {
+        if let Some((mut watcher, receiver, mut missing_paths)) = watcher.take() {
+            loop {
+                let event = match receiver.recv_timeout(PATHS_REFRESH_INTERVAL) {
+                    Ok(RawEvent { op: Ok(event), .. }) => event,
+                    Ok(_) | Err(RecvTimeoutError::Disconnected) => break,
+                    Err(RecvTimeoutError::Timeout) => {
+                        // Paths are updated when the config is reloaded, and missing paths are
+                        // watched once they are created, which is a change to reload as well.
+                        let updated_paths = watched_paths.get();
+                        let created = missing_paths.iter().any(|path| path.exists());
+                        if updated_paths != config_paths || created {
+                            let result = update_paths(&mut watcher, &config_paths, &updated_paths);
+                            config_paths = updated_paths;
+                            match result {
+                                Ok(missing) => missing_paths = missing,
+                                Err(error) => {
+                                    error!(message = "Failed to read files to watch.", %error);
+                                    break;
+                                }
+                            }
+                            debug!(message = "Updated paths to watch.");
+                        }
+                        if created {
+                            info!("Configuration file created.");
+                            raise_sighup();
+                        }
+                        continue;
+                    }
+                };
+
                 if event.intersects(Op::CREATE | Op::REMOVE | Op::WRITE | Op::CLOSE_WRITE) {
                     debug!(message = "Configuration file change detected.", event = ?event);
 
@@ -54,9 +111,15 @@ pub fn spawn_thread<'a>(
 
                     // We need to read paths to resolve any inode changes that may have happened.
                     // And we need to do it before raising sighup to avoid missing any change.
-                    if let Err(error) = add_paths(&mut watcher, &config_paths) {
-                        error!(message = "Failed to read files to watch.", %error);
-                        break;
+                    let updated_paths = watched_paths.get();
+                    let result = update_paths(&mut watcher, &config_paths, &updated_paths);
+                    config_paths = updated_paths;
+                    match result {
+                        Ok(missing) => missing_paths = missing,
+                        Err(error) => {
+                            error!(message = "Failed to read files to watch.", %error);
+                            break;
+                        }
                     }
 
                     debug!(message = "Reloaded paths.");
@@ -71,6 +134,7 @@ pub fn spawn_thread<'a>(
 
         thread::sleep(RETRY_TIMEOUT);
 
+        config_paths = watched_paths.get();
         watcher = create_watcher(&config_paths)
             .map_err(|error| error!(message = "Failed to create file watcher.", %error))
             .ok();
@@ -84,7 +148,7 @@ pub fn spawn_thread<'a>(
         }
     });
 
-    Ok(())
+    Ok(paths)
 }
 
 #[cfg(windows)]
@@ -92,7 +156,7 @@ pub fn spawn_thread<'a>(
 pub fn spawn_thread<'a>(
     _config_paths: impl IntoIterator<Item = &'a PathBuf> + 'a,
     _delay: impl Into<Option<Duration>>,
-) -> Result<(), Error> {
+) -> Result<WatchedPaths, Error> {
     Err("Reloading config on Windows isn't currently supported. Related issue https://github.com/vectordotdev/vector/issues/938 .".into())
 }
 
@@ -107,20 +171,52 @@ fn raise_sighup() {
 #[cfg(unix)]
 fn create_watcher(
     config_paths: &[PathBuf],
-) -> Result<(RecommendedWatcher, Receiver<RawEvent>), Error> {
+) -> Result<(RecommendedWatcher, Receiver<RawEvent>, Vec<PathBuf>), Error> {
     info!("Creating configuration file watcher.");
     let (sender, receiver) = channel();
     let mut watcher = raw_watcher(sender)?;
-    add_paths(&mut watcher, config_paths)?;
-    Ok((watcher, receiver))
+    let missing_paths = add_paths(&mut watcher, config_paths)?;
+    Ok((watcher, receiver, missing_paths))
+}
+
+/// Stops watching the paths that are no longer in the updated paths, such as the paths of a secret
+/// backend that was removed from the config, and watches the updated paths, returning the ones that
+/// don't exist.
+#[cfg(unix)]
+fn update_paths(
+    watcher: &mut RecommendedWatcher,
+    config_paths: &[PathBuf],
+    updated_paths: &[PathBuf],
+) -> Result<Vec<PathBuf>, Error> {
+    for path in config_paths
+        .iter()
+        .filter(|path| !updated_paths.contains(path))
+    {
+        // Paths that were missing, or were deleted since, aren't watched.
+        if let Err(error) = watcher.unwatch(path) {
+            debug!(message = "Failed to stop watching path.", path = ?path, %error);
+        }
+    }
+    add_paths(watcher, updated_paths)
 }
 
+/// Watches the given paths, and returns the ones that don't exist, such as paths secrets are read
+/// from before they are created.
 #[cfg(unix)]
-fn add_paths(watcher: &mut RecommendedWatcher, config_paths: &[PathBuf]) -> Result<(), Error> {
+fn add_paths(
+    watcher: &mut RecommendedWatcher,
+    config_paths: &[PathBuf],
+) -> Result<Vec<PathBuf>, Error> {
+    let mut missing_paths = Vec::new();
     for path in config_paths {
+        if !path.exists() {
+            warn!(message = "Skipping missing path to watch.", path = ?path);
+            missing_paths.push(path.clone());
+            continue;
+        }
         watcher.watch(path, RecursiveMode::NonRecursive)?;
     }
-    Ok(())
+    Ok(missing_paths)
 }
 
 #[cfg(all(test, unix, not(target_os = "macos")))] // https://github.com/vectordotdev/vector/issues/5000
@@ -190,4 +286,70 @@ mod tests {
             panic!("Test timed out");
         }
     }
+
+    #[tokio::test]
+    async fn missing_file_created() {
+        trace_init();
+
+        let delay = Duration::from_secs(3);
+        let file_path = temp_file();
+        let mut signal = signal(SignalKind::hangup()).expect("Signal handlers should not panic.");
+
+        spawn_thread(&[file_path.clone()], delay).unwrap();
+        File::create(&file_path).unwrap();
+
+        if tokio::time::timeout(PATHS_REFRESH_INTERVAL * 3, signal.recv())
+            .await
+            .is_err()
+        {
+            panic!("Test timed out");
+        }
+    }
+
+    #[test]
+    fn removed_paths_are_unwatched() {
+        trace_init();
+
+        let kept_path = temp_file();
+        let removed_path = temp_file();
+        let mut kept = File::create(&kept_path).unwrap();
+        let mut removed = File::create(&removed_path).unwrap();
+
+        let paths = vec![kept_path.clone(), removed_path.clone()];
+        let (mut watcher, receiver, _) = create_watcher(&paths).unwrap();
+        update_paths(&mut watcher, &paths, &[kept_path.clone()]).unwrap();
+
+        removed.write_all(&[0]).unwrap();
+        removed.sync_all().unwrap();
+        kept.write_all(&[0]).unwrap();
+        kept.sync_all().unwrap();
+
+        let events = std::iter::from_fn(|| receiver.recv_timeout(Duration::from_secs(1)).ok())
+            .collect::<Vec<_>>();
+        assert!(events
+            .iter()
+            .any(|event| event.path.as_ref() == Some(&kept_path)));
+        assert!(!events
+            .iter()
+            .any(|event| event.path.as_ref() == Some(&removed_path)));
+    }
+
+    #[tokio::test]
+    async fn updated_paths() {
+        trace_init();
+
+        let delay = Duration::from_secs(3);
+        let file_path = temp_file();
+        let added_path = temp_file();
+        File::create(&file_path).unwrap();
+        let mut added = File::create(&added_path).unwrap();
+
+        let paths = spawn_thread(&[file_path.clone()], delay).unwrap();
+        paths.set(vec![file_path, added_path]);
+        tokio::time::sleep(PATHS_REFRESH_INTERVAL * 2).await;
+
+        if !test(&mut added, delay * 5).await {
+            panic!("Test timed out");
+        }
+    }
 }
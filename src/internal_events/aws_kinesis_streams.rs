@@ -0,0 +1,82 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsRequestError<'a, E> {
+    pub operation: &'static str,
+    pub shard_id: Option<&'a str>,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AwsKinesisStreamsRequestError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Kinesis request failed.",
+            operation = %self.operation,
+            shard_id = %self.shard_id.unwrap_or_default(),
+            error = %self.error,
+            error_code = "failed_kinesis_request",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_kinesis_request",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsCheckpointError<'a> {
+    pub shard_id: &'a str,
+    pub error: crate::Error,
+}
+
+impl<'a> InternalEvent for AwsKinesisStreamsCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read or write the shard checkpoint.",
+            shard_id = %self.shard_id,
+            error = %self.error,
+            error_code = "failed_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsKinesisStreamsAcknowledgementError<'a> {
+    pub shard_id: &'a str,
+}
+
+impl<'a> InternalEvent for AwsKinesisStreamsAcknowledgementError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Records were not delivered; they will be read again from the last checkpoint.",
+            shard_id = %self.shard_id,
+            error_code = "failed_acknowledgement",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_acknowledgement",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -13,7 +13,7 @@ mod s3 {
     };
 
     use super::*;
-    use crate::sources::aws_s3::sqs::ProcessingError;
+    use crate::sources::aws_s3::{sqs::ProcessingError, ObjectError};
 
     #[derive(Debug)]
     pub struct SqsMessageReceiveError<'a, E> {
@@ -143,6 +143,84 @@ mod s3 {
             counter!("sqs_message_delete_batch_failed_total", 1);
         }
     }
+
+    #[derive(Debug)]
+    pub struct S3ListObjectsError<'a, E> {
+        pub bucket: &'a str,
+        pub error: &'a E,
+    }
+
+    impl<'a, E: std::fmt::Display> InternalEvent for S3ListObjectsError<'a, E> {
+        fn emit(self) {
+            error!(
+                message = "Failed to list S3 objects.",
+                bucket = %self.bucket,
+                error = %self.error,
+                error_code = "failed_listing_s3_objects",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_secs = 10,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_listing_s3_objects",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ObjectProcessingError<'a> {
+        pub bucket: &'a str,
+        pub key: &'a str,
+        pub error: &'a ObjectError,
+    }
+
+    impl<'a> InternalEvent for S3ObjectProcessingError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to process S3 object.",
+                bucket = %self.bucket,
+                key = %self.key,
+                error = %self.error,
+                error_code = "failed_processing_s3_object",
+                error_type = error_type::READER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_secs = 10,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_processing_s3_object",
+                "error_type" => error_type::READER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ProcessedKeysWriteError {
+        pub error: crate::Error,
+    }
+
+    impl InternalEvent for S3ProcessedKeysWriteError {
+        fn emit(self) {
+            error!(
+                message = "Failed to record processed S3 object.",
+                error = %self.error,
+                error_code = "failed_writing_processed_keys",
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_secs = 10,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_writing_processed_keys",
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,66 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ClickhouseCoercionError<'a, E> {
+    pub column: &'a str,
+    pub error: E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for ClickhouseCoercionError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Field can't be coerced to the type of its column; discarding event.",
+            column = %self.column,
+            error = %self.error,
+            error_code = "coercion_failed",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "coercion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "coercion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ClickhouseDescribeTableError<E> {
+    pub error: E,
+    pub retriable: bool,
+}
+
+impl<E: std::fmt::Display> InternalEvent for ClickhouseDescribeTableError<E> {
+    fn emit(self) {
+        let message = if self.retriable {
+            "Failed to describe the table; retrying."
+        } else {
+            "Failed to describe the table; stopping the sink."
+        };
+        error!(
+            message = %message,
+            error = %self.error,
+            error_code = "describe_table_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "describe_table_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
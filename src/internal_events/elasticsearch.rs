@@ -40,3 +40,102 @@ impl<'a> InternalEvent for ElasticsearchResponseError<'a> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct ElasticsearchInvalidVersion<'a> {
+    pub version: &'a str,
+}
+
+impl<'a> InternalEvent for ElasticsearchInvalidVersion<'a> {
+    fn emit(self) {
+        error!(
+            message = "Document version is not an unsigned integer; discarding event.",
+            version = %self.version,
+            error_code = "invalid_version",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_version",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "invalid_version",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchMissingId {
+    pub action: &'static str,
+}
+
+impl InternalEvent for ElasticsearchMissingId {
+    fn emit(self) {
+        error!(
+            message = "Bulk action requires a document id, but the event has none; discarding event.",
+            action = %self.action,
+            error_code = "missing_id",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "missing_id",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "missing_id",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchPartialRetry {
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl InternalEvent for ElasticsearchPartialRetry {
+    fn emit(self) {
+        warn!(
+            message = "Some bulk items failed with a transient error; retrying them.",
+            failed = %self.failed,
+            total = %self.total,
+            internal_log_rate_secs = 10,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchPartialRetryError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for ElasticsearchPartialRetryError {
+    fn emit(self) {
+        error!(
+            message = "Failed to retry the failed bulk items.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ElasticsearchSearchError<'a, E> {
+    pub operation: &'static str,
+    pub endpoint: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for ElasticsearchSearchError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Elasticsearch request failed.",
+            operation = %self.operation,
+            endpoint = %self.endpoint,
+            error = %self.error,
+            error_code = "failed_search_request",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_search_request",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchCheckpointError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ElasticsearchCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to write checkpoint.",
+            error = %self.error,
+            error_code = "failed_writing_checkpoint",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_writing_checkpoint",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchPointInTimeExpired<'a> {
+    pub indices: &'a str,
+    pub checkpoint_path: &'a Path,
+}
+
+impl<'a> InternalEvent for ElasticsearchPointInTimeExpired<'a> {
+    fn emit(self) {
+        error!(
+            message = "Point in time expired during the search, stopping. Increase `keep_alive_secs` and remove the checkpoint to start the search over.",
+            indices = %self.indices,
+            checkpoint_path = ?self.checkpoint_path,
+            error_code = "point_in_time_expired",
+            error_type = error_type::TIMED_OUT,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "point_in_time_expired",
+            "error_type" => error_type::TIMED_OUT,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+use crate::event::metric::{MetricKind, MetricValue};
+
+#[derive(Debug)]
+pub struct GraphiteInvalidMetricError<'a> {
+    pub value: &'a MetricValue,
+    pub kind: &'a MetricKind,
+}
+
+impl<'a> InternalEvent for GraphiteInvalidMetricError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Unsupported metric type; dropping event.",
+            error_code = "invalid_metric",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            value = ?self.value,
+            kind = ?self.kind,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_metric",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "invalid_metric",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
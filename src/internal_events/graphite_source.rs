@@ -0,0 +1,33 @@
+use bytes::Bytes;
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GraphiteInvalidRecordError<'a> {
+    pub error: &'a crate::sources::graphite::parser::ParseError,
+    pub bytes: Bytes,
+}
+
+const INVALID_LINE: &str = "invalid_line";
+
+impl<'a> InternalEvent for GraphiteInvalidRecordError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Invalid line from graphite, discarding.",
+            error = %self.error,
+            error_code = INVALID_LINE,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            bytes = %String::from_utf8_lossy(&self.bytes),
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => INVALID_LINE,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
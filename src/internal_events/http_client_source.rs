@@ -0,0 +1,119 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type, http_error_code};
+
+#[derive(Debug)]
+pub struct HttpClientEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub endpoint: &'a str,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientEventsReceived<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            endpoint = %self.endpoint,
+            url = %self.url,
+        );
+        counter!(
+            "component_received_events_total", self.count as u64,
+            "uri" => self.endpoint.to_owned(),
+        );
+        counter!(
+            "component_received_event_bytes_total", self.byte_size as u64,
+            "uri" => self.endpoint.to_owned(),
+        );
+        // deprecated
+        counter!(
+            "events_in_total", self.count as u64,
+            "uri" => self.endpoint.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpResponseError<'a> {
+    pub code: hyper::StatusCode,
+    pub endpoint: &'a str,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientHttpResponseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "HTTP error response.",
+            endpoint = %self.endpoint,
+            url = %self.url,
+            stage = error_stage::RECEIVING,
+            error_type = error_type::REQUEST_FAILED,
+            error_code = %http_error_code(self.code.as_u16()),
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.endpoint.to_owned(),
+            "stage" => error_stage::RECEIVING,
+            "error_type" => error_type::REQUEST_FAILED,
+            "error_code" => http_error_code(self.code.as_u16()),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientHttpError<'a> {
+    pub error: crate::Error,
+    pub endpoint: &'a str,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientHttpError<'a> {
+    fn emit(self) {
+        error!(
+            message = "HTTP request processing error.",
+            endpoint = %self.endpoint,
+            url = %self.url,
+            error = ?self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.endpoint.to_owned(),
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientCursorError<'a> {
+    pub error: String,
+    pub endpoint: &'a str,
+    pub url: &'a str,
+}
+
+impl<'a> InternalEvent for HttpClientCursorError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read the cursor of the next page.",
+            endpoint = %self.endpoint,
+            url = %self.url,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.endpoint.to_owned(),
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
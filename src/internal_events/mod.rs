@@ -16,11 +16,15 @@ mod aws_ec2_metadata;
 mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+mod aws_kinesis_streams;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod azure_blob;
 mod batch;
+#[cfg(feature = "sinks-clickhouse")]
+mod clickhouse;
 mod common;
 mod conditions;
 #[cfg(feature = "sinks-datadog_metrics")]
@@ -37,6 +41,8 @@ mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
 mod elasticsearch;
+#[cfg(feature = "sources-elasticsearch")]
+mod elasticsearch_source;
 mod encoding_transcode;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
@@ -50,9 +56,15 @@ mod fluent;
 mod gcp_pubsub;
 #[cfg(feature = "transforms-geoip")]
 mod geoip;
+#[cfg(feature = "sinks-graphite")]
+mod graphite_sink;
+#[cfg(feature = "sources-graphite")]
+mod graphite_source;
 mod heartbeat;
 mod http;
 pub mod http_client;
+#[cfg(feature = "sources-http_client")]
+mod http_client_source;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -84,6 +96,8 @@ mod open;
     feature = "transforms-log_to_metric",
 ))]
 mod parser;
+#[cfg(feature = "sinks-postgres")]
+mod postgres;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -145,8 +159,12 @@ pub(crate) use self::aws_ec2_metadata::*;
 pub(crate) use self::aws_ecs_metrics::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub(crate) use self::aws_kinesis_streams::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-clickhouse")]
+pub(crate) use self::clickhouse::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -162,6 +180,8 @@ pub(crate) use self::dnstap::*;
 pub(crate) use self::docker_logs::*;
 #[cfg(feature = "sinks-elasticsearch")]
 pub(crate) use self::elasticsearch::*;
+#[cfg(feature = "sources-elasticsearch")]
+pub(crate) use self::elasticsearch_source::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
@@ -180,6 +200,10 @@ pub(crate) use self::fluent::*;
 pub(crate) use self::gcp_pubsub::*;
 #[cfg(feature = "transforms-geoip")]
 pub(crate) use self::geoip::*;
+#[cfg(feature = "sinks-graphite")]
+pub(crate) use self::graphite_sink::*;
+#[cfg(feature = "sources-graphite")]
+pub(crate) use self::graphite_source::*;
 #[cfg(any(
     feature = "sources-utils-http",
     feature = "sources-utils-http-encoding",
@@ -187,6 +211,8 @@ pub(crate) use self::geoip::*;
     feature = "sources-splunk_hec",
 ))]
 pub(crate) use self::http::*;
+#[cfg(feature = "sources-http_client")]
+pub(crate) use self::http_client_source::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -216,6 +242,8 @@ pub(crate) use self::nginx_metrics::*;
     feature = "transforms-log_to_metric",
 ))]
 pub(crate) use self::parser::*;
+#[cfg(feature = "sinks-postgres")]
+pub(crate) use self::postgres::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
@@ -0,0 +1,65 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct PostgresColumnCoercionError<'a> {
+    pub column: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for PostgresColumnCoercionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to coerce field to the type of its column; dropping event.",
+            column = %self.column,
+            error = %self.error,
+            error_code = "column_coercion_failed",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "column_coercion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "error_code" => "column_coercion_failed",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresSendError<'a> {
+    pub error: &'a tokio_postgres::Error,
+}
+
+impl<'a> InternalEvent for PostgresSendError<'a> {
+    fn emit(self) {
+        let error_code = self
+            .error
+            .code()
+            .map_or("UNKNOWN", |code| code.code())
+            .to_owned();
+        error!(
+            message = "Failed to write rows.",
+            error = %self.error,
+            error_code = %error_code,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => error_code,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::Parser;
+use vrl_lsp::Schema;
+
+use crate::{
+    config::{self, ComponentKey},
+    topology::schema::merged_definition,
+};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    #[clap(name = "config", short, long, use_value_delimiter(true))]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(name = "config-dir", short = 'C', long, use_value_delimiter(true))]
+    config_dirs: Vec<PathBuf>,
+
+    /// The transform whose input schema is used to complete and type check
+    /// event fields. Requires a configuration to be given.
+    #[clap(long)]
+    component: Option<String>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// Run the VRL language server, using the merged input schema of the given
+/// component, if any, as the schema of the events handled by programs.
+pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let schema = match &opts.component {
+        Some(component) => match schema(opts, component) {
+            Ok(schema) => Some(schema),
+            Err(code) => return code,
+        },
+        None => None,
+    };
+
+    match vrl_lsp::run(vrl_lsp::cmd::functions(), schema) {
+        Ok(()) => exitcode::OK,
+        Err(error) => {
+            error!(message = "Language server failed.", %error);
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+fn schema(opts: &Opts, component: &str) -> Result<Schema, exitcode::ExitCode> {
+    let paths = config::process_paths(&opts.paths_with_formats()).ok_or(exitcode::CONFIG)?;
+
+    let config = config::load_from_paths(&paths).map_err(|errs| {
+        #[allow(clippy::print_stderr)]
+        for err in errs {
+            eprintln!("{}", err);
+        }
+        exitcode::CONFIG
+    })?;
+
+    let transform = config
+        .transform(&ComponentKey::from(component))
+        .ok_or_else(|| {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("No transform named {:?} in the configuration.", component);
+            }
+            exitcode::CONFIG
+        })?;
+
+    let definition = merged_definition(&transform.inputs, &config, &mut HashMap::new());
+
+    Ok(Schema {
+        event: definition.event_kind().clone(),
+        metadata: definition.metadata_kind().clone(),
+    })
+}
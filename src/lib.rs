@@ -60,6 +60,8 @@ pub mod http;
 pub(crate) mod kafka;
 #[allow(unreachable_pub)]
 pub mod kubernetes;
+#[cfg(feature = "vrl-cli")]
+pub(crate) mod language_server;
 pub mod line_agg;
 pub mod list;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
@@ -0,0 +1,417 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use k8s_openapi::api::core::v1::{ConfigMap, Secret};
+use kube::{
+    api::{Api, ListParams},
+    config::{self as kube_config, KubeConfigOptions},
+    runtime::{reflector, watcher},
+    Client, Config as ClientConfig, ResourceExt,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use super::Result;
+use crate::{
+    config::{
+        self,
+        provider::{ProviderConfig, ProviderDescription},
+        ConfigBuilder, Format,
+    },
+    kubernetes::custom_reflector,
+    signal,
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub(crate) struct KubernetesConfig {
+    /// The namespace to read ConfigMaps and Secrets from. Defaults to the
+    /// namespace of the client configuration, i.e. the namespace Vector runs in.
+    namespace: Option<String>,
+    /// The label selector that ConfigMaps and Secrets holding configuration
+    /// must match.
+    label_selector: String,
+    /// Whether Secrets matching `label_selector` are read as well as ConfigMaps.
+    include_secrets: bool,
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+    /// How long to wait after a change before reloading, so that changes to
+    /// several objects are applied together.
+    reload_delay_ms: u64,
+}
+
+impl Default for KubernetesConfig {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            label_selector: "vector.dev/config=true".to_owned(),
+            include_secrets: false,
+            kube_config_file: None,
+            reload_delay_ms: 1000,
+        }
+    }
+}
+
+/// A configuration file read from a key of a ConfigMap or Secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConfigEntry {
+    /// The kind and name of the object, used to identify the entry in errors.
+    object: String,
+    /// The key of the entry, whose extension determines the format.
+    key: String,
+    data: Vec<u8>,
+}
+
+fn config_map_entries(config_map: &ConfigMap) -> Vec<ConfigEntry> {
+    let object = format!("configmap/{}", config_map.name());
+    let mut data = BTreeMap::new();
+    for (key, value) in config_map.data.iter().flatten() {
+        data.insert(key.clone(), value.clone().into_bytes());
+    }
+    for (key, value) in config_map.binary_data.iter().flatten() {
+        data.insert(key.clone(), value.0.clone());
+    }
+    data.into_iter()
+        .map(|(key, data)| ConfigEntry {
+            object: object.clone(),
+            key,
+            data,
+        })
+        .collect()
+}
+
+fn secret_entries(secret: &Secret) -> Vec<ConfigEntry> {
+    let object = format!("secret/{}", secret.name());
+    secret
+        .data
+        .iter()
+        .flatten()
+        .map(|(key, value)| ConfigEntry {
+            object: object.clone(),
+            key: key.clone(),
+            data: value.0.clone(),
+        })
+        .collect()
+}
+
+/// Orders the entries by object and key, so that the same set of objects
+/// always assembles into the same configuration.
+fn collect_entries<'a>(
+    config_maps: impl IntoIterator<Item = &'a ConfigMap>,
+    secrets: impl IntoIterator<Item = &'a Secret>,
+) -> Vec<ConfigEntry> {
+    let mut entries = config_maps
+        .into_iter()
+        .flat_map(config_map_entries)
+        .chain(secrets.into_iter().flat_map(secret_entries))
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| (&a.object, &a.key).cmp(&(&b.object, &b.key)));
+    entries
+}
+
+/// Assembles a `ConfigBuilder` from the entries, the same way configuration
+/// files read from a directory are merged. Keys not ending in .toml, .json,
+/// .yaml, or .yml are ignored.
+fn build_config(entries: &[ConfigEntry]) -> Result {
+    let mut builder = ConfigBuilder::default();
+    let mut errors = Vec::new();
+
+    for entry in entries {
+        let format = match Format::from_path(&entry.key) {
+            Ok(format) => format,
+            Err(_) => {
+                debug!(
+                    message = "Ignoring entry with unrecognized format.",
+                    object = %entry.object,
+                    key = %entry.key
+                );
+                continue;
+            }
+        };
+
+        match config::load::<_, ConfigBuilder>(entry.data.as_slice(), format) {
+            Ok((loaded, warnings)) => {
+                for warning in warnings {
+                    warn!("{}: {}", entry.object, warning);
+                }
+                if let Err(append_errors) = builder.append(loaded) {
+                    errors.extend(
+                        append_errors.into_iter().map(|error| {
+                            format!("{} key {:?}: {}", entry.object, entry.key, error)
+                        }),
+                    );
+                }
+            }
+            Err(load_errors) => errors.extend(
+                load_errors
+                    .into_iter()
+                    .map(|error| format!("{} key {:?}: {}", entry.object, entry.key, error)),
+            ),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(builder)
+    } else {
+        Err(errors)
+    }
+}
+
+async fn client(kube_config_file: Option<&PathBuf>) -> std::result::Result<Client, String> {
+    // If the user passed a custom Kubeconfig use it, otherwise
+    // we attempt to load the local kubec-config, followed by the
+    // in-cluster environment variables
+    let client_config = match kube_config_file {
+        Some(kc) => {
+            let kubeconfig = kube_config::Kubeconfig::read_from(kc)
+                .map_err(|error| format!("Unable to read kubeconfig: {}", error))?;
+            ClientConfig::from_custom_kubeconfig(kubeconfig, &KubeConfigOptions::default())
+                .await
+                .map_err(|error| format!("Invalid kubeconfig: {}", error))?
+        }
+        None => ClientConfig::infer()
+            .await
+            .map_err(|error| format!("Unable to infer Kubernetes configuration: {}", error))?,
+    };
+    Client::try_from(client_config)
+        .map_err(|error| format!("Unable to create Kubernetes client: {}", error))
+}
+
+/// Watches the ConfigMaps and Secrets, returning a stream of `ConfigBuilder`
+/// whenever the assembled configuration changes.
+fn watch_config(
+    config_maps: Api<ConfigMap>,
+    secrets: Option<Api<Secret>>,
+    list_params: ListParams,
+    reload_delay: Duration,
+    mut current: Vec<ConfigEntry>,
+) -> impl Stream<Item = signal::SignalTo> {
+    // Changes are signaled on this channel as the watchers receive them.
+    let (changes_tx, mut changes_rx) = mpsc::channel::<()>(1);
+
+    let config_map_store_w = reflector::store::Writer::default();
+    let config_map_store = config_map_store_w.as_reader();
+    let tx = changes_tx.clone();
+    tokio::spawn(custom_reflector(
+        config_map_store_w,
+        watcher(config_maps, list_params.clone()).inspect(move |_| {
+            let _ = tx.try_send(());
+        }),
+        Duration::ZERO,
+    ));
+
+    let secret_store = secrets.map(|secrets| {
+        let secret_store_w = reflector::store::Writer::default();
+        let secret_store = secret_store_w.as_reader();
+        let tx = changes_tx.clone();
+        tokio::spawn(custom_reflector(
+            secret_store_w,
+            watcher(secrets, list_params).inspect(move |_| {
+                let _ = tx.try_send(());
+            }),
+            Duration::ZERO,
+        ));
+        secret_store
+    });
+    drop(changes_tx);
+
+    stream! {
+        while changes_rx.recv().await.is_some() {
+            // Give the reflectors time to apply this and any following changes.
+            tokio::time::sleep(reload_delay).await;
+            while changes_rx.try_recv().is_ok() {}
+
+            let config_maps = config_map_store.state();
+            let secrets = secret_store
+                .as_ref()
+                .map(|store| store.state())
+                .unwrap_or_default();
+            let entries = collect_entries(
+                config_maps.iter().map(AsRef::as_ref),
+                secrets.iter().map(AsRef::as_ref),
+            );
+            if entries == current {
+                continue;
+            }
+
+            info!(message = "Kubernetes configuration changed.", objects = config_maps.len() + secrets.len());
+            match build_config(&entries) {
+                Ok(config_builder) => {
+                    current = entries;
+                    yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                }
+                Err(errors) => {
+                    // Keep running the current configuration, and retry
+                    // when the objects change again.
+                    current = entries;
+                    for error in errors {
+                        error!(message = "Invalid Kubernetes configuration.", %error);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes")]
+impl ProviderConfig for KubernetesConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let client = client(self.kube_config_file.as_ref())
+            .await
+            .map_err(|error| vec![error])?;
+
+        let (config_maps, secrets): (Api<ConfigMap>, Api<Secret>) = match &self.namespace {
+            Some(namespace) => (
+                Api::namespaced(client.clone(), namespace),
+                Api::namespaced(client, namespace),
+            ),
+            None => (
+                Api::default_namespaced(client.clone()),
+                Api::default_namespaced(client),
+            ),
+        };
+        let secrets = self.include_secrets.then_some(secrets);
+        let list_params = ListParams::default().labels(&self.label_selector);
+
+        info!(
+            message = "Attempting to retrieve configuration.",
+            label_selector = %self.label_selector
+        );
+
+        let config_map_list = config_maps
+            .list(&list_params)
+            .await
+            .map_err(|error| vec![format!("Unable to list ConfigMaps: {}", error)])?;
+        let secret_list = match &secrets {
+            Some(secrets) => {
+                secrets
+                    .list(&list_params)
+                    .await
+                    .map_err(|error| vec![format!("Unable to list Secrets: {}", error)])?
+                    .items
+            }
+            None => Vec::new(),
+        };
+
+        let entries = collect_entries(&config_map_list.items, &secret_list);
+        let config_builder = build_config(&entries)?;
+
+        // Watch for changes to the configuration.
+        signal_handler.add(watch_config(
+            config_maps,
+            secrets,
+            list_params,
+            Duration::from_millis(self.reload_delay_ms),
+            entries,
+        ));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "kubernetes"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<KubernetesConfig>("kubernetes")
+}
+
+impl_generate_config_from_default!(KubernetesConfig);
+
+#[cfg(all(test, feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+mod tests {
+    use k8s_openapi::{apimachinery::pkg::apis::meta::v1::ObjectMeta, ByteString};
+
+    use super::*;
+
+    fn config_map(name: &str, data: &[(&str, &str)]) -> ConfigMap {
+        ConfigMap {
+            metadata: ObjectMeta {
+                name: Some(name.to_owned()),
+                ..ObjectMeta::default()
+            },
+            data: Some(
+                data.iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            ),
+            ..ConfigMap::default()
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesConfig>();
+    }
+
+    #[test]
+    fn assembles_config_from_objects() {
+        let sources = config_map(
+            "sources",
+            &[
+                (
+                    "sources.toml",
+                    "[sources.in]\ntype = \"demo_logs\"\nformat = \"json\"",
+                ),
+                ("README.md", "ignored"),
+            ],
+        );
+        let sinks = config_map(
+            "sinks",
+            &[(
+                "sinks.yaml",
+                "sinks:\n  out:\n    type: blackhole\n    inputs: [\"in\"]",
+            )],
+        );
+        let secret = Secret {
+            metadata: ObjectMeta {
+                name: Some("transforms".to_owned()),
+                ..ObjectMeta::default()
+            },
+            data: Some(
+                [(
+                    "transforms.json".to_owned(),
+                    ByteString(br#"{"transforms": {}}"#.to_vec()),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+            ..Secret::default()
+        };
+
+        let entries = collect_entries([&sources, &sinks], [&secret]);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| format!("{}/{}", entry.object, entry.key))
+                .collect::<Vec<_>>(),
+            vec![
+                "configmap/sinks/sinks.yaml",
+                "configmap/sources/README.md",
+                "configmap/sources/sources.toml",
+                "secret/transforms/transforms.json",
+            ]
+        );
+
+        let builder = build_config(&entries).unwrap();
+        assert!(builder.sources.contains_key(&"in".into()));
+        assert!(builder.sinks.contains_key(&"out".into()));
+    }
+
+    #[test]
+    fn reports_invalid_entries() {
+        let invalid = config_map("invalid", &[("vector.toml", "[sources.in")]);
+
+        let errors = build_config(&collect_entries([&invalid], std::iter::empty())).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("configmap/invalid key \"vector.toml\":"),
+            "{}",
+            errors[0]
+        );
+    }
+}
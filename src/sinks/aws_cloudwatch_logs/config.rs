@@ -198,6 +198,10 @@ impl SinkConfig for CloudwatchLogsSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl GenerateConfig for CloudwatchLogsSinkConfig {
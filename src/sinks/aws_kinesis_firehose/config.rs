@@ -174,6 +174,10 @@ impl SinkConfig for KinesisFirehoseSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl KinesisFirehoseSinkConfig {
@@ -81,6 +81,7 @@ async fn firehose_put_records() {
         bulk: Some(BulkConfig {
             index: Some(stream.clone()),
             action: None,
+            ..Default::default()
         }),
         aws: Some(region),
         ..Default::default()
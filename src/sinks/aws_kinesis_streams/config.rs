@@ -8,8 +8,9 @@ use tower::ServiceBuilder;
 
 use super::service::KinesisResponse;
 use crate::{
-    aws::{create_client, is_retriable_error, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
+    aws::{create_client, is_retriable_error, AwsAuthentication, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfig},
+    common::kinesis::KinesisClientBuilder,
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
         SinkContext,
@@ -43,22 +44,6 @@ enum HealthcheckError {
     NoMatchingStreamName { stream_name: String },
 }
 
-pub struct KinesisClientBuilder;
-
-impl ClientBuilder for KinesisClientBuilder {
-    type Config = aws_sdk_kinesis::config::Config;
-    type Client = aws_sdk_kinesis::client::Client;
-    type DefaultMiddleware = aws_sdk_kinesis::middleware::DefaultMiddleware;
-
-    fn default_middleware() -> Self::DefaultMiddleware {
-        aws_sdk_kinesis::middleware::DefaultMiddleware::new()
-    }
-
-    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
-        aws_sdk_kinesis::client::Client::with_config(client, config.into())
-    }
-}
-
 #[derive(Clone, Copy, Debug, Default)]
 pub struct KinesisDefaultBatchSettings;
 
@@ -184,6 +169,10 @@ impl SinkConfig for KinesisSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl GenerateConfig for KinesisSinkConfig {
@@ -9,7 +9,7 @@ use vector_core::sink::VectorSink;
 
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
         SinkContext,
@@ -109,6 +109,10 @@ impl SinkConfig for S3SinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(self.encoding.encoding())
+    }
 }
 
 impl S3SinkConfig {
@@ -100,6 +100,10 @@ impl SinkConfig for SqsSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl SqsSinkConfig {
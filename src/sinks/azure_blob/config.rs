@@ -7,7 +7,7 @@ use tower::ServiceBuilder;
 
 use super::request_builder::AzureBlobRequestOptions;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
@@ -95,6 +95,10 @@ impl SinkConfig for AzureBlobSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(self.encoding.encoding())
+    }
 }
 
 const DEFAULT_REQUEST_LIMITS: TowerRequestConfig =
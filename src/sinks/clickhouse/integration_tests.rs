@@ -0,0 +1,545 @@
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use chrono::{TimeZone, Utc};
+use futures::{
+    future::{ok, ready},
+    stream,
+};
+use serde_json::Value;
+use tokio::time::{timeout, Duration};
+use vector_core::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent};
+use warp::Filter;
+
+use super::*;
+use crate::{
+    codecs::TimestampFormat,
+    config::{log_schema, SinkConfig, SinkContext},
+    event::Value as EventValue,
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn clickhouse_address() -> String {
+    std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
+}
+
+#[tokio::test]
+async fn insert_events() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        compression: Compression::None,
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp String, message String, items Array(String)",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, mut receiver) = make_event();
+    input_event
+        .as_mut_log()
+        .insert("items", vec!["item1", "item2"]);
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::once(ready(input_event.clone())),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+
+    let expected = serde_json::to_value(input_event.into_log()).unwrap();
+    assert_eq!(expected, output.data[0]);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn skip_unknown_fields() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        skip_unknown_fields: true,
+        compression: Compression::None,
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(&table, "host String, timestamp String, message String")
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, mut receiver) = make_event();
+    input_event.as_mut_log().insert("unknown", "mysteries");
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::once(ready(input_event.clone())),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+
+    input_event.as_mut_log().remove("unknown");
+    let expected = serde_json::to_value(input_event.into_log()).unwrap();
+    assert_eq!(expected, output.data[0]);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn insert_events_unix_timestamps() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        compression: Compression::None,
+        encoding: Transformer::new(None, None, Some(TimestampFormat::Unix)).unwrap(),
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime('UTC'), message String",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, _receiver) = make_event();
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::once(ready(input_event.clone())),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+
+    let exp_event = input_event.as_mut_log();
+    exp_event.insert(
+        log_schema().timestamp_key(),
+        format!(
+            "{}",
+            exp_event
+                .get(log_schema().timestamp_key())
+                .unwrap()
+                .as_timestamp()
+                .unwrap()
+                .format("%Y-%m-%d %H:%M:%S")
+        ),
+    );
+
+    let expected = serde_json::to_value(exp_event).unwrap();
+    assert_eq!(expected, output.data[0]);
+}
+
+#[tokio::test]
+async fn insert_events_unix_timestamps_toml_config() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let config: ClickhouseConfig = toml::from_str(&format!(
+        r#"
+host = "{}"
+table = "{}"
+compression = "none"
+[request]
+retry_attempts = 1
+[batch]
+max_events = 1
+[encoding]
+timestamp_format = "unix""#,
+        host, table
+    ))
+    .unwrap();
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime('UTC'), message String",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, _receiver) = make_event();
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::once(ready(input_event.clone())),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+
+    let exp_event = input_event.as_mut_log();
+    exp_event.insert(
+        log_schema().timestamp_key(),
+        format!(
+            "{}",
+            exp_event
+                .get(log_schema().timestamp_key())
+                .unwrap()
+                .as_timestamp()
+                .unwrap()
+                .format("%Y-%m-%d %H:%M:%S")
+        ),
+    );
+
+    let expected = serde_json::to_value(exp_event).unwrap();
+    assert_eq!(expected, output.data[0]);
+}
+
+#[tokio::test]
+async fn insert_events_row_binary() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let config: ClickhouseConfig = toml::from_str(&format!(
+        r#"
+endpoint = "{}"
+table = "{}"
+format = "row_binary"
+compression = "none"
+[request]
+retry_attempts = 1"#,
+        host, table
+    ))
+    .unwrap();
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host LowCardinality(String), timestamp DateTime64(3, 'UTC'), message String,
+             count UInt8, labels Map(String, String), items Array(String),
+             spans Nested(name String, duration UInt32),
+             length UInt64 MATERIALIZED length(message)",
+        )
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (mut input_event, mut receiver) = make_event();
+    let log = input_event.as_mut_log();
+    log.insert(
+        log_schema().timestamp_key(),
+        Utc.ymd(2022, 8, 1).and_hms_milli(12, 30, 15, 250),
+    );
+    log.insert("count", "42");
+    log.insert("labels.env", "prod");
+    log.insert("items", vec!["item1", "item2"]);
+    log.insert(
+        "spans",
+        vec![
+            EventValue::from(BTreeMap::from([
+                ("name".to_string(), EventValue::from("db")),
+                ("duration".to_string(), EventValue::from(12)),
+            ])),
+            EventValue::from(BTreeMap::from([(
+                "name".to_string(),
+                EventValue::from("http"),
+            )])),
+        ],
+    );
+    // Out of the range of the `count` column, so this row is discarded.
+    let (mut invalid_event, _) = make_event();
+    invalid_event.as_mut_log().insert("count", 300);
+
+    run_and_assert_sink_compliance(
+        sink,
+        stream::iter(vec![input_event, invalid_event]),
+        &HTTP_SINK_TAGS,
+    )
+    .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+    assert_eq!(
+        serde_json::json!({
+            "host": "example.com",
+            "timestamp": "2022-08-01 12:30:15.250",
+            "message": "raw log line",
+            "count": 42,
+            "labels": {"env": "prod"},
+            "items": ["item1", "item2"],
+            "spans.name": ["db", "http"],
+            "spans.duration": [12, 0],
+        }),
+        output.data[0]
+    );
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn row_binary_unsupported_column() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        format: Format::RowBinary,
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime, amount Decimal(9, 2)",
+        )
+        .await;
+
+    let (sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let error = healthcheck.await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Column \"amount\" has an unsupported type \"Decimal(9, 2)\""
+    );
+
+    let (event, _receiver) = make_event();
+    assert!(sink.run_events(vec![event]).await.is_err());
+    assert_eq!(0, client.select_all(&table).await.rows);
+}
+
+#[tokio::test]
+async fn no_retry_on_incorrect_data() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: table.clone(),
+        compression: Compression::None,
+        batch,
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    // the event contains a message field, but its being omitted to
+    // fail the request.
+    client
+        .create_table(&table, "host String, timestamp String")
+        .await;
+
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (input_event, mut receiver) = make_event();
+
+    // Retries should go on forever, so if we are retrying incorrectly
+    // this timeout should trigger.
+    timeout(Duration::from_secs(5), sink.run_events(vec![input_event]))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+#[tokio::test]
+async fn no_retry_on_incorrect_data_warp() {
+    trace_init();
+
+    let visited = Arc::new(AtomicBool::new(false));
+    let routes = warp::any().and_then(move || {
+        assert!(!visited.load(Ordering::SeqCst), "Should not retry request.");
+        visited.store(true, Ordering::SeqCst);
+
+        ok::<_, Infallible>(warp::reply::with_status(
+            "Code: 117",
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))
+    });
+    let server = warp::serve(routes).bind("0.0.0.0:8124".parse::<SocketAddr>().unwrap());
+    tokio::spawn(server);
+
+    let host = String::from("http://localhost:8124");
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: host.parse().unwrap(),
+        table: gen_table(),
+        batch,
+        ..Default::default()
+    };
+    let (sink, _hc) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (input_event, mut receiver) = make_event();
+
+    // Retries should go on forever, so if we are retrying incorrectly
+    // this timeout should trigger.
+    timeout(Duration::from_secs(5), sink.run_events(vec![input_event]))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
+}
+
+fn make_event() -> (Event, BatchStatusReceiver) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let mut event = LogEvent::from("raw log line").with_batch_notifier(&batch);
+    event.insert("host", "example.com");
+    (event.into(), receiver)
+}
+
+struct ClickhouseClient {
+    host: String,
+    client: reqwest::Client,
+}
+
+impl ClickhouseClient {
+    fn new(host: String) -> Self {
+        ClickhouseClient {
+            host,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn create_table(&self, table: &str, schema: &str) {
+        let response = self
+            .client
+            .post(&self.host)
+            //
+            .body(format!(
+                "CREATE TABLE {}
+                 ({})
+                 ENGINE = MergeTree()
+                 ORDER BY (host, timestamp);",
+                table, schema
+            ))
+            .send()
+            .await
+            .unwrap();
+
+        if !response.status().is_success() {
+            panic!("create table failed: {}", response.text().await.unwrap())
+        }
+    }
+
+    async fn select_all(&self, table: &str) -> QueryResponse {
+        let response = self
+            .client
+            .post(&self.host)
+            .body(format!("SELECT * FROM {} FORMAT JSON", table))
+            .send()
+            .await
+            .unwrap();
+
+        if !response.status().is_success() {
+            panic!("select all failed: {}", response.text().await.unwrap())
+        } else {
+            let text = response.text().await.unwrap();
+            match serde_json::from_str(&text) {
+                Ok(value) => value,
+                Err(_) => panic!("json failed: {:?}", text),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)] // deserialize all fields
+struct QueryResponse {
+    data: Vec<Value>,
+    meta: Vec<Value>,
+    rows: usize,
+    statistics: Stats,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)] // deserialize all fields
+struct Stats {
+    bytes_read: usize,
+    elapsed: f64,
+    rows_read: usize,
+}
+
+fn gen_table() -> String {
+    format!("test_{}", random_string(10).to_lowercase())
+}
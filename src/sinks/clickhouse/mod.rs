@@ -0,0 +1,539 @@
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{stream::BoxStream, FutureExt, SinkExt, StreamExt};
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::time::sleep;
+
+use self::row_binary::{Column, ColumnError, DescribedColumn};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
+    http::{Auth, HttpClient, HttpError, MaybeAuth},
+    internal_events::{ClickhouseCoercionError, ClickhouseDescribeTableError},
+    sinks::util::{
+        http::{BatchedHttpSink, HttpEventEncoder, HttpRetryLogic, HttpSink},
+        retries::{ExponentialBackoff, RetryAction, RetryLogic},
+        BatchConfig, BatchSettings, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
+        StreamSink, TowerRequestConfig, TowerRequestSettings, UriSerde,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[cfg(test)]
+#[cfg(feature = "clickhouse-integration-tests")]
+mod integration_tests;
+mod row_binary;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClickhouseConfig {
+    // Deprecated name
+    #[serde(alias = "host")]
+    pub endpoint: UriSerde,
+    pub table: String,
+    pub database: Option<String>,
+    #[serde(default)]
+    pub format: Format,
+    #[serde(default)]
+    pub skip_unknown_fields: bool,
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    pub tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Derivative, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Format {
+    /// Inserts rows as JSON objects, letting ClickHouse convert their fields.
+    #[derivative(Default)]
+    JsonEachRow,
+    /// Inserts rows in the native binary format, coercing fields to the types of the columns of the table.
+    RowBinary,
+}
+
+inventory::submit! {
+    SinkDescription::new::<ClickhouseConfig>("clickhouse")
+}
+
+impl_generate_config_from_default!(ClickhouseConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "clickhouse")]
+impl SinkConfig for ClickhouseConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let batch = self.batch.into_batch_settings()?;
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let config = ClickhouseConfig {
+            auth: self.auth.choose_one(&self.endpoint.auth)?,
+            ..self.clone()
+        };
+
+        let sink = match config.format {
+            Format::JsonEachRow => super::VectorSink::from_event_sink(batched_sink(
+                config.clone(),
+                None,
+                batch,
+                request,
+                client.clone(),
+            )),
+            // The columns are only known once the table is described, which is deferred to the
+            // sink so that an unavailable server doesn't prevent Vector from starting.
+            Format::RowBinary => super::VectorSink::from_event_streamsink(RowBinarySink {
+                config: config.clone(),
+                client: client.clone(),
+                batch,
+                request,
+            }),
+        };
+
+        let healthcheck = healthcheck(client, config).boxed();
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "clickhouse"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl ClickhouseConfig {
+    fn database(&self) -> &str {
+        if let Some(database) = &self.database {
+            database.as_str()
+        } else {
+            "default"
+        }
+    }
+}
+
+fn batched_sink(
+    config: ClickhouseConfig,
+    columns: Option<Arc<[Column]>>,
+    batch: BatchSettings<Buffer>,
+    request: TowerRequestSettings,
+    client: HttpClient,
+) -> impl futures::Sink<Event, Error = ()> + Send + Unpin {
+    let compression = config.compression;
+    BatchedHttpSink::with_logic(
+        ClickhouseSink { config, columns },
+        Buffer::new(batch.size, compression),
+        ClickhouseRetryLogic::default(),
+        request,
+        batch.timeout,
+        client,
+    )
+    .sink_map_err(|error| error!(message = "Fatal clickhouse sink error.", %error))
+}
+
+/// Inserts rows encoded as `RowBinary`, once the columns of the table are described.
+struct RowBinarySink {
+    config: ClickhouseConfig,
+    client: HttpClient,
+    batch: BatchSettings<Buffer>,
+    request: TowerRequestSettings,
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for RowBinarySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.peekable();
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60));
+
+        let columns = loop {
+            match describe_table(&self.client, &self.config).await {
+                Ok(columns) => break columns,
+                Err(error) => {
+                    let retriable = error.is_retriable();
+                    emit!(ClickhouseDescribeTableError {
+                        error: &error,
+                        retriable,
+                    });
+                    if !retriable {
+                        return Err(());
+                    }
+                }
+            }
+
+            // Stops retrying once the input is exhausted, as there is nothing left to insert.
+            let delay = backoff.next().expect("backoff should never end");
+            tokio::select! {
+                _ = sleep(delay) => {}
+                None = Pin::new(&mut input).peek() => return Ok(()),
+            }
+        };
+
+        let this = *self;
+        let sink = batched_sink(
+            this.config,
+            Some(columns.into()),
+            this.batch,
+            this.request,
+            this.client,
+        );
+        input.map(Ok).forward(sink).await
+    }
+}
+
+struct ClickhouseSink {
+    config: ClickhouseConfig,
+    /// The columns rows are inserted in, when they are encoded as `RowBinary`.
+    columns: Option<Arc<[Column]>>,
+}
+
+pub struct ClickhouseEventEncoder {
+    transformer: Transformer,
+    columns: Option<Arc<[Column]>>,
+}
+
+impl HttpEventEncoder<BytesMut> for ClickhouseEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+        self.transformer.transform(&mut event);
+        let log = event.into_log();
+
+        match &self.columns {
+            None => {
+                let mut body =
+                    crate::serde::json::to_bytes(&log).expect("Events should be valid json!");
+                body.put_u8(b'\n');
+
+                Some(body)
+            }
+            Some(columns) => {
+                let mut body = BytesMut::new();
+                match row_binary::encode_row(columns, &log, &mut body) {
+                    Ok(()) => Some(body),
+                    Err(error) => {
+                        emit!(ClickhouseCoercionError {
+                            column: error.column(),
+                            error: &error,
+                        });
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for ClickhouseSink {
+    type Input = BytesMut;
+    type Output = BytesMut;
+    type Encoder = ClickhouseEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        ClickhouseEventEncoder {
+            transformer: self.config.encoding.clone(),
+            columns: self.columns.clone(),
+        }
+    }
+
+    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let uri = set_uri_query(
+            &self.config.endpoint.uri,
+            self.config.database(),
+            &self.config.table,
+            self.config.skip_unknown_fields,
+            self.columns.as_deref(),
+        )
+        .expect("Unable to encode uri");
+
+        let content_type = if self.columns.is_some() {
+            "application/octet-stream"
+        } else {
+            "application/x-ndjson"
+        };
+        let mut builder = Request::post(&uri).header("Content-Type", content_type);
+
+        if let Some(ce) = self.config.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+
+        let mut request = builder.body(events.freeze()).unwrap();
+
+        if let Some(auth) = &self.config.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Result<()> {
+    if config.format == Format::RowBinary {
+        // Also reports columns that can't be encoded, which would stop the sink.
+        describe_table(&client, &config).await?;
+        return Ok(());
+    }
+
+    // TODO: check if table exists?
+    let uri = format!("{}/?query=SELECT%201", config.endpoint);
+    let mut request = Request::get(uri).body(Body::empty()).unwrap();
+
+    if let Some(auth) = &config.auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(super::HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum DescribeTableError {
+    #[snafu(display("Describing the table failed: {}", source))]
+    DescribeRequest { source: HttpError },
+    #[snafu(display("Failed to read the table description: {}", source))]
+    ReadDescription { source: hyper::Error },
+    #[snafu(display("Describing the table failed with status {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid table description: {}", source))]
+    InvalidDescription { source: serde_json::Error },
+    #[snafu(display("{}", source))]
+    UnsupportedColumn { source: ColumnError },
+}
+
+impl DescribeTableError {
+    /// Whether describing the table again may succeed, as opposed to errors in its schema.
+    fn is_retriable(&self) -> bool {
+        match self {
+            Self::DescribeRequest { .. } | Self::ReadDescription { .. } => true,
+            Self::UnexpectedStatus { status, .. } => status.is_server_error(),
+            Self::InvalidDescription { .. } | Self::UnsupportedColumn { .. } => false,
+        }
+    }
+}
+
+/// Fetches the columns of the table, to encode rows as `RowBinary`.
+async fn describe_table(
+    client: &HttpClient,
+    config: &ClickhouseConfig,
+) -> Result<Vec<Column>, DescribeTableError> {
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair(
+            "query",
+            format!(
+                "DESCRIBE TABLE \"{}\".\"{}\" FORMAT JSONEachRow",
+                config.database(),
+                config.table.replace('\"', "\\\"")
+            )
+            .as_str(),
+        )
+        .finish();
+    let uri = format!("{}/?{}", config.endpoint, query);
+    let mut request = Request::get(uri).body(Body::empty()).unwrap();
+
+    if let Some(auth) = &config.auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await.context(DescribeRequestSnafu)?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(ReadDescriptionSnafu)?;
+    if status != StatusCode::OK {
+        return Err(DescribeTableError::UnexpectedStatus {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        });
+    }
+
+    let described = serde_json::Deserializer::from_slice(&body)
+        .into_iter::<DescribedColumn>()
+        .collect::<Result<Vec<_>, _>>()
+        .context(InvalidDescriptionSnafu)?;
+    Column::from_described(described).context(UnsupportedColumnSnafu)
+}
+
+/// Builds the URI rows are inserted at. Rows are encoded as `RowBinary` when the columns they
+/// are inserted in are given, and as `JSONEachRow` otherwise.
+fn set_uri_query(
+    uri: &Uri,
+    database: &str,
+    table: &str,
+    skip_unknown: bool,
+    columns: Option<&[Column]>,
+) -> crate::Result<Uri> {
+    let table = format!("\"{}\".\"{}\"", database, table.replace('\"', "\\\""));
+    let insert = match columns {
+        None => format!("INSERT INTO {} FORMAT JSONEachRow", table),
+        Some(columns) => format!(
+            "INSERT INTO {} ({}) FORMAT RowBinary",
+            table,
+            columns
+                .iter()
+                .map(|column| format!("\"{}\"", column.name.replace('\"', "\\\"")))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("query", &insert)
+        .finish();
+
+    let mut uri = uri.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push('?');
+    if columns.is_none() {
+        uri.push_str("input_format_import_nested_json=1&");
+        if skip_unknown {
+            uri.push_str("input_format_skip_unknown_fields=1&");
+        }
+    }
+    uri.push_str(query.as_str());
+
+    uri.parse::<Uri>()
+        .context(super::UriParseSnafu)
+        .map_err(Into::into)
+}
+
+#[derive(Debug, Default, Clone)]
+struct ClickhouseRetryLogic {
+    inner: HttpRetryLogic,
+}
+
+impl RetryLogic for ClickhouseRetryLogic {
+    type Error = HttpError;
+    type Response = http::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        self.inner.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        match response.status() {
+            StatusCode::INTERNAL_SERVER_ERROR => {
+                let body = response.body();
+
+                // Currently, clickhouse returns 500's incorrect data and type mismatch errors.
+                // This attempts to check if the body starts with `Code: {code_num}` and to not
+                // retry those errors.
+                //
+                // Reference: https://github.com/vectordotdev/vector/pull/693#issuecomment-517332654
+                // Error code definitions: https://github.com/ClickHouse/ClickHouse/blob/master/dbms/src/Common/ErrorCodes.cpp
+                //
+                // Fix already merged: https://github.com/ClickHouse/ClickHouse/pull/6271
+                if body.starts_with(b"Code: 117") {
+                    RetryAction::DontRetry("incorrect data".into())
+                } else if body.starts_with(b"Code: 53") {
+                    RetryAction::DontRetry("type mismatch".into())
+                } else {
+                    RetryAction::Retry(String::from_utf8_lossy(body).to_string().into())
+                }
+            }
+            _ => self.inner.should_retry_response(response),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ClickhouseConfig>();
+    }
+
+    #[test]
+    fn encode_valid() {
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_\"table\"",
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
+    }
+
+    #[test]
+    fn encode_row_binary() {
+        let described = serde_json::Deserializer::from_str(
+            r#"{"name": "timestamp", "type": "DateTime64(3)", "default_type": ""}
+            {"name": "message", "type": "String", "default_type": ""}
+            {"name": "length", "type": "UInt64", "default_type": "MATERIALIZED"}"#,
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        let columns = Column::from_described(described).unwrap();
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            true,
+            Some(&columns),
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?query=INSERT+INTO+%22my_database%22.%22my_table%22+%28%22timestamp%22%2C+%22message%22%29+FORMAT+RowBinary");
+    }
+
+    #[test]
+    fn encode_invalid() {
+        set_uri_query(
+            &"localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            false,
+            None,
+        )
+        .unwrap_err();
+    }
+}
@@ -0,0 +1,869 @@
+//! Encoding of log events in the `RowBinary` format, driven by the columns of the target table.
+//!
+//! Reference: https://clickhouse.com/docs/en/interfaces/formats/#rowbinary
+use std::{
+    borrow::Cow,
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
+};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde::Deserialize;
+use snafu::Snafu;
+use uuid::Uuid;
+
+use crate::event::{LogEvent, Value};
+
+/// A column of the table, as described by `DESCRIBE TABLE`.
+#[derive(Deserialize)]
+pub(super) struct DescribedColumn {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    default_type: String,
+}
+
+/// A column events are inserted in.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Column {
+    pub(super) name: String,
+    ty: ColumnType,
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum ColumnError {
+    #[snafu(display("Column {:?} has an unsupported type {:?}", column, ty))]
+    UnsupportedType { column: String, ty: String },
+}
+
+impl Column {
+    /// Keeps the columns values are inserted in. Materialized and alias columns are computed by ClickHouse.
+    pub(super) fn from_described(
+        described: Vec<DescribedColumn>,
+    ) -> Result<Vec<Column>, ColumnError> {
+        described
+            .into_iter()
+            .filter(|column| !matches!(column.default_type.as_str(), "MATERIALIZED" | "ALIAS"))
+            .map(|column| match column.ty.parse() {
+                Ok(ty) => Ok(Column {
+                    name: column.name,
+                    ty,
+                }),
+                Err(()) => Err(ColumnError::UnsupportedType {
+                    column: column.name,
+                    ty: column.ty,
+                }),
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ColumnType {
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Bool,
+    String,
+    FixedString(usize),
+    Uuid,
+    Date,
+    Date32,
+    DateTime,
+    DateTime64(u32),
+    Enum8(Vec<(String, i8)>),
+    Enum16(Vec<(String, i16)>),
+    IPv4,
+    IPv6,
+    Nullable(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+    /// The elements of a tuple, named or not. `Nested` columns are arrays of named tuples.
+    Tuple(Vec<(Option<String>, ColumnType)>),
+}
+
+impl FromStr for ColumnType {
+    type Err = ();
+
+    fn from_str(ty: &str) -> Result<Self, Self::Err> {
+        let ty = ty.trim();
+        let (name, args) = match ty.find('(') {
+            Some(open) if ty.ends_with(')') => {
+                (&ty[..open], Some(split_args(&ty[open + 1..ty.len() - 1])))
+            }
+            Some(_) => return Err(()),
+            None => (ty, None),
+        };
+
+        Ok(match (name, args.as_deref()) {
+            ("UInt8", None) => Self::UInt8,
+            ("UInt16", None) => Self::UInt16,
+            ("UInt32", None) => Self::UInt32,
+            ("UInt64", None) => Self::UInt64,
+            ("Int8", None) => Self::Int8,
+            ("Int16", None) => Self::Int16,
+            ("Int32", None) => Self::Int32,
+            ("Int64", None) => Self::Int64,
+            ("Float32", None) => Self::Float32,
+            ("Float64", None) => Self::Float64,
+            ("Bool", None) => Self::Bool,
+            ("String", None) => Self::String,
+            ("FixedString", Some([size])) => Self::FixedString(size.parse().map_err(|_| ())?),
+            ("UUID", None) => Self::Uuid,
+            ("Date", None) => Self::Date,
+            ("Date32", None) => Self::Date32,
+            // The time zone of a column only applies to the textual representation of its values.
+            ("DateTime", None | Some([_])) => Self::DateTime,
+            ("DateTime64", Some([precision] | [precision, _])) => match precision.parse() {
+                Ok(precision) if precision <= 9 => Self::DateTime64(precision),
+                _ => return Err(()),
+            },
+            ("Enum8", Some(variants)) => Self::Enum8(parse_enum(variants)?),
+            ("Enum16", Some(variants)) => Self::Enum16(parse_enum(variants)?),
+            ("IPv4", None) => Self::IPv4,
+            ("IPv6", None) => Self::IPv6,
+            ("Nullable", Some([ty])) => Self::Nullable(Box::new(ty.parse()?)),
+            // Low cardinality values are encoded like the values of their inner type.
+            ("LowCardinality", Some([ty])) => ty.parse()?,
+            ("Array", Some([ty])) => Self::Array(Box::new(ty.parse()?)),
+            ("Map", Some([key, value])) => {
+                Self::Map(Box::new(key.parse()?), Box::new(value.parse()?))
+            }
+            ("Tuple", Some(elements)) => Self::Tuple(
+                elements
+                    .iter()
+                    .map(|element| parse_tuple_element(element))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ("Nested", Some(elements)) => Self::Array(Box::new(Self::Tuple(
+                elements
+                    .iter()
+                    .map(|element| match parse_tuple_element(element)? {
+                        (Some(name), ty) => Ok((Some(name), ty)),
+                        (None, _) => Err(()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ))),
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Splits the arguments of a type at the commas that aren't nested in parentheses or quotes.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                split.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(args[start..].trim());
+    split
+}
+
+/// Parses an element of a tuple, either a type or a name followed by a type.
+fn parse_tuple_element(element: &str) -> Result<(Option<String>, ColumnType), ()> {
+    let head = element.split('(').next().unwrap_or_default();
+    match head.trim().split_once(char::is_whitespace) {
+        Some((name, _)) => {
+            let ty = element[name.len()..].parse()?;
+            Ok((Some(name.trim_matches('`').to_string()), ty))
+        }
+        None => Ok((None, element.parse()?)),
+    }
+}
+
+/// Parses the `'name' = value` variants of an enum.
+fn parse_enum<T: FromStr>(variants: &[&str]) -> Result<Vec<(String, T)>, ()> {
+    variants
+        .iter()
+        .map(|variant| {
+            let (name, value) = variant.rsplit_once('=').ok_or(())?;
+            let name = name
+                .trim()
+                .strip_prefix('\'')
+                .and_then(|name| name.strip_suffix('\''))
+                .ok_or(())?
+                .replace("\\'", "'");
+            Ok((name, value.trim().parse().map_err(|_| ())?))
+        })
+        .collect()
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum CoercionError {
+    #[snafu(display("Column {:?}: {} value can't be coerced to {}", column, kind, expected))]
+    Mismatch {
+        column: String,
+        kind: &'static str,
+        expected: &'static str,
+    },
+    #[snafu(display("Column {:?}: value is out of the range of {}", column, expected))]
+    OutOfRange {
+        column: String,
+        expected: &'static str,
+    },
+}
+
+impl CoercionError {
+    pub(super) fn column(&self) -> &str {
+        match self {
+            Self::Mismatch { column, .. } | Self::OutOfRange { column, .. } => column,
+        }
+    }
+}
+
+/// The reason a value couldn't be coerced, before it's attributed to a column.
+enum Invalid {
+    Mismatch(&'static str, &'static str),
+    OutOfRange(&'static str),
+}
+
+/// Appends a row holding the fields of an event to `buf`. On error, `buf` is left untouched.
+///
+/// Fields are coerced to the types of the columns they are inserted in. Missing and null fields are inserted as nulls
+/// in nullable columns, and as the default value of the type of other columns.
+pub(super) fn encode_row(
+    columns: &[Column],
+    log: &LogEvent,
+    buf: &mut BytesMut,
+) -> Result<(), CoercionError> {
+    let mut row = BytesMut::new();
+    for column in columns {
+        let value = column_value(log, &column.name);
+        encode_value(&column.ty, value.as_deref(), &mut row).map_err(|invalid| match invalid {
+            Invalid::Mismatch(kind, expected) => CoercionError::Mismatch {
+                column: column.name.clone(),
+                kind,
+                expected,
+            },
+            Invalid::OutOfRange(expected) => CoercionError::OutOfRange {
+                column: column.name.clone(),
+                expected,
+            },
+        })?;
+    }
+    buf.extend_from_slice(&row);
+    Ok(())
+}
+
+/// Looks up the value of a column. The subcolumns of a flattened `Nested` column, like `items.name`, are also read
+/// from the fields of an array of objects, like `items`.
+fn column_value<'a>(log: &'a LogEvent, name: &str) -> Option<Cow<'a, Value>> {
+    if let Some(value) = log.get(name) {
+        return Some(Cow::Borrowed(value));
+    }
+    let (parent, field) = name.rsplit_once('.')?;
+    match log.get(parent)? {
+        Value::Array(items) => Some(Cow::Owned(Value::Array(
+            items
+                .iter()
+                .map(|item| {
+                    item.as_object()
+                        .and_then(|item| item.get(field))
+                        .cloned()
+                        .unwrap_or(Value::Null)
+                })
+                .collect(),
+        ))),
+        _ => None,
+    }
+}
+
+fn encode_value(ty: &ColumnType, value: Option<&Value>, buf: &mut BytesMut) -> Result<(), Invalid> {
+    let value = match value {
+        None | Some(Value::Null) => {
+            encode_default(ty, buf);
+            return Ok(());
+        }
+        Some(value) => value,
+    };
+
+    match ty {
+        ColumnType::UInt8 => buf.put_u8(integer(value, "UInt8")?),
+        ColumnType::UInt16 => buf.put_u16_le(integer(value, "UInt16")?),
+        ColumnType::UInt32 => buf.put_u32_le(integer(value, "UInt32")?),
+        ColumnType::UInt64 => buf.put_u64_le(integer(value, "UInt64")?),
+        ColumnType::Int8 => buf.put_i8(integer(value, "Int8")?),
+        ColumnType::Int16 => buf.put_i16_le(integer(value, "Int16")?),
+        ColumnType::Int32 => buf.put_i32_le(integer(value, "Int32")?),
+        ColumnType::Int64 => buf.put_i64_le(integer(value, "Int64")?),
+        ColumnType::Float32 => buf.put_f32_le(float(value, "Float32")? as f32),
+        ColumnType::Float64 => buf.put_f64_le(float(value, "Float64")?),
+        ColumnType::Bool => buf.put_u8(boolean(value)?.into()),
+        ColumnType::String => {
+            let string = string(value);
+            put_varint(buf, string.len() as u64);
+            buf.put_slice(&string);
+        }
+        ColumnType::FixedString(size) => {
+            let string = string(value);
+            if string.len() > *size {
+                return Err(Invalid::OutOfRange("FixedString"));
+            }
+            buf.put_slice(&string);
+            buf.put_bytes(0, size - string.len());
+        }
+        ColumnType::Uuid => {
+            let uuid = match value {
+                Value::Bytes(bytes) => std::str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|uuid| Uuid::parse_str(uuid.trim()).ok())
+                    .ok_or(Invalid::Mismatch("string", "UUID"))?,
+                value => return Err(Invalid::Mismatch(kind(value), "UUID")),
+            };
+            // UUIDs are encoded as two little endian halves.
+            let uuid = uuid.as_u128();
+            buf.put_u64_le((uuid >> 64) as u64);
+            buf.put_u64_le(uuid as u64);
+        }
+        ColumnType::Date => {
+            let days = days_since_epoch(timestamp(value, "Date")?);
+            buf.put_u16_le(u16::try_from(days).map_err(|_| Invalid::OutOfRange("Date"))?);
+        }
+        ColumnType::Date32 => {
+            let days = days_since_epoch(timestamp(value, "Date32")?);
+            buf.put_i32_le(i32::try_from(days).map_err(|_| Invalid::OutOfRange("Date32"))?);
+        }
+        ColumnType::DateTime => {
+            let seconds = timestamp(value, "DateTime")?.timestamp();
+            buf.put_u32_le(u32::try_from(seconds).map_err(|_| Invalid::OutOfRange("DateTime"))?);
+        }
+        ColumnType::DateTime64(precision) => {
+            let timestamp = timestamp(value, "DateTime64")?;
+            let ticks = i128::from(timestamp.timestamp()) * 10i128.pow(*precision)
+                + i128::from(timestamp.timestamp_subsec_nanos()) / 10i128.pow(9 - precision);
+            buf.put_i64_le(i64::try_from(ticks).map_err(|_| Invalid::OutOfRange("DateTime64"))?);
+        }
+        ColumnType::Enum8(variants) => buf.put_i8(enum_value(value, variants, "Enum8")?),
+        ColumnType::Enum16(variants) => buf.put_i16_le(enum_value(value, variants, "Enum16")?),
+        ColumnType::IPv4 => match ip_address(value, "IPv4")? {
+            IpAddr::V4(address) => buf.put_u32_le(address.into()),
+            IpAddr::V6(address) => match address.octets() {
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => {
+                    buf.put_u32_le(Ipv4Addr::new(a, b, c, d).into())
+                }
+                _ => return Err(Invalid::OutOfRange("IPv4")),
+            },
+        },
+        ColumnType::IPv6 => match ip_address(value, "IPv6")? {
+            IpAddr::V4(address) => buf.put_slice(&address.to_ipv6_mapped().octets()),
+            IpAddr::V6(address) => buf.put_slice(&address.octets()),
+        },
+        ColumnType::Nullable(ty) => {
+            buf.put_u8(0);
+            encode_value(ty, Some(value), buf)?;
+        }
+        ColumnType::Array(ty) => match value {
+            Value::Array(items) => {
+                put_varint(buf, items.len() as u64);
+                for item in items {
+                    encode_value(ty, Some(item), buf)?;
+                }
+            }
+            value => return Err(Invalid::Mismatch(kind(value), "Array")),
+        },
+        ColumnType::Map(key_ty, value_ty) => match value {
+            Value::Object(map) => {
+                put_varint(buf, map.len() as u64);
+                for (key, value) in map {
+                    encode_value(key_ty, Some(&Value::from(key.as_str())), buf)?;
+                    encode_value(value_ty, Some(value), buf)?;
+                }
+            }
+            value => return Err(Invalid::Mismatch(kind(value), "Map")),
+        },
+        ColumnType::Tuple(elements) => match value {
+            Value::Object(map) if elements.iter().all(|(name, _)| name.is_some()) => {
+                for (name, ty) in elements {
+                    encode_value(ty, name.as_ref().and_then(|name| map.get(name)), buf)?;
+                }
+            }
+            Value::Array(items) if items.len() == elements.len() => {
+                for ((_, ty), item) in elements.iter().zip(items) {
+                    encode_value(ty, Some(item), buf)?;
+                }
+            }
+            value => return Err(Invalid::Mismatch(kind(value), "Tuple")),
+        },
+    }
+    Ok(())
+}
+
+/// Encodes the value ClickHouse inserts when a field is missing.
+fn encode_default(ty: &ColumnType, buf: &mut BytesMut) {
+    match ty {
+        ColumnType::UInt8 | ColumnType::Int8 | ColumnType::Bool => buf.put_u8(0),
+        ColumnType::UInt16 | ColumnType::Int16 | ColumnType::Date => buf.put_u16_le(0),
+        ColumnType::UInt32
+        | ColumnType::Int32
+        | ColumnType::Float32
+        | ColumnType::Date32
+        | ColumnType::DateTime
+        | ColumnType::IPv4 => buf.put_u32_le(0),
+        ColumnType::UInt64
+        | ColumnType::Int64
+        | ColumnType::Float64
+        | ColumnType::DateTime64(_) => buf.put_u64_le(0),
+        ColumnType::FixedString(size) => buf.put_bytes(0, *size),
+        ColumnType::Uuid | ColumnType::IPv6 => buf.put_bytes(0, 16),
+        // The default value of an enum is its first variant.
+        ColumnType::Enum8(variants) => buf.put_i8(variants.first().map_or(0, |(_, value)| *value)),
+        ColumnType::Enum16(variants) => {
+            buf.put_i16_le(variants.first().map_or(0, |(_, value)| *value))
+        }
+        ColumnType::Nullable(_) => buf.put_u8(1),
+        ColumnType::String | ColumnType::Array(_) | ColumnType::Map(_, _) => put_varint(buf, 0),
+        ColumnType::Tuple(elements) => {
+            for (_, ty) in elements {
+                encode_default(ty, buf);
+            }
+        }
+    }
+}
+
+/// Encodes an unsigned integer as LEB128, as the lengths of strings, arrays and maps are.
+fn put_varint(buf: &mut BytesMut, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.put_u8(byte);
+            return;
+        }
+        buf.put_u8(byte | 0x80);
+    }
+}
+
+fn integer<T: TryFrom<i128>>(value: &Value, expected: &'static str) -> Result<T, Invalid> {
+    let integer = match value {
+        Value::Integer(integer) => i128::from(*integer),
+        Value::Float(float) if float.fract() == 0.0 && float.abs() < 2f64.powi(64) => {
+            float.into_inner() as i128
+        }
+        Value::Boolean(boolean) => i128::from(*boolean),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|integer| integer.trim().parse().ok())
+            .ok_or(Invalid::Mismatch("string", expected))?,
+        value => return Err(Invalid::Mismatch(kind(value), expected)),
+    };
+    T::try_from(integer).map_err(|_| Invalid::OutOfRange(expected))
+}
+
+fn float(value: &Value, expected: &'static str) -> Result<f64, Invalid> {
+    match value {
+        Value::Float(float) => Ok(float.into_inner()),
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|float| float.trim().parse().ok())
+            .ok_or(Invalid::Mismatch("string", expected)),
+        value => Err(Invalid::Mismatch(kind(value), expected)),
+    }
+}
+
+fn boolean(value: &Value) -> Result<bool, Invalid> {
+    match value {
+        Value::Boolean(boolean) => Ok(*boolean),
+        Value::Integer(0) => Ok(false),
+        Value::Integer(1) => Ok(true),
+        Value::Bytes(bytes) => match bytes.as_ref() {
+            b"true" | b"1" => Ok(true),
+            b"false" | b"0" => Ok(false),
+            _ => Err(Invalid::Mismatch("string", "Bool")),
+        },
+        value => Err(Invalid::Mismatch(kind(value), "Bool")),
+    }
+}
+
+/// Strings hold the raw bytes of bytes values, and the JSON representation of objects and arrays.
+fn string(value: &Value) -> Cow<'_, [u8]> {
+    match value {
+        Value::Bytes(bytes) => Cow::Borrowed(bytes.as_ref()),
+        Value::Timestamp(timestamp) => Cow::Owned(
+            timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into_bytes(),
+        ),
+        Value::Object(_) | Value::Array(_) => {
+            Cow::Owned(serde_json::to_vec(value).expect("Values should be valid json!"))
+        }
+        value => Cow::Owned(value.to_string_lossy().into_bytes()),
+    }
+}
+
+/// Timestamps can also be coerced from Unix timestamps in seconds, and from RFC 3339 or `YYYY-MM-DD hh:mm:ss` dates
+/// in UTC.
+fn timestamp(value: &Value, expected: &'static str) -> Result<DateTime<Utc>, Invalid> {
+    match value {
+        Value::Timestamp(timestamp) => Ok(*timestamp),
+        Value::Integer(seconds) => Utc
+            .timestamp_opt(*seconds, 0)
+            .single()
+            .ok_or(Invalid::OutOfRange(expected)),
+        Value::Float(seconds) => {
+            // The fraction is taken from the floor so that it stays positive before the epoch,
+            // and a fraction rounding up to a whole second carries over.
+            let seconds = seconds.into_inner();
+            let mut whole = seconds.floor();
+            let mut nanos = ((seconds - whole) * 1e9).round();
+            if nanos >= 1e9 {
+                whole += 1.0;
+                nanos = 0.0;
+            }
+            Utc.timestamp_opt(whole as i64, nanos as u32)
+                .single()
+                .ok_or(Invalid::OutOfRange(expected))
+        }
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|timestamp| {
+                let timestamp = timestamp.trim();
+                DateTime::parse_from_rfc3339(timestamp)
+                    .map(|timestamp| timestamp.with_timezone(&Utc))
+                    .or_else(|_| {
+                        NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f")
+                            .map(|timestamp| Utc.from_utc_datetime(&timestamp))
+                    })
+                    .or_else(|_| {
+                        NaiveDate::parse_from_str(timestamp, "%Y-%m-%d")
+                            .map(|date| Utc.from_utc_datetime(&date.and_hms(0, 0, 0)))
+                    })
+                    .ok()
+            })
+            .ok_or(Invalid::Mismatch("string", expected)),
+        value => Err(Invalid::Mismatch(kind(value), expected)),
+    }
+}
+
+/// The kind of a value, as reported in coercion errors.
+const fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) | Value::Regex(_) => "string",
+        Value::Timestamp(_) => "timestamp",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "float",
+        Value::Boolean(_) => "boolean",
+        Value::Object(_) => "map",
+        Value::Array(_) => "array",
+        Value::Null => "null",
+    }
+}
+
+fn days_since_epoch(timestamp: DateTime<Utc>) -> i64 {
+    timestamp.timestamp().div_euclid(86_400)
+}
+
+/// Enum values are coerced from the names of their variants, or from their values.
+fn enum_value<T: Copy + TryFrom<i64> + PartialEq>(
+    value: &Value,
+    variants: &[(String, T)],
+    expected: &'static str,
+) -> Result<T, Invalid> {
+    match value {
+        Value::Bytes(bytes) => variants
+            .iter()
+            .find(|(name, _)| name.as_bytes() == bytes.as_ref())
+            .map(|(_, value)| *value)
+            .ok_or(Invalid::OutOfRange(expected)),
+        Value::Integer(integer) => T::try_from(*integer)
+            .ok()
+            .filter(|integer| variants.iter().any(|(_, value)| value == integer))
+            .ok_or(Invalid::OutOfRange(expected)),
+        value => Err(Invalid::Mismatch(kind(value), expected)),
+    }
+}
+
+fn ip_address(value: &Value, expected: &'static str) -> Result<IpAddr, Invalid> {
+    match value {
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|address| address.trim().parse().ok())
+            .ok_or(Invalid::Mismatch("string", expected)),
+        Value::Integer(integer) => u32::try_from(*integer)
+            .map(|address| IpAddr::V4(Ipv4Addr::from(address)))
+            .map_err(|_| Invalid::OutOfRange(expected)),
+        value => Err(Invalid::Mismatch(kind(value), expected)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn column(name: &str, ty: &str) -> Column {
+        Column {
+            name: name.to_string(),
+            ty: ty.parse().unwrap(),
+        }
+    }
+
+    fn encode(columns: &[Column], log: &LogEvent) -> Result<Vec<u8>, CoercionError> {
+        let mut buf = BytesMut::new();
+        encode_row(columns, log, &mut buf).map(|()| buf.to_vec())
+    }
+
+    #[test]
+    fn parses_column_types() {
+        assert_eq!(
+            "LowCardinality(Nullable(String))".parse(),
+            Ok(ColumnType::Nullable(Box::new(ColumnType::String)))
+        );
+        assert_eq!(
+            "DateTime64(3, 'Europe/Paris')".parse(),
+            Ok(ColumnType::DateTime64(3))
+        );
+        assert_eq!(
+            "Map(String, Array(UInt8))".parse(),
+            Ok(ColumnType::Map(
+                Box::new(ColumnType::String),
+                Box::new(ColumnType::Array(Box::new(ColumnType::UInt8)))
+            ))
+        );
+        assert_eq!(
+            "Enum8('a, b' = 1, 'it\\'s' = -2)".parse(),
+            Ok(ColumnType::Enum8(vec![
+                ("a, b".to_string(), 1),
+                ("it's".to_string(), -2)
+            ]))
+        );
+        assert_eq!(
+            "Nested(name String, `values` Array(Float64))".parse(),
+            Ok(ColumnType::Array(Box::new(ColumnType::Tuple(vec![
+                (Some("name".to_string()), ColumnType::String),
+                (
+                    Some("values".to_string()),
+                    ColumnType::Array(Box::new(ColumnType::Float64))
+                ),
+            ]))))
+        );
+        assert_eq!(
+            "Tuple(UInt8, DateTime('UTC'))".parse(),
+            Ok(ColumnType::Tuple(vec![
+                (None, ColumnType::UInt8),
+                (None, ColumnType::DateTime)
+            ]))
+        );
+        assert_eq!("Decimal(9, 2)".parse::<ColumnType>(), Err(()));
+        assert_eq!("DateTime64(12)".parse::<ColumnType>(), Err(()));
+    }
+
+    #[test]
+    fn skips_computed_columns() {
+        let described = vec![
+            DescribedColumn {
+                name: "message".to_string(),
+                ty: "String".to_string(),
+                default_type: "".to_string(),
+            },
+            DescribedColumn {
+                name: "length".to_string(),
+                ty: "UInt64".to_string(),
+                default_type: "MATERIALIZED".to_string(),
+            },
+        ];
+        assert_eq!(
+            Column::from_described(described).unwrap(),
+            vec![column("message", "String")]
+        );
+
+        let described = vec![DescribedColumn {
+            name: "amount".to_string(),
+            ty: "Decimal(9, 2)".to_string(),
+            default_type: "".to_string(),
+        }];
+        assert!(Column::from_described(described).is_err());
+    }
+
+    #[test]
+    fn encodes_scalars() {
+        let columns = [
+            column("count", "UInt16"),
+            column("delta", "Int8"),
+            column("ratio", "Float64"),
+            column("ok", "Bool"),
+            column("message", "String"),
+            column("code", "FixedString(4)"),
+            column("level", "LowCardinality(String)"),
+            column("status", "Enum8('ok' = 1, 'error' = 2)"),
+            column("ip", "IPv4"),
+        ];
+        let mut log = LogEvent::default();
+        log.insert("count", "300");
+        log.insert("delta", -2);
+        log.insert("ratio", 1);
+        log.insert("ok", true);
+        log.insert("message", "héllo");
+        log.insert("code", "ab");
+        log.insert("level", "info");
+        log.insert("status", "error");
+        log.insert("ip", "10.0.0.1");
+
+        let mut expected = vec![0x2c, 0x01, 0xfe];
+        expected.extend(1f64.to_le_bytes());
+        expected.push(1);
+        expected.push(6);
+        expected.extend("héllo".as_bytes());
+        expected.extend(b"ab\0\0");
+        expected.push(4);
+        expected.extend(b"info");
+        expected.push(2);
+        expected.extend([1, 0, 0, 10]);
+        assert_eq!(encode(&columns, &log).unwrap(), expected);
+    }
+
+    #[test]
+    fn encodes_timestamps() {
+        let columns = [
+            column("date", "Date"),
+            column("timestamp", "DateTime('UTC')"),
+            column("precise", "DateTime64(3)"),
+            column("parsed", "DateTime64(6)"),
+        ];
+        let timestamp = Utc.ymd(2022, 8, 1).and_hms_milli(12, 30, 15, 250);
+        let mut log = LogEvent::default();
+        log.insert("date", "2022-08-01");
+        log.insert("timestamp", timestamp);
+        log.insert("precise", timestamp);
+        log.insert("parsed", "2022-08-01T14:30:15.25+02:00");
+
+        let mut expected = Vec::new();
+        expected.extend(19_205u16.to_le_bytes());
+        expected.extend(1_659_357_015u32.to_le_bytes());
+        expected.extend(1_659_357_015_250i64.to_le_bytes());
+        expected.extend(1_659_357_015_250_000i64.to_le_bytes());
+        assert_eq!(encode(&columns, &log).unwrap(), expected);
+    }
+
+    #[test]
+    fn encodes_float_timestamps() {
+        let columns = [
+            column("before_epoch", "DateTime64(3)"),
+            column("rounded_up", "DateTime64(3)"),
+        ];
+        let mut log = LogEvent::default();
+        log.insert("before_epoch", -1.5);
+        log.insert("rounded_up", 1.999_999_999_9);
+
+        let mut expected = Vec::new();
+        expected.extend((-1_500i64).to_le_bytes());
+        expected.extend(2_000i64.to_le_bytes());
+        assert_eq!(encode(&columns, &log).unwrap(), expected);
+    }
+
+    #[test]
+    fn encodes_containers() {
+        let columns = [
+            column("tags", "Array(String)"),
+            column("labels", "Map(String, UInt8)"),
+            column("maybe", "Nullable(UInt8)"),
+            column("missing", "Nullable(UInt8)"),
+            column("spans.name", "Array(String)"),
+            column("spans.duration", "Array(UInt32)"),
+            column("uuid", "UUID"),
+        ];
+        let mut log = LogEvent::default();
+        log.insert("tags", vec!["a", "b"]);
+        log.insert(
+            "labels",
+            BTreeMap::from([
+                ("x".to_string(), Value::from(1)),
+                ("y".to_string(), Value::from(2)),
+            ]),
+        );
+        log.insert("maybe", 7);
+        log.insert(
+            "spans",
+            vec![
+                Value::from(BTreeMap::from([
+                    ("name".to_string(), Value::from("db")),
+                    ("duration".to_string(), Value::from(12)),
+                ])),
+                Value::from(BTreeMap::from([("name".to_string(), Value::from("http"))])),
+            ],
+        );
+        log.insert("uuid", "61f0c404-5cb3-11e7-907b-a6006ad3dba0");
+
+        let mut expected = vec![2, 1, b'a', 1, b'b', 2, 1, b'x', 1, 1, b'y', 2, 0, 7, 1];
+        expected.extend([2, 2, b'd', b'b', 4, b'h', b't', b't', b'p']);
+        expected.push(2);
+        expected.extend(12u32.to_le_bytes());
+        expected.extend(0u32.to_le_bytes());
+        expected.extend(0x61f0_c404_5cb3_11e7u64.to_le_bytes());
+        expected.extend(0x907b_a600_6ad3_dba0u64.to_le_bytes());
+        assert_eq!(encode(&columns, &log).unwrap(), expected);
+    }
+
+    #[test]
+    fn encodes_nested_columns() {
+        let columns = [column("spans", "Nested(name String, duration UInt32)")];
+        let mut log = LogEvent::default();
+        log.insert(
+            "spans",
+            vec![Value::from(BTreeMap::from([
+                ("name".to_string(), Value::from("db")),
+                ("duration".to_string(), Value::from(12)),
+            ]))],
+        );
+
+        let mut expected = vec![1, 2, b'd', b'b'];
+        expected.extend(12u32.to_le_bytes());
+        assert_eq!(encode(&columns, &log).unwrap(), expected);
+    }
+
+    #[test]
+    fn encodes_varints() {
+        let mut buf = BytesMut::new();
+        put_varint(&mut buf, 300);
+        put_varint(&mut buf, 127);
+        assert_eq!(&buf[..], &[0xac, 0x02, 0x7f]);
+    }
+
+    #[test]
+    fn reports_coercion_failures() {
+        let columns = [column("message", "String"), column("count", "UInt8")];
+        let mut buf = BytesMut::from(&b"previous"[..]);
+
+        let mut log = LogEvent::default();
+        log.insert("count", 256);
+        let error = encode_row(&columns, &log, &mut buf).unwrap_err();
+        assert_eq!(error.column(), "count");
+        assert_eq!(
+            error.to_string(),
+            "Column \"count\": value is out of the range of UInt8"
+        );
+
+        log.insert("count", vec![1]);
+        let error = encode_row(&columns, &log, &mut buf).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Column \"count\": array value can't be coerced to UInt8"
+        );
+
+        assert_eq!(&buf[..], b"previous");
+    }
+}
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use tokio::io;
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{console::sink::WriterSink, Healthcheck, VectorSink},
 };
@@ -82,6 +82,10 @@ impl SinkConfig for ConsoleSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(self.encoding.encoding())
+    }
 }
 
 #[cfg(test)]
@@ -87,18 +87,20 @@ impl GenerateConfig for DatadogTracesConfig {
     }
 }
 
-/// Datadog traces API has two routes: one for traces and another one for stats.
+/// Datadog traces API has two routes: one for traces and another one for stats. Profiles are sent to the profiling
+/// intake.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DatadogTracesEndpoint {
     Traces,
-    #[allow(dead_code)] // This will be used when APM stats will be generated
     APMStats,
+    Profiles,
 }
 
-/// Store traces & APM stats endpoints actual URIs.
+/// Store traces, APM stats & profiles endpoints actual URIs.
 pub struct DatadogTracesEndpointConfiguration {
     traces_endpoint: Uri,
     stats_endpoint: Uri,
+    profiles_endpoint: Uri,
 }
 
 impl DatadogTracesEndpointConfiguration {
@@ -106,6 +108,7 @@ impl DatadogTracesEndpointConfiguration {
         match endpoint {
             DatadogTracesEndpoint::Traces => self.traces_endpoint.clone(),
             DatadogTracesEndpoint::APMStats => self.stats_endpoint.clone(),
+            DatadogTracesEndpoint::Profiles => self.profiles_endpoint.clone(),
         }
     }
 }
@@ -121,12 +124,21 @@ impl DatadogTracesConfig {
             )
         });
 
+        let profiles_base_uri = self.endpoint.clone().unwrap_or_else(|| {
+            format!(
+                "https://intake.profile.{}",
+                get_base_domain(self.site.as_ref(), None)
+            )
+        });
+
         let traces_endpoint = build_uri(&base_uri, "/api/v0.2/traces")?;
         let stats_endpoint = build_uri(&base_uri, "/api/v0.2/stats")?;
+        let profiles_endpoint = build_uri(&profiles_base_uri, "/api/v2/profile")?;
 
         Ok(DatadogTracesEndpointConfiguration {
             traces_endpoint,
             stats_endpoint,
+            profiles_endpoint,
         })
     }
 }
@@ -14,6 +14,7 @@ use super::{
     stats,
 };
 use crate::{
+    common::datadog::apm_stats::{ClientStatsPayload, StatsPayload},
     event::{Event, TraceEvent, Value},
     sinks::util::{Compression, Compressor, IncrementalRequestBuilder},
 };
@@ -77,7 +78,7 @@ pub struct RequestMetadata {
     endpoint: DatadogTracesEndpoint,
     finalizers: EventFinalizers,
     uncompressed_size: usize,
-    content_type: String,
+    headers: BTreeMap<String, String>,
 }
 
 impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequestBuilder {
@@ -92,18 +93,36 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
     ) -> Vec<Result<(Self::Metadata, Self::Payload), Self::Error>> {
         let (key, events) = input;
         let mut results = Vec::new();
-        let n = events.len();
-        let traces_event = events
-            .into_iter()
-            .filter_map(|e| e.try_into_trace())
-            .collect::<Vec<TraceEvent>>();
+        let mut traces_event = Vec::new();
+        // APM stats payloads and profiles received by the `datadog_agent` source are relayed as is, each in its own
+        // request.
+        for event in events.into_iter().filter_map(|e| e.try_into_trace()) {
+            if event.contains("apm_stats") {
+                results.push(build_apm_stats_relay_request(
+                    &key,
+                    event,
+                    self.compression,
+                    &self.api_key,
+                ));
+            } else if event.contains("profile") {
+                results.push(Ok(build_profile_request(&key, event, &self.api_key)));
+            } else {
+                traces_event.push(event);
+            }
+        }
+        if traces_event.is_empty() {
+            return results;
+        }
+        let n = traces_event.len();
 
-        results.push(build_apm_stats_request(
-            &key,
-            &traces_event,
-            self.compression,
-            &self.api_key,
-        ));
+        if traces_event.iter().any(|t| !stats::apm_stats_computed(t)) {
+            results.push(build_apm_stats_request(
+                &key,
+                &traces_event,
+                self.compression,
+                &self.api_key,
+            ));
+        }
 
         self.trace_encoder
             .encode_trace(&key, traces_event)
@@ -120,7 +139,7 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
                         endpoint: DatadogTracesEndpoint::Traces,
                         finalizers: processed.take_finalizers(),
                         uncompressed_size,
-                        content_type: "application/x-protobuf".to_string(),
+                        headers: payload_headers("application/x-protobuf", self.compression),
                     };
                     let mut compressor = Compressor::from(self.compression);
                     match compressor.write_all(&payload) {
@@ -142,12 +161,8 @@ impl IncrementalRequestBuilder<(PartitionKey, Vec<Event>)> for DatadogTracesRequ
     }
 
     fn build_request(&mut self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
-        let mut headers = BTreeMap::<String, String>::new();
-        headers.insert("Content-Type".to_string(), metadata.content_type);
+        let mut headers = metadata.headers;
         headers.insert("DD-API-KEY".to_string(), metadata.api_key.to_string());
-        if let Some(ce) = self.compression.content_encoding() {
-            headers.insert("Content-Encoding".to_string(), ce.to_string());
-        }
         TraceApiRequest {
             batch_size: metadata.batch_size,
             body: payload,
@@ -230,8 +245,14 @@ impl DatadogTracesEncoder {
             // We only send tags at the Trace level
             tags: BTreeMap::new(),
             agent_version: key.agent_version.clone().unwrap_or_default(),
-            target_tps: key.target_tps.map(|tps| tps as f64).unwrap_or_default(),
-            error_tps: key.error_tps.map(|tps| tps as f64).unwrap_or_default(),
+            target_tps: key
+                .target_tps
+                .map(|tps| tps.into_inner())
+                .unwrap_or_default(),
+            error_tps: key
+                .error_tps
+                .map(|tps| tps.into_inner())
+                .unwrap_or_default(),
         }
     }
 
@@ -297,6 +318,23 @@ impl DatadogTracesEncoder {
                 .map(|v| v.to_string_lossy())
                 .unwrap_or_default(),
             chunks: vec![chunk],
+            tags: trace
+                .get("tracer_tags")
+                .and_then(|m| m.as_object())
+                .map(|m| {
+                    m.iter()
+                        .map(|(k, v)| (k.clone(), v.to_string_lossy()))
+                        .collect::<BTreeMap<String, String>>()
+                })
+                .unwrap_or_default(),
+            env: trace
+                .get("tracer_env")
+                .map(|v| v.to_string_lossy())
+                .unwrap_or_default(),
+            hostname: trace
+                .get("tracer_hostname")
+                .map(|v| v.to_string_lossy())
+                .unwrap_or_default(),
             app_version: trace
                 .get("app_version")
                 .map(|v| v.to_string_lossy())
@@ -396,6 +434,15 @@ impl DatadogTracesEncoder {
     }
 }
 
+fn payload_headers(content_type: &str, compression: Compression) -> BTreeMap<String, String> {
+    let mut headers = BTreeMap::<String, String>::new();
+    headers.insert("Content-Type".to_string(), content_type.to_string());
+    if let Some(ce) = compression.content_encoding() {
+        headers.insert("Content-Encoding".to_string(), ce.to_string());
+    }
+    headers
+}
+
 fn build_apm_stats_request(
     key: &PartitionKey,
     events: &[TraceEvent],
@@ -403,11 +450,62 @@ fn build_apm_stats_request(
     default_api_key: &Arc<str>,
 ) -> Result<(RequestMetadata, Bytes), RequestBuilderError> {
     let payload = stats::compute_apm_stats(key, events);
+    encode_apm_stats_payload(
+        key,
+        &payload,
+        EventFinalizers::default(),
+        0,
+        compression,
+        default_api_key,
+    )
+}
+
+/// Rebuilds the APM stats payload computed by a Datadog trace-agent, out of an event of the `datadog_agent` source.
+fn build_apm_stats_relay_request(
+    key: &PartitionKey,
+    mut event: TraceEvent,
+    compression: Compression,
+    default_api_key: &Arc<str>,
+) -> Result<(RequestMetadata, Bytes), RequestBuilderError> {
+    let payload = StatsPayload {
+        agent_hostname: key.hostname.clone().unwrap_or_default(),
+        agent_env: key.env.clone().unwrap_or_default(),
+        stats: match event.get("apm_stats") {
+            Some(Value::Array(stats)) => stats
+                .iter()
+                .filter_map(|s| s.as_object().map(ClientStatsPayload::from))
+                .collect(),
+            _ => vec![],
+        },
+        agent_version: key.agent_version.clone().unwrap_or_default(),
+        client_computed: event
+            .get("client_computed")
+            .and_then(|v| v.as_boolean())
+            .unwrap_or(false),
+    };
+    encode_apm_stats_payload(
+        key,
+        &payload,
+        event.take_finalizers(),
+        1,
+        compression,
+        default_api_key,
+    )
+}
+
+fn encode_apm_stats_payload(
+    key: &PartitionKey,
+    payload: &StatsPayload,
+    finalizers: EventFinalizers,
+    batch_size: usize,
+    compression: Compression,
+    default_api_key: &Arc<str>,
+) -> Result<(RequestMetadata, Bytes), RequestBuilderError> {
     let encoded_payload =
-        rmp_serde::to_vec_named(&payload).map_err(|e| RequestBuilderError::FailedToEncode {
+        rmp_serde::to_vec_named(payload).map_err(|e| RequestBuilderError::FailedToEncode {
             message: "APM stats encoding failed.",
             reason: e.to_string(),
-            dropped_events: 0,
+            dropped_events: batch_size as u64,
         })?;
     let uncompressed_size = encoded_payload.len();
     let metadata = RequestMetadata {
@@ -415,11 +513,11 @@ fn build_apm_stats_request(
             .api_key
             .clone()
             .unwrap_or_else(|| Arc::clone(default_api_key)),
-        batch_size: 0,
+        batch_size,
         endpoint: DatadogTracesEndpoint::APMStats,
-        finalizers: EventFinalizers::default(),
+        finalizers,
         uncompressed_size,
-        content_type: "application/msgpack".to_string(),
+        headers: payload_headers("application/msgpack", compression),
     };
     let mut compressor = Compressor::from(compression);
     match compressor.write_all(&encoded_payload) {
@@ -427,7 +525,41 @@ fn build_apm_stats_request(
         Err(e) => Err(RequestBuilderError::FailedToEncode {
             message: "APM stats payload compression failed.",
             reason: e.to_string(),
-            dropped_events: 0,
+            dropped_events: batch_size as u64,
         }),
     }
 }
+
+/// Profiles are sent untouched, along with the headers they were received with, so they are never compressed again.
+fn build_profile_request(
+    key: &PartitionKey,
+    mut event: TraceEvent,
+    default_api_key: &Arc<str>,
+) -> (RequestMetadata, Bytes) {
+    let profile = event.get("profile").and_then(|p| p.as_object());
+    let body = profile
+        .and_then(|p| p.get("body"))
+        .map(|b| b.coerce_to_bytes())
+        .unwrap_or_default();
+    let headers = profile
+        .and_then(|p| p.get("headers"))
+        .and_then(|h| h.as_object())
+        .map(|h| {
+            h.iter()
+                .map(|(k, v)| (k.clone(), v.to_string_lossy()))
+                .collect::<BTreeMap<String, String>>()
+        })
+        .unwrap_or_default();
+    let metadata = RequestMetadata {
+        api_key: key
+            .api_key
+            .clone()
+            .unwrap_or_else(|| Arc::clone(default_api_key)),
+        batch_size: 1,
+        endpoint: DatadogTracesEndpoint::Profiles,
+        finalizers: event.take_finalizers(),
+        uncompressed_size: body.len(),
+        headers,
+    };
+    (metadata, body)
+}
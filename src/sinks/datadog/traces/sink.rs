@@ -5,10 +5,11 @@ use futures_util::{
     stream::{self, BoxStream},
     StreamExt,
 };
+use ordered_float::NotNan;
 use tower::Service;
 use vector_core::{
     config::log_schema,
-    event::Event,
+    event::{Event, Value},
     partition::Partitioner,
     sink::StreamSink,
     stream::{BatcherSettings, DriverResponse},
@@ -31,8 +32,8 @@ pub(crate) struct PartitionKey {
     pub(crate) agent_version: Option<String>,
     // Those two last fields are configuration value and not a per-trace/span information, they come from the Datadog
     // trace-agent config directly: https://github.com/DataDog/datadog-agent/blob/0f73a78/pkg/trace/config/config.go#L293-L294
-    pub(crate) target_tps: Option<i64>,
-    pub(crate) error_tps: Option<i64>,
+    pub(crate) target_tps: Option<NotNan<f64>>,
+    pub(crate) error_tps: Option<NotNan<f64>>,
 }
 
 impl Partitioner for EventPartitioner {
@@ -52,13 +53,22 @@ impl Partitioner for EventPartitioner {
                 env: t.get("env").map(|s| s.to_string_lossy()),
                 hostname: t.get(log_schema().host_key()).map(|s| s.to_string_lossy()),
                 agent_version: t.get("agent_version").map(|s| s.to_string_lossy()),
-                target_tps: t.get("target_tps").and_then(|tps| tps.as_integer()),
-                error_tps: t.get("error_tps").and_then(|tps| tps.as_integer()),
+                target_tps: t.get("target_tps").and_then(tps),
+                error_tps: t.get("error_tps").and_then(tps),
             },
         }
     }
 }
 
+/// The Datadog trace-agent reports its target and error TPS as floats, but they may also have been set as integers.
+fn tps(value: &Value) -> Option<NotNan<f64>> {
+    match value {
+        Value::Float(tps) => Some(*tps),
+        Value::Integer(tps) => NotNan::new(*tps as f64).ok(),
+        _ => None,
+    }
+}
+
 pub struct TracesSink<S> {
     service: S,
     request_builder: DatadogTracesRequestBuilder,
@@ -1,15 +1,15 @@
 use std::collections::BTreeMap;
 
-use chrono::Utc;
-use prost::Message;
-use serde::{Deserialize, Serialize};
-use serde_bytes;
-
 use super::{ddsketch_full, sink::PartitionKey};
 use crate::{
+    common::datadog::apm_stats::{
+        ClientGroupedStats, ClientStatsBucket, ClientStatsPayload, StatsPayload,
+    },
     event::{TraceEvent, Value},
     metrics::AgentDDSketch,
 };
+use chrono::Utc;
+use prost::Message;
 
 const MEASURED_KEY: &str = "_dd.measured";
 const PARTIAL_VERSION_KEY: &str = "_dd.partial_version";
@@ -466,9 +466,21 @@ fn extract_weight_from_root_span(spans: &[&BTreeMap<String, Value>]) -> f64 {
         })
 }
 
+/// Whether the APM stats of a trace were already computed by the Datadog trace-agent it was received from, in which
+/// case they are relayed from the APM stats payloads of that agent.
+pub(crate) fn apm_stats_computed(trace: &TraceEvent) -> bool {
+    trace
+        .get("apm_stats_computed")
+        .and_then(|v| v.as_boolean())
+        .unwrap_or(false)
+}
+
 pub(crate) fn compute_apm_stats(key: &PartitionKey, traces: &[TraceEvent]) -> StatsPayload {
     let mut aggregator = Aggregator::new();
-    traces.iter().for_each(|t| aggregator.handle_trace(key, t));
+    traces
+        .iter()
+        .filter(|t| !apm_stats_computed(t))
+        .for_each(|t| aggregator.handle_trace(key, t));
     StatsPayload {
         agent_hostname: key.hostname.clone().unwrap_or_default(),
         agent_env: key.env.clone().unwrap_or_default(),
@@ -477,69 +489,3 @@ pub(crate) fn compute_apm_stats(key: &PartitionKey, traces: &[TraceEvent]) -> St
         client_computed: false,
     }
 }
-
-// On the agent side APM Stats payload are encoded into the messagepack format using this
-// go code https://github.com/DataDog/datadog-agent/blob/b5bed4d/pkg/trace/pb/stats_gen.go.
-// Note that this code is generated from code itself generate from this .proto file
-// https://github.com/DataDog/datadog-agent/blob/dc2f202/pkg/trace/pb/stats.proto.
-// All the subsequent struct are dedicated to be used with rmp_serde and the fields names
-// exactly match the ones of the go code.
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "PascalCase")]
-pub(crate) struct StatsPayload {
-    pub(crate) agent_hostname: String,
-    pub(crate) agent_env: String,
-    pub(crate) stats: Vec<ClientStatsPayload>,
-    pub(crate) agent_version: String,
-    pub(crate) client_computed: bool,
-}
-
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "PascalCase")]
-pub(crate) struct ClientStatsPayload {
-    pub(crate) hostname: String,
-    pub(crate) env: String,
-    pub(crate) version: String,
-    pub(crate) stats: Vec<ClientStatsBucket>,
-    pub(crate) lang: String,
-    pub(crate) tracer_version: String,
-    #[serde(rename = "RuntimeID")]
-    pub(crate) runtime_id: String,
-    pub(crate) sequence: u64,
-    pub(crate) agent_aggregation: String,
-    pub(crate) service: String,
-    #[serde(rename = "ContainerID")]
-    pub(crate) container_id: String,
-    pub(crate) tags: Vec<String>,
-}
-
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "PascalCase")]
-pub(crate) struct ClientStatsBucket {
-    pub(crate) start: u64,
-    pub(crate) duration: u64,
-    pub(crate) stats: Vec<ClientGroupedStats>,
-    pub(crate) agent_time_shift: i64,
-}
-
-#[derive(Debug, PartialEq, Deserialize, Serialize)]
-#[serde(rename_all = "PascalCase")]
-pub(crate) struct ClientGroupedStats {
-    pub(crate) service: String,
-    pub(crate) name: String,
-    pub(crate) resource: String,
-    #[serde(rename = "HTTPStatusCode")]
-    pub(crate) http_status_code: u32,
-    pub(crate) r#type: String,
-    #[serde(rename = "DBType")]
-    pub(crate) db_type: String,
-    pub(crate) hits: u64,
-    pub(crate) errors: u64,
-    pub(crate) duration: u64,
-    #[serde(with = "serde_bytes")]
-    pub(crate) ok_summary: Vec<u8>,
-    #[serde(with = "serde_bytes")]
-    pub(crate) error_summary: Vec<u8>,
-    pub(crate) synthetics: bool,
-    pub(crate) top_level_hits: u64,
-}
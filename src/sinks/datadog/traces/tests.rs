@@ -10,8 +10,11 @@ use prost::Message;
 use rmp_serde;
 use vector_core::event::{BatchNotifier, BatchStatus, Event};
 
-use super::{dd_proto, ddsketch_full, stats::StatsPayload, DatadogTracesConfig};
+use super::{dd_proto, ddsketch_full, DatadogTracesConfig};
 use crate::{
+    common::datadog::apm_stats::{
+        ClientGroupedStats, ClientStatsBucket, ClientStatsPayload, StatsPayload,
+    },
     config::SinkConfig,
     event::{TraceEvent, Value},
     sinks::util::test::{build_test_server_status, load_sink},
@@ -306,3 +309,117 @@ async fn multiple_traces() {
     assert_eq!(cgs_trace_1.resource, "trace_1");
     assert_eq!(cgs_trace_1.service, "a_service");
 }
+
+#[tokio::test]
+async fn relays_agent_apm_stats_and_profiles() {
+    let mut t = simple_trace_event("a_resource".to_string());
+    t.insert("target_tps", 10.5);
+    t.insert("apm_stats_computed", true);
+    t.metadata_mut().set_datadog_api_key(Arc::from("a_key"));
+
+    let client_stats = ClientStatsPayload {
+        hostname: "a_host".to_string(),
+        env: "an_env".to_string(),
+        version: "v1.2".to_string(),
+        stats: vec![ClientStatsBucket {
+            start: 1_431_648_000_000_000_000,
+            duration: 10_000_000_000,
+            stats: vec![ClientGroupedStats {
+                service: "a_service".to_string(),
+                name: "a_name".to_string(),
+                resource: "a_resource".to_string(),
+                http_status_code: 200,
+                r#type: "web".to_string(),
+                db_type: "".to_string(),
+                hits: 10,
+                errors: 1,
+                duration: 123_456,
+                ok_summary: vec![1, 2, 3],
+                error_summary: vec![4, 5],
+                synthetics: false,
+                top_level_hits: 9,
+            }],
+            agent_time_shift: 0,
+        }],
+        lang: "".to_string(),
+        tracer_version: "".to_string(),
+        runtime_id: "".to_string(),
+        sequence: 0,
+        agent_aggregation: "".to_string(),
+        service: "".to_string(),
+        container_id: "an_id".to_string(),
+        tags: vec![],
+    };
+    let mut stats = TraceEvent::default();
+    stats.insert("host", "a_host");
+    stats.insert("env", "an_env");
+    stats.insert("agent_version", "1.23456");
+    stats.insert("client_computed", false);
+    stats.insert("apm_stats", vec![Value::from(client_stats.clone())]);
+    stats.metadata_mut().set_datadog_api_key(Arc::from("a_key"));
+
+    let mut profile = TraceEvent::default();
+    profile.insert(
+        "profile",
+        BTreeMap::from([
+            ("body".to_string(), Value::from(Bytes::from("a_profile"))),
+            (
+                "headers".to_string(),
+                Value::from(BTreeMap::from([(
+                    "content-type".to_string(),
+                    Value::from("multipart/form-data; boundary=a_boundary"),
+                )])),
+            ),
+        ]),
+    );
+    profile
+        .metadata_mut()
+        .set_datadog_api_key(Arc::from("another_key"));
+
+    let events = vec![Event::Trace(t), Event::Trace(stats), Event::Trace(profile)];
+    let rx = start_test(BatchStatus::Delivered, StatusCode::OK, events).await;
+
+    // No APM stats are computed out of the trace, as the agent already did.
+    let output = rx.take(3).collect::<Vec<_>>().await;
+    let request = |path: &str| {
+        output
+            .iter()
+            .find(|(parts, _)| parts.uri.path() == path)
+            .unwrap()
+    };
+
+    let (trace_parts, trace_body) = request("/api/v0.2/traces");
+    assert_eq!(trace_parts.headers.get("DD-API-KEY").unwrap(), "a_key");
+    let decoded_payload = dd_proto::TracePayload::decode(trace_body.clone()).unwrap();
+    assert_eq!(decoded_payload.target_tps, 10.5);
+    assert_eq!(decoded_payload.error_tps, 5.0);
+
+    let (stats_parts, stats_body) = request("/api/v0.2/stats");
+    assert_eq!(
+        stats_parts.headers.get("Content-Type").unwrap(),
+        "application/msgpack"
+    );
+    let sp: StatsPayload = rmp_serde::from_slice(stats_body).unwrap();
+    assert_eq!(
+        sp,
+        StatsPayload {
+            agent_hostname: "a_host".to_string(),
+            agent_env: "an_env".to_string(),
+            stats: vec![client_stats],
+            agent_version: "1.23456".to_string(),
+            client_computed: false,
+        }
+    );
+
+    let (profile_parts, profile_body) = request("/api/v2/profile");
+    assert_eq!(
+        profile_parts.headers.get("Content-Type").unwrap(),
+        "multipart/form-data; boundary=a_boundary"
+    );
+    assert!(profile_parts.headers.get("Content-Encoding").is_none());
+    assert_eq!(
+        profile_parts.headers.get("DD-API-KEY").unwrap(),
+        "another_key"
+    );
+    assert_eq!(profile_body, &Bytes::from("a_profile"));
+}
@@ -18,10 +18,11 @@ use crate::{
     sinks::{
         elasticsearch::{
             retry::ElasticsearchRetryLogic,
-            service::{ElasticsearchService, HttpRequestBuilder},
+            service::{ElasticsearchService, HttpRequestBuilder, PartialRetry},
             sink::ElasticsearchSink,
             BatchActionTemplateSnafu, ElasticsearchAuth, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu,
+            ElasticsearchCommonMode, ElasticsearchMode, IndexTemplateSnafu, PipelineTemplateSnafu,
+            RoutingTemplateSnafu, VersionTemplateSnafu, VersionType,
         },
         util::{
             http::RequestConfig, BatchConfig, Compression, RealtimeSizeBasedDefaultBatchSettings,
@@ -29,7 +30,7 @@ use crate::{
         },
         Healthcheck, VectorSink,
     },
-    template::Template,
+    template::{Template, TemplateParseError},
     tls::TlsConfig,
     transforms::metric_to_log::MetricToLogConfig,
 };
@@ -63,6 +64,10 @@ pub struct ElasticsearchConfig {
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     #[serde(default)]
     pub request: RequestConfig,
+    /// Retries only the items of a bulk request that failed with a transient error, such as a full write queue,
+    /// rather than dropping the whole request. The events of a request are kept in memory until it completes.
+    #[serde(default)]
+    pub request_retry_partial: bool,
     pub auth: Option<ElasticsearchAuth>,
     pub query: Option<HashMap<String, String>>,
     pub aws: Option<RegionOrEndpoint>,
@@ -91,6 +96,17 @@ impl ElasticsearchConfig {
             .transpose()?)
     }
 
+    fn bulk_template(
+        &self,
+        field: impl Fn(&BulkConfig) -> Option<&String>,
+    ) -> Result<Option<Template>, TemplateParseError> {
+        self.bulk
+            .as_ref()
+            .and_then(field)
+            .map(|value| Template::try_from(value.as_str()))
+            .transpose()
+    }
+
     pub fn index(&self) -> crate::Result<Template> {
         let index = self
             .bulk
@@ -106,9 +122,23 @@ impl ElasticsearchConfig {
             ElasticsearchMode::Bulk => {
                 let index = self.index()?;
                 let bulk_action = self.bulk_action()?;
+                let routing = self
+                    .bulk_template(|bulk| bulk.routing.as_ref())
+                    .context(RoutingTemplateSnafu)?;
+                let pipeline = self
+                    .bulk_template(|bulk| bulk.pipeline.as_ref())
+                    .context(PipelineTemplateSnafu)?;
+                let version = self
+                    .bulk_template(|bulk| bulk.version.as_ref())
+                    .context(VersionTemplateSnafu)?;
+                let version_type = self.bulk.as_ref().and_then(|bulk| bulk.version_type);
                 Ok(ElasticsearchCommonMode::Bulk {
                     index,
                     action: bulk_action,
+                    routing,
+                    pipeline,
+                    version,
+                    version_type,
                 })
             }
             ElasticsearchMode::DataStream => Ok(ElasticsearchCommonMode::DataStream(
@@ -123,6 +153,10 @@ impl ElasticsearchConfig {
 pub struct BulkConfig {
     pub action: Option<String>,
     pub index: Option<String>,
+    pub routing: Option<String>,
+    pub pipeline: Option<String>,
+    pub version: Option<String>,
+    pub version_type: Option<VersionType>,
 }
 
 impl BulkConfig {
@@ -313,9 +347,20 @@ impl SinkConfig for ElasticsearchConfig {
             credentials_provider: common.aws_auth.clone(),
         };
 
+        let partial_retry = self.request_retry_partial.then(|| PartialRetry {
+            request_builder: common.request_builder.clone(),
+            attempts: request_limits.retry_attempts,
+            initial_backoff: request_limits.retry_initial_backoff_secs,
+            max_backoff: request_limits.retry_max_duration_secs,
+        });
+
         let service = ServiceBuilder::new()
             .settings(request_limits, ElasticsearchRetryLogic)
-            .service(ElasticsearchService::new(http_client, http_request_builder));
+            .service(ElasticsearchService::new(
+                http_client,
+                http_request_builder,
+                partial_retry,
+            ));
 
         let sink = ElasticsearchSink {
             batch_settings,
@@ -1,21 +1,50 @@
-use std::{io, io::Write};
+use std::{borrow::Cow, io, io::Write};
 
+use serde::Serialize;
 use vector_core::{event::Event, ByteSizeOf};
 
 use crate::{
     codecs::Transformer,
     event::{EventFinalizers, Finalizable, LogEvent},
     sinks::{
-        elasticsearch::BulkAction,
+        elasticsearch::{BulkAction, VersionType},
         util::encoding::{as_tracked_write, Encoder},
     },
 };
 
+#[derive(Clone)]
 pub struct ProcessedEvent {
     pub index: String,
     pub bulk_action: BulkAction,
     pub log: LogEvent,
     pub id: Option<String>,
+    pub options: BulkActionOptions,
+}
+
+/// The optional metadata of the bulk action of an event.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkActionOptions {
+    pub routing: Option<String>,
+    pub pipeline: Option<String>,
+    pub version: Option<u64>,
+    pub version_type: Option<VersionType>,
+}
+
+impl BulkActionOptions {
+    /// Leaves out the options the bulk API doesn't accept for the action.
+    fn supported_by(&self, bulk_action: BulkAction) -> Cow<'_, Self> {
+        match bulk_action {
+            BulkAction::Index | BulkAction::Create => Cow::Borrowed(self),
+            BulkAction::Update | BulkAction::Upsert => Cow::Owned(Self {
+                routing: self.routing.clone(),
+                ..Self::default()
+            }),
+            BulkAction::Delete => Cow::Owned(Self {
+                pipeline: None,
+                ..self.clone()
+            }),
+        }
+    }
 }
 
 impl Finalizable for ProcessedEvent {
@@ -26,7 +55,11 @@ impl Finalizable for ProcessedEvent {
 
 impl ByteSizeOf for ProcessedEvent {
     fn allocated_bytes(&self) -> usize {
-        self.index.allocated_bytes() + self.log.allocated_bytes() + self.id.allocated_bytes()
+        self.index.allocated_bytes()
+            + self.log.allocated_bytes()
+            + self.id.allocated_bytes()
+            + self.options.routing.allocated_bytes()
+            + self.options.pipeline.allocated_bytes()
     }
 }
 
@@ -57,12 +90,40 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
                 &self.doc_type,
                 self.suppress_type_name,
                 &event.id,
+                &event.options.supported_by(event.bulk_action),
             )?;
+            let bulk_action = event.bulk_action;
             written_bytes +=
                 as_tracked_write::<_, _, io::Error>(writer, &log, |mut writer, log| {
                     writer.write_all(&[b'\n'])?;
-                    serde_json::to_writer(&mut writer, log)?;
-                    writer.write_all(&[b'\n'])?;
+                    match bulk_action {
+                        BulkAction::Index | BulkAction::Create => {
+                            serde_json::to_writer(&mut writer, log)?;
+                            writer.write_all(&[b'\n'])?;
+                        }
+                        BulkAction::Update => {
+                            serde_json::to_writer(
+                                &mut writer,
+                                &UpdateBody {
+                                    doc: log,
+                                    doc_as_upsert: false,
+                                },
+                            )?;
+                            writer.write_all(&[b'\n'])?;
+                        }
+                        BulkAction::Upsert => {
+                            serde_json::to_writer(
+                                &mut writer,
+                                &UpdateBody {
+                                    doc: log,
+                                    doc_as_upsert: true,
+                                },
+                            )?;
+                            writer.write_all(&[b'\n'])?;
+                        }
+                        // A deletion has no document.
+                        BulkAction::Delete => {}
+                    }
                     Ok(())
                 })?;
         }
@@ -70,6 +131,13 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
     }
 }
 
+#[derive(Serialize)]
+struct UpdateBody<'a> {
+    doc: &'a LogEvent,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    doc_as_upsert: bool,
+}
+
 fn write_bulk_action(
     writer: &mut dyn Write,
     bulk_action: &str,
@@ -77,39 +145,45 @@ fn write_bulk_action(
     doc_type: &str,
     suppress_type: bool,
     id: &Option<String>,
+    options: &BulkActionOptions,
 ) -> std::io::Result<usize> {
     as_tracked_write(
         writer,
-        (bulk_action, index, doc_type, id, suppress_type),
-        |writer, (bulk_action, index, doc_type, id, suppress_type)| match (id, suppress_type) {
-            (Some(id), true) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, id
-                )
+        (bulk_action, index, doc_type, id, suppress_type, options),
+        |writer, (bulk_action, index, doc_type, id, suppress_type, options)| {
+            write!(writer, r#"{{"{}":{{"_index":"#, bulk_action)?;
+            write_json_string(writer, index)?;
+            if !suppress_type {
+                writer.write_all(br#","_type":"#)?;
+                write_json_string(writer, doc_type)?;
+            }
+            if let Some(id) = id {
+                writer.write_all(br#","_id":"#)?;
+                write_json_string(writer, id)?;
+            }
+            if let Some(routing) = &options.routing {
+                writer.write_all(br#","routing":"#)?;
+                write_json_string(writer, routing)?;
             }
-            (Some(id), false) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}","_id":"{}"}}}}"#,
-                    bulk_action, index, doc_type, id
-                )
+            if let Some(pipeline) = &options.pipeline {
+                writer.write_all(br#","pipeline":"#)?;
+                write_json_string(writer, pipeline)?;
             }
-            (None, true) => {
-                write!(writer, r#"{{"{}":{{"_index":"{}"}}}}"#, bulk_action, index)
+            if let Some(version) = options.version {
+                write!(writer, r#","version":{}"#, version)?;
             }
-            (None, false) => {
-                write!(
-                    writer,
-                    r#"{{"{}":{{"_index":"{}","_type":"{}"}}}}"#,
-                    bulk_action, index, doc_type
-                )
+            if let Some(version_type) = options.version_type {
+                write!(writer, r#","version_type":"{}""#, version_type.as_str())?;
             }
+            writer.write_all(b"}}")
         },
     )
 }
 
+fn write_json_string(writer: &mut dyn Write, value: &str) -> std::io::Result<()> {
+    serde_json::to_writer(writer, value).map_err(io::Error::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,6 +199,7 @@ mod tests {
             "TYPE",
             true,
             &Some("ID".to_string()),
+            &BulkActionOptions::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -146,7 +221,15 @@ mod tests {
     fn suppress_type_without_id() {
         let mut writer = Vec::new();
 
-        let _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", true, &None);
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            true,
+            &None,
+            &BulkActionOptions::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -173,6 +256,7 @@ mod tests {
             "TYPE",
             false,
             &Some("ID".to_string()),
+            &BulkActionOptions::default(),
         );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
@@ -195,7 +279,15 @@ mod tests {
     fn type_without_id() {
         let mut writer = Vec::new();
 
-        let _ = write_bulk_action(&mut writer, "ACTION", "INDEX", "TYPE", false, &None);
+        let _ = write_bulk_action(
+            &mut writer,
+            "ACTION",
+            "INDEX",
+            "TYPE",
+            false,
+            &None,
+            &BulkActionOptions::default(),
+        );
 
         let value: serde_json::Value = serde_json::from_slice(&writer).unwrap();
         let value = value.as_object().unwrap();
@@ -14,7 +14,7 @@ use vector_core::{
 
 use super::{config::DATA_STREAM_TIMESTAMP_KEY, *};
 use crate::{
-    aws::RegionOrEndpoint,
+    aws::{AwsAuthentication, RegionOrEndpoint},
     config::{ProxyConfig, SinkConfig, SinkContext},
     http::HttpClient,
     sinks::{
@@ -111,6 +111,7 @@ async fn ensure_pipeline_in_params() {
         bulk: Some(BulkConfig {
             index: Some(index),
             action: None,
+            ..Default::default()
         }),
         pipeline: Some(pipeline.clone()),
         ..config()
@@ -130,6 +131,7 @@ async fn structures_events_correctly() {
         bulk: Some(BulkConfig {
             index: Some(index.clone()),
             action: None,
+            ..Default::default()
         }),
         doc_type: Some("log_lines".into()),
         id_key: Some("my_id".into()),
@@ -312,6 +314,7 @@ async fn insert_events_in_data_stream() {
         bulk: Some(BulkConfig {
             index: Some(stream_index.clone()),
             action: None,
+            ..Default::default()
         }),
         ..config()
     };
@@ -338,6 +341,7 @@ async fn run_insert_tests(
     config.bulk = Some(BulkConfig {
         index: Some(gen_index()),
         action: None,
+        ..Default::default()
     });
     run_insert_tests_with_config(&config, break_events, status).await;
 }
@@ -17,26 +17,19 @@ use std::convert::TryFrom;
 
 pub use common::*;
 pub use config::*;
-pub use encoder::ElasticsearchEncoder;
+pub use encoder::{BulkActionOptions, ElasticsearchEncoder};
 use http::{uri::InvalidUri, Request};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 
-use crate::aws::AwsAuthentication;
+pub use crate::common::elasticsearch::ElasticsearchAuth;
 use crate::{
     config::SinkDescription,
     event::{EventRef, LogEvent},
-    internal_events::TemplateRenderingError,
+    internal_events::{ElasticsearchInvalidVersion, TemplateRenderingError},
     template::{Template, TemplateParseError},
 };
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
-#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
-pub enum ElasticsearchAuth {
-    Basic { user: String, password: String },
-    Aws(AwsAuthentication),
-}
-
 #[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum ElasticsearchMode {
@@ -56,14 +49,21 @@ impl Default for ElasticsearchMode {
 pub enum BulkAction {
     Index,
     Create,
+    Update,
+    /// An `update` of the document, which is created from the event if it doesn't exist.
+    Upsert,
+    Delete,
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
 impl BulkAction {
+    /// The name of the action in the bulk API.
     pub const fn as_str(&self) -> &'static str {
         match self {
             BulkAction::Index => "index",
             BulkAction::Create => "create",
+            BulkAction::Update | BulkAction::Upsert => "update",
+            BulkAction::Delete => "delete",
         }
     }
 
@@ -71,6 +71,8 @@ impl BulkAction {
         match self {
             BulkAction::Index => "/index",
             BulkAction::Create => "/create",
+            BulkAction::Update | BulkAction::Upsert => "/update",
+            BulkAction::Delete => "/delete",
         }
     }
 }
@@ -82,11 +84,31 @@ impl TryFrom<&str> for BulkAction {
         match input {
             "index" => Ok(BulkAction::Index),
             "create" => Ok(BulkAction::Create),
+            "update" => Ok(BulkAction::Update),
+            "upsert" => Ok(BulkAction::Upsert),
+            "delete" => Ok(BulkAction::Delete),
             _ => Err(format!("Invalid bulk action: {}", input)),
         }
     }
 }
 
+/// The external versioning of the documents written by the bulk actions.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum VersionType {
+    External,
+    ExternalGte,
+}
+
+impl VersionType {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            VersionType::External => "external",
+            VersionType::ExternalGte => "external_gte",
+        }
+    }
+}
+
 inventory::submit! {
     SinkDescription::new::<ElasticsearchConfig>("elasticsearch")
 }
@@ -98,6 +120,10 @@ pub enum ElasticsearchCommonMode {
     Bulk {
         index: Template,
         action: Option<Template>,
+        routing: Option<Template>,
+        pipeline: Option<Template>,
+        version: Option<Template>,
+        version_type: Option<VersionType>,
     },
     DataStream(DataStreamConfig),
 }
@@ -143,6 +169,37 @@ impl ElasticsearchCommonMode {
         }
     }
 
+    /// Renders the options of the bulk action of the event, which is dropped if one of them can't be rendered.
+    fn bulk_action_options(&self, log: &LogEvent) -> Option<BulkActionOptions> {
+        match self {
+            Self::Bulk {
+                routing,
+                pipeline,
+                version,
+                version_type,
+                ..
+            } => {
+                let version = match render_option(version, log, "bulk.version")? {
+                    Some(version) => match version.parse::<u64>() {
+                        Ok(version) => Some(version),
+                        Err(_) => {
+                            emit!(ElasticsearchInvalidVersion { version: &version });
+                            return None;
+                        }
+                    },
+                    None => None,
+                };
+                Some(BulkActionOptions {
+                    routing: render_option(routing, log, "bulk.routing")?,
+                    pipeline: render_option(pipeline, log, "bulk.pipeline")?,
+                    version_type: version.map(|_| version_type.unwrap_or(VersionType::External)),
+                    version,
+                })
+            }
+            Self::DataStream(_) => Some(BulkActionOptions::default()),
+        }
+    }
+
     const fn as_data_stream_config(&self) -> Option<&DataStreamConfig> {
         match self {
             Self::DataStream(value) => Some(value),
@@ -151,6 +208,28 @@ impl ElasticsearchCommonMode {
     }
 }
 
+/// Renders an optional template, returning `None` if it can't be rendered.
+fn render_option(
+    template: &Option<Template>,
+    log: &LogEvent,
+    field: &'static str,
+) -> Option<Option<String>> {
+    match template {
+        Some(template) => template
+            .render_string(log)
+            .map(Some)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok(),
+        None => Some(None),
+    }
+}
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
 pub enum ParseError {
@@ -162,6 +241,12 @@ pub enum ParseError {
     IndexTemplate { source: TemplateParseError },
     #[snafu(display("Batch action template parse error: {}", source))]
     BatchActionTemplate { source: TemplateParseError },
+    #[snafu(display("Routing template parse error: {}", source))]
+    RoutingTemplate { source: TemplateParseError },
+    #[snafu(display("Pipeline template parse error: {}", source))]
+    PipelineTemplate { source: TemplateParseError },
+    #[snafu(display("Version template parse error: {}", source))]
+    VersionTemplate { source: TemplateParseError },
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     RegionRequired,
 }
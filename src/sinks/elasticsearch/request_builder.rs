@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::Bytes;
 use vector_core::ByteSizeOf;
 
@@ -16,12 +18,15 @@ use crate::{
 pub struct ElasticsearchRequestBuilder {
     pub compression: Compression,
     pub encoder: ElasticsearchEncoder,
+    /// Keeps the events of the requests, so that the failed items of a bulk request can be sent again.
+    pub retain_events: bool,
 }
 
 pub struct Metadata {
     finalizers: EventFinalizers,
     batch_size: usize,
     events_byte_size: usize,
+    original_events: Option<Arc<Vec<ProcessedEvent>>>,
 }
 
 impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
@@ -47,10 +52,12 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             .reduce(|a, b| a + b)
             .unwrap_or(0);
 
+        let finalizers = events.take_finalizers();
         let metadata = Metadata {
-            finalizers: events.take_finalizers(),
+            finalizers,
             batch_size: events.len(),
             events_byte_size,
+            original_events: self.retain_events.then(|| Arc::new(events.clone())),
         };
         (metadata, events)
     }
@@ -65,6 +72,7 @@ impl RequestBuilder<Vec<ProcessedEvent>> for ElasticsearchRequestBuilder {
             finalizers: metadata.finalizers,
             batch_size: metadata.batch_size,
             events_byte_size: metadata.events_byte_size,
+            original_events: metadata.original_events,
         }
     }
 }
@@ -1,4 +1,5 @@
-use http::StatusCode;
+use bytes::Bytes;
+use http::{Response, StatusCode};
 use serde::Deserialize;
 
 use crate::{
@@ -20,6 +21,10 @@ enum EsResultItem {
     Index(EsIndexResult),
     #[serde(rename = "create")]
     Create(EsIndexResult),
+    #[serde(rename = "update")]
+    Update(EsIndexResult),
+    #[serde(rename = "delete")]
+    Delete(EsIndexResult),
 }
 
 impl EsResultItem {
@@ -28,12 +33,16 @@ impl EsResultItem {
         match self {
             EsResultItem::Index(r) => r,
             EsResultItem::Create(r) => r,
+            EsResultItem::Update(r) => r,
+            EsResultItem::Delete(r) => r,
         }
     }
 }
 
 #[derive(Deserialize, Debug)]
 struct EsIndexResult {
+    #[serde(default)]
+    status: u16,
     error: Option<EsErrorDetails>,
 }
 
@@ -102,10 +111,61 @@ fn get_error_reason(body: &str) -> String {
     }
 }
 
+/// The items of a bulk request that failed.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct BulkFailures {
+    /// The positions of the items that failed with a transient error, and can be sent again.
+    pub(super) retriable: Vec<usize>,
+    /// Whether some items failed with an error that sending them again won't solve.
+    pub(super) permanent: bool,
+}
+
+/// Finds the failed items of the response to a bulk request of `items` items. A response that failed as a whole fails
+/// all of its items.
+pub(super) fn bulk_failures(response: &Response<Bytes>, items: usize) -> BulkFailures {
+    let status = response.status();
+    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        return BulkFailures {
+            retriable: (0..items).collect(),
+            permanent: false,
+        };
+    }
+    if !status.is_success() {
+        return BulkFailures {
+            retriable: Vec::new(),
+            permanent: true,
+        };
+    }
+
+    let body = String::from_utf8_lossy(response.body());
+    if !body.contains("\"errors\":true") {
+        return BulkFailures::default();
+    }
+    match serde_json::from_str::<EsResultResponse>(&body) {
+        Ok(result) => {
+            let mut failures = BulkFailures::default();
+            for (position, item) in result.items.into_iter().enumerate() {
+                let item = item.result();
+                if item.error.is_none() {
+                    continue;
+                }
+                if item.status == StatusCode::TOO_MANY_REQUESTS.as_u16() || item.status >= 500 {
+                    failures.retriable.push(position);
+                } else {
+                    failures.permanent = true;
+                }
+            }
+            failures
+        }
+        Err(_) => BulkFailures {
+            retriable: Vec::new(),
+            permanent: true,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use bytes::Bytes;
-    use http::Response;
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -143,4 +203,32 @@ mod tests {
         let reason = get_error_reason(json);
         assert_eq!(reason, "error type: mapper_parsing_exception, reason: object mapping for [host] tried to parse field [host] as object, but found a concrete value");
     }
+
+    #[test]
+    fn finds_bulk_failures() {
+        let json = r#"{"took":3,"errors":true,"items":[{"index":{"_index":"logs","_id":"1","status":201}},{"update":{"_index":"logs","_id":"2","status":429,"error":{"type":"es_rejected_execution_exception","reason":"rejected execution"}}},{"delete":{"_index":"logs","_id":"3","status":409,"error":{"type":"version_conflict_engine_exception","reason":"version conflict"}}},{"create":{"_index":"logs","_id":"4","status":503,"error":{"type":"unavailable_shards_exception","reason":"primary shard is not active"}}}]}"#;
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+        assert_eq!(
+            bulk_failures(&response, 4),
+            BulkFailures {
+                retriable: vec![1, 3],
+                permanent: true,
+            }
+        );
+
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(r#"{"took":3,"errors":false,"items":[]}"#))
+            .unwrap();
+        assert_eq!(bulk_failures(&response, 2), BulkFailures::default());
+
+        let response = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .body(Bytes::new())
+            .unwrap();
+        assert_eq!(bulk_failures(&response, 2).retriable, vec![0, 1]);
+    }
 }
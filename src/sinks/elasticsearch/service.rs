@@ -1,15 +1,18 @@
 use std::{
+    cmp,
     collections::HashMap,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use aws_types::credentials::SharedCredentialsProvider;
 use aws_types::region::Region;
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use http::{Response, Uri};
+use http::{Response, StatusCode, Uri};
 use hyper::{service::Service, Body, Request};
+use tokio::time::sleep;
 use tower::ServiceExt;
 use vector_core::{internal_event::EventsSent, stream::DriverResponse, ByteSizeOf};
 
@@ -17,10 +20,18 @@ use crate::sinks::elasticsearch::sign_request;
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
-    internal_events::ElasticsearchResponseError,
-    sinks::util::{
-        http::{HttpBatchService, RequestConfig},
-        Compression, ElementCount,
+    internal_events::{
+        ElasticsearchPartialRetry, ElasticsearchPartialRetryError, ElasticsearchResponseError,
+    },
+    sinks::{
+        elasticsearch::{
+            encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder,
+            retry::bulk_failures,
+        },
+        util::{
+            http::{HttpBatchService, RequestConfig},
+            Compression, ElementCount, RequestBuilder,
+        },
     },
 };
 
@@ -30,6 +41,8 @@ pub struct ElasticsearchRequest {
     pub finalizers: EventFinalizers,
     pub batch_size: usize,
     pub events_byte_size: usize,
+    /// The events of the request, kept to send the failed items of a bulk request again.
+    pub original_events: Option<Arc<Vec<ProcessedEvent>>>,
 }
 
 impl ByteSizeOf for ElasticsearchRequest {
@@ -56,12 +69,14 @@ pub struct ElasticsearchService {
         BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
         ElasticsearchRequest,
     >,
+    partial_retry: Option<Arc<PartialRetry>>,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        partial_retry: Option<PartialRetry>,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -70,7 +85,34 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            partial_retry: partial_retry.map(Arc::new),
+        }
+    }
+}
+
+/// Sends the items of a bulk request that failed with a transient error again, rather than the whole request.
+pub struct PartialRetry {
+    pub request_builder: ElasticsearchRequestBuilder,
+    pub attempts: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl PartialRetry {
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.max_backoff);
+        cmp::min(backoff, self.max_backoff)
+    }
+
+    fn request(&self, events: Vec<ProcessedEvent>) -> Result<ElasticsearchRequest, crate::Error> {
+        let (metadata, events) = self.request_builder.split_input(events);
+        let payload = self.request_builder.encode_events(events)?;
+        Ok(self.request_builder.build_request(metadata, payload))
     }
 }
 
@@ -147,20 +189,110 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
+    fn call(&mut self, mut req: ElasticsearchRequest) -> Self::Future {
         let mut http_service = self.batch_service.clone();
+        let partial_retry = self.partial_retry.clone();
         Box::pin(async move {
-            http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size = req.events_byte_size;
-            let http_response = http_service.call(req).await?;
-            let event_status = get_event_status(&http_response);
-            Ok(ElasticsearchResponse {
-                event_status,
-                http_response,
-                batch_size,
-                events_byte_size,
-            })
+            let mut attempt = 0;
+            let mut rejected = false;
+            // The response to the first request, once its failed items are being retried. Since the request as a whole
+            // succeeded, returning it stops the retry policy from sending items that were already indexed again.
+            let mut first_response = None;
+            loop {
+                let original_events = req.original_events.take();
+                let result = match http_service.ready().await {
+                    Ok(_) => http_service.call(req).await,
+                    Err(error) => Err(error),
+                };
+                let http_response = match result {
+                    Ok(http_response) => http_response,
+                    Err(error) => match first_response {
+                        Some(first_response) => {
+                            emit!(ElasticsearchPartialRetryError { error });
+                            return Ok(ElasticsearchResponse {
+                                event_status: EventStatus::Errored,
+                                http_response: first_response,
+                                batch_size,
+                                events_byte_size,
+                            });
+                        }
+                        None => return Err(error),
+                    },
+                };
+
+                // A first request that failed as a whole is retried as a whole, by the retry policy.
+                let retry = partial_retry
+                    .as_ref()
+                    .filter(|_| attempt > 0 || http_response.status().is_success());
+                if let (Some(retry), Some(events)) = (retry, original_events) {
+                    let failures = bulk_failures(&http_response, events.len());
+                    if failures.permanent && !rejected {
+                        emit!(ElasticsearchResponseError::new(
+                            "Response contained errors.",
+                            &http_response
+                        ));
+                        rejected = true;
+                    }
+                    if !failures.retriable.is_empty() && (attempt as usize) < retry.attempts {
+                        emit!(ElasticsearchPartialRetry {
+                            failed: failures.retriable.len(),
+                            total: events.len(),
+                        });
+                        sleep(retry.backoff(attempt)).await;
+                        attempt += 1;
+                        first_response.get_or_insert(http_response);
+
+                        let failed = failures
+                            .retriable
+                            .into_iter()
+                            .filter_map(|position| events.get(position).cloned())
+                            .collect();
+                        req = match retry.request(failed) {
+                            Ok(req) => req,
+                            Err(error) => {
+                                emit!(ElasticsearchPartialRetryError { error });
+                                return Ok(ElasticsearchResponse {
+                                    event_status: EventStatus::Errored,
+                                    http_response: first_response.expect("first response is set"),
+                                    batch_size,
+                                    events_byte_size,
+                                });
+                            }
+                        };
+                        continue;
+                    }
+                }
+
+                // The retried items failed as a whole, after the last attempt.
+                let status = http_response.status();
+                if let Some(first_response) = first_response {
+                    if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                        emit!(ElasticsearchResponseError::new(
+                            "Retrying failed bulk items failed.",
+                            &http_response
+                        ));
+                        return Ok(ElasticsearchResponse {
+                            event_status: EventStatus::Errored,
+                            http_response: first_response,
+                            batch_size,
+                            events_byte_size,
+                        });
+                    }
+                }
+
+                let mut event_status = get_event_status(&http_response);
+                if rejected && event_status == EventStatus::Delivered {
+                    event_status = EventStatus::Rejected;
+                }
+                return Ok(ElasticsearchResponse {
+                    event_status,
+                    http_response,
+                    batch_size,
+                    events_byte_size,
+                });
+            }
         })
     }
 }
@@ -11,6 +11,7 @@ use vector_core::{
 use crate::{
     codecs::Transformer,
     event::{Event, LogEvent, Value},
+    internal_events::ElasticsearchMissingId,
     sinks::{
         elasticsearch::{
             encoder::ProcessedEvent, request_builder::ElasticsearchRequestBuilder,
@@ -99,6 +100,7 @@ pub fn process_log(
 ) -> Option<ProcessedEvent> {
     let index = mode.index(&log)?;
     let bulk_action = mode.bulk_action(&log)?;
+    let options = mode.bulk_action_options(&log)?;
 
     if let Some(cfg) = mode.as_data_stream_config() {
         cfg.sync_fields(&mut log);
@@ -112,11 +114,24 @@ pub fn process_log(
     } else {
         None
     };
+    // Only `index` and `create` let Elasticsearch generate the id of the document.
+    if id.is_none()
+        && matches!(
+            bulk_action,
+            BulkAction::Update | BulkAction::Upsert | BulkAction::Delete
+        )
+    {
+        emit!(ElasticsearchMissingId {
+            action: bulk_action.as_str(),
+        });
+        return None;
+    }
     Some(ProcessedEvent {
         index,
         bulk_action,
         log,
         id,
+        options,
     })
 }
 
@@ -1,16 +1,38 @@
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use serde_json::json;
+use tower::Service;
+use warp::Filter;
 
 use crate::{
     codecs::Transformer,
-    event::{LogEvent, Metric, MetricKind, MetricValue, Value},
+    config::ProxyConfig,
+    event::{EventStatus, LogEvent, Metric, MetricKind, MetricValue, Value},
+    http::HttpClient,
     sinks::{
         elasticsearch::{
-            sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchCommon,
-            ElasticsearchConfig, ElasticsearchMode,
+            retry::ElasticsearchRetryLogic,
+            service::{
+                ElasticsearchResponse, ElasticsearchService, HttpRequestBuilder, PartialRetry,
+            },
+            sink::process_log,
+            BulkAction, BulkConfig, DataStreamConfig, ElasticsearchCommon, ElasticsearchConfig,
+            ElasticsearchMode, VersionType,
+        },
+        util::{
+            encoding::Encoder,
+            retries::{RetryAction, RetryLogic},
+            RequestBuilder,
         },
-        util::encoding::Encoder,
     },
     template::Template,
+    test_util::next_addr,
 };
 
 #[tokio::test]
@@ -23,6 +45,7 @@ async fn sets_create_action_when_configured() {
         bulk: Some(BulkConfig {
             action: Some(String::from("{{ action }}te")),
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         ..Default::default()
@@ -70,6 +93,7 @@ async fn encode_datastream_mode() {
         bulk: Some(BulkConfig {
             action: None,
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         mode: ElasticsearchMode::DataStream,
@@ -111,6 +135,7 @@ async fn encode_datastream_mode_no_routing() {
         bulk: Some(BulkConfig {
             action: None,
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         mode: ElasticsearchMode::DataStream,
@@ -152,6 +177,7 @@ async fn handle_metrics() {
         bulk: Some(BulkConfig {
             action: Some(String::from("create")),
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         ..Default::default()
@@ -193,6 +219,7 @@ async fn decode_bulk_action_error() {
         bulk: Some(BulkConfig {
             action: Some(String::from("{{ action }}")),
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         ..Default::default()
@@ -212,6 +239,7 @@ async fn decode_bulk_action() {
         bulk: Some(BulkConfig {
             action: Some(String::from("create")),
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         ..Default::default()
@@ -233,6 +261,7 @@ async fn encode_datastream_mode_no_sync() {
         bulk: Some(BulkConfig {
             action: None,
             index: Some(String::from("vector")),
+            ..Default::default()
         }),
         endpoint: String::from("https://example.com"),
         mode: ElasticsearchMode::DataStream,
@@ -276,6 +305,7 @@ async fn allows_using_excepted_fields() {
         bulk: Some(BulkConfig {
             action: None,
             index: Some(String::from("{{ idx }}")),
+            ..Default::default()
         }),
         encoding: Transformer::new(
             None,
@@ -308,3 +338,256 @@ async fn allows_using_excepted_fields() {
     assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
     assert_eq!(encoded.len(), encoded_size);
 }
+
+#[tokio::test]
+async fn encodes_bulk_action_options() {
+    let config = ElasticsearchConfig {
+        bulk: Some(BulkConfig {
+            action: Some(String::from("{{ action }}")),
+            index: Some(String::from("vector")),
+            routing: Some(String::from("{{ tenant }}")),
+            pipeline: Some(String::from("enrich-{{ tenant }}")),
+            version: Some(String::from("{{ sequence }}")),
+            version_type: Some(VersionType::External),
+        }),
+        endpoint: String::from("https://example.com"),
+        suppress_type_name: true,
+        encoding: Transformer::new(
+            None,
+            Some(vec!["action".to_string(), "sequence".to_string()]),
+            None,
+        )
+        .unwrap(),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+
+    let event = |action: &str, id: &str| {
+        let mut log = LogEvent::from("hello there");
+        log.insert("action", action);
+        log.insert("tenant", "acme");
+        log.insert("sequence", 42);
+        log.insert("id", id);
+        process_log(log, &es.mode, &Some("id".to_string())).unwrap()
+    };
+
+    let mut encoded = vec![];
+    let encoded_size = es
+        .request_builder
+        .encoder
+        .encode_input(
+            vec![
+                event("index", "1"),
+                event("upsert", "2"),
+                event("update", "3"),
+                event("delete", "4"),
+            ],
+            &mut encoded,
+        )
+        .unwrap();
+
+    // Updates don't accept a pipeline nor a version, and deletions don't accept a pipeline.
+    let expected = r#"{"index":{"_index":"vector","_id":"1","routing":"acme","pipeline":"enrich-acme","version":42,"version_type":"external"}}
+{"message":"hello there","tenant":"acme"}
+{"update":{"_index":"vector","_id":"2","routing":"acme"}}
+{"doc":{"message":"hello there","tenant":"acme"},"doc_as_upsert":true}
+{"update":{"_index":"vector","_id":"3","routing":"acme"}}
+{"doc":{"message":"hello there","tenant":"acme"}}
+{"delete":{"_index":"vector","_id":"4","routing":"acme","version":42,"version_type":"external"}}
+"#;
+    assert_eq!(std::str::from_utf8(&encoded).unwrap(), expected);
+    assert_eq!(encoded.len(), encoded_size);
+}
+
+#[tokio::test]
+async fn escapes_bulk_action_metadata() {
+    let config = ElasticsearchConfig {
+        bulk: Some(BulkConfig {
+            index: Some(String::from("vector")),
+            routing: Some(String::from("{{ tenant }}")),
+            ..Default::default()
+        }),
+        endpoint: String::from("https://example.com"),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+
+    let mut log = LogEvent::from("hello there");
+    log.insert("tenant", r#"a"b"#);
+
+    let mut encoded = vec![];
+    es.request_builder
+        .encoder
+        .encode_input(
+            vec![process_log(log, &es.mode, &None).unwrap()],
+            &mut encoded,
+        )
+        .unwrap();
+
+    let action = std::str::from_utf8(&encoded)
+        .unwrap()
+        .lines()
+        .next()
+        .unwrap();
+    let action: serde_json::Value = serde_json::from_str(action).unwrap();
+    assert_eq!(action["index"]["routing"], r#"a"b"#);
+}
+
+#[tokio::test]
+async fn drops_events_with_invalid_version() {
+    let config = ElasticsearchConfig {
+        bulk: Some(BulkConfig {
+            index: Some(String::from("vector")),
+            version: Some(String::from("{{ sequence }}")),
+            ..Default::default()
+        }),
+        endpoint: String::from("https://example.com"),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+
+    let mut log = LogEvent::from("hello there");
+    log.insert("sequence", "latest");
+    assert!(process_log(log, &es.mode, &None).is_none());
+
+    let log = LogEvent::from("hello there");
+    assert!(process_log(log, &es.mode, &None).is_none());
+}
+
+#[tokio::test]
+async fn drops_events_without_id_when_action_requires_one() {
+    let config = ElasticsearchConfig {
+        bulk: Some(BulkConfig {
+            action: Some(String::from("{{ action }}")),
+            index: Some(String::from("vector")),
+            ..Default::default()
+        }),
+        endpoint: String::from("https://example.com"),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+    let id_key = Some("id".to_string());
+
+    for action in ["update", "upsert", "delete"] {
+        let mut log = LogEvent::from("hello there");
+        log.insert("action", action);
+        assert!(process_log(log.clone(), &es.mode, &id_key).is_none());
+
+        log.insert("id", "1");
+        assert!(process_log(log, &es.mode, &id_key).is_some());
+    }
+
+    for action in ["index", "create"] {
+        let mut log = LogEvent::from("hello there");
+        log.insert("action", action);
+        assert!(process_log(log, &es.mode, &id_key).is_some());
+    }
+}
+
+/// Serves bulk requests, recording their bodies, and rejecting the second item of the first request with a transient
+/// error. Later requests are answered with `retry_status`.
+fn serve_bulk(retry_status: warp::http::StatusCode) -> (String, Arc<Mutex<Vec<String>>>) {
+    let addr = next_addr();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let bulk = {
+        let requests = Arc::clone(&requests);
+        warp::path!("_bulk")
+            .and(warp::post())
+            .and(warp::body::bytes())
+            .map(move |body: Bytes| {
+                let mut requests = requests.lock().unwrap();
+                requests.push(String::from_utf8(body.to_vec()).unwrap());
+                let (response, status) = if requests.len() == 1 {
+                    let response = json!({
+                        "errors": true,
+                        "items": [
+                            { "index": { "status": 201 } },
+                            { "index": { "status": 429, "error": { "type": "es_rejected_execution_exception", "reason": "rejected execution" } } },
+                        ],
+                    });
+                    (response, warp::http::StatusCode::OK)
+                } else {
+                    let response = json!({ "errors": false, "items": [{ "index": { "status": 201 } }] });
+                    (response, retry_status)
+                };
+                warp::reply::with_status(warp::reply::json(&response), status)
+            })
+    };
+    tokio::spawn(warp::serve(bulk).run(addr));
+
+    (format!("http://{}", addr), requests)
+}
+
+/// Sends the events through a service retrying the failed items of bulk requests once.
+async fn send_with_partial_retry(endpoint: String, messages: &[&str]) -> ElasticsearchResponse {
+    let config = ElasticsearchConfig {
+        bulk: Some(BulkConfig {
+            index: Some(String::from("vector")),
+            ..Default::default()
+        }),
+        endpoint,
+        request_retry_partial: true,
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_config(&config).await.unwrap();
+
+    let http_request_builder = HttpRequestBuilder {
+        bulk_uri: es.bulk_uri.clone(),
+        query_params: es.query_params.clone(),
+        region: None,
+        compression: config.compression,
+        http_request_config: es.request.clone(),
+        http_auth: None,
+        credentials_provider: None,
+    };
+    let partial_retry = PartialRetry {
+        request_builder: es.request_builder.clone(),
+        attempts: 1,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(10),
+    };
+    let mut service = ElasticsearchService::new(
+        HttpClient::new(None, &ProxyConfig::default()).unwrap(),
+        http_request_builder,
+        Some(partial_retry),
+    );
+
+    let events = messages
+        .iter()
+        .map(|message| process_log(LogEvent::from(*message), &es.mode, &None).unwrap())
+        .collect();
+    let (metadata, events) = es.request_builder.split_input(events);
+    let payload = es.request_builder.encode_events(events).unwrap();
+    let request = es.request_builder.build_request(metadata, payload);
+
+    service.call(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn retries_failed_bulk_items() {
+    let (endpoint, requests) = serve_bulk(warp::http::StatusCode::OK);
+
+    let response = send_with_partial_retry(endpoint, &["first", "second"]).await;
+    assert_eq!(response.event_status, EventStatus::Delivered);
+    assert_eq!(response.batch_size, 2);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].lines().count(), 4);
+    assert_eq!(requests[1].lines().count(), 2);
+    assert!(requests[1].contains("second"));
+    assert!(!requests[1].contains("first"));
+}
+
+#[tokio::test]
+async fn failed_bulk_item_retry_is_not_retried_as_a_whole() {
+    let (endpoint, requests) = serve_bulk(warp::http::StatusCode::SERVICE_UNAVAILABLE);
+
+    let response = send_with_partial_retry(endpoint, &["first", "second"]).await;
+    assert_eq!(response.event_status, EventStatus::Errored);
+    assert!(matches!(
+        ElasticsearchRetryLogic.should_retry_response(&response),
+        RetryAction::DontRetry(_)
+    ));
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
@@ -21,7 +21,7 @@ use tokio_util::codec::Encoder as _;
 use vector_core::{internal_event::EventsSent, ByteSizeOf};
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType, Transformer},
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext,
         SinkDescription,
@@ -154,6 +154,10 @@ impl SinkConfig for FileSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(self.encoding.encoding())
+    }
 }
 
 #[derive(Debug)]
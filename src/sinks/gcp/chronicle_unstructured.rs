@@ -170,6 +170,10 @@ impl SinkConfig for ChronicleUnstructuredConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl ChronicleUnstructuredConfig {
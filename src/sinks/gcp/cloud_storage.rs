@@ -13,7 +13,7 @@ use uuid::Uuid;
 use vector_core::event::{EventFinalizers, Finalizable};
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType, Transformer},
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext,
         SinkDescription,
@@ -150,6 +150,10 @@ impl SinkConfig for GcsSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(self.encoding.encoding())
+    }
 }
 
 impl GcsSinkConfig {
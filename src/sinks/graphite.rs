@@ -0,0 +1,477 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use bytes::{BufMut, BytesMut};
+use chrono::Utc;
+use derivative::Derivative;
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+    },
+    event::{
+        metric::{Metric, MetricTags, MetricValue},
+        Event,
+    },
+    internal_events::{GraphiteInvalidMetricError, TemplateRenderingError},
+    sinks::util::{encode_namespace, tcp::TcpSinkConfig, udp::UdpSinkConfig},
+    template::Template,
+};
+
+/// Configuration for the `graphite` sink.
+#[configurable_component(sink)]
+#[derive(Clone, Debug)]
+// TODO: add back when serde-rs/serde#1358 is addressed
+// #[serde(deny_unknown_fields)]
+pub struct GraphiteSinkConfig {
+    /// The namespace of metrics that have none, which is prefixed to their name.
+    ///
+    /// Ignored when `path_template` is set.
+    pub default_namespace: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: GraphiteFormat,
+
+    /// A template to build the path of metrics from their name, namespace and tags, such as
+    /// `{{ tags.host }}.{{ namespace }}.{{ name }}`.
+    ///
+    /// The tags are flattened into path nodes instead of being sent as a tagged series. Tag values have `.` and
+    /// whitespace replaced with `_`, so that each one stays a single path node. Metrics missing a field referred to by
+    /// the template are dropped.
+    pub path_template: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub mode: Mode,
+}
+
+/// The type of socket to use.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Mode {
+    /// Send metrics over TCP.
+    Tcp(#[configurable(derived)] TcpSinkConfig),
+
+    /// Send metrics over UDP.
+    Udp(#[configurable(derived)] UdpSinkConfig),
+}
+
+/// The Carbon protocol to encode metrics with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphiteFormat {
+    /// The Carbon plaintext protocol, one `<path> <value> <timestamp>` line per metric.
+    #[derivative(Default)]
+    Plaintext,
+
+    /// The Carbon pickle protocol, as accepted by the pickle receivers of Carbon daemons.
+    Pickle,
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("The `pickle` format is only supported in `tcp` mode."))]
+    PickleOverUdp,
+}
+
+inventory::submit! {
+    SinkDescription::new::<GraphiteSinkConfig>("graphite")
+}
+
+fn default_address() -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 2003)
+}
+
+impl GenerateConfig for GraphiteSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(&Self {
+            default_namespace: None,
+            format: GraphiteFormat::Plaintext,
+            path_template: None,
+            mode: Mode::Tcp(TcpSinkConfig::from_address(default_address().to_string())),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "graphite")]
+impl SinkConfig for GraphiteSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let encoder = GraphiteEncoder {
+            default_namespace: self.default_namespace.clone(),
+            format: self.format,
+            path_template: self.path_template.clone(),
+        };
+        match &self.mode {
+            Mode::Tcp(config) => config.build(Default::default(), encoder),
+            Mode::Udp(config) => {
+                if self.format == GraphiteFormat::Pickle {
+                    return Err(Box::new(BuildError::PickleOverUdp));
+                }
+                config.build(Default::default(), encoder)
+            }
+        }
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "graphite"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        None
+    }
+}
+
+/// Replaces the characters Graphite treats as separators so a tag can't split a path or series.
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            ';' | '=' | '~' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn encode_tags(tags: &MetricTags) -> String {
+    // https://graphite.readthedocs.io/en/latest/tags.html#carbon
+    // `tags` is already sorted by key because of BTreeMap
+    tags.iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| format!(";{}={}", sanitize(name), sanitize(value)))
+        .collect()
+}
+
+fn encode_pickle(path: &str, timestamp: f64, value: f64, bytes: &mut BytesMut) {
+    // https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-pickle-protocol
+    //
+    // The payload is a protocol 2 pickle of `[(path, (timestamp, value))]`, prefixed by its
+    // length as a big-endian u32. It only uses opcodes accepted by Carbon's safe unpickler.
+    let mut payload = BytesMut::new();
+    payload.put_slice(b"\x80\x02"); // PROTO 2
+    payload.put_u8(b']'); // EMPTY_LIST
+    payload.put_u8(b'X'); // BINUNICODE
+    payload.put_u32_le(path.len() as u32);
+    payload.put_slice(path.as_bytes());
+    payload.put_u8(b'G'); // BINFLOAT
+    payload.put_f64(timestamp);
+    payload.put_u8(b'G'); // BINFLOAT
+    payload.put_f64(value);
+    payload.put_u8(b'\x86'); // TUPLE2, the datapoint
+    payload.put_u8(b'\x86'); // TUPLE2, the path and datapoint
+    payload.put_u8(b'a'); // APPEND
+    payload.put_u8(b'.'); // STOP
+
+    bytes.put_u32(payload.len() as u32);
+    bytes.put_slice(&payload);
+}
+
+#[derive(Debug, Clone)]
+struct GraphiteEncoder {
+    default_namespace: Option<String>,
+    format: GraphiteFormat,
+    path_template: Option<Template>,
+}
+
+impl GraphiteEncoder {
+    fn encode_path(&self, metric: &Metric) -> Option<String> {
+        match &self.path_template {
+            Some(template) => {
+                // Sanitize tag values first so flattening a tag can't introduce extra path nodes.
+                let mut flattened = metric.clone();
+                if let Some(tags) = metric.tags() {
+                    for (name, value) in tags {
+                        flattened.insert_tag(name.clone(), sanitize(value).replace('.', "_"));
+                    }
+                }
+                match template.render_string(&flattened) {
+                    Ok(path) => Some(sanitize(&path)),
+                    Err(error) => {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("path_template"),
+                            drop_event: true,
+                        });
+                        None
+                    }
+                }
+            }
+            None => {
+                let path = encode_namespace(
+                    metric.namespace().or(self.default_namespace.as_deref()),
+                    '.',
+                    sanitize(metric.name()),
+                );
+                let tags = metric.tags().map(encode_tags).unwrap_or_default();
+                Some(path + &tags)
+            }
+        }
+    }
+}
+
+impl Encoder<Event> for GraphiteEncoder {
+    type Error = codecs::encoding::Error;
+
+    fn encode(&mut self, event: Event, bytes: &mut BytesMut) -> Result<(), Self::Error> {
+        let metric = event.as_metric();
+        let value = match metric.value() {
+            MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+            _ => {
+                emit!(GraphiteInvalidMetricError {
+                    value: metric.value(),
+                    kind: &metric.kind(),
+                });
+
+                return Ok(());
+            }
+        };
+
+        let path = match self.encode_path(metric) {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let timestamp = metric.timestamp().unwrap_or_else(Utc::now);
+
+        match self.format {
+            GraphiteFormat::Plaintext => {
+                let line = format!("{} {} {}\n", path, value, timestamp.timestamp());
+                bytes.put_slice(line.as_bytes());
+            }
+            GraphiteFormat::Pickle => {
+                let timestamp = timestamp.timestamp_millis() as f64 / 1000.0;
+                encode_pickle(&path, timestamp, value, bytes);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use futures::stream;
+    #[cfg(feature = "sources-graphite")]
+    use {crate::sources::graphite::parser::parse, std::str::from_utf8};
+
+    use super::*;
+    use crate::{
+        event::metric::{MetricKind, StatisticKind},
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr, trace_init, CountReceiver,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GraphiteSinkConfig>();
+    }
+
+    fn encoder(format: GraphiteFormat, path_template: Option<&str>) -> GraphiteEncoder {
+        GraphiteEncoder {
+            default_namespace: None,
+            format,
+            path_template: path_template.map(|template| Template::try_from(template).unwrap()),
+        }
+    }
+
+    fn gauge() -> Metric {
+        Metric::new(
+            "cpu.load",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        )
+        .with_namespace(Some("system"))
+        .with_tags(Some(
+            vec![
+                ("host".to_owned(), "web01.example.com".to_owned()),
+                ("region".to_owned(), "us east".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+        ))
+        .with_timestamp(Some(Utc.timestamp(1660000000, 0)))
+    }
+
+    fn encode(mut encoder: GraphiteEncoder, metric: Metric) -> BytesMut {
+        let mut bytes = BytesMut::new();
+        encoder.encode(Event::Metric(metric), &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn encode_plaintext_tagged_series() {
+        assert_eq!(
+            &encode(encoder(GraphiteFormat::Plaintext, None), gauge())[..],
+            b"system.cpu.load;host=web01.example.com;region=us_east 1.5 1660000000\n"
+        );
+    }
+
+    #[test]
+    fn encode_plaintext_default_namespace() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_timestamp(Some(Utc.timestamp(1660000000, 0)));
+        let mut encoder = encoder(GraphiteFormat::Plaintext, None);
+        encoder.default_namespace = Some("app".into());
+
+        assert_eq!(
+            &encode(encoder, metric)[..],
+            b"app.requests 10 1660000000\n"
+        );
+    }
+
+    #[test]
+    fn encode_plaintext_path_template() {
+        let encoder = encoder(
+            GraphiteFormat::Plaintext,
+            Some("{{ namespace }}.{{ tags.region }}.{{ tags.host }}.{{ name }}"),
+        );
+
+        assert_eq!(
+            &encode(encoder, gauge())[..],
+            b"system.us_east.web01_example_com.cpu.load 1.5 1660000000\n"
+        );
+    }
+
+    #[test]
+    fn encode_path_template_missing_tag() {
+        let encoder = encoder(
+            GraphiteFormat::Plaintext,
+            Some("{{ tags.missing }}.{{ name }}"),
+        );
+
+        assert!(encode(encoder, gauge()).is_empty());
+    }
+
+    #[test]
+    fn encode_unsupported_metric() {
+        let metric = Metric::new(
+            "distribution",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        );
+
+        assert!(encode(encoder(GraphiteFormat::Plaintext, None), metric).is_empty());
+    }
+
+    #[test]
+    fn encode_pickle() {
+        let metric = Metric::new(
+            "foo",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 2.0 },
+        )
+        .with_timestamp(Some(Utc.timestamp(1660000000, 500_000_000)));
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(b"\x80\x02]X\x03\x00\x00\x00foo");
+        expected.push(b'G');
+        expected.extend_from_slice(&1660000000.5f64.to_be_bytes());
+        expected.push(b'G');
+        expected.extend_from_slice(&2.0f64.to_be_bytes());
+        expected.extend_from_slice(b"\x86\x86a.");
+        let mut framed = (expected.len() as u32).to_be_bytes().to_vec();
+        framed.extend_from_slice(&expected);
+
+        assert_eq!(
+            &encode(encoder(GraphiteFormat::Pickle, None), metric)[..],
+            &framed[..]
+        );
+    }
+
+    #[cfg(feature = "sources-graphite")]
+    #[test]
+    fn encode_plaintext_roundtrip() {
+        let metric1 = Metric::new(
+            "disk.used",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 42.5 },
+        )
+        .with_tags(Some(
+            vec![("datacenter".to_owned(), "dc1".to_owned())]
+                .into_iter()
+                .collect(),
+        ))
+        .with_timestamp(Some(Utc.timestamp(1660000000, 0)));
+
+        let frame = encode(encoder(GraphiteFormat::Plaintext, None), metric1.clone());
+        let metric2 = parse(from_utf8(&frame).unwrap().trim()).unwrap();
+        vector_common::assert_event_data_eq!(metric1, metric2);
+    }
+
+    #[tokio::test]
+    async fn pickle_requires_tcp() {
+        let config = GraphiteSinkConfig {
+            default_namespace: None,
+            format: GraphiteFormat::Pickle,
+            path_template: None,
+            mode: Mode::Udp(UdpSinkConfig::from_address(next_addr().to_string())),
+        };
+
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_to_graphite() {
+        trace_init();
+
+        let addr = next_addr();
+        let config = GraphiteSinkConfig {
+            default_namespace: Some("ns".into()),
+            format: GraphiteFormat::Plaintext,
+            path_template: None,
+            mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
+        };
+
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let mut receiver = CountReceiver::receive_lines(addr);
+
+        let events = vec![
+            Event::Metric(gauge()),
+            Event::Metric(
+                Metric::new(
+                    "counter",
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 3.0 },
+                )
+                .with_timestamp(Some(Utc.timestamp(1660000010, 0))),
+            ),
+        ];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+        receiver.connected().await;
+        let output = receiver.await;
+        assert_eq!(
+            output,
+            vec![
+                "system.cpu.load;host=web01.example.com;region=us_east 1.5 1660000000",
+                "ns.counter 3 1660000010",
+            ]
+        );
+    }
+}
@@ -125,6 +125,10 @@ impl SinkConfig for LokiConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 pub fn valid_label_name(label: &Template) -> bool {
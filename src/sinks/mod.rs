@@ -46,6 +46,8 @@ pub mod file;
 pub mod gcp;
 #[cfg(any(feature = "sinks-gcp"))]
 pub mod gcs_common;
+#[cfg(feature = "sinks-graphite")]
+pub mod graphite;
 #[cfg(feature = "sinks-honeycomb")]
 pub mod honeycomb;
 #[cfg(feature = "sinks-http")]
@@ -68,6 +70,8 @@ pub mod new_relic;
 pub mod new_relic_logs;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
@@ -212,6 +216,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-gcp")]
     GcpPubsub(#[configurable(derived)] gcp::pubsub::PubsubConfig),
 
+    /// Graphite.
+    #[cfg(feature = "sinks-graphite")]
+    Graphite(#[configurable(derived)] graphite::GraphiteSinkConfig),
+
     /// Honeycomb.
     #[cfg(feature = "sinks-honeycomb")]
     Honeycomb(#[configurable(derived)] honeycomb::HoneycombConfig),
@@ -264,6 +272,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-papertrail")]
     Papertrail(#[configurable(derived)] papertrail::PapertrailConfig),
 
+    /// PostgreSQL.
+    #[cfg(feature = "sinks-postgres")]
+    Postgres(#[configurable(derived)] postgres::PostgresConfig),
+
     /// Prometheus Exporter.
     #[cfg(feature = "sinks-prometheus")]
     PrometheusExporter(#[configurable(derived)] prometheus::exporter::PrometheusExporterConfig),
@@ -106,6 +106,10 @@ impl SinkConfig for NatsSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl std::convert::TryFrom<&NatsSinkConfig> for nats::asynk::Options {
@@ -98,6 +98,10 @@ impl SinkConfig for PapertrailConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         None
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,1145 @@
+use std::{
+    error::Error as StdError,
+    path::PathBuf,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{future::BoxFuture, pin_mut, stream, FutureExt, SinkExt, StreamExt};
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslConnector, SslMethod},
+};
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tokio_postgres::{
+    binary_copy::BinaryCopyInWriter,
+    types::{to_sql_checked, IsNull, ToSql, Type},
+    Client, Config, Error as PgError, NoTls,
+};
+use tower::{Service, ServiceBuilder};
+use vector_common::internal_event::BytesSent;
+use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+    },
+    event::{Event, Value},
+    internal_events::{PostgresColumnCoercionError, PostgresSendError},
+    sinks::util::{
+        batch::BatchConfig,
+        retries::{RetryAction, RetryLogic},
+        sink::Response,
+        BatchSink, Concurrency, EncodedEvent, EncodedLength, ServiceBuilderExt, SinkBatchSettings,
+        TowerRequestConfig, VecBuffer,
+    },
+};
+
+/// The maximum number of parameters PostgreSQL accepts in a single statement.
+const MAX_PARAMETERS: usize = 65_535;
+
+inventory::submit! {
+    SinkDescription::new::<PostgresConfig>("postgres")
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid endpoint: {}", source))]
+    InvalidEndpoint { source: PgError },
+    #[snafu(display("at least one column must be configured"))]
+    NoColumns,
+    #[snafu(display("failed to create tls connector: {}", source))]
+    TlsFailed { source: ErrorStack },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("failed to connect: {}", source))]
+    ConnectionFailed { source: PgError },
+    #[snafu(display("failed to query table: {}", source))]
+    QueryFailed { source: PgError },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PostgresDefaultBatchSettings;
+
+impl SinkBatchSettings for PostgresDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration of TLS when connecting to PostgreSQL.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresTlsConfig {
+    /// Absolute path to an additional CA certificate file.
+    ///
+    /// The certficate must be in the DER or PEM (X.509) format.
+    ca_file: PathBuf,
+}
+
+/// How rows are written to the table.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WriteMode {
+    /// Rows are written with `COPY ... FROM STDIN` in the binary format.
+    ///
+    /// This is the fastest mode, but the type of each column must match its configured type exactly.
+    #[derivative(Default)]
+    Copy,
+
+    /// Rows are written with multi-row `INSERT` statements.
+    ///
+    /// Values are cast to the type of their column by PostgreSQL, so the configured type only needs to be assignable
+    /// to it.
+    Insert,
+}
+
+/// The type a field is coerced to before it's written to a column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    /// Text. Objects and arrays are written as JSON.
+    #[derivative(Default)]
+    Text,
+
+    /// A 64-bit integer. Strings are parsed, and floats without a fractional part are converted.
+    Integer,
+
+    /// A 64-bit float. Strings are parsed, and integers are converted.
+    Float,
+
+    /// A boolean. The strings `true` and `false` are parsed.
+    Boolean,
+
+    /// A timestamp with a time zone. Strings are parsed as RFC 3339, and integers as seconds since the Unix epoch.
+    Timestamp,
+
+    /// JSONB. Any value is written as its JSON representation.
+    Json,
+}
+
+impl ColumnType {
+    const fn pg_type(self) -> Type {
+        match self {
+            Self::Text => Type::TEXT,
+            Self::Integer => Type::INT8,
+            Self::Float => Type::FLOAT8,
+            Self::Boolean => Type::BOOL,
+            Self::Timestamp => Type::TIMESTAMPTZ,
+            Self::Json => Type::JSONB,
+        }
+    }
+}
+
+/// A column of the table, and the event field it's written from.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnConfig {
+    /// The name of the column.
+    name: String,
+
+    /// The event field written to the column.
+    ///
+    /// If not set, the field with the same name as the column is used. Missing and `null` fields are written as `NULL`.
+    field: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default, rename = "type")]
+    column_type: ColumnType,
+}
+
+impl ColumnConfig {
+    fn field(&self) -> &str {
+        self.field.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Configuration for the `postgres` sink.
+#[configurable_component(sink)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresConfig {
+    /// The PostgreSQL server to write to.
+    ///
+    /// Must be in the [Connection URI
+    /// format](https://www.postgresql.org/docs/current/libpq-connect.html#id-1.7.3.8.3.6).
+    endpoint: String,
+
+    /// The schema of the table.
+    ///
+    /// If not set, the table is looked up with the server's `search_path`.
+    schema: Option<String>,
+
+    /// The table to write to.
+    table: String,
+
+    /// The columns of the table to write, and the event fields they are written from.
+    columns: Vec<ColumnConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    mode: WriteMode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<PostgresDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    tls: Option<PostgresTlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for PostgresConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "postgres://postgres@localhost:5432/postgres"
+            table = "logs"
+            columns = [
+                { name = "timestamp", type = "timestamp" },
+                { name = "message" },
+            ]
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "postgres")]
+impl SinkConfig for PostgresConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if self.columns.is_empty() {
+            return Err(BuildError::NoColumns.into());
+        }
+
+        let connector = self.build_connector()?;
+        let healthcheck = healthcheck(connector.clone(), self.select_statement()).boxed();
+        let sink = self.new(connector)?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+impl PostgresConfig {
+    fn new(&self, connector: Connector) -> crate::Result<super::VectorSink> {
+        // Batches are written in a transaction on a single connection.
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            concurrency: Concurrency::Fixed(1),
+            ..Default::default()
+        });
+        let batch = self.batch.into_batch_settings()?;
+
+        let service = PostgresService {
+            client: Arc::new(Mutex::new(None)),
+            connector,
+            writer: Arc::new(Writer {
+                table: self.table_name(),
+                columns: self.column_names(),
+                types: self
+                    .columns
+                    .iter()
+                    .map(|column| column.column_type.pg_type())
+                    .collect(),
+                mode: self.mode,
+            }),
+        };
+        let svc = ServiceBuilder::new()
+            .settings(request, PostgresRetryLogic)
+            .service(service);
+
+        let columns = self.columns.clone();
+        let sink = BatchSink::new(svc, VecBuffer::new(batch.size), batch.timeout)
+            .with_flat_map(move |event| stream::iter(encode_event(event, &columns)).map(Ok))
+            .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
+
+        Ok(super::VectorSink::from_event_sink(sink))
+    }
+
+    fn build_connector(&self) -> crate::Result<Connector> {
+        let config = self.endpoint.parse().context(InvalidEndpointSnafu)?;
+        let tls = match &self.tls {
+            Some(tls_config) => {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls_client()).context(TlsFailedSnafu)?;
+                builder
+                    .set_ca_file(tls_config.ca_file.clone())
+                    .context(TlsFailedSnafu)?;
+                Some(MakeTlsConnector::new(builder.build()))
+            }
+            None => None,
+        };
+
+        Ok(Connector { config, tls })
+    }
+
+    fn table_name(&self) -> String {
+        match &self.schema {
+            Some(schema) => format!(
+                "{}.{}",
+                quote_identifier(schema),
+                quote_identifier(&self.table)
+            ),
+            None => quote_identifier(&self.table),
+        }
+    }
+
+    fn column_names(&self) -> String {
+        self.columns
+            .iter()
+            .map(|column| quote_identifier(&column.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn select_statement(&self) -> String {
+        format!(
+            "SELECT {} FROM {} LIMIT 0",
+            self.column_names(),
+            self.table_name()
+        )
+    }
+}
+
+/// Quotes an identifier, so that it's used verbatim rather than folded to lower case.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Checks that the server is reachable, and that the table has the configured columns.
+async fn healthcheck(connector: Connector, select: String) -> crate::Result<()> {
+    let client = connector.connect().await.context(ConnectionFailedSnafu)?;
+    client
+        .query(select.as_str(), &[])
+        .await
+        .context(QueryFailedSnafu)?;
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct Connector {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+}
+
+impl Connector {
+    async fn connect(&self) -> Result<Client, PgError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let (client, connection) = self.config.connect(tls.clone()).await?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self.config.connect(NoTls).await?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+
+        Ok(client)
+    }
+}
+
+/// A value coerced to the type of its column.
+#[derive(Clone, Debug, PartialEq)]
+enum ColumnValue {
+    Text(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Timestamp(DateTime<Utc>),
+    Json(String),
+}
+
+impl ColumnValue {
+    fn coerce(value: &Value, column_type: ColumnType) -> Result<Self, String> {
+        let coerced = match (column_type, value) {
+            (ColumnType::Text, Value::Bytes(bytes)) => {
+                Self::Text(String::from_utf8_lossy(bytes).into_owned())
+            }
+            (ColumnType::Text, Value::Object(_) | Value::Array(_)) => {
+                Self::Text(serde_json::to_string(value).map_err(|error| error.to_string())?)
+            }
+            (ColumnType::Text, value) => Self::Text(value.to_string_lossy()),
+
+            (ColumnType::Integer, Value::Integer(integer)) => Self::Integer(*integer),
+            (ColumnType::Integer, Value::Float(float))
+                if float.fract() == 0.0
+                    && **float >= i64::MIN as f64
+                    && **float < i64::MAX as f64 =>
+            {
+                Self::Integer(float.into_inner() as i64)
+            }
+            (ColumnType::Integer, Value::Bytes(bytes)) => Self::Integer(
+                String::from_utf8_lossy(bytes)
+                    .trim()
+                    .parse()
+                    .map_err(|error| format!("invalid integer: {}", error))?,
+            ),
+
+            (ColumnType::Float, Value::Float(float)) => Self::Float(float.into_inner()),
+            (ColumnType::Float, Value::Integer(integer)) => Self::Float(*integer as f64),
+            (ColumnType::Float, Value::Bytes(bytes)) => Self::Float(
+                String::from_utf8_lossy(bytes)
+                    .trim()
+                    .parse()
+                    .map_err(|error| format!("invalid float: {}", error))?,
+            ),
+
+            (ColumnType::Boolean, Value::Boolean(boolean)) => Self::Boolean(*boolean),
+            (ColumnType::Boolean, Value::Bytes(bytes)) => Self::Boolean(
+                String::from_utf8_lossy(bytes)
+                    .trim()
+                    .parse()
+                    .map_err(|error| format!("invalid boolean: {}", error))?,
+            ),
+
+            (ColumnType::Timestamp, Value::Timestamp(timestamp)) => Self::Timestamp(*timestamp),
+            (ColumnType::Timestamp, Value::Integer(seconds)) => Self::Timestamp(
+                Utc.timestamp_opt(*seconds, 0)
+                    .single()
+                    .ok_or_else(|| format!("timestamp out of range: {}", seconds))?,
+            ),
+            (ColumnType::Timestamp, Value::Bytes(bytes)) => Self::Timestamp(
+                DateTime::parse_from_rfc3339(String::from_utf8_lossy(bytes).trim())
+                    .map_err(|error| format!("invalid timestamp: {}", error))?
+                    .with_timezone(&Utc),
+            ),
+
+            (ColumnType::Json, value) => {
+                Self::Json(serde_json::to_string(value).map_err(|error| error.to_string())?)
+            }
+
+            (column_type, value) => {
+                return Err(format!(
+                    "cannot coerce {} to {:?}",
+                    value.kind(),
+                    column_type
+                ))
+            }
+        };
+
+        Ok(coerced)
+    }
+
+    fn encoded_length(&self) -> usize {
+        match self {
+            Self::Text(text) | Self::Json(text) => text.len(),
+            Self::Integer(_) | Self::Float(_) | Self::Timestamp(_) => 8,
+            Self::Boolean(_) => 1,
+        }
+    }
+}
+
+impl ToSql for ColumnValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        match self {
+            Self::Text(text) => text.to_sql(ty, out),
+            Self::Integer(integer) => integer.to_sql(ty, out),
+            Self::Float(float) => float.to_sql(ty, out),
+            Self::Boolean(boolean) => boolean.to_sql(ty, out),
+            Self::Timestamp(timestamp) => timestamp.to_sql(ty, out),
+            Self::Json(json) => {
+                // The binary format of JSONB is a version number, followed by the JSON text.
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                out.put_slice(json.as_bytes());
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <String as ToSql>::accepts(ty)
+            || <i64 as ToSql>::accepts(ty)
+            || <f64 as ToSql>::accepts(ty)
+            || <bool as ToSql>::accepts(ty)
+            || <DateTime<Utc> as ToSql>::accepts(ty)
+            || matches!(*ty, Type::JSON | Type::JSONB)
+    }
+
+    to_sql_checked!();
+}
+
+/// The values of an event, one per column.
+#[derive(Clone, Debug)]
+struct PostgresRow {
+    values: Vec<Option<ColumnValue>>,
+}
+
+impl EncodedLength for PostgresRow {
+    fn encoded_length(&self) -> usize {
+        self.values
+            .iter()
+            .flatten()
+            .map(ColumnValue::encoded_length)
+            .sum()
+    }
+}
+
+fn encode_event(event: Event, columns: &[ColumnConfig]) -> Option<EncodedEvent<PostgresRow>> {
+    let byte_size = event.size_of();
+    let log = event.into_log();
+
+    let values = columns
+        .iter()
+        .map(|column| match log.get(column.field()) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => ColumnValue::coerce(value, column.column_type)
+                .map(Some)
+                .map_err(|error| {
+                    emit!(PostgresColumnCoercionError {
+                        column: &column.name,
+                        error,
+                    });
+                }),
+        })
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    Some(EncodedEvent::new(PostgresRow { values }, byte_size))
+}
+
+/// Writes batches of rows to the table.
+struct Writer {
+    table: String,
+    columns: String,
+    types: Vec<Type>,
+    mode: WriteMode,
+}
+
+impl Writer {
+    /// Writes the rows in a single transaction, so that either all or none of them are written.
+    async fn write(&self, client: &mut Client, rows: &[PostgresRow]) -> Result<u64, PgError> {
+        let transaction = client.transaction().await?;
+
+        let count = match self.mode {
+            WriteMode::Copy => {
+                let statement = format!(
+                    "COPY {} ({}) FROM STDIN (FORMAT binary)",
+                    self.table, self.columns
+                );
+                let sink = transaction.copy_in(statement.as_str()).await?;
+                let writer = BinaryCopyInWriter::new(sink, &self.types);
+                pin_mut!(writer);
+                for row in rows {
+                    writer.as_mut().write(&row.params()).await?;
+                }
+                writer.finish().await?
+            }
+            WriteMode::Insert => {
+                let mut count = 0;
+                let rows_per_statement = MAX_PARAMETERS / self.types.len();
+                for chunk in rows.chunks(rows_per_statement) {
+                    // Every row's parameters are typed, as the values are encoded with the configured
+                    // types rather than the types the server would infer from the columns.
+                    let statement = transaction
+                        .prepare_typed(
+                            &self.insert_statement(chunk.len()),
+                            &self.types.repeat(chunk.len()),
+                        )
+                        .await?;
+                    let params = chunk
+                        .iter()
+                        .flat_map(PostgresRow::params)
+                        .collect::<Vec<_>>();
+                    count += transaction.execute(&statement, &params).await?;
+                }
+                count
+            }
+        };
+
+        transaction.commit().await?;
+
+        Ok(count)
+    }
+
+    fn insert_statement(&self, rows: usize) -> String {
+        let width = self.types.len();
+        let values = (0..rows)
+            .map(|row| {
+                let params = (1..=width)
+                    .map(|column| format!("${}", row * width + column))
+                    .collect::<Vec<_>>();
+                format!("({})", params.join(", "))
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.table,
+            self.columns,
+            values.join(", ")
+        )
+    }
+}
+
+impl PostgresRow {
+    fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.values
+            .iter()
+            .map(|value| value as &(dyn ToSql + Sync))
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct PostgresResponse;
+
+impl Response for PostgresResponse {}
+
+#[derive(Debug, Clone)]
+struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PgError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error.code() {
+            // Connection exceptions, serialization failures and deadlocks, insufficient
+            // resources, and server shutdowns are transient. Errors in the data or the
+            // schema, such as constraint violations, aren't.
+            Some(code) => ["08", "40", "53", "57P"]
+                .iter()
+                .any(|class| code.code().starts_with(class)),
+            // Errors without a code are retried only if the connection was lost. Others, such as
+            // values that can't be encoded, would fail again.
+            None => {
+                error.is_closed()
+                    || error
+                        .source()
+                        .map_or(false, |source| source.is::<std::io::Error>())
+            }
+        }
+    }
+
+    fn should_retry_response(&self, _response: &Self::Response) -> RetryAction {
+        RetryAction::Successful
+    }
+}
+
+#[derive(Clone)]
+struct PostgresService {
+    client: Arc<Mutex<Option<Client>>>,
+    connector: Connector,
+    writer: Arc<Writer>,
+}
+
+impl Service<Vec<PostgresRow>> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PgError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, rows: Vec<PostgresRow>) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let byte_size = rows.iter().map(EncodedLength::encoded_length).sum();
+
+            let mut client = service.client.lock().await;
+            let result = match client.as_mut() {
+                Some(connected) if !connected.is_closed() => {
+                    service.writer.write(connected, &rows).await
+                }
+                _ => match service.connector.connect().await {
+                    Ok(connected) => service.writer.write(client.insert(connected), &rows).await,
+                    Err(error) => Err(error),
+                },
+            };
+
+            match result {
+                Ok(_) => {
+                    emit!(BytesSent {
+                        byte_size,
+                        protocol: "tcp",
+                    });
+                    Ok(PostgresResponse)
+                }
+                Err(error) => {
+                    emit!(PostgresSendError { error: &error });
+                    // Reconnect on the next request if the connection was lost.
+                    if client.as_ref().map_or(false, Client::is_closed) {
+                        *client = None;
+                    }
+                    Err(error)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PostgresConfig>();
+    }
+
+    fn column(name: &str, column_type: ColumnType) -> ColumnConfig {
+        ColumnConfig {
+            name: name.to_owned(),
+            field: None,
+            column_type,
+        }
+    }
+
+    #[test]
+    fn coerce_values() {
+        let timestamp = Utc.ymd(2022, 8, 1).and_hms(12, 30, 0);
+        let cases = [
+            (
+                Value::from("text"),
+                ColumnType::Text,
+                ColumnValue::Text("text".into()),
+            ),
+            (
+                Value::from(42),
+                ColumnType::Text,
+                ColumnValue::Text("42".into()),
+            ),
+            (
+                serde_json::from_str::<Value>(r#"{"a":1}"#).unwrap(),
+                ColumnType::Text,
+                ColumnValue::Text(r#"{"a":1}"#.into()),
+            ),
+            (
+                Value::from(" 42 "),
+                ColumnType::Integer,
+                ColumnValue::Integer(42),
+            ),
+            (
+                Value::from(42.0),
+                ColumnType::Integer,
+                ColumnValue::Integer(42),
+            ),
+            (Value::from(1.5), ColumnType::Float, ColumnValue::Float(1.5)),
+            (Value::from(2), ColumnType::Float, ColumnValue::Float(2.0)),
+            (
+                Value::from("true"),
+                ColumnType::Boolean,
+                ColumnValue::Boolean(true),
+            ),
+            (
+                Value::from(timestamp),
+                ColumnType::Timestamp,
+                ColumnValue::Timestamp(timestamp),
+            ),
+            (
+                Value::from("2022-08-01T14:30:00+02:00"),
+                ColumnType::Timestamp,
+                ColumnValue::Timestamp(timestamp),
+            ),
+            (
+                Value::from(1_659_357_000),
+                ColumnType::Timestamp,
+                ColumnValue::Timestamp(timestamp),
+            ),
+            (
+                Value::from(vec![Value::from(1), Value::from("a")]),
+                ColumnType::Json,
+                ColumnValue::Json(r#"[1,"a"]"#.into()),
+            ),
+            (
+                Value::from("a"),
+                ColumnType::Json,
+                ColumnValue::Json(r#""a""#.into()),
+            ),
+        ];
+
+        for (value, column_type, expected) in cases {
+            assert_eq!(
+                ColumnValue::coerce(&value, column_type),
+                Ok(expected),
+                "{:?} as {:?}",
+                value,
+                column_type
+            );
+        }
+    }
+
+    #[test]
+    fn coerce_invalid_values() {
+        let cases = [
+            (Value::from("forty-two"), ColumnType::Integer),
+            (Value::from(1.5), ColumnType::Integer),
+            (Value::from(true), ColumnType::Float),
+            (Value::from("yes"), ColumnType::Boolean),
+            (Value::from("yesterday"), ColumnType::Timestamp),
+        ];
+
+        for (value, column_type) in cases {
+            assert!(
+                ColumnValue::coerce(&value, column_type).is_err(),
+                "{:?} as {:?}",
+                value,
+                column_type
+            );
+        }
+    }
+
+    #[test]
+    fn encode_event_columns() {
+        let mut log = LogEvent::from("hello");
+        log.insert("status", "200");
+        log.insert("request.path", "/index.html");
+        log.insert("user", Value::Null);
+
+        let columns = [
+            column("message", ColumnType::Text),
+            ColumnConfig {
+                field: Some("request.path".to_owned()),
+                ..column("path", ColumnType::Text)
+            },
+            column("status", ColumnType::Integer),
+            column("user", ColumnType::Text),
+            column("missing", ColumnType::Json),
+        ];
+
+        let row = encode_event(log.into(), &columns).unwrap().item;
+        assert_eq!(
+            row.values,
+            vec![
+                Some(ColumnValue::Text("hello".into())),
+                Some(ColumnValue::Text("/index.html".into())),
+                Some(ColumnValue::Integer(200)),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_event_drops_invalid_values() {
+        let mut log = LogEvent::from("hello");
+        log.insert("status", "ok");
+
+        assert!(encode_event(log.into(), &[column("status", ColumnType::Integer)]).is_none());
+    }
+
+    #[test]
+    fn statements() {
+        let config: PostgresConfig = toml::from_str(
+            r#"
+            endpoint = "postgres://localhost/postgres"
+            schema = "audit"
+            table = "Events"
+            columns = [
+                { name = "timestamp", type = "timestamp" },
+                { name = "message" },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.select_statement(),
+            r#"SELECT "timestamp", "message" FROM "audit"."Events" LIMIT 0"#
+        );
+
+        let writer = Writer {
+            table: config.table_name(),
+            columns: config.column_names(),
+            types: vec![Type::TIMESTAMPTZ, Type::TEXT],
+            mode: WriteMode::Insert,
+        };
+        assert_eq!(
+            writer.insert_statement(2),
+            r#"INSERT INTO "audit"."Events" ("timestamp", "message") VALUES ($1, $2), ($3, $4)"#
+        );
+    }
+
+    #[test]
+    fn quote_identifiers() {
+        assert_eq!(quote_identifier("events"), r#""events""#);
+        assert_eq!(quote_identifier(r#"my "events""#), r#""my ""events""""#);
+    }
+}
+
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests {
+    use futures::stream;
+    use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    };
+
+    fn pg_host() -> String {
+        std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+    }
+
+    fn pg_url() -> String {
+        std::env::var("PG_URL")
+            .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+    }
+
+    fn config(table: &str, mode: WriteMode) -> PostgresConfig {
+        PostgresConfig {
+            endpoint: pg_url(),
+            schema: None,
+            table: table.to_owned(),
+            columns: vec![
+                ColumnConfig {
+                    name: "ts".to_owned(),
+                    field: Some("timestamp".to_owned()),
+                    column_type: ColumnType::Timestamp,
+                },
+                ColumnConfig {
+                    name: "message".to_owned(),
+                    field: None,
+                    column_type: ColumnType::Text,
+                },
+                ColumnConfig {
+                    name: "count".to_owned(),
+                    field: None,
+                    column_type: ColumnType::Integer,
+                },
+                ColumnConfig {
+                    name: "attributes".to_owned(),
+                    field: None,
+                    column_type: ColumnType::Json,
+                },
+            ],
+            mode,
+            batch: Default::default(),
+            request: Default::default(),
+            tls: None,
+            acknowledgements: Default::default(),
+        }
+    }
+
+    async fn create_table(client: &Client) -> String {
+        let table = format!("test_{}", random_string(10).to_lowercase());
+        client
+            .execute(
+                &format!(
+                    "CREATE TABLE {} (ts timestamptz, message text, count bigint, attributes jsonb)",
+                    table
+                ),
+                &[],
+            )
+            .await
+            .unwrap();
+        table
+    }
+
+    async fn insert_events(mode: WriteMode) {
+        trace_init();
+
+        let client = Connector {
+            config: pg_url().parse().unwrap(),
+            tls: None,
+        }
+        .connect()
+        .await
+        .unwrap();
+        let table = create_table(&client).await;
+
+        let config = config(&table, mode);
+        let (sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        healthcheck.await.unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = (0..10)
+            .map(|index| {
+                let mut log =
+                    LogEvent::from(format!("message {}", index)).with_batch_notifier(&batch);
+                log.insert("count", index);
+                log.insert("attributes.index", index);
+                Event::from(log)
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT message, count, attributes->>'index' FROM {} ORDER BY count",
+                    table
+                ),
+                &[],
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 10);
+        for (index, row) in rows.iter().enumerate() {
+            assert_eq!(row.get::<_, String>(0), format!("message {}", index));
+            assert_eq!(row.get::<_, i64>(1), index as i64);
+            assert_eq!(row.get::<_, String>(2), index.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn insert_events_with_copy() {
+        insert_events(WriteMode::Copy).await;
+    }
+
+    #[tokio::test]
+    async fn insert_events_with_insert() {
+        insert_events(WriteMode::Insert).await;
+    }
+
+    #[tokio::test]
+    async fn insert_events_into_columns_of_other_types() {
+        trace_init();
+
+        let client = Connector {
+            config: pg_url().parse().unwrap(),
+            tls: None,
+        }
+        .connect()
+        .await
+        .unwrap();
+        // `count` is an `integer` rather than the `bigint` its configured type is encoded as.
+        let table = format!("test_{}", random_string(10).to_lowercase());
+        client
+            .execute(
+                &format!(
+                    "CREATE TABLE {} (ts timestamptz, message varchar(32), count integer, attributes json)",
+                    table
+                ),
+                &[],
+            )
+            .await
+            .unwrap();
+
+        let config = config(&table, WriteMode::Insert);
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = (0..3)
+            .map(|index| {
+                let mut log =
+                    LogEvent::from(format!("message {}", index)).with_batch_notifier(&batch);
+                log.insert("count", index);
+                Event::from(log)
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let rows = client
+            .query(&format!("SELECT count FROM {} ORDER BY count", table), &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.get::<_, i32>(0))
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn healthcheck_fails_for_missing_table() {
+        trace_init();
+
+        let config = config("does_not_exist", WriteMode::Copy);
+        let (_, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        assert!(healthcheck.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_batch_with_constraint_violation() {
+        trace_init();
+
+        let client = Connector {
+            config: pg_url().parse().unwrap(),
+            tls: None,
+        }
+        .connect()
+        .await
+        .unwrap();
+        let table = create_table(&client).await;
+        client
+            .execute(
+                &format!("ALTER TABLE {} ALTER COLUMN count SET NOT NULL", table),
+                &[],
+            )
+            .await
+            .unwrap();
+
+        let config = config(&table, WriteMode::Insert);
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let mut with_count = LogEvent::from("with count").with_batch_notifier(&batch);
+        with_count.insert("count", 1);
+        let without_count = LogEvent::from("without count").with_batch_notifier(&batch);
+        let events = vec![Event::from(with_count), Event::from(without_count)];
+        drop(batch);
+
+        sink.run(stream::iter(events)).await.unwrap();
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Errored));
+
+        // The transaction was rolled back, so neither row was written.
+        let row = client
+            .query_one(&format!("SELECT COUNT(*) FROM {}", table), &[])
+            .await
+            .unwrap();
+        assert_eq!(row.get::<_, i64>(0), 0);
+    }
+}
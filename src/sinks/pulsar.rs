@@ -148,6 +148,10 @@ impl SinkConfig for PulsarSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         None
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl PulsarSinkConfig {
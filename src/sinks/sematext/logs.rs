@@ -87,6 +87,7 @@ impl SinkConfig for SematextLogsConfig {
             bulk: Some(BulkConfig {
                 action: None,
                 index: Some(self.token.clone()),
+                ..Default::default()
             }),
             batch: self.batch,
             request: RequestConfig {
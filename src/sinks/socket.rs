@@ -131,6 +131,15 @@ impl SinkConfig for SocketSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         None
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        match &self.mode {
+            Mode::Tcp(TcpMode { encoding, .. }) => Some(encoding.encoding()),
+            Mode::Udp(UdpMode { encoding, .. }) => Some(encoding),
+            #[cfg(unix)]
+            Mode::Unix(UnixMode { encoding, .. }) => Some(encoding.encoding()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -114,6 +114,10 @@ impl SinkConfig for HecLogsSinkConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         Some(&self.acknowledgements.inner)
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        Some(&self.encoding)
+    }
 }
 
 impl HecLogsSinkConfig {
@@ -208,6 +212,10 @@ impl SinkConfig for HecSinkCompatConfig {
     fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
         self.config.acknowledgements()
     }
+
+    fn encoding(&self) -> Option<&EncodingConfig> {
+        self.config.encoding()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,188 @@
+use std::{collections::BTreeMap, io, path::PathBuf, sync::Arc};
+
+use aws_sdk_dynamodb::{model::AttributeValue, Client as DynamodbClient};
+use tokio::sync::Mutex;
+
+use crate::aws::ClientBuilder;
+
+/// The checkpoint of a shard that was consumed up to its end, after a reshard closed it.
+pub(super) const SHARD_END: &str = "SHARD_END";
+
+/// The partition key of the DynamoDB checkpoint table.
+pub(super) const KEY_ATTRIBUTE: &str = "checkpoint_key";
+
+const SEQUENCE_NUMBER_ATTRIBUTE: &str = "sequence_number";
+
+pub(super) struct DynamodbClientBuilder;
+
+impl ClientBuilder for DynamodbClientBuilder {
+    type Config = aws_sdk_dynamodb::config::Config;
+    type Client = aws_sdk_dynamodb::client::Client;
+    type DefaultMiddleware = aws_sdk_dynamodb::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_dynamodb::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_dynamodb::client::Client::with_config(client, config.into())
+    }
+}
+
+/// Stores the sequence number of the last record delivered from each shard.
+///
+/// Checkpoints are keyed by `<stream name>/<shard ID>`, so that a store can be shared by sources consuming different
+/// streams.
+#[derive(Clone)]
+pub(super) struct CheckpointStore {
+    stream_name: String,
+    backend: Backend,
+}
+
+#[derive(Clone)]
+enum Backend {
+    File(Arc<FileCheckpoints>),
+    Dynamodb {
+        client: DynamodbClient,
+        table_name: String,
+    },
+}
+
+struct FileCheckpoints {
+    path: PathBuf,
+    sequence_numbers: Mutex<BTreeMap<String, String>>,
+}
+
+impl CheckpointStore {
+    pub(super) async fn file(stream_name: String, path: PathBuf) -> crate::Result<Self> {
+        let sequence_numbers = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            stream_name,
+            backend: Backend::File(Arc::new(FileCheckpoints {
+                path,
+                sequence_numbers: Mutex::new(sequence_numbers),
+            })),
+        })
+    }
+
+    pub(super) fn dynamodb(
+        stream_name: String,
+        client: DynamodbClient,
+        table_name: String,
+    ) -> Self {
+        Self {
+            stream_name,
+            backend: Backend::Dynamodb { client, table_name },
+        }
+    }
+
+    fn key(&self, shard_id: &str) -> String {
+        format!("{}/{}", self.stream_name, shard_id)
+    }
+
+    pub(super) async fn get(&self, shard_id: &str) -> crate::Result<Option<String>> {
+        let key = self.key(shard_id);
+        match &self.backend {
+            Backend::File(checkpoints) => {
+                Ok(checkpoints.sequence_numbers.lock().await.get(&key).cloned())
+            }
+            Backend::Dynamodb { client, table_name } => {
+                let output = client
+                    .get_item()
+                    .table_name(table_name)
+                    .key(KEY_ATTRIBUTE, AttributeValue::S(key))
+                    .consistent_read(true)
+                    .send()
+                    .await?;
+
+                Ok(output
+                    .item
+                    .and_then(|mut item| item.remove(SEQUENCE_NUMBER_ATTRIBUTE))
+                    .and_then(|value| match value {
+                        AttributeValue::S(sequence_number) => Some(sequence_number),
+                        _ => None,
+                    }))
+            }
+        }
+    }
+
+    pub(super) async fn set(&self, shard_id: &str, sequence_number: String) -> crate::Result<()> {
+        let key = self.key(shard_id);
+        match &self.backend {
+            Backend::File(checkpoints) => {
+                let mut sequence_numbers = checkpoints.sequence_numbers.lock().await;
+                sequence_numbers.insert(key, sequence_number);
+
+                // Write to a temporary file first, so that a crash can't leave the checkpoints half written.
+                let tmp_path = checkpoints.path.with_extension("json.tmp");
+                tokio::fs::write(&tmp_path, serde_json::to_vec(&*sequence_numbers)?).await?;
+                tokio::fs::rename(&tmp_path, &checkpoints.path).await?;
+                Ok(())
+            }
+            Backend::Dynamodb { client, table_name } => {
+                client
+                    .put_item()
+                    .table_name(table_name)
+                    .item(KEY_ATTRIBUTE, AttributeValue::S(key))
+                    .item(
+                        SEQUENCE_NUMBER_ATTRIBUTE,
+                        AttributeValue::S(sequence_number),
+                    )
+                    .send()
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn file_checkpoints_persist() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("checkpoints.json");
+
+        let store = CheckpointStore::file("stream".into(), path.clone())
+            .await
+            .unwrap();
+        assert_eq!(store.get("shardId-000000000000").await.unwrap(), None);
+
+        store
+            .set(
+                "shardId-000000000000",
+                "49590338271490256608559692538361571095921575989136588898".into(),
+            )
+            .await
+            .unwrap();
+        store
+            .set("shardId-000000000001", SHARD_END.into())
+            .await
+            .unwrap();
+
+        let store = CheckpointStore::file("stream".into(), path.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get("shardId-000000000000").await.unwrap().as_deref(),
+            Some("49590338271490256608559692538361571095921575989136588898")
+        );
+        assert_eq!(
+            store.get("shardId-000000000001").await.unwrap().as_deref(),
+            Some(SHARD_END)
+        );
+
+        // Checkpoints of other streams are kept apart.
+        let other = CheckpointStore::file("other".into(), path).await.unwrap();
+        assert_eq!(other.get("shardId-000000000000").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,314 @@
+use std::{path::PathBuf, time::Duration};
+
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use super::{
+    checkpoint::{CheckpointStore, DynamodbClientBuilder},
+    source::KinesisSource,
+};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    codecs::DecodingConfig,
+    common::kinesis::KinesisClientBuilder,
+    config::{AcknowledgementsConfig, Output, SourceConfig, SourceContext},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    tls::TlsConfig,
+};
+
+const CHECKPOINTS_FILENAME: &str = "checkpoints.json";
+
+/// The most records a single `GetRecords` request can return.
+const MAX_RECORDS_LIMIT: u32 = 10_000;
+
+/// Where to start reading a shard that has no checkpoint yet.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StartingPosition {
+    /// Start with the records added to the shard after the source started.
+    #[derivative(Default)]
+    Latest,
+
+    /// Start with the oldest record of the shard that is still retained by the stream.
+    TrimHorizon,
+}
+
+/// Where the source stores the sequence number of the last record delivered from each shard.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckpointConfig {
+    /// Checkpoints are stored in a file in the data directory.
+    #[derivative(Default)]
+    File,
+
+    /// Checkpoints are stored in a DynamoDB table, or in a table of a DynamoDB-compatible service.
+    Dynamodb(#[configurable(derived)] DynamodbCheckpointConfig),
+}
+
+/// Configuration of a DynamoDB checkpoint table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct DynamodbCheckpointConfig {
+    /// The name of the table to store checkpoints in.
+    ///
+    /// The table must have a string partition key named `checkpoint_key`.
+    pub table_name: String,
+
+    /// The region and endpoint of the table.
+    ///
+    /// Defaults to the region and endpoint of the stream when neither is set.
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+}
+
+/// Configuration for the `aws_kinesis_streams` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKinesisStreamsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The name of the stream to consume.
+    pub stream_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub starting_position: StartingPosition,
+
+    /// The maximum number of records to fetch from a shard in a single request.
+    ///
+    /// Must be between 1 and 10000, the limit of Kinesis.
+    #[serde(default = "default_max_records")]
+    #[derivative(Default(value = "default_max_records()"))]
+    pub max_records: u32,
+
+    /// How long to wait before reading a shard again once it has no more records, in milliseconds.
+    ///
+    /// Kinesis allows five reads per second per shard, shared by all of its consumers.
+    #[serde(default = "default_poll_interval_ms")]
+    #[derivative(Default(value = "default_poll_interval_ms()"))]
+    pub poll_interval_ms: u64,
+
+    /// The interval between listings of the stream's shards, in seconds.
+    ///
+    /// Shards are also listed as soon as a shard is closed by a reshard, to pick up its children.
+    #[serde(default = "default_shard_discovery_interval_secs")]
+    #[derivative(Default(value = "default_shard_discovery_interval_secs()"))]
+    pub shard_discovery_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint: CheckpointConfig,
+
+    /// The directory used to persist file checkpoints.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write permissions to this
+    /// directory.
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_kinesis_streams")]
+impl SourceConfig for AwsKinesisStreamsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        if !(1..=MAX_RECORDS_LIMIT).contains(&self.max_records) {
+            return Err(format!(
+                "`max_records` must be between 1 and {}, got {}",
+                MAX_RECORDS_LIMIT, self.max_records
+            )
+            .into());
+        }
+
+        let client = create_client::<KinesisClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        let checkpoints = self.build_checkpoints(&cx).await?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+        Ok(Box::pin(
+            KinesisSource {
+                client,
+                stream_name: self.stream_name.clone(),
+                starting_position: self.starting_position,
+                max_records: self.max_records as i32,
+                poll_interval: Duration::from_millis(self.poll_interval_ms),
+                shard_discovery_interval: Duration::from_secs(self.shard_discovery_interval_secs),
+                checkpoints,
+                decoder,
+                acknowledgements,
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_kinesis_streams"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
+}
+
+impl AwsKinesisStreamsConfig {
+    async fn build_checkpoints(&self, cx: &SourceContext) -> crate::Result<CheckpointStore> {
+        match &self.checkpoint {
+            CheckpointConfig::File => {
+                let data_dir = cx
+                    .globals
+                    .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+                CheckpointStore::file(
+                    self.stream_name.clone(),
+                    data_dir.join(CHECKPOINTS_FILENAME),
+                )
+                .await
+            }
+            CheckpointConfig::Dynamodb(config) => {
+                let region = if config.region == RegionOrEndpoint::default() {
+                    &self.region
+                } else {
+                    &config.region
+                };
+                let client = create_client::<DynamodbClientBuilder>(
+                    &self.auth,
+                    region.region(),
+                    region.endpoint()?,
+                    &cx.proxy,
+                    &self.tls,
+                    false,
+                )
+                .await?;
+                Ok(CheckpointStore::dynamodb(
+                    self.stream_name.clone(),
+                    client,
+                    config.table_name.clone(),
+                ))
+            }
+        }
+    }
+}
+
+const fn default_max_records() -> u32 {
+    1000
+}
+
+const fn default_poll_interval_ms() -> u64 {
+    1000
+}
+
+const fn default_shard_discovery_interval_secs() -> u64 {
+    30
+}
+
+impl_generate_config_from_default!(AwsKinesisStreamsConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SourceSender;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsKinesisStreamsConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_max_records() {
+        let config = AwsKinesisStreamsConfig {
+            stream_name: "events".to_string(),
+            max_records: 0,
+            ..Default::default()
+        };
+
+        let error = config
+            .build(SourceContext::new_test(SourceSender::new_test().0, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`max_records` must be between 1 and 10000, got 0"
+        );
+    }
+
+    #[test]
+    fn parses_dynamodb_checkpoint() {
+        let config: AwsKinesisStreamsConfig = toml::from_str(
+            r#"
+            stream_name = "events"
+            region = "us-east-1"
+            starting_position = "trim_horizon"
+
+            [checkpoint]
+            type = "dynamodb"
+            table_name = "vector-checkpoints"
+            endpoint = "http://localhost:8000"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.starting_position, StartingPosition::TrimHorizon);
+        match config.checkpoint {
+            CheckpointConfig::Dynamodb(checkpoint) => {
+                assert_eq!(checkpoint.table_name, "vector-checkpoints");
+                assert_eq!(
+                    checkpoint.region.endpoint.as_deref(),
+                    Some("http://localhost:8000")
+                );
+            }
+            CheckpointConfig::File => panic!("expected DynamoDB checkpoints"),
+        }
+    }
+}
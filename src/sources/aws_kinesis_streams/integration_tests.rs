@@ -0,0 +1,133 @@
+#![cfg(feature = "aws-kinesis-streams-integration-tests")]
+#![cfg(test)]
+
+use std::{collections::HashSet, time::Duration};
+
+use aws_sdk_kinesis::types::Blob;
+use futures::StreamExt;
+use tokio::time::{sleep, timeout};
+
+use super::config::{AwsKinesisStreamsConfig, StartingPosition};
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    common::kinesis::KinesisClientBuilder,
+    config::{log_schema, ProxyConfig, SourceConfig, SourceContext},
+    event::Event,
+    test_util::{
+        components::{assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
+        random_string,
+    },
+    SourceSender,
+};
+
+fn kinesis_address() -> String {
+    std::env::var("KINESIS_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
+}
+
+fn gen_stream() -> String {
+    format!("test-{}", random_string(10).to_lowercase())
+}
+
+async fn client() -> aws_sdk_kinesis::Client {
+    let region = RegionOrEndpoint::with_both("localstack", kinesis_address());
+    create_client::<KinesisClientBuilder>(
+        &AwsAuthentication::test_auth(),
+        region.region(),
+        region.endpoint().unwrap(),
+        &ProxyConfig::default(),
+        &None,
+        false,
+    )
+    .await
+    .unwrap()
+}
+
+async fn ensure_stream(client: &aws_sdk_kinesis::Client, stream_name: &str) {
+    client
+        .create_stream()
+        .stream_name(stream_name)
+        .shard_count(2)
+        .send()
+        .await
+        .unwrap();
+
+    // Wait for localstack to persist the stream, otherwise it returns ResourceNotFound errors.
+    sleep(Duration::from_secs(1)).await;
+}
+
+async fn put_records(client: &aws_sdk_kinesis::Client, stream_name: &str, count: usize) {
+    for i in 0..count {
+        client
+            .put_record()
+            .stream_name(stream_name)
+            .partition_key(format!("key-{}", i))
+            .data(Blob::new(calculate_message(i)))
+            .send()
+            .await
+            .unwrap();
+    }
+}
+
+fn calculate_message(index: usize) -> String {
+    format!("Test message: {}", index)
+}
+
+#[tokio::test]
+async fn consumes_all_shards() {
+    assert_source_compliance(&HTTP_PULL_SOURCE_TAGS, async {
+        let client = client().await;
+        let stream_name = gen_stream();
+        ensure_stream(&client, &stream_name).await;
+
+        let num_events = 10;
+        put_records(&client, &stream_name, num_events).await;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = AwsKinesisStreamsConfig {
+            region: RegionOrEndpoint::with_both("localstack", kinesis_address()),
+            auth: AwsAuthentication::test_auth(),
+            stream_name: stream_name.clone(),
+            starting_position: StartingPosition::TrimHorizon,
+            poll_interval_ms: 100,
+            data_dir: Some(data_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+
+        let (tx, rx) = SourceSender::new_test();
+        tokio::spawn(async move {
+            config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+        });
+
+        let events: Vec<Event> = timeout(Duration::from_secs(10), rx.take(num_events).collect())
+            .await
+            .unwrap();
+
+        let mut expected_messages = (0..num_events)
+            .map(calculate_message)
+            .collect::<HashSet<_>>();
+        for event in events {
+            let log = event.as_log();
+            let message = log
+                .get(log_schema().message_key())
+                .unwrap()
+                .to_string_lossy();
+            if !expected_messages.remove(&message) {
+                panic!("Received unexpected message: {:?}", message);
+            }
+            assert_eq!(
+                log.get("stream_name").unwrap().to_string_lossy(),
+                stream_name
+            );
+            assert!(log.get("shard_id").is_some());
+            assert!(log.get("partition_key").is_some());
+            assert!(log.get("sequence_number").is_some());
+        }
+        assert!(expected_messages.is_empty());
+    })
+    .await;
+}
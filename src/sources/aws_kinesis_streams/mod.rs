@@ -0,0 +1,12 @@
+mod checkpoint;
+mod config;
+mod integration_tests;
+mod source;
+
+pub use config::AwsKinesisStreamsConfig;
+
+use crate::config::SourceDescription;
+
+inventory::submit! {
+    SourceDescription::new::<AwsKinesisStreamsConfig>("aws_kinesis_streams")
+}
@@ -0,0 +1,493 @@
+use std::{collections::HashSet, panic, sync::Arc, time::Duration};
+
+use aws_sdk_kinesis::{
+    error::GetRecordsErrorKind,
+    model::{Record, Shard, ShardIteratorType},
+    types::SdkError,
+    Client as KinesisClient,
+};
+use chrono::{TimeZone, Utc};
+use lookup::path;
+use tokio::{
+    select,
+    sync::mpsc::{unbounded_channel, UnboundedSender},
+    time::sleep,
+};
+
+use super::{
+    checkpoint::{CheckpointStore, SHARD_END},
+    config::StartingPosition,
+};
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{
+        AwsKinesisStreamsAcknowledgementError, AwsKinesisStreamsCheckpointError,
+        AwsKinesisStreamsRequestError, EndpointBytesReceived, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util,
+    SourceSender,
+};
+
+pub(super) struct KinesisSource {
+    pub client: KinesisClient,
+    pub stream_name: String,
+    pub starting_position: StartingPosition,
+    pub max_records: i32,
+    pub poll_interval: Duration,
+    pub shard_discovery_interval: Duration,
+    pub checkpoints: CheckpointStore,
+    pub decoder: Decoder,
+    pub acknowledgements: bool,
+}
+
+/// A shard of the stream, and the shards it was created from by a reshard.
+#[derive(Clone, Debug, PartialEq)]
+struct ShardInfo {
+    id: String,
+    parents: Vec<String>,
+}
+
+impl From<Shard> for ShardInfo {
+    fn from(shard: Shard) -> Self {
+        Self {
+            id: shard.shard_id.unwrap_or_default(),
+            parents: shard
+                .parent_shard_id
+                .into_iter()
+                .chain(shard.adjacent_parent_shard_id)
+                .collect(),
+        }
+    }
+}
+
+/// Decides which shards are consumed.
+///
+/// A shard created by a reshard is only consumed once its parents have been consumed up to their end, so that the
+/// records of a partition key are delivered in order. Parents that are no longer listed have expired from the stream
+/// and aren't waited on.
+#[derive(Debug, Default)]
+struct ShardTracker {
+    running: HashSet<String>,
+    finished: HashSet<String>,
+}
+
+impl ShardTracker {
+    /// Returns the shards that can start being consumed, along with whether they should be read from their start
+    /// because one of their parents was consumed.
+    fn start(&mut self, shards: &[ShardInfo]) -> Vec<(ShardInfo, bool)> {
+        let listed = shards
+            .iter()
+            .map(|shard| shard.id.as_str())
+            .collect::<HashSet<_>>();
+
+        let mut ready = Vec::new();
+        for shard in shards {
+            if self.running.contains(&shard.id) || self.finished.contains(&shard.id) {
+                continue;
+            }
+
+            let mut listed_parents = shard
+                .parents
+                .iter()
+                .filter(|parent| listed.contains(parent.as_str()))
+                .peekable();
+            let has_listed_parents = listed_parents.peek().is_some();
+            if listed_parents.all(|parent| self.finished.contains(parent)) {
+                self.running.insert(shard.id.clone());
+                ready.push((shard.clone(), has_listed_parents));
+            }
+        }
+        ready
+    }
+
+    fn finish(&mut self, shard_id: String) {
+        self.running.remove(&shard_id);
+        self.finished.insert(shard_id);
+    }
+}
+
+/// The outcome of forwarding a batch of records.
+enum Forwarded {
+    Delivered,
+    NotDelivered,
+    Closed,
+}
+
+impl KinesisSource {
+    pub(super) async fn run(self, out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let source = Arc::new(self);
+        let (finished_tx, mut finished_rx) = unbounded_channel();
+        let mut tracker = ShardTracker::default();
+        let mut task_handles = vec![];
+
+        let task_shutdown = shutdown.clone();
+        tokio::pin!(shutdown);
+        loop {
+            match source.list_shards().await {
+                Ok(shards) => {
+                    for (shard, from_parent) in tracker.start(&shards) {
+                        let source = Arc::clone(&source);
+                        let out = out.clone();
+                        let shutdown = task_shutdown.clone();
+                        let finished_tx = finished_tx.clone();
+                        task_handles.push(tokio::spawn(async move {
+                            select! {
+                                _ = shutdown => {},
+                                _ = source.consume_shard(shard, from_parent, out, finished_tx) => {},
+                            }
+                        }));
+                    }
+                }
+                Err(error) => emit!(AwsKinesisStreamsRequestError {
+                    operation: "ListShards",
+                    shard_id: None,
+                    error: &error,
+                }),
+            }
+
+            select! {
+                _ = &mut shutdown => break,
+                Some(shard_id) = finished_rx.recv() => tracker.finish(shard_id),
+                _ = sleep(source.shard_discovery_interval) => {},
+            }
+        }
+
+        // Wait for all of the shard tasks to finish. If any one of them panics, we resume that panic here to properly
+        // shutdown Vector.
+        for task_handle in task_handles.drain(..) {
+            if let Err(e) = task_handle.await {
+                if e.is_panic() {
+                    panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn list_shards(&self) -> crate::Result<Vec<ShardInfo>> {
+        let mut shards = Vec::new();
+        let mut next_token: Option<String> = None;
+        loop {
+            // The stream name must not be set along with a pagination token.
+            let request = match next_token {
+                Some(token) => self.client.list_shards().next_token(token),
+                None => self.client.list_shards().stream_name(&self.stream_name),
+            };
+            let output = request.send().await?;
+
+            shards.extend(
+                output
+                    .shards
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Into::into),
+            );
+            next_token = output.next_token;
+            if next_token.is_none() {
+                return Ok(shards);
+            }
+        }
+    }
+
+    async fn shard_iterator(
+        &self,
+        shard_id: &str,
+        checkpoint: Option<&str>,
+        from_parent: bool,
+    ) -> crate::Result<Option<String>> {
+        let request = self
+            .client
+            .get_shard_iterator()
+            .stream_name(&self.stream_name)
+            .shard_id(shard_id);
+        let request = match checkpoint {
+            Some(sequence_number) => request
+                .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+                .starting_sequence_number(sequence_number),
+            None if from_parent || self.starting_position == StartingPosition::TrimHorizon => {
+                request.shard_iterator_type(ShardIteratorType::TrimHorizon)
+            }
+            None => request.shard_iterator_type(ShardIteratorType::Latest),
+        };
+
+        Ok(request.send().await?.shard_iterator)
+    }
+
+    /// Reads the checkpoint of a shard, retrying until it succeeds since starting over could deliver records twice.
+    async fn checkpoint(&self, shard_id: &str) -> Option<String> {
+        loop {
+            match self.checkpoints.get(shard_id).await {
+                Ok(checkpoint) => return checkpoint,
+                Err(error) => {
+                    emit!(AwsKinesisStreamsCheckpointError { shard_id, error });
+                    sleep(self.poll_interval).await;
+                }
+            }
+        }
+    }
+
+    async fn set_checkpoint(&self, shard_id: &str, sequence_number: String) {
+        if let Err(error) = self.checkpoints.set(shard_id, sequence_number).await {
+            emit!(AwsKinesisStreamsCheckpointError { shard_id, error });
+        }
+    }
+
+    /// Consumes a shard until it is closed by a reshard and all of its records were delivered.
+    async fn consume_shard(
+        &self,
+        shard: ShardInfo,
+        from_parent: bool,
+        mut out: SourceSender,
+        finished: UnboundedSender<String>,
+    ) {
+        let shard_id = shard.id.as_str();
+        let mut checkpoint = self.checkpoint(shard_id).await;
+        let mut iterator = None;
+
+        while checkpoint.as_deref() != Some(SHARD_END) {
+            let current = match iterator.take() {
+                Some(current) => current,
+                None => {
+                    match self
+                        .shard_iterator(shard_id, checkpoint.as_deref(), from_parent)
+                        .await
+                    {
+                        Ok(Some(current)) => current,
+                        Ok(None) => {
+                            // A closed shard that was read up to its end has no iterator.
+                            self.set_checkpoint(shard_id, SHARD_END.into()).await;
+                            break;
+                        }
+                        Err(error) => {
+                            emit!(AwsKinesisStreamsRequestError {
+                                operation: "GetShardIterator",
+                                shard_id: Some(shard_id),
+                                error: &error,
+                            });
+                            sleep(self.poll_interval).await;
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let output = match self
+                .client
+                .get_records()
+                .shard_iterator(&current)
+                .limit(self.max_records)
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(SdkError::ServiceError { err, .. })
+                    if matches!(
+                        err.kind,
+                        GetRecordsErrorKind::ProvisionedThroughputExceededException(_)
+                    ) =>
+                {
+                    // Other consumers share the read limits of the shard, so keep the iterator and back off.
+                    iterator = Some(current);
+                    sleep(self.poll_interval).await;
+                    continue;
+                }
+                Err(error) => {
+                    // The iterator may have expired, so a new one is requested from the last checkpoint.
+                    emit!(AwsKinesisStreamsRequestError {
+                        operation: "GetRecords",
+                        shard_id: Some(shard_id),
+                        error: &error,
+                    });
+                    sleep(self.poll_interval).await;
+                    continue;
+                }
+            };
+
+            let records = output.records.unwrap_or_default();
+            if let Some(sequence_number) = records
+                .last()
+                .and_then(|record| record.sequence_number.clone())
+            {
+                match self.forward(shard_id, records, &mut out).await {
+                    Forwarded::Delivered => {
+                        self.set_checkpoint(shard_id, sequence_number.clone()).await;
+                        checkpoint = Some(sequence_number);
+                    }
+                    Forwarded::NotDelivered => {
+                        emit!(AwsKinesisStreamsAcknowledgementError { shard_id });
+                        sleep(self.poll_interval).await;
+                        continue;
+                    }
+                    Forwarded::Closed => return,
+                }
+            } else {
+                sleep(self.poll_interval).await;
+            }
+
+            match output.next_shard_iterator {
+                Some(next) => iterator = Some(next),
+                None => {
+                    self.set_checkpoint(shard_id, SHARD_END.into()).await;
+                    break;
+                }
+            }
+        }
+
+        // The coordinator may already be gone if the source is shutting down.
+        let _ = finished.send(shard.id);
+    }
+
+    /// Sends the events decoded from the records, waiting for them to be acknowledged if needed.
+    async fn forward(
+        &self,
+        shard_id: &str,
+        records: Vec<Record>,
+        out: &mut SourceSender,
+    ) -> Forwarded {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+
+        let mut byte_size = 0;
+        let mut events = Vec::with_capacity(records.len());
+        for record in records {
+            let data = match record.data {
+                Some(data) => data.into_inner(),
+                None => continue,
+            };
+            byte_size += data.len();
+
+            let timestamp = record
+                .approximate_arrival_timestamp
+                .map(|timestamp| Utc.timestamp(timestamp.secs(), timestamp.subsec_nanos()));
+            let partition_key = record.partition_key;
+            let sequence_number = record.sequence_number;
+
+            let decoded = util::decode_message(
+                self.decoder.clone(),
+                "aws_kinesis_streams",
+                &data,
+                timestamp,
+                &batch,
+            );
+            events.extend(decoded.map(|mut event| {
+                if let Event::Log(ref mut log) = event {
+                    log.try_insert(path!("stream_name"), self.stream_name.clone());
+                    log.try_insert(path!("shard_id"), shard_id.to_owned());
+                    if let Some(partition_key) = &partition_key {
+                        log.try_insert(path!("partition_key"), partition_key.clone());
+                    }
+                    if let Some(sequence_number) = &sequence_number {
+                        log.try_insert(path!("sequence_number"), sequence_number.clone());
+                    }
+                }
+                event
+            }));
+        }
+        emit!(EndpointBytesReceived {
+            byte_size,
+            protocol: "http",
+            endpoint: &self.stream_name,
+        });
+        drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Forwarded::Closed;
+        }
+
+        match receiver {
+            None => Forwarded::Delivered,
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Forwarded::Delivered,
+                BatchStatus::Errored | BatchStatus::Rejected => Forwarded::NotDelivered,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard(id: &str, parents: &[&str]) -> ShardInfo {
+        ShardInfo {
+            id: id.to_owned(),
+            parents: parents.iter().map(|parent| (*parent).to_owned()).collect(),
+        }
+    }
+
+    fn ids(ready: &[(ShardInfo, bool)]) -> Vec<(&str, bool)> {
+        ready
+            .iter()
+            .map(|(shard, from_parent)| (shard.id.as_str(), *from_parent))
+            .collect()
+    }
+
+    #[test]
+    fn starts_shards_without_parents() {
+        let mut tracker = ShardTracker::default();
+        let shards = vec![shard("a", &[]), shard("b", &[])];
+
+        assert_eq!(
+            ids(&tracker.start(&shards)),
+            vec![("a", false), ("b", false)]
+        );
+        // Running shards aren't started twice.
+        assert!(tracker.start(&shards).is_empty());
+    }
+
+    #[test]
+    fn waits_for_parents_after_split() {
+        let mut tracker = ShardTracker::default();
+        let shards = vec![shard("a", &[]), shard("b", &["a"]), shard("c", &["a"])];
+
+        assert_eq!(ids(&tracker.start(&shards)), vec![("a", false)]);
+
+        tracker.finish("a".into());
+        assert_eq!(ids(&tracker.start(&shards)), vec![("b", true), ("c", true)]);
+    }
+
+    #[test]
+    fn waits_for_both_parents_after_merge() {
+        let mut tracker = ShardTracker::default();
+        let shards = vec![shard("a", &[]), shard("b", &[]), shard("c", &["a", "b"])];
+
+        assert_eq!(
+            ids(&tracker.start(&shards)),
+            vec![("a", false), ("b", false)]
+        );
+
+        tracker.finish("a".into());
+        assert!(tracker.start(&shards).is_empty());
+
+        tracker.finish("b".into());
+        assert_eq!(ids(&tracker.start(&shards)), vec![("c", true)]);
+    }
+
+    #[test]
+    fn ignores_expired_parents() {
+        let mut tracker = ShardTracker::default();
+        let shards = vec![shard("b", &["a"])];
+
+        assert_eq!(ids(&tracker.start(&shards)), vec![("b", false)]);
+    }
+
+    #[test]
+    fn shard_info_from_shard() {
+        let shard = Shard::builder()
+            .shard_id("shardId-000000000002")
+            .parent_shard_id("shardId-000000000000")
+            .adjacent_parent_shard_id("shardId-000000000001")
+            .build();
+
+        assert_eq!(
+            ShardInfo::from(shard),
+            ShardInfo {
+                id: "shardId-000000000002".into(),
+                parents: vec!["shardId-000000000000".into(), "shardId-000000000001".into()],
+            }
+        );
+    }
+}
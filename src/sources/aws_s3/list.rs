@@ -0,0 +1,459 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::error::ListObjectsV2Error;
+use aws_sdk_s3::output::ListObjectsV2Output;
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_client::SdkError;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::FutureExt;
+use snafu::{ResultExt, Snafu};
+use tokio::{fs, io::AsyncWriteExt, pin, select, time::sleep};
+use vector_config::configurable_component;
+
+use super::{ObjectError, ObjectProcessor};
+use crate::{
+    config::{AcknowledgementsConfig, SourceContext},
+    internal_events::{S3ListObjectsError, S3ObjectProcessingError, S3ProcessedKeysWriteError},
+    SourceSender,
+};
+
+const PROCESSED_KEYS_FILENAME: &str = "processed_keys";
+
+/// Bucket listing configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to list.
+    pub(super) bucket: String,
+
+    /// Only objects whose key starts with this prefix are read.
+    pub(super) prefix: Option<String>,
+
+    /// Only objects whose key sorts after this key are read.
+    ///
+    /// Keys are listed in UTF-8 binary order, so this can skip large parts of a bucket whose keys start with a date.
+    pub(super) start_after: Option<String>,
+
+    /// Only objects last modified at or after this time are read, as an RFC 3339 timestamp.
+    pub(super) modified_after: Option<String>,
+
+    /// Only objects last modified before this time are read, as an RFC 3339 timestamp.
+    pub(super) modified_before: Option<String>,
+
+    /// How long to wait between listings of the bucket, in seconds.
+    ///
+    /// Objects added to the bucket since the previous listing are read by the next one.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    pub(super) poll_secs: u32,
+}
+
+const fn default_poll_secs() -> u32 {
+    60
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Invalid timestamp {:?}: {}", timestamp, source))]
+    InvalidTimestamp {
+        source: chrono::ParseError,
+        timestamp: String,
+    },
+    #[snafu(display("Unable to open processed keys at {:?}: {}", path, source))]
+    OpenProcessedKeys {
+        source: io::Error,
+        path: std::path::PathBuf,
+    },
+}
+
+pub(super) struct Ingestor {
+    s3_client: S3Client,
+    objects: ObjectProcessor,
+
+    bucket: String,
+    prefix: Option<String>,
+    start_after: Option<String>,
+    modified_after: Option<DateTime<Utc>>,
+    modified_before: Option<DateTime<Utc>>,
+    poll_interval: Duration,
+
+    processed: ProcessedKeys,
+}
+
+impl Ingestor {
+    pub(super) async fn new(
+        s3_client: S3Client,
+        objects: ObjectProcessor,
+        config: Config,
+        data_dir: &Path,
+    ) -> Result<Ingestor, IngestorNewError> {
+        let path = data_dir.join(PROCESSED_KEYS_FILENAME);
+        let processed = ProcessedKeys::open(config.bucket.clone(), &path)
+            .await
+            .context(OpenProcessedKeysSnafu { path })?;
+
+        Ok(Ingestor {
+            s3_client,
+            objects,
+
+            bucket: config.bucket,
+            prefix: config.prefix,
+            start_after: config.start_after,
+            modified_after: parse_timestamp(config.modified_after)?,
+            modified_before: parse_timestamp(config.modified_before)?,
+            poll_interval: Duration::from_secs(config.poll_secs.into()),
+
+            processed,
+        })
+    }
+
+    pub(super) async fn run(
+        mut self,
+        cx: SourceContext,
+        acknowledgements: AcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let mut out = cx.out;
+        let shutdown = cx.shutdown.fuse();
+        pin!(shutdown);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once(&mut out, acknowledgements) => {},
+            }
+            select! {
+                _ = &mut shutdown => break,
+                _ = sleep(self.poll_interval) => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the objects of a single listing of the bucket that weren't processed yet, or were overwritten since.
+    async fn run_once(&mut self, out: &mut SourceSender, acknowledgements: bool) {
+        let mut continuation_token = None;
+        // The keys of the listing, so that processed objects that were deleted since are forgotten.
+        let mut listed = HashSet::new();
+        loop {
+            let output = match self.list_objects(continuation_token).await {
+                Ok(output) => output,
+                Err(error) => {
+                    emit!(S3ListObjectsError {
+                        bucket: &self.bucket,
+                        error: &error,
+                    });
+                    return;
+                }
+            };
+
+            for object in output.contents.unwrap_or_default() {
+                let key = match object.key {
+                    Some(key) => key,
+                    None => continue,
+                };
+                // Keys ending with a slash are placeholders for folders, and have no content.
+                if key.ends_with('/') {
+                    continue;
+                }
+                listed.insert(self.processed.qualify(&key));
+                if self.processed.contains(&key, object.e_tag.as_deref()) {
+                    continue;
+                }
+                let last_modified = object
+                    .last_modified
+                    .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()));
+                if !self.in_time_window(last_modified) {
+                    continue;
+                }
+
+                match self
+                    .objects
+                    .process(&self.bucket, &key, out, acknowledgements)
+                    .await
+                {
+                    Ok(()) => {
+                        if let Err(error) = self.processed.insert(&key, object.e_tag).await {
+                            emit!(S3ProcessedKeysWriteError { error });
+                        }
+                    }
+                    Err(error) => {
+                        emit!(S3ObjectProcessingError {
+                            bucket: &self.bucket,
+                            key: &key,
+                            error: &error,
+                        });
+                        // The object is read again by the next listing, unless the pipeline is gone.
+                        if matches!(error, ObjectError::PipelineSend { .. }) {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            continuation_token = match output.next_continuation_token {
+                Some(token) => Some(token),
+                None => break,
+            };
+        }
+
+        if let Err(error) = self.processed.retain(&listed).await {
+            emit!(S3ProcessedKeysWriteError { error });
+        }
+    }
+
+    fn in_time_window(&self, last_modified: Option<DateTime<Utc>>) -> bool {
+        match last_modified {
+            Some(last_modified) => {
+                self.modified_after
+                    .map_or(true, |after| last_modified >= after)
+                    && self
+                        .modified_before
+                        .map_or(true, |before| last_modified < before)
+            }
+            None => self.modified_after.is_none() && self.modified_before.is_none(),
+        }
+    }
+
+    async fn list_objects(
+        &self,
+        continuation_token: Option<String>,
+    ) -> Result<ListObjectsV2Output, SdkError<ListObjectsV2Error>> {
+        self.s3_client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .set_prefix(self.prefix.clone())
+            .set_start_after(self.start_after.clone())
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+    }
+}
+
+fn parse_timestamp(timestamp: Option<String>) -> Result<Option<DateTime<Utc>>, IngestorNewError> {
+    timestamp
+        .map(|timestamp| {
+            DateTime::parse_from_rfc3339(&timestamp)
+                .map(|parsed| parsed.with_timezone(&Utc))
+                .context(InvalidTimestampSnafu { timestamp })
+        })
+        .transpose()
+}
+
+/// The keys of the objects that were processed, with their ETag, so that an object that is overwritten is read again.
+///
+/// Objects are appended to a file as JSON arrays of their key and ETag, one per line, so that recording an object
+/// doesn't rewrite the whole file. Once a listing completes, the objects it didn't list are forgotten, and the file is
+/// compacted if that removed any, or if most of its lines are superseded, so that it's bounded by the size of the
+/// listing. Keys are qualified by their bucket, so that changing the listed bucket doesn't skip any object.
+struct ProcessedKeys {
+    bucket: String,
+    path: PathBuf,
+    file: fs::File,
+    etags: HashMap<String, Option<String>>,
+    /// The number of lines of the file, including the ones superseded by a later line for the same key.
+    lines: usize,
+}
+
+impl ProcessedKeys {
+    async fn open(bucket: String, path: &Path) -> io::Result<Self> {
+        let contents = match fs::read(path).await {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error),
+        };
+
+        // A line without a newline was cut short by a crash. It's ignored, and terminated so that it isn't joined with
+        // the next object.
+        let mut etags = HashMap::new();
+        let mut lines = 0;
+        for line in contents.split(|byte| *byte == b'\n') {
+            if let Ok((key, etag)) = serde_json::from_slice::<(String, Option<String>)>(line) {
+                etags.insert(key, etag);
+                lines += 1;
+            }
+        }
+        let mut file = Self::open_file(path).await?;
+        if contents.last().map_or(false, |byte| *byte != b'\n') {
+            file.write_all(b"\n").await?;
+        }
+
+        Ok(Self {
+            bucket,
+            path: path.to_owned(),
+            file,
+            etags,
+            lines,
+        })
+    }
+
+    async fn open_file(path: &Path) -> io::Result<fs::File> {
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+    }
+
+    fn qualify(&self, key: &str) -> String {
+        format!("{}/{}", self.bucket, key)
+    }
+
+    /// Whether the object was processed, and wasn't overwritten since.
+    fn contains(&self, key: &str, etag: Option<&str>) -> bool {
+        self.etags
+            .get(&self.qualify(key))
+            .map_or(false, |processed| processed.as_deref() == etag)
+    }
+
+    async fn insert(&mut self, key: &str, etag: Option<String>) -> crate::Result<()> {
+        let key = self.qualify(key);
+        let mut line = serde_json::to_vec(&(&key, &etag))?;
+        line.push(b'\n');
+        self.file.write_all(&line).await?;
+        self.file.sync_data().await?;
+        self.etags.insert(key, etag);
+        self.lines += 1;
+        Ok(())
+    }
+
+    /// Forgets the objects whose qualified key isn't in the complete listing `listed`, compacting the file if needed.
+    async fn retain(&mut self, listed: &HashSet<String>) -> crate::Result<()> {
+        let count = self.etags.len();
+        self.etags.retain(|key, _| listed.contains(key));
+        if self.etags.len() < count || self.lines > 2 * self.etags.len() {
+            self.compact().await?;
+        }
+        Ok(())
+    }
+
+    /// Rewrites the file with one line per object, to a temporary file first so that a crash can't lose any object.
+    async fn compact(&mut self) -> crate::Result<()> {
+        let mut contents = Vec::new();
+        for entry in &self.etags {
+            serde_json::to_writer(&mut contents, &entry)?;
+            contents.push(b'\n');
+        }
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut tmp_file = fs::File::create(&tmp_path).await?;
+        tmp_file.write_all(&contents).await?;
+        tmp_file.sync_data().await?;
+        fs::rename(&tmp_path, &self.path).await?;
+
+        self.file = Self::open_file(&self.path).await?;
+        self.lines = self.etags.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn processed_keys_persist() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(PROCESSED_KEYS_FILENAME);
+        let etag = Some("\"a1\"");
+
+        let mut processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        assert!(!processed.contains("logs/2022-08-01.log", etag));
+        processed
+            .insert("logs/2022-08-01.log", etag.map(Into::into))
+            .await
+            .unwrap();
+        processed
+            .insert("key\nwith newline", etag.map(Into::into))
+            .await
+            .unwrap();
+
+        // Simulate a crash while a key was being written.
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, b"[\"bucket/torn"))
+            .unwrap();
+
+        let mut processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        assert!(processed.contains("logs/2022-08-01.log", etag));
+        assert!(processed.contains("key\nwith newline", etag));
+        assert!(!processed.contains("torn", etag));
+
+        processed.insert("after", None).await.unwrap();
+        let processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        assert!(processed.contains("after", None));
+
+        let other = ProcessedKeys::open("other".into(), &path).await.unwrap();
+        assert!(!other.contains("logs/2022-08-01.log", etag));
+    }
+
+    #[tokio::test]
+    async fn processed_keys_overwritten_object() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(PROCESSED_KEYS_FILENAME);
+
+        let mut processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        processed
+            .insert("log", Some("\"v1\"".into()))
+            .await
+            .unwrap();
+        assert!(processed.contains("log", Some("\"v1\"")));
+        assert!(!processed.contains("log", Some("\"v2\"")));
+
+        processed
+            .insert("log", Some("\"v2\"".into()))
+            .await
+            .unwrap();
+        let processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        assert!(processed.contains("log", Some("\"v2\"")));
+        assert!(!processed.contains("log", Some("\"v1\"")));
+    }
+
+    #[tokio::test]
+    async fn processed_keys_forget_unlisted_objects() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(PROCESSED_KEYS_FILENAME);
+
+        let mut processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        for key in ["deleted", "kept"] {
+            processed.insert(key, None).await.unwrap();
+        }
+        for _ in 0..3 {
+            processed.insert("kept", None).await.unwrap();
+        }
+
+        let listed = HashSet::from([processed.qualify("kept")]);
+        processed.retain(&listed).await.unwrap();
+        assert!(!processed.contains("deleted", None));
+        assert!(processed.contains("kept", None));
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        // Objects recorded after compacting are appended to the new file.
+        processed.insert("new", None).await.unwrap();
+        let processed = ProcessedKeys::open("bucket".into(), &path).await.unwrap();
+        assert!(!processed.contains("deleted", None));
+        assert!(processed.contains("kept", None));
+        assert!(processed.contains("new", None));
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(
+            parse_timestamp(Some("2022-08-01T12:00:00+02:00".into())).unwrap(),
+            Some(Utc.ymd(2022, 8, 1).and_hms(10, 0, 0))
+        );
+        assert_eq!(parse_timestamp(None).unwrap(), None);
+        assert!(parse_timestamp(Some("yesterday".into())).is_err());
+    }
+}
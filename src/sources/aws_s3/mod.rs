@@ -1,14 +1,25 @@
 use std::convert::TryInto;
+use std::future::ready;
 use std::io::ErrorKind;
+use std::path::PathBuf;
 
 use async_compression::tokio::bufread;
+use aws_sdk_s3::error::GetObjectError;
 use aws_sdk_s3::types::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_client::SdkError;
+use aws_types::region::Region;
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use codecs::{decoding::FramingError, CharacterDelimitedDecoder};
 use futures::stream;
-use futures::{stream::StreamExt, TryStreamExt};
-use snafu::Snafu;
+use futures::{stream::StreamExt, Stream, TryStreamExt};
+use lookup::path;
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
 use tokio_util::io::StreamReader;
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::{config::LogNamespace, ByteSizeOf};
 
 use super::util::MultilineConfig;
 use crate::aws::create_client;
@@ -19,13 +30,17 @@ use crate::tls::TlsConfig;
 use crate::{
     aws::auth::AwsAuthentication,
     config::{
-        AcknowledgementsConfig, DataType, Output, ProxyConfig, SourceConfig, SourceContext,
-        SourceDescription,
+        log_schema, AcknowledgementsConfig, DataType, Output, ProxyConfig, SourceConfig,
+        SourceContext, SourceDescription,
     },
-    line_agg,
+    event::{BatchNotifier, BatchStatus, LogEvent},
+    internal_events::{BytesReceived, OldEventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
     serde::bool_or_struct,
+    SourceSender,
 };
 
+mod list;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -59,6 +74,9 @@ enum Strategy {
     /// Consumes objects by processing bucket notification events sent to an [AWS SQS queue](\(urls.aws_sqs)).
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes objects by periodically listing a bucket, skipping the objects that were already processed.
+    List,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -85,6 +103,17 @@ pub struct AwsS3Config {
     /// Only relevant when `strategy = "sqs"`.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for listing a bucket.
+    ///
+    /// Only relevant when `strategy = "list"`.
+    list: Option<list::Config>,
+
+    /// The directory used to persist the keys of processed objects.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write permissions to this
+    /// directory. Only relevant when `strategy = "list"`.
+    data_dir: Option<PathBuf>,
+
     /// The ARN of an [IAM role](\(urls.aws_iam_role)) to assume at startup.
     #[deprecated]
     assume_role: Option<String>,
@@ -128,6 +157,11 @@ impl SourceConfig for AwsS3Config {
                     .await?
                     .run(cx, self.acknowledgements),
             )),
+            Strategy::List => Ok(Box::pin(
+                self.create_list_ingestor(multiline_config, &cx)
+                    .await?
+                    .run(cx, self.acknowledgements),
+            )),
         }
     }
 
@@ -197,6 +231,46 @@ impl AwsS3Config {
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_list_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+    ) -> crate::Result<list::Ingestor> {
+        let config = self
+            .list
+            .clone()
+            .ok_or(CreateListIngestorError::ConfigMissing)?;
+
+        let region = self
+            .region
+            .region()
+            .ok_or(CreateListIngestorError::RegionMissing)?;
+
+        let endpoint = self
+            .region
+            .endpoint()
+            .map_err(|_| CreateListIngestorError::InvalidEndpoint)?;
+
+        let s3_client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            Some(region.clone()),
+            endpoint,
+            &cx.proxy,
+            &self.tls_options,
+            false,
+        )
+        .await?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        let objects = ObjectProcessor::new(region, s3_client.clone(), self.compression, multiline);
+        let ingestor = list::Ingestor::new(s3_client, objects, config, &data_dir).await?;
+
+        Ok(ingestor)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -215,6 +289,214 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+#[derive(Debug, Snafu)]
+enum CreateListIngestorError {
+    #[snafu(display("Configuration for `list` required when strategy=list"))]
+    ConfigMissing,
+    #[snafu(display("Region is required"))]
+    RegionMissing,
+    #[snafu(display("Endpoint is invalid"))]
+    InvalidEndpoint,
+}
+
+/// An error reading an object and forwarding its lines.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Snafu)]
+pub enum ObjectError {
+    #[snafu(display("Failed to fetch s3://{}/{}: {}", bucket, key, source))]
+    GetObject {
+        source: SdkError<GetObjectError>,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Failed to read all of s3://{}/{}: {}", bucket, key, source))]
+    ReadObject {
+        source: Box<dyn FramingError>,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Failed to flush all of s3://{}/{}: {}", bucket, key, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display("Sink reported an error sending events"))]
+    ErrorAcknowledgement,
+}
+
+/// Reads objects and forwards their lines as events, for all of the strategies.
+pub(super) struct ObjectProcessor {
+    region: Region,
+    s3_client: S3Client,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+}
+
+impl ObjectProcessor {
+    pub(super) const fn new(
+        region: Region,
+        s3_client: S3Client,
+        compression: Compression,
+        multiline: Option<line_agg::Config>,
+    ) -> Self {
+        Self {
+            region,
+            s3_client,
+            compression,
+            multiline,
+        }
+    }
+
+    /// Forwards the lines of an object, waiting for them to be acknowledged if needed.
+    pub(super) async fn process(
+        &self,
+        bucket: &str,
+        key: &str,
+        out: &mut SourceSender,
+        acknowledgements: bool,
+    ) -> Result<(), ObjectError> {
+        let object = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context(GetObjectSnafu { bucket, key })?;
+
+        let metadata = object.metadata;
+        let timestamp = object
+            .last_modified
+            .map(|ts| Utc.timestamp(ts.secs(), ts.subsec_nanos()))
+            .unwrap_or_else(Utc::now);
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+        let object_reader = s3_object_decoder(
+            self.compression,
+            key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid ack'ing the object
+        //
+        // String is used as we cannot clone std::io::Error to take ownership in closure
+        //
+        // FramedRead likely stops when it gets an i/o error but I found it more clear to
+        // show that we `take_while` there hasn't been an error
+        //
+        // This can result in objects being partially processed before an error, but we
+        // prefer duplicate lines over message loss. Future work could include recording
+        // the offset of the object that has been read, but this would only be relevant in
+        // the case that the same vector instance processes the same message.
+        let mut read_error = None;
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(object_reader, CharacterDelimitedDecoder::new(b'\n'))
+                .map(|res| {
+                    res.map(|bytes| {
+                        emit!(BytesReceived {
+                            byte_size: bytes.len(),
+                            protocol: "http",
+                        });
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context)| line),
+            ),
+            None => lines,
+        };
+
+        let bucket_name = Bytes::from(bucket.as_bytes().to_vec());
+        let object_key = Bytes::from(key.as_bytes().to_vec());
+        let aws_region = Bytes::from(self.region.as_ref().as_bytes().to_vec());
+
+        let mut stream = lines.filter_map(move |line| {
+            let mut log = LogEvent::from_bytes_legacy(&line).with_batch_notifier_option(&batch);
+
+            log.insert(path!("bucket"), bucket_name.clone());
+            log.insert(path!("object"), object_key.clone());
+            log.insert(path!("region"), aws_region.clone());
+            log.insert(log_schema().source_type_key(), Bytes::from("aws_s3"));
+            log.insert(log_schema().timestamp_key(), timestamp);
+
+            if let Some(metadata) = &metadata {
+                for (key, value) in metadata {
+                    log.insert(key.as_str(), value.clone());
+                }
+            }
+
+            emit!(OldEventsReceived {
+                count: 1,
+                byte_size: log.size_of()
+            });
+
+            ready(Some(log))
+        });
+
+        let send_error = match out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(error) => {
+                // count is set to 0 to have no discarded events considering
+                // the events are not yet acknowledged and will be retried in
+                // case of error
+                emit!(StreamClosedError { error, count: 0 });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        if let Some(error) = read_error {
+            Err(ObjectError::ReadObject {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(ObjectError::PipelineSend {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else {
+            match receiver {
+                None => Ok(()),
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => Ok(()),
+                    BatchStatus::Errored => Err(ObjectError::ErrorAcknowledgement),
+                    BatchStatus::Rejected => {
+                        error!(
+                            message = "Sink reported events were rejected.",
+                            internal_log_rate_secs = 5,
+                        );
+                        // Failed events cannot be retried, so the object is considered processed.
+                        Ok(())
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
@@ -375,7 +657,7 @@ mod integration_tests {
     use aws_sdk_sqs::Client as SqsClient;
     use pretty_assertions::assert_eq;
 
-    use super::{sqs, AwsS3Config, Compression, Strategy};
+    use super::{list, sqs, AwsS3Config, Compression, Strategy};
     use crate::aws::create_client;
     use crate::aws::{AwsAuthentication, RegionOrEndpoint};
     use crate::common::sqs::SqsClientBuilder;
@@ -573,6 +855,72 @@ mod integration_tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn s3_list_process_objects() {
+        trace_init();
+
+        assert_source_compliance(&["protocol"], async move {
+            let s3 = s3_client().await;
+            let bucket = create_bucket(&s3).await;
+
+            let logs: Vec<String> = random_lines(100).take(10).collect();
+            let (first, second) = logs.split_at(4);
+            for (key, lines) in [("logs/first.log", first), ("logs/second.log", second)] {
+                s3.put_object()
+                    .bucket(bucket.clone())
+                    .key(key)
+                    .body(ByteStream::from(lines.join("\n").into_bytes()))
+                    .send()
+                    .await
+                    .expect("Could not put object");
+            }
+            s3.put_object()
+                .bucket(bucket.clone())
+                .key("other/ignored.log")
+                .body(ByteStream::from_static(b"ignored"))
+                .send()
+                .await
+                .expect("Could not put object");
+
+            let data_dir = tempfile::tempdir().unwrap();
+            let config = AwsS3Config {
+                region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
+                strategy: Strategy::List,
+                list: Some(list::Config {
+                    bucket: bucket.clone(),
+                    prefix: Some("logs/".to_owned()),
+                    poll_secs: 1,
+                    ..Default::default()
+                }),
+                data_dir: Some(data_dir.path().to_path_buf()),
+                acknowledgements: true.into(),
+                ..Default::default()
+            };
+
+            let (tx, rx) = SourceSender::new_test_finalize(Delivered);
+            let cx = SourceContext::new_test(tx, None);
+            let source = config.build(cx).await.unwrap();
+            tokio::spawn(async move { source.await.unwrap() });
+
+            let events = collect_n(rx, logs.len()).await;
+
+            let mut messages = events
+                .iter()
+                .map(|event| {
+                    let log = event.as_log();
+                    assert_eq!(log["bucket"], bucket.clone().into());
+                    assert!(log["object"].to_string_lossy().starts_with("logs/"));
+                    log["message"].to_string_lossy()
+                })
+                .collect::<Vec<_>>();
+            let mut expected = logs.clone();
+            messages.sort();
+            expected.sort();
+            assert_eq!(messages, expected);
+        })
+        .await;
+    }
+
     fn s3_address() -> String {
         std::env::var("S3_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
     }
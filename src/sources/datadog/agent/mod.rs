@@ -260,6 +260,14 @@ impl SourceConfig for DatadogAgentConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn decoding(&self, global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            global_log_namespace.merge(self.log_namespace),
+        ))
+    }
 }
 
 #[derive(Clone, Copy, Debug, Snafu)]
@@ -11,7 +11,7 @@ use codecs::{
     decoding::{Deserializer, DeserializerConfig, Framer},
     BytesDecoder, BytesDeserializer,
 };
-use futures::{Stream, StreamExt};
+use futures::{FutureExt, Stream, StreamExt};
 use http::HeaderMap;
 use indoc::indoc;
 use ordered_float::NotNan;
@@ -22,7 +22,10 @@ use value::Kind;
 use vector_core::config::LogNamespace;
 
 use crate::{
-    common::datadog::{DatadogMetricType, DatadogPoint, DatadogSeriesMetric},
+    common::datadog::{
+        apm_stats::{ClientGroupedStats, ClientStatsBucket, ClientStatsPayload, StatsPayload},
+        DatadogMetricType, DatadogPoint, DatadogSeriesMetric,
+    },
     config::{log_schema, SourceConfig, SourceContext},
     event::{
         into_event_stream,
@@ -41,6 +44,11 @@ use crate::{
     },
     SourceSender,
 };
+#[cfg(feature = "sinks-datadog_traces")]
+use crate::{
+    config::{SinkConfig, SinkContext},
+    sinks::util::test::build_test_server_status,
+};
 
 fn test_logs_schema_definition() -> schema::Definition {
     schema::Definition::empty_legacy_namespace().with_field(
@@ -1000,6 +1008,9 @@ async fn decode_traces() {
             tracer_version: "v577".to_string(),
             runtime_id: "123abc".to_string(),
             chunks: vec![chunk],
+            tags: BTreeMap::from_iter([("tracer".to_string(), "tag".to_string())].into_iter()),
+            env: "tracer_env".to_string(),
+            hostname: "tracer_hostname".to_string(),
             app_version: "v314".to_string(),
         };
 
@@ -1113,6 +1124,16 @@ async fn decode_traces() {
             assert_eq!(trace_v2.as_map()["tracer_version"], "v577".into());
             assert_eq!(trace_v2.as_map()["runtime_id"], "123abc".into());
             assert_eq!(trace_v2.as_map()["app_version"], "v314".into());
+            assert_eq!(
+                trace_v2.as_map()["tracer_tags"].as_object().unwrap()["tracer"],
+                "tag".into()
+            );
+            assert_eq!(trace_v2.as_map()["tracer_env"], "tracer_env".into());
+            assert_eq!(
+                trace_v2.as_map()["tracer_hostname"],
+                "tracer_hostname".into()
+            );
+            assert_eq!(trace_v2.as_map()["apm_stats_computed"], true.into());
             assert_eq!(trace_v2.as_map()["priority"], Value::Integer(42));
             assert_eq!(
                 trace_v2.as_map()["target_tps"],
@@ -1161,6 +1182,357 @@ async fn decode_traces() {
     .await;
 }
 
+fn apm_stats_payload() -> StatsPayload {
+    StatsPayload {
+        agent_hostname: "a_hostname".to_string(),
+        agent_env: "an_environment".to_string(),
+        stats: vec![ClientStatsPayload {
+            hostname: "a_hostname".to_string(),
+            env: "an_environment".to_string(),
+            version: "v314".to_string(),
+            stats: vec![ClientStatsBucket {
+                start: 1_431_648_000_000_000_000,
+                duration: 10_000_000_000,
+                stats: vec![ClientGroupedStats {
+                    service: "a_service".to_string(),
+                    name: "a_name".to_string(),
+                    resource: "a_resource".to_string(),
+                    http_status_code: 200,
+                    r#type: "web".to_string(),
+                    db_type: "".to_string(),
+                    hits: 12,
+                    errors: 2,
+                    duration: 1_000_000_000,
+                    ok_summary: vec![8, 1, 2, 3],
+                    error_summary: vec![8, 4, 5],
+                    synthetics: false,
+                    top_level_hits: 10,
+                }],
+                agent_time_shift: 0,
+            }],
+            lang: "go".to_string(),
+            tracer_version: "v1.38.0".to_string(),
+            runtime_id: "123abc".to_string(),
+            sequence: 3,
+            agent_aggregation: "".to_string(),
+            service: "a_service".to_string(),
+            container_id: "an_id".to_string(),
+            tags: vec!["a:tag".to_string()],
+        }],
+        agent_version: "v1.23456".to_string(),
+        client_computed: true,
+    }
+}
+
+#[tokio::test]
+async fn decode_apm_stats() {
+    assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+        let (rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "dd-api-key",
+            "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+        );
+        headers.insert("content-type", "application/msgpack".parse().unwrap());
+
+        let payload = apm_stats_payload();
+        let body = rmp_serde::to_vec_named(&payload).unwrap();
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    send_with_path(
+                        addr,
+                        unsafe { str::from_utf8_unchecked(&body) },
+                        headers,
+                        "/api/v0.2/stats"
+                    )
+                    .await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let stats = events[0].as_trace();
+        assert_eq!(stats.as_map()["host"], "a_hostname".into());
+        assert_eq!(stats.as_map()["env"], "an_environment".into());
+        assert_eq!(stats.as_map()["agent_version"], "v1.23456".into());
+        assert_eq!(stats.as_map()["client_computed"], true.into());
+        assert_eq!(
+            stats.as_map()[log_schema().source_type_key()],
+            "datadog_agent".into()
+        );
+        assert_eq!(
+            stats.as_map()["apm_stats"],
+            Value::from(vec![Value::from(payload.stats[0].clone())])
+        );
+        assert_eq!(
+            &events[0].metadata().datadog_api_key().as_ref().unwrap()[..],
+            "12345678abcdefgh12345678abcdefgh"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn ignores_json_apm_stats() {
+    let (mut rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", "application/json".parse().unwrap());
+
+    assert_eq!(
+        200,
+        send_with_path(
+            addr,
+            r#"{"hostname":"a_hostname"}"#,
+            headers,
+            "/api/v0.2/stats"
+        )
+        .await
+    );
+    assert!(rx.next().now_or_never().is_none());
+}
+
+#[tokio::test]
+async fn passes_through_profiles() {
+    assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
+        let (rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "dd-api-key",
+            "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+        );
+        headers.insert(
+            "content-type",
+            "multipart/form-data; boundary=a_boundary".parse().unwrap(),
+        );
+        headers.insert(
+            "x-datadog-additional-tags",
+            "host:a_hostname,default_env:an_environment".parse().unwrap(),
+        );
+        headers.insert("user-agent", "a_tracer".parse().unwrap());
+
+        let body = "--a_boundary\r\nContent-Disposition: form-data; name=\"version\"\r\n\r\n3\r\n--a_boundary--\r\n";
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    200,
+                    send_with_path(addr, body, headers, "/api/v2/profile").await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let profile = events[0].as_trace().as_map()["profile"]
+            .as_object()
+            .unwrap();
+        assert_eq!(profile["body"], Value::from(Bytes::from(body)));
+        assert_eq!(
+            profile["headers"],
+            Value::from(BTreeMap::from([
+                (
+                    "content-type".to_string(),
+                    Value::from("multipart/form-data; boundary=a_boundary")
+                ),
+                (
+                    "x-datadog-additional-tags".to_string(),
+                    Value::from("host:a_hostname,default_env:an_environment")
+                ),
+            ]))
+        );
+        assert_eq!(
+            &events[0].metadata().datadog_api_key().as_ref().unwrap()[..],
+            "12345678abcdefgh12345678abcdefgh"
+        );
+    })
+    .await;
+}
+
+/// Sends events received by the source to a `datadog_traces` sink, and returns the requests it made.
+#[cfg(feature = "sinks-datadog_traces")]
+async fn relay_to_datadog_traces(
+    events: Vec<Event>,
+    requests: usize,
+) -> Vec<(http::request::Parts, Bytes)> {
+    let addr = next_addr();
+    let config: Box<dyn SinkConfig> = toml::from_str(&format!(
+        indoc! {r#"
+            type = "datadog_traces"
+            default_api_key = "atoken"
+            compression = "none"
+            endpoint = "http://{}"
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+    let (rx, _trigger, server) = build_test_server_status(addr, http::StatusCode::OK);
+    tokio::spawn(server);
+
+    sink.run_events(events).await.unwrap();
+    rx.take(requests).collect().await
+}
+
+#[cfg(feature = "sinks-datadog_traces")]
+#[tokio::test]
+async fn relays_traces_without_loss() {
+    let (rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "dd-api-key",
+        "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+    );
+
+    let span = |span_id: u64, parent_id: u64| ddtrace_proto::Span {
+        service: "a_service".to_string(),
+        name: "a_name".to_string(),
+        resource: "a_resource".to_string(),
+        trace_id: u64::MAX - 1,
+        span_id,
+        parent_id,
+        start: 1_431_648_000_000_001i64,
+        duration: 1_000_000_000i64,
+        error: 1i32,
+        meta: BTreeMap::from([
+            ("foo".to_string(), "bar".to_string()),
+            ("http.status_code".to_string(), "500".to_string()),
+        ]),
+        metrics: BTreeMap::from([
+            ("_top_level".to_string(), 1.0f64),
+            ("_sampling_priority_v1".to_string(), 2.0f64),
+        ]),
+        r#type: "web".to_string(),
+        meta_struct: BTreeMap::from([("appsec".to_string(), vec![0u8, 159, 146, 150])]),
+    };
+    let chunk = |priority: i32, spans: Vec<ddtrace_proto::Span>| ddtrace_proto::TraceChunk {
+        priority,
+        origin: "synthetics".to_string(),
+        spans,
+        tags: BTreeMap::from([("_dd.p.dm".to_string(), "-4".to_string())]),
+        dropped_trace: false,
+    };
+    let tracer_payload = ddtrace_proto::TracerPayload {
+        container_id: "an_id".to_string(),
+        language_name: "go".to_string(),
+        language_version: "go1.19".to_string(),
+        tracer_version: "v1.38.0".to_string(),
+        runtime_id: "123abc".to_string(),
+        chunks: vec![
+            chunk(2, vec![span(1, 0), span(2, 1)]),
+            chunk(-1, vec![span(3, 0)]),
+        ],
+        tags: BTreeMap::from([("_dd.tags.container".to_string(), "a:tag".to_string())]),
+        env: "tracer_env".to_string(),
+        hostname: "tracer_hostname".to_string(),
+        app_version: "v314".to_string(),
+    };
+    let payload = ddtrace_proto::TracePayload {
+        host_name: "a_hostname".to_string(),
+        env: "an_environment".to_string(),
+        traces: vec![],
+        transactions: vec![],
+        tracer_payloads: vec![tracer_payload.clone()],
+        tags: BTreeMap::new(),
+        agent_version: "v1.23456".to_string(),
+        target_tps: 10.5f64,
+        error_tps: 2.5f64,
+    };
+    let body = payload.encode_to_vec();
+
+    let events = spawn_collect_n(
+        async move {
+            assert_eq!(
+                200,
+                send_with_path(
+                    addr,
+                    unsafe { str::from_utf8_unchecked(&body) },
+                    headers,
+                    "/api/v0.2/traces"
+                )
+                .await
+            );
+        },
+        rx,
+        2,
+    )
+    .await;
+
+    // Each chunk is relayed in its own tracer payload, and no APM stats are computed out of them.
+    let mut requests = relay_to_datadog_traces(events, 1).await;
+    let (parts, body) = requests.pop().unwrap();
+    assert_eq!(parts.uri.path(), "/api/v0.2/traces");
+    assert_eq!(
+        parts.headers.get("DD-API-KEY").unwrap(),
+        "12345678abcdefgh12345678abcdefgh"
+    );
+    let relayed = ddtrace_proto::TracePayload::decode(body).unwrap();
+    assert_eq!(
+        relayed,
+        ddtrace_proto::TracePayload {
+            tracer_payloads: tracer_payload
+                .chunks
+                .iter()
+                .map(|chunk| ddtrace_proto::TracerPayload {
+                    chunks: vec![chunk.clone()],
+                    ..tracer_payload.clone()
+                })
+                .collect(),
+            ..payload
+        }
+    );
+}
+
+#[cfg(feature = "sinks-datadog_traces")]
+#[tokio::test]
+async fn relays_apm_stats_without_loss() {
+    let (rx, _, _, addr) = source(EventStatus::Delivered, true, true, false).await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "dd-api-key",
+        "12345678abcdefgh12345678abcdefgh".parse().unwrap(),
+    );
+    headers.insert("content-type", "application/msgpack".parse().unwrap());
+
+    let payload = apm_stats_payload();
+    let body = rmp_serde::to_vec_named(&payload).unwrap();
+
+    let events = spawn_collect_n(
+        async move {
+            assert_eq!(
+                200,
+                send_with_path(
+                    addr,
+                    unsafe { str::from_utf8_unchecked(&body) },
+                    headers,
+                    "/api/v0.2/stats"
+                )
+                .await
+            );
+        },
+        rx,
+        1,
+    )
+    .await;
+
+    let mut requests = relay_to_datadog_traces(events, 1).await;
+    let (parts, body) = requests.pop().unwrap();
+    assert_eq!(parts.uri.path(), "/api/v0.2/stats");
+    let relayed: StatsPayload = rmp_serde::from_slice(&body).unwrap();
+    assert_eq!(relayed, payload);
+}
+
 #[tokio::test]
 async fn split_outputs() {
     assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
@@ -2,14 +2,14 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use bytes::Bytes;
 use chrono::{TimeZone, Utc};
-use futures::future;
-use http::StatusCode;
+use http::{HeaderMap, StatusCode};
 use ordered_float::NotNan;
 use prost::Message;
 use vector_core::ByteSizeOf;
-use warp::{filters::BoxedFilter, path, path::FullPath, reply::Response, Filter, Rejection, Reply};
+use warp::{filters::BoxedFilter, path, path::FullPath, reply::Response, Filter};
 
 use crate::{
+    common::datadog::apm_stats::StatsPayload,
     event::{Event, TraceEvent, Value},
     internal_events::EventsReceived,
     sources::{
@@ -27,10 +27,27 @@ pub(crate) fn build_warp_filter(
     out: SourceSender,
     source: DatadogAgentSource,
 ) -> BoxedFilter<(Response,)> {
-    build_trace_filter(acknowledgements, multiple_outputs, out, source)
-        .or(build_stats_filter())
-        .unify()
-        .boxed()
+    build_trace_filter(
+        acknowledgements,
+        multiple_outputs,
+        out.clone(),
+        source.clone(),
+    )
+    .or(build_stats_filter(
+        acknowledgements,
+        multiple_outputs,
+        out.clone(),
+        source.clone(),
+    ))
+    .unify()
+    .or(build_profile_filter(
+        acknowledgements,
+        multiple_outputs,
+        out,
+        source,
+    ))
+    .unify()
+    .boxed()
 }
 
 fn build_trace_filter(
@@ -86,15 +103,104 @@ fn build_trace_filter(
         .boxed()
 }
 
-fn build_stats_filter() -> BoxedFilter<(Response,)> {
+fn build_stats_filter(
+    acknowledgements: bool,
+    multiple_outputs: bool,
+    out: SourceSender,
+    source: DatadogAgentSource,
+) -> BoxedFilter<(Response,)> {
     warp::post()
         .and(path!("api" / "v0.2" / "stats" / ..))
-        .and_then(|| {
-            // APM stats are discarded on purpose, they will be computed in the `datadog_traces` sink
-            // thus we simply reply with a 200/OK response.
-            let response: Result<Response, Rejection> = Ok(warp::reply().into_response());
-            future::ready(response)
-        })
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("dd-api-key"))
+        .and(warp::query::<ApiKeyQueryParams>())
+        .and(warp::body::bytes())
+        .and_then(
+            move |path: FullPath,
+                  encoding_header: Option<String>,
+                  content_type: Option<String>,
+                  api_token: Option<String>,
+                  query_params: ApiKeyQueryParams,
+                  body: Bytes| {
+                let events = source
+                    .decode(&encoding_header, body, path.as_str())
+                    .and_then(|body| {
+                        // Older trace-agents send their APM stats as JSON, along with older trace payloads. Those
+                        // stats are discarded, and computed in the `datadog_traces` sink instead.
+                        if !content_type.as_deref().map_or(false, |content_type| {
+                            content_type.starts_with("application/msgpack")
+                        }) {
+                            return Ok(Vec::new());
+                        }
+                        handle_dd_stats_payload(
+                            body,
+                            source.api_key_extractor.extract(
+                                path.as_str(),
+                                api_token,
+                                query_params.dd_api_key,
+                            ),
+                            &source,
+                        )
+                        .map_err(|error| {
+                            ErrorMessage::new(
+                                StatusCode::UNPROCESSABLE_ENTITY,
+                                format!("Error decoding Datadog APM stats: {:?}", error),
+                            )
+                        })
+                    });
+                if multiple_outputs {
+                    handle_request(events, acknowledgements, out.clone(), Some(agent::TRACES))
+                } else {
+                    handle_request(events, acknowledgements, out.clone(), None)
+                }
+            },
+        )
+        .boxed()
+}
+
+fn build_profile_filter(
+    acknowledgements: bool,
+    multiple_outputs: bool,
+    out: SourceSender,
+    source: DatadogAgentSource,
+) -> BoxedFilter<(Response,)> {
+    warp::post()
+        .and(path!("api" / "v2" / "profile" / ..))
+        .and(warp::path::full())
+        .and(warp::header::headers_cloned())
+        .and(warp::query::<ApiKeyQueryParams>())
+        .and(warp::body::bytes())
+        .and_then(
+            move |path: FullPath,
+                  headers: HeaderMap,
+                  query_params: ApiKeyQueryParams,
+                  body: Bytes| {
+                // Profiles are passed through untouched, compressed or not, along with the headers describing them.
+                let events = source.decode(&None, body, path.as_str()).map(|body| {
+                    let api_token = headers
+                        .get("dd-api-key")
+                        .and_then(|value| value.to_str().ok())
+                        .map(String::from);
+                    handle_dd_profile_payload(
+                        body,
+                        &headers,
+                        source.api_key_extractor.extract(
+                            path.as_str(),
+                            api_token,
+                            query_params.dd_api_key,
+                        ),
+                        &source,
+                    )
+                });
+                if multiple_outputs {
+                    handle_request(events, acknowledgements, out.clone(), Some(agent::TRACES))
+                } else {
+                    handle_request(events, acknowledgements, out.clone(), None)
+                }
+            },
+        )
         .boxed()
 }
 
@@ -151,6 +257,9 @@ fn handle_dd_trace_payload_v1(
                 Bytes::from("datadog_agent"),
             );
             trace_event.insert("payload_version", "v2".to_string());
+            // The trace-agent computes the APM stats of those traces before sampling them, and sends them to the
+            // stats route, so that the `datadog_traces` sink relays them instead of computing them again.
+            trace_event.insert("apm_stats_computed", true);
             trace_event.insert(source.log_schema_host_key, hostname.clone());
             trace_event.insert("env", env.clone());
             trace_event.insert("agent_version", agent_version.clone());
@@ -191,6 +300,12 @@ fn convert_dd_tracer_payload(payload: ddtrace_proto::TracerPayload) -> Vec<Trace
             trace_event.insert("tracer_version", payload.tracer_version.clone());
             trace_event.insert("runtime_id", payload.runtime_id.clone());
             trace_event.insert("app_version", payload.app_version.clone());
+            trace_event.insert(
+                "tracer_tags",
+                Value::from(convert_tags(payload.tags.clone())),
+            );
+            trace_event.insert("tracer_env", payload.env.clone());
+            trace_event.insert("tracer_hostname", payload.hostname.clone());
             trace_event
         })
         .collect()
@@ -264,6 +379,94 @@ fn handle_dd_trace_payload_v0(
     Ok(enriched_events)
 }
 
+fn handle_dd_stats_payload(
+    frame: Bytes,
+    api_key: Option<Arc<str>>,
+    source: &DatadogAgentSource,
+) -> crate::Result<Vec<Event>> {
+    let payload: StatsPayload = rmp_serde::from_slice(&frame)?;
+
+    let mut trace_event = TraceEvent::default();
+    if let Some(k) = &api_key {
+        trace_event
+            .metadata_mut()
+            .set_datadog_api_key(Arc::clone(k));
+    }
+    trace_event.insert(
+        source.log_schema_source_type_key,
+        Bytes::from("datadog_agent"),
+    );
+    trace_event.insert(source.log_schema_host_key, payload.agent_hostname);
+    trace_event.insert("env", payload.agent_env);
+    trace_event.insert("agent_version", payload.agent_version);
+    trace_event.insert("client_computed", payload.client_computed);
+    trace_event.insert(
+        "apm_stats",
+        payload
+            .stats
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<Value>>(),
+    );
+
+    emit!(EventsReceived {
+        byte_size: trace_event.size_of(),
+        count: 1,
+    });
+
+    Ok(vec![Event::Trace(trace_event)])
+}
+
+fn handle_dd_profile_payload(
+    body: Bytes,
+    headers: &HeaderMap,
+    api_key: Option<Arc<str>>,
+    source: &DatadogAgentSource,
+) -> Vec<Event> {
+    // The API key is kept in the event metadata, like for any other event.
+    let profile_headers = headers
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.as_str();
+            name == "content-type"
+                || name == "content-encoding"
+                || ((name.starts_with("dd-") || name.starts_with("x-datadog-"))
+                    && name != "dd-api-key")
+        })
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.to_string(), Value::from(value)))
+        })
+        .collect::<BTreeMap<String, Value>>();
+
+    let mut trace_event = TraceEvent::default();
+    if let Some(k) = &api_key {
+        trace_event
+            .metadata_mut()
+            .set_datadog_api_key(Arc::clone(k));
+    }
+    trace_event.insert(
+        source.log_schema_source_type_key,
+        Bytes::from("datadog_agent"),
+    );
+    trace_event.insert(
+        "profile",
+        BTreeMap::from([
+            ("body".to_string(), Value::from(body)),
+            ("headers".to_string(), Value::from(profile_headers)),
+        ]),
+    );
+
+    emit!(EventsReceived {
+        byte_size: trace_event.size_of(),
+        count: 1,
+    });
+
+    vec![Event::Trace(trace_event)]
+}
+
 fn convert_span(dd_span: ddtrace_proto::Span) -> BTreeMap<String, Value> {
     let mut span = BTreeMap::<String, Value>::new();
     span.insert("service".into(), Value::from(dd_span.service));
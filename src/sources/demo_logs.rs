@@ -270,6 +270,14 @@ impl SourceConfig for DemoLogsConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
 }
 
 /// Configuration for the `generator` source.
@@ -298,6 +306,10 @@ impl SourceConfig for DemoLogsCompatConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn decoding(&self, global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        self.0.decoding(global_log_namespace)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,93 @@
+use serde_json::json;
+use tempfile::tempdir;
+
+use super::*;
+use crate::test_util::{
+    components::{run_and_assert_source_compliance_advanced, HTTP_PULL_SOURCE_TAGS},
+    random_string, trace_init,
+};
+
+fn http_server() -> String {
+    std::env::var("ELASTICSEARCH_HTTP_ADDRESS").unwrap_or_else(|_| "http://localhost:9200".into())
+}
+
+/// Creates an index holding the documents, and makes them visible to searches.
+async fn create_index(index: &str, count: usize) {
+    let client = reqwest::Client::new();
+    let body = (0..count)
+        .map(|n| {
+            format!(
+                "{}\n{}\n",
+                json!({ "index": { "_index": index, "_id": n.to_string() } }),
+                json!({ "message": format!("document {}", n) })
+            )
+        })
+        .collect::<String>();
+
+    let response = client
+        .post(format!("{}/_bulk?refresh=true", http_server()))
+        .header("Content-Type", "application/x-ndjson")
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+}
+
+fn config(index: &str, pagination: Pagination) -> ElasticsearchConfig {
+    ElasticsearchConfig {
+        endpoint: http_server(),
+        indices: vec![index.to_string()],
+        query: default_query(),
+        page_size: 7,
+        keep_alive_secs: 60,
+        pagination,
+        metadata_key: default_metadata_key(),
+        auth: None,
+        aws: None,
+        tls: None,
+        data_dir: None,
+        acknowledgements: Default::default(),
+    }
+}
+
+async fn read_index(pagination: Pagination) {
+    trace_init();
+
+    let index = format!("test-{}", random_string(10).to_lowercase());
+    create_index(&index, 20).await;
+    let data_dir = tempdir().unwrap();
+
+    let events = run_and_assert_source_compliance_advanced(
+        config(&index, pagination),
+        |cx| cx.globals.data_dir = Some(data_dir.path().to_path_buf()),
+        Some(Duration::from_secs(10)),
+        None,
+        &HTTP_PULL_SOURCE_TAGS,
+    )
+    .await;
+
+    let mut ids = events
+        .iter()
+        .map(|event| {
+            let log = event.as_log();
+            assert_eq!(log["@metadata.index"], index.as_str().into());
+            log["@metadata.id"]
+                .to_string_lossy()
+                .parse::<usize>()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, (0..20).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn reads_index_with_point_in_time() {
+    read_index(Pagination::PointInTime).await;
+}
+
+#[tokio::test]
+async fn reads_index_with_scroll() {
+    read_index(Pagination::Scroll).await;
+}
@@ -0,0 +1,840 @@
+use std::{collections::BTreeMap, io, path::PathBuf, time::Duration};
+
+use aws_types::{credentials::SharedCredentialsProvider, region::Region};
+use bytes::Bytes;
+use chrono::Utc;
+use futures::FutureExt;
+use http::{header::CONTENT_TYPE, Method, Request, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use snafu::{ResultExt, Snafu};
+use tokio::time::sleep;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    aws::RegionOrEndpoint,
+    common::elasticsearch::{sign_request, ElasticsearchAuth},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig,
+        SourceContext, SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
+    http::{Auth, HttpClient, MaybeAuth},
+    internal_events::{
+        ElasticsearchCheckpointError, ElasticsearchPointInTimeExpired, ElasticsearchSearchError,
+        EndpointBytesReceived, EventsReceived, StreamClosedError,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    sinks::util::UriSerde,
+    sources,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+#[cfg(all(test, feature = "es-integration-tests"))]
+mod integration_tests;
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.json";
+
+/// How long to wait before retrying a failed request, or sending again events that weren't delivered.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one index must be searched"))]
+    NoIndices,
+    #[snafu(display("Invalid query: {}", source))]
+    InvalidQuery { source: serde_json::Error },
+    #[snafu(display("A region is required to authenticate with AWS"))]
+    RegionRequired,
+    #[snafu(display("Could not read checkpoint from {:?}: {}", path, source))]
+    ReadCheckpoint { path: PathBuf, source: io::Error },
+}
+
+#[derive(Debug, Snafu)]
+enum SearchError {
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: crate::Error },
+    #[snafu(display("Unexpected status {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+}
+
+/// Pagination of the search results.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Pagination {
+    /// Pages through a [point in time](\(urls.elasticsearch_point_in_time)) with `search_after`.
+    ///
+    /// The position in the search is checkpointed after each page, so that a restart resumes the search rather than
+    /// starting it over. Requires Elasticsearch 7.10 or later.
+    #[derivative(Default)]
+    PointInTime,
+
+    /// Pages through a [scroll](\(urls.elasticsearch_scroll)).
+    ///
+    /// Supported by older clusters and OpenSearch. A scroll can't be resumed, so a restart before the search completes
+    /// starts it over.
+    Scroll,
+}
+
+/// Configuration for the `elasticsearch` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ElasticsearchConfig {
+    /// The Elasticsearch endpoint to search.
+    ///
+    /// The endpoint must contain an HTTP scheme, and may specify a hostname or IP address and port.
+    endpoint: String,
+
+    /// The indices to search.
+    ///
+    /// Wildcards, aliases and data streams are allowed.
+    indices: Vec<String>,
+
+    /// The query to run, as a JSON object of the [query DSL](\(urls.elasticsearch_query_dsl)).
+    ///
+    /// By default, all documents of the indices are read.
+    #[serde(default = "default_query")]
+    query: String,
+
+    /// The number of documents requested per page.
+    #[serde(default = "default_page_size")]
+    page_size: u32,
+
+    /// How long the cluster keeps the search context alive between two pages, in seconds.
+    #[serde(default = "default_keep_alive_secs")]
+    keep_alive_secs: u32,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pagination: Pagination,
+
+    /// The field the `index` and `id` of each document are inserted in.
+    #[serde(default = "default_metadata_key")]
+    metadata_key: String,
+
+    #[configurable(derived)]
+    auth: Option<ElasticsearchAuth>,
+
+    #[configurable(derived)]
+    aws: Option<RegionOrEndpoint>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    /// The directory used to persist the progress of the search.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write
+    /// permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_query() -> String {
+    r#"{"match_all": {}}"#.to_string()
+}
+
+const fn default_page_size() -> u32 {
+    1000
+}
+
+const fn default_keep_alive_secs() -> u32 {
+    300
+}
+
+fn default_metadata_key() -> String {
+    "@metadata".to_string()
+}
+
+inventory::submit! {
+    SourceDescription::new::<ElasticsearchConfig>("elasticsearch")
+}
+
+impl GenerateConfig for ElasticsearchConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoint: "http://localhost:9200".to_string(),
+            indices: vec!["logs-*".to_string()],
+            query: default_query(),
+            page_size: default_page_size(),
+            keep_alive_secs: default_keep_alive_secs(),
+            pagination: Pagination::default(),
+            metadata_key: default_metadata_key(),
+            auth: None,
+            aws: None,
+            tls: None,
+            data_dir: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "elasticsearch")]
+impl SourceConfig for ElasticsearchConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        if self.indices.is_empty() {
+            return Err(BuildError::NoIndices.into());
+        }
+        let indices = self.indices.join(",");
+        let query: JsonValue = serde_json::from_str(&self.query).context(InvalidQuerySnafu)?;
+
+        let authorization = match &self.auth {
+            Some(ElasticsearchAuth::Basic { user, password }) => Some(Auth::Basic {
+                user: user.clone(),
+                password: password.clone(),
+            }),
+            _ => None,
+        };
+        let uri = self.endpoint.parse::<UriSerde>()?;
+        let http_auth = authorization.choose_one(&uri.auth)?;
+        let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
+
+        let (aws_auth, region) = match &self.auth {
+            Some(ElasticsearchAuth::Aws(aws)) => {
+                let region = self
+                    .aws
+                    .as_ref()
+                    .and_then(|aws| aws.region())
+                    .ok_or(BuildError::RegionRequired)?;
+                (
+                    Some(aws.credentials_provider(region.clone()).await?),
+                    Some(region),
+                )
+            }
+            Some(ElasticsearchAuth::Basic { .. }) | None => (None, None),
+        };
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        // A checkpoint only applies to the search it was written for.
+        let search = format!("{}\n{}", indices, query);
+        let checkpoint = Checkpoint::load(data_dir.join(CHECKPOINT_FILENAME), search).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        let source = ElasticsearchSource {
+            base_url,
+            indices,
+            query,
+            page_size: self.page_size,
+            keep_alive: format!("{}s", self.keep_alive_secs),
+            pagination: self.pagination,
+            metadata_key: self.metadata_key.clone(),
+            http_auth,
+            aws_auth,
+            region,
+            client,
+            acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
+            checkpoint,
+        };
+
+        Ok(source.run(cx.shutdown, cx.out).boxed())
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "elasticsearch"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenPointInTimeResponse {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    pit_id: Option<String>,
+    #[serde(rename = "_scroll_id")]
+    scroll_id: Option<String>,
+    hits: Hits,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hits {
+    hits: Vec<Hit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    #[serde(rename = "_index")]
+    index: String,
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(rename = "_source", default)]
+    source: serde_json::Map<String, JsonValue>,
+    sort: Option<JsonValue>,
+}
+
+struct ElasticsearchSource {
+    base_url: String,
+    indices: String,
+    query: JsonValue,
+    page_size: u32,
+    keep_alive: String,
+    pagination: Pagination,
+    metadata_key: String,
+    http_auth: Option<Auth>,
+    aws_auth: Option<SharedCredentialsProvider>,
+    region: Option<Region>,
+    client: HttpClient,
+    acknowledgements: bool,
+    checkpoint: Checkpoint,
+}
+
+impl ElasticsearchSource {
+    async fn run(mut self, shutdown: ShutdownSignal, mut out: SourceSender) -> Result<(), ()> {
+        if self.checkpoint.state.done {
+            info!(
+                message = "Search was already completed, no documents to read.",
+                indices = %self.indices,
+            );
+            return Ok(());
+        }
+
+        let search = async {
+            match self.pagination {
+                Pagination::PointInTime => self.search_point_in_time(&mut out).await,
+                Pagination::Scroll => self.scroll(&mut out).await,
+            }
+        };
+
+        tokio::select! {
+            result = search => result,
+            _ = shutdown => Ok(()),
+        }
+    }
+
+    /// Pages through a point in time, resuming from the checkpointed position.
+    async fn search_point_in_time(&mut self, out: &mut SourceSender) -> Result<(), ()> {
+        loop {
+            let pit_id = match self.checkpoint.state.pit_id.clone() {
+                Some(pit_id) => pit_id,
+                None => {
+                    let path = format!("/{}/_pit?keep_alive={}", self.indices, self.keep_alive);
+                    match self
+                        .request::<OpenPointInTimeResponse>(Method::POST, &path, None)
+                        .await
+                    {
+                        Ok(response) => {
+                            self.checkpoint.state.pit_id = Some(response.id.clone());
+                            response.id
+                        }
+                        Err(error) => {
+                            self.retry_after("open_point_in_time", &error).await;
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let mut body = json!({
+                "size": self.page_size,
+                "query": self.query,
+                "pit": { "id": pit_id, "keep_alive": self.keep_alive },
+                "sort": [{ "_shard_doc": "asc" }],
+                "track_total_hits": false,
+            });
+            if let Some(search_after) = &self.checkpoint.state.search_after {
+                body["search_after"] = search_after.clone();
+            }
+
+            let response = match self
+                .request::<SearchResponse>(Method::POST, "/_search", Some(body))
+                .await
+            {
+                Ok(response) => response,
+                Err(SearchError::UnexpectedStatus {
+                    status: StatusCode::NOT_FOUND,
+                    ..
+                }) if self.checkpoint.state.search_after.is_none() => {
+                    warn!(
+                        message = "Point in time expired before the first page, opening a new one.",
+                        indices = %self.indices,
+                    );
+                    self.checkpoint.state.pit_id = None;
+                    continue;
+                }
+                Err(SearchError::UnexpectedStatus {
+                    status: StatusCode::NOT_FOUND,
+                    ..
+                }) => {
+                    // The sort values of `_shard_doc` only apply to the point in time they were read from, so the
+                    // search can't be resumed from a new one without reading documents again.
+                    emit!(ElasticsearchPointInTimeExpired {
+                        indices: &self.indices,
+                        checkpoint_path: &self.checkpoint.path,
+                    });
+                    return Err(());
+                }
+                Err(error) => {
+                    self.retry_after("search", &error).await;
+                    continue;
+                }
+            };
+
+            // The id of a point in time can change between searches.
+            let pit_id = response.pit_id.unwrap_or(pit_id);
+            let hits = response.hits.hits;
+            let search_after = match hits.last() {
+                Some(hit) => hit.sort.clone(),
+                None => {
+                    let body = json!({ "id": pit_id });
+                    if let Err(error) = self
+                        .request::<JsonValue>(Method::DELETE, "/_pit", Some(body))
+                        .await
+                    {
+                        emit!(ElasticsearchSearchError {
+                            operation: "close_point_in_time",
+                            endpoint: &self.base_url,
+                            error: &error,
+                        });
+                    }
+                    self.checkpoint.finish().await;
+                    return Ok(());
+                }
+            };
+
+            self.forward(&hits, out).await?;
+
+            self.checkpoint.state.pit_id = Some(pit_id);
+            self.checkpoint.state.search_after = search_after;
+            self.checkpoint.save().await;
+        }
+    }
+
+    /// Pages through a scroll. Only the completion of the search is checkpointed.
+    async fn scroll(&mut self, out: &mut SourceSender) -> Result<(), ()> {
+        let mut scroll_id: Option<String> = None;
+        loop {
+            let result = match &scroll_id {
+                None => {
+                    let path = format!("/{}/_search?scroll={}", self.indices, self.keep_alive);
+                    let body = json!({
+                        "size": self.page_size,
+                        "query": self.query,
+                        "sort": ["_doc"],
+                    });
+                    self.request::<SearchResponse>(Method::POST, &path, Some(body))
+                        .await
+                }
+                Some(id) => {
+                    let body = json!({ "scroll": self.keep_alive, "scroll_id": id });
+                    self.request::<SearchResponse>(Method::POST, "/_search/scroll", Some(body))
+                        .await
+                }
+            };
+
+            let response = match result {
+                Ok(response) => response,
+                Err(SearchError::UnexpectedStatus {
+                    status: StatusCode::NOT_FOUND,
+                    ..
+                }) if scroll_id.is_some() => {
+                    warn!(
+                        message = "Scroll expired, restarting the search from the beginning.",
+                        indices = %self.indices,
+                    );
+                    scroll_id = None;
+                    continue;
+                }
+                Err(error) => {
+                    self.retry_after("scroll", &error).await;
+                    continue;
+                }
+            };
+
+            scroll_id = response.scroll_id.or(scroll_id);
+            let hits = response.hits.hits;
+            if hits.is_empty() {
+                if let Some(id) = scroll_id {
+                    let body = json!({ "scroll_id": [id] });
+                    if let Err(error) = self
+                        .request::<JsonValue>(Method::DELETE, "/_search/scroll", Some(body))
+                        .await
+                    {
+                        emit!(ElasticsearchSearchError {
+                            operation: "clear_scroll",
+                            endpoint: &self.base_url,
+                            error: &error,
+                        });
+                    }
+                }
+                self.checkpoint.finish().await;
+                return Ok(());
+            }
+
+            self.forward(&hits, out).await?;
+        }
+    }
+
+    async fn retry_after(&self, operation: &'static str, error: &SearchError) {
+        emit!(ElasticsearchSearchError {
+            operation,
+            endpoint: &self.base_url,
+            error,
+        });
+        sleep(RETRY_INTERVAL).await;
+    }
+
+    /// Sends the documents of a page, sending them again until they are delivered if acknowledgements are enabled.
+    async fn forward(&self, hits: &[Hit], out: &mut SourceSender) -> Result<(), ()> {
+        loop {
+            let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+            let events = hits
+                .iter()
+                .map(|hit| Event::from(self.to_log(hit).with_batch_notifier_option(&batch)))
+                .collect::<Vec<_>>();
+            drop(batch); // Drop last reference to batch acknowledgement finalizer
+
+            let count = events.len();
+            emit!(EventsReceived {
+                count,
+                byte_size: events.size_of(),
+            });
+            if let Err(error) = out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+
+            match receiver {
+                None => return Ok(()),
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => return Ok(()),
+                    BatchStatus::Rejected => {
+                        error!(
+                            message = "Sink reported events were rejected.",
+                            internal_log_rate_secs = 5,
+                        );
+                        return Ok(());
+                    }
+                    BatchStatus::Errored => {
+                        warn!(
+                            message =
+                                "Sink reported events were not delivered, sending them again.",
+                            internal_log_rate_secs = 5,
+                        );
+                        sleep(RETRY_INTERVAL).await;
+                    }
+                },
+            }
+        }
+    }
+
+    fn to_log(&self, hit: &Hit) -> LogEvent {
+        let mut log = LogEvent::from(
+            hit.source
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::from(value.clone())))
+                .collect::<BTreeMap<_, _>>(),
+        );
+        log.insert(
+            self.metadata_key.as_str(),
+            json!({ "index": hit.index, "id": hit.id }),
+        );
+        log.try_insert(log_schema().source_type_key(), Bytes::from("elasticsearch"));
+        log.try_insert(log_schema().timestamp_key(), Utc::now());
+        log
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<JsonValue>,
+    ) -> Result<T, SearchError> {
+        let body = body.map_or_else(Bytes::new, |body| Bytes::from(body.to_string()));
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(format!("{}{}", self.base_url, path))
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(authorization) = &self.http_auth {
+            builder = authorization.apply_builder(builder);
+        }
+        let mut request = builder.body(body).map_err(|error| SearchError::Request {
+            source: error.into(),
+        })?;
+
+        if let Some(credentials_provider) = &self.aws_auth {
+            sign_request(&mut request, credentials_provider, &self.region)
+                .await
+                .context(RequestSnafu)?;
+        }
+
+        let response = self
+            .client
+            .send(request.map(hyper::Body::from))
+            .await
+            .map_err(|error| SearchError::Request {
+                source: error.into(),
+            })?;
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(|error| SearchError::Request {
+                source: error.into(),
+            })?;
+        emit!(EndpointBytesReceived {
+            byte_size: body.len(),
+            protocol: "http",
+            endpoint: &self.base_url,
+        });
+
+        if !parts.status.is_success() {
+            return Err(SearchError::UnexpectedStatus {
+                status: parts.status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        serde_json::from_slice(&body).context(InvalidResponseSnafu)
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CheckpointState {
+    /// The indices and query of the search.
+    search: String,
+    pit_id: Option<String>,
+    search_after: Option<JsonValue>,
+    done: bool,
+}
+
+/// The progress of the search.
+struct Checkpoint {
+    path: PathBuf,
+    state: CheckpointState,
+}
+
+impl Checkpoint {
+    async fn load(path: PathBuf, search: String) -> Result<Self, BuildError> {
+        let state = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(io::Error::from)
+                .context(ReadCheckpointSnafu { path: &path })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => CheckpointState::default(),
+            Err(source) => return Err(BuildError::ReadCheckpoint { path, source }),
+        };
+
+        let state = if state.search == search {
+            state
+        } else {
+            CheckpointState {
+                search,
+                ..Default::default()
+            }
+        };
+
+        Ok(Self { path, state })
+    }
+
+    async fn finish(&mut self) {
+        self.state.pit_id = None;
+        self.state.search_after = None;
+        self.state.done = true;
+        self.save().await;
+    }
+
+    async fn save(&self) {
+        if let Err(error) = self.write().await {
+            emit!(ElasticsearchCheckpointError { error });
+        }
+    }
+
+    /// Writes the checkpoint to a temporary file first, so that a crash can't leave it half written.
+    async fn write(&self) -> Result<(), io::Error> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(&self.state)?;
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_source_compliance_advanced, HTTP_PULL_SOURCE_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ElasticsearchConfig>();
+    }
+
+    fn config(endpoint: String) -> ElasticsearchConfig {
+        ElasticsearchConfig {
+            endpoint,
+            indices: vec!["logs".to_string()],
+            query: default_query(),
+            page_size: 2,
+            keep_alive_secs: default_keep_alive_secs(),
+            pagination: Pagination::PointInTime,
+            metadata_key: default_metadata_key(),
+            auth: None,
+            aws: None,
+            tls: None,
+            data_dir: None,
+            acknowledgements: Default::default(),
+        }
+    }
+
+    /// Serves a point in time over the five documents of the `logs` index.
+    fn serve_point_in_time() -> std::net::SocketAddr {
+        let addr = next_addr();
+        let open = warp::path!("logs" / "_pit")
+            .and(warp::post())
+            .map(|| warp::reply::json(&json!({ "id": "pit" })));
+        let search = warp::path!("_search")
+            .and(warp::post())
+            .and(warp::body::json())
+            .map(|body: JsonValue| {
+                assert_eq!(body["pit"]["id"], "pit");
+                let after = body["search_after"][0].as_i64().unwrap_or(-1);
+                let size = body["size"].as_i64().unwrap();
+                let hits = (after + 1..5)
+                    .take(size as usize)
+                    .map(|n| {
+                        json!({
+                            "_index": "logs",
+                            "_id": n.to_string(),
+                            "_source": { "message": format!("document {}", n) },
+                            "sort": [n],
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                warp::reply::json(&json!({ "pit_id": "pit", "hits": { "hits": hits } }))
+            });
+        let close = warp::path!("_pit")
+            .and(warp::delete())
+            .map(|| warp::reply::json(&json!({ "succeeded": true })));
+        tokio::spawn(warp::serve(open.or(search).or(close)).run(addr));
+        addr
+    }
+
+    /// Runs the source until the search completes.
+    async fn run(config: ElasticsearchConfig, data_dir: PathBuf) -> Vec<Event> {
+        run_and_assert_source_compliance_advanced(
+            config,
+            |cx| cx.globals.data_dir = Some(data_dir),
+            Some(Duration::from_secs(3)),
+            None,
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await
+    }
+
+    fn read_checkpoint(data_dir: &std::path::Path) -> CheckpointState {
+        let contents = std::fs::read(data_dir.join("default").join(CHECKPOINT_FILENAME)).unwrap();
+        serde_json::from_slice(&contents).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_point_in_time() {
+        let addr = serve_point_in_time();
+        let data_dir = tempdir().unwrap();
+
+        let events = run(
+            config(format!("http://{}", addr)),
+            data_dir.path().to_path_buf(),
+        )
+        .await;
+        assert_eq!(events.len(), 5);
+        let log = events[3].as_log();
+        assert_eq!(log["message"], "document 3".into());
+        assert_eq!(log["@metadata.index"], "logs".into());
+        assert_eq!(log["@metadata.id"], "3".into());
+        assert_eq!(log[log_schema().source_type_key()], "elasticsearch".into());
+
+        let checkpoint = read_checkpoint(data_dir.path());
+        assert!(checkpoint.done);
+        assert_eq!(checkpoint.pit_id, None);
+    }
+
+    fn write_checkpoint(data_dir: &std::path::Path, pit_id: &str, search_after: JsonValue) {
+        let checkpoint_dir = data_dir.join("default");
+        std::fs::create_dir_all(&checkpoint_dir).unwrap();
+        let state = CheckpointState {
+            search: format!("logs\n{}", json!({ "match_all": {} })),
+            pit_id: Some(pit_id.to_string()),
+            search_after: Some(search_after),
+            done: false,
+        };
+        std::fs::write(
+            checkpoint_dir.join(CHECKPOINT_FILENAME),
+            serde_json::to_vec(&state).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn resumes_from_checkpoint() {
+        let addr = serve_point_in_time();
+        let data_dir = tempdir().unwrap();
+        let config = config(format!("http://{}", addr));
+        write_checkpoint(data_dir.path(), "pit", json!([2]));
+
+        let events = run(config, data_dir.path().to_path_buf()).await;
+        let ids = events
+            .iter()
+            .map(|event| event.as_log()["@metadata.id"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![Value::from("3"), Value::from("4")]);
+        assert!(read_checkpoint(data_dir.path()).done);
+    }
+
+    #[tokio::test]
+    async fn stops_when_point_in_time_expires() {
+        let addr = next_addr();
+        let search = warp::path!("_search").and(warp::post()).map(|| {
+            warp::reply::with_status(
+                warp::reply::json(&json!({ "error": "search_context_missing_exception" })),
+                warp::http::StatusCode::NOT_FOUND,
+            )
+        });
+        tokio::spawn(warp::serve(search).run(addr));
+
+        let data_dir = tempdir().unwrap();
+        write_checkpoint(data_dir.path(), "expired", json!([2]));
+
+        let (tx, _rx) = SourceSender::new_test();
+        let mut cx = SourceContext::new_test(tx, None);
+        cx.globals.data_dir = Some(data_dir.path().to_path_buf());
+        let source = config(format!("http://{}", addr)).build(cx).await.unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(3), source)
+            .await
+            .unwrap();
+        assert_eq!(result, Err(()));
+
+        // The checkpoint is kept, rather than the search silently starting over.
+        let checkpoint = read_checkpoint(data_dir.path());
+        assert!(!checkpoint.done);
+        assert_eq!(checkpoint.pit_id.as_deref(), Some("expired"));
+        assert_eq!(checkpoint.search_after, Some(json!([2])));
+    }
+}
@@ -272,6 +272,18 @@ impl SourceConfig for ExecConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or_else(|| self.decoding.default_stream_framing());
+        Some(DecodingConfig::new(
+            framing,
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
 }
 
 async fn run_scheduled(
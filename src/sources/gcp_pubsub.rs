@@ -302,6 +302,14 @@ impl SourceConfig for PubsubConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
 }
 
 impl_generate_config_from_default!(PubsubConfig);
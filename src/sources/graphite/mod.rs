@@ -0,0 +1,425 @@
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+use bytes::Bytes;
+use codecs::{
+    decoding::{self, Deserializer, Framer},
+    NewlineDelimitedDecoder, StreamDecodingError,
+};
+use futures::StreamExt;
+use smallvec::{smallvec, SmallVec};
+use tokio::net::UdpSocket;
+use tokio_util::udp::UdpFramed;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use self::parser::ParseError;
+use super::util::{SocketListenAddr, TcpNullAcker, TcpSource};
+use crate::{
+    codecs::Decoder,
+    config::{
+        self, GenerateConfig, Output, Resource, SourceConfig, SourceContext, SourceDescription,
+    },
+    event::Event,
+    internal_events::{
+        EventsReceived, GraphiteInvalidRecordError, SocketBytesReceived, SocketMode,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsSourceConfig},
+    udp, SourceSender,
+};
+
+pub mod parser;
+
+use parser::parse;
+
+/// Configuration for the `graphite` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum GraphiteConfig {
+    /// Listen on TCP.
+    Tcp(#[configurable(derived)] TcpConfig),
+
+    /// Listen on UDP.
+    Udp(#[configurable(derived)] UdpConfig),
+}
+
+/// UDP configuration for the `graphite` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UdpConfig {
+    /// The address to listen for messages on.
+    address: SocketAddr,
+
+    /// The size, in bytes, of the receive buffer used for the socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+}
+
+impl UdpConfig {
+    pub const fn from_address(address: SocketAddr) -> Self {
+        Self {
+            address,
+            receive_buffer_bytes: None,
+        }
+    }
+}
+
+/// TCP configuration for the `graphite` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct TcpConfig {
+    /// The address to listen for connections on.
+    address: SocketListenAddr,
+
+    #[configurable(derived)]
+    keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsSourceConfig>,
+
+    /// The timeout before a connection is forcefully closed during shutdown.
+    #[serde(default = "default_shutdown_timeout_secs")]
+    shutdown_timeout_secs: u64,
+
+    /// The size, in bytes, of the receive buffer used for each connection.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of TCP connections that will be allowed at any given time.
+    connection_limit: Option<u32>,
+}
+
+impl TcpConfig {
+    #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
+    pub fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            keepalive: None,
+            tls: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            receive_buffer_bytes: None,
+            connection_limit: None,
+        }
+    }
+}
+
+const fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+inventory::submit! {
+    SourceDescription::new::<GraphiteConfig>("graphite")
+}
+
+impl GenerateConfig for GraphiteConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::Tcp(TcpConfig::from_address(
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), 2003)).into(),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "graphite")]
+impl SourceConfig for GraphiteConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        match self {
+            GraphiteConfig::Udp(config) => {
+                let socket = UdpSocket::bind(&config.address).await?;
+                Ok(Box::pin(graphite_udp(
+                    config.clone(),
+                    socket,
+                    cx.shutdown,
+                    cx.out,
+                )))
+            }
+            GraphiteConfig::Tcp(config) => {
+                let tls_config = config.tls.as_ref().map(|tls| tls.tls_config.clone());
+                let tls_client_metadata_key = config
+                    .tls
+                    .as_ref()
+                    .and_then(|tls| tls.client_metadata_key.clone());
+                let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
+                GraphiteTcpSource.run(
+                    config.address,
+                    config.keepalive,
+                    config.shutdown_timeout_secs,
+                    tls,
+                    tls_client_metadata_key,
+                    config.receive_buffer_bytes,
+                    cx,
+                    false.into(),
+                    config.connection_limit,
+                )
+            }
+        }
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(config::DataType::Metric)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "graphite"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match self.clone() {
+            Self::Tcp(tcp) => vec![tcp.address.into()],
+            Self::Udp(udp) => vec![Resource::udp(udp.address)],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct GraphiteDeserializer {
+    socket_mode: Option<SocketMode>,
+}
+
+impl GraphiteDeserializer {
+    const fn udp() -> Self {
+        Self {
+            socket_mode: Some(SocketMode::Udp),
+        }
+    }
+}
+
+impl decoding::format::Deserializer for GraphiteDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        _log_namespace: LogNamespace,
+    ) -> crate::Result<SmallVec<[Event; 1]>> {
+        if let Some(mode) = self.socket_mode {
+            emit!(SocketBytesReceived {
+                mode,
+                byte_size: bytes.len(),
+            });
+        }
+
+        // Carbon ignores blank lines, so we do too rather than reporting them as invalid.
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return Ok(smallvec![]);
+        }
+
+        match std::str::from_utf8(&bytes)
+            .map_err(|_| ParseError::Malformed("line is not valid UTF-8"))
+            .and_then(parse)
+        {
+            Ok(metric) => {
+                let event = Event::Metric(metric);
+                emit!(EventsReceived {
+                    count: 1,
+                    byte_size: event.size_of(),
+                });
+                Ok(smallvec![event])
+            }
+            Err(error) => {
+                emit!(GraphiteInvalidRecordError {
+                    error: &error,
+                    bytes
+                });
+                Err(Box::new(error))
+            }
+        }
+    }
+}
+
+async fn graphite_udp(
+    config: UdpConfig,
+    socket: UdpSocket,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(
+        message = "Listening.",
+        addr = %config.address,
+        r#type = "udp"
+    );
+
+    let codec = Decoder::new(
+        Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        Deserializer::Boxed(Box::new(GraphiteDeserializer::udp())),
+    );
+    let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok(((events, _byte_size), _sock)) => {
+                let count = events.len();
+                if let Err(error) = out.send_batch(events).await {
+                    emit!(StreamClosedError { error, count });
+                }
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs::Decoder`, no
+                // further handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct GraphiteTcpSource;
+
+impl TcpSource for GraphiteTcpSource {
+    type Error = codecs::decoding::Error;
+    type Item = SmallVec<[Event; 1]>;
+    type Decoder = Decoder;
+    type Acker = TcpNullAcker;
+
+    fn decoder(&self) -> Self::Decoder {
+        Decoder::new(
+            Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+            Deserializer::Boxed(Box::new(GraphiteDeserializer::default())),
+        )
+    }
+
+    fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
+        TcpNullAcker
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::channel::mpsc;
+    use futures_util::SinkExt;
+    use tokio::{
+        io::AsyncWriteExt,
+        time::{sleep, Duration, Instant},
+    };
+    use vector_core::{config::ComponentKey, event::EventContainer};
+
+    use super::*;
+    use crate::{
+        event::metric::{MetricKind, MetricValue},
+        test_util::{
+            collect_limited,
+            components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GraphiteConfig>();
+    }
+
+    #[tokio::test]
+    async fn test_graphite_udp() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async move {
+            let in_addr = next_addr();
+            let config = GraphiteConfig::Udp(UdpConfig::from_address(in_addr));
+            let (sender, mut receiver) = mpsc::channel(200);
+            tokio::spawn(async move {
+                let bind_addr = next_addr();
+                let socket = UdpSocket::bind(bind_addr).await.unwrap();
+                socket.connect(in_addr).await.unwrap();
+                while let Some(bytes) = receiver.next().await {
+                    socket.send(bytes).await.unwrap();
+                }
+            });
+            test_graphite(config, sender).await;
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_graphite_tcp() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async move {
+            let in_addr = next_addr();
+            let config = GraphiteConfig::Tcp(TcpConfig::from_address(in_addr.into()));
+            let (sender, mut receiver) = mpsc::channel(200);
+            tokio::spawn(async move {
+                while let Some(bytes) = receiver.next().await {
+                    tokio::net::TcpStream::connect(in_addr)
+                        .await
+                        .unwrap()
+                        .write_all(bytes)
+                        .await
+                        .unwrap();
+                }
+            });
+            test_graphite(config, sender).await;
+        })
+        .await;
+    }
+
+    async fn test_graphite(config: GraphiteConfig, mut sender: mpsc::Sender<&'static [u8]>) {
+        let component_key = ComponentKey::from("graphite");
+        let (tx, rx) = SourceSender::new_with_buffer(4096);
+        let (source_ctx, shutdown) = SourceContext::new_shutdown(&component_key, tx);
+        let source = config
+            .build(source_ctx)
+            .await
+            .expect("failed to build graphite source");
+
+        tokio::spawn(async move {
+            source.await.expect("source should not fail");
+        });
+
+        // Give the source time to start listening.
+        sleep(Duration::from_millis(250)).await;
+
+        sender
+            .send(b"servers.web01.load 0.5 1660000000\n\ndisk.used;datacenter=dc1 42 1660000010\nbroken line here now\n")
+            .await
+            .unwrap();
+
+        sleep(Duration::from_millis(250)).await;
+        shutdown
+            .shutdown_all(Instant::now() + Duration::from_millis(100))
+            .await;
+
+        let metrics = collect_limited(rx)
+            .await
+            .into_iter()
+            .flat_map(EventContainer::into_events)
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+
+        assert_eq!(metrics.len(), 2);
+
+        assert_eq!(metrics[0].name(), "servers.web01.load");
+        assert_eq!(metrics[0].kind(), MetricKind::Absolute);
+        assert_eq!(metrics[0].value(), &MetricValue::Gauge { value: 0.5 });
+        assert_eq!(metrics[0].tags(), None);
+        assert_eq!(
+            metrics[0]
+                .timestamp()
+                .map(|timestamp| timestamp.timestamp()),
+            Some(1660000000)
+        );
+
+        assert_eq!(metrics[1].name(), "disk.used");
+        assert_eq!(metrics[1].value(), &MetricValue::Gauge { value: 42.0 });
+        assert_eq!(
+            metrics[1]
+                .tags()
+                .and_then(|tags| tags.get("datacenter"))
+                .map(String::as_str),
+            Some("dc1")
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::{error, fmt, num::ParseFloatError};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
+
+/// Parses a single line of the Carbon plaintext protocol.
+///
+/// Lines have the form `<path> <value> [<timestamp>]`. The path may carry Graphite tags using the
+/// tagged series syntax, `name;tag1=value1;tag2=value2`. A missing timestamp, or the `-1` sentinel
+/// accepted by Carbon, leaves the metric without a timestamp.
+pub fn parse(line: &str) -> Result<Metric, ParseError> {
+    // https://graphite.readthedocs.io/en/latest/feeding-carbon.html#the-plaintext-protocol
+    let parts = line.split_whitespace().collect::<Vec<_>>();
+    if parts.len() < 2 || parts.len() > 3 {
+        return Err(ParseError::Malformed(
+            "should be a path, a value and an optional timestamp separated by whitespace",
+        ));
+    }
+
+    let (name, tags) = parse_path(parts[0])?;
+    let value: f64 = parts[1].parse()?;
+    let timestamp = match parts.get(2) {
+        Some(timestamp) => parse_timestamp(timestamp)?,
+        None => None,
+    };
+
+    Ok(
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+            .with_tags(tags)
+            .with_timestamp(timestamp),
+    )
+}
+
+fn parse_path(path: &str) -> Result<(String, Option<MetricTags>), ParseError> {
+    // https://graphite.readthedocs.io/en/latest/tags.html#carbon
+    let mut chunks = path.split(';');
+    let name = chunks.next().unwrap_or_default();
+    if name.is_empty() {
+        return Err(ParseError::Malformed("metric path can't be empty"));
+    }
+
+    let mut tags = MetricTags::new();
+    for chunk in chunks {
+        match chunk.split_once('=') {
+            Some((key, value)) if !key.is_empty() && !value.is_empty() => {
+                tags.insert(key.to_owned(), value.to_owned());
+            }
+            _ => {
+                return Err(ParseError::Malformed(
+                    "tags should be non empty `key=value` pairs",
+                ))
+            }
+        }
+    }
+
+    Ok((name.to_owned(), (!tags.is_empty()).then(|| tags)))
+}
+
+fn parse_timestamp(input: &str) -> Result<Option<DateTime<Utc>>, ParseError> {
+    let timestamp: f64 = input.parse()?;
+    if timestamp == -1.0 {
+        return Ok(None);
+    }
+    if !timestamp.is_finite() || timestamp < 0.0 {
+        return Err(ParseError::Malformed(
+            "timestamp should be a positive number of seconds",
+        ));
+    }
+
+    let seconds = timestamp.trunc();
+    let nanos = ((timestamp - seconds) * 1e9) as u32;
+    Utc.timestamp_opt(seconds as i64, nanos)
+        .single()
+        .map(Some)
+        .ok_or(ParseError::Malformed("timestamp is out of range"))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    Malformed(&'static str),
+    InvalidFloat(ParseFloatError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Graphite parse error: {:?}", self)
+    }
+}
+
+vector_common::impl_event_data_eq!(ParseError);
+
+impl error::Error for ParseError {}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(e: ParseFloatError) -> ParseError {
+        ParseError::InvalidFloat(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use vector_common::assert_event_data_eq;
+
+    use super::{parse, ParseError};
+    use crate::event::metric::{Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn basic_gauge() {
+        assert_event_data_eq!(
+            parse("foo.bar.baz 42 1660000000"),
+            Ok(Metric::new(
+                "foo.bar.baz",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 42.0 },
+            )
+            .with_timestamp(Some(Utc.timestamp(1660000000, 0)))),
+        );
+    }
+
+    #[test]
+    fn fractional_value_and_timestamp() {
+        assert_event_data_eq!(
+            parse("foo -1.5 1660000000.25"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: -1.5 },
+            )
+            .with_timestamp(Some(Utc.timestamp(1660000000, 250_000_000)))),
+        );
+    }
+
+    #[test]
+    fn missing_timestamp() {
+        let expected = Metric::new(
+            "foo",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        );
+        assert_event_data_eq!(parse("foo 1"), Ok(expected.clone()));
+        assert_event_data_eq!(parse("foo 1 -1"), Ok(expected));
+    }
+
+    #[test]
+    fn tagged_series() {
+        assert_event_data_eq!(
+            parse("disk.used;datacenter=dc1;server=web01 9.5 1660000000"),
+            Ok(Metric::new(
+                "disk.used",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 9.5 },
+            )
+            .with_tags(Some(
+                vec![
+                    ("datacenter".to_owned(), "dc1".to_owned()),
+                    ("server".to_owned(), "web01".to_owned()),
+                ]
+                .into_iter()
+                .collect(),
+            ))
+            .with_timestamp(Some(Utc.timestamp(1660000000, 0)))),
+        );
+    }
+
+    #[test]
+    fn extra_whitespace() {
+        assert_event_data_eq!(
+            parse("  foo\t 3  1660000000 "),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 3.0 },
+            )
+            .with_timestamp(Some(Utc.timestamp(1660000000, 0)))),
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(matches!(parse("foo"), Err(ParseError::Malformed(_))));
+        assert!(matches!(parse("foo 1 2 3"), Err(ParseError::Malformed(_))));
+        assert!(matches!(
+            parse(";a=b 1 1660000000"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse("foo;a 1 1660000000"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse("foo;a= 1 1660000000"),
+            Err(ParseError::Malformed(_))
+        ));
+        assert!(matches!(parse("foo 1 -5"), Err(ParseError::Malformed(_))));
+    }
+
+    #[test]
+    fn invalid_value() {
+        assert!(matches!(
+            parse("foo bar 1660000000"),
+            Err(ParseError::InvalidFloat(_))
+        ));
+        assert!(matches!(
+            parse("foo 1 now"),
+            Err(ParseError::InvalidFloat(_))
+        ));
+    }
+}
@@ -148,6 +148,14 @@ impl SourceConfig for LogplexConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
 }
 
 // Add a compatibility alias to avoid breaking existing configs
@@ -179,6 +187,10 @@ impl SourceConfig for LogplexCompatConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn decoding(&self, global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        self.0.decoding(global_log_namespace)
+    }
 }
 
 fn decode_message(
@@ -203,14 +203,9 @@ impl HttpSource for SimpleHttpSource {
     }
 }
 
-#[async_trait::async_trait]
-#[typetag::serde(name = "http")]
-impl SourceConfig for SimpleHttpConfig {
-    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        if self.encoding.is_some() && (self.framing.is_some() || self.decoding.is_some()) {
-            return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
-        }
-
+impl SimpleHttpConfig {
+    /// Resolves the decoding, which the deprecated `encoding` option takes precedence over.
+    fn decoding_config(&self) -> DecodingConfig {
         let (framing, decoding) = if let Some(encoding) = self.encoding {
             match encoding {
                 Encoding::Text => (
@@ -239,7 +234,19 @@ impl SourceConfig for SimpleHttpConfig {
             (framing, decoding)
         };
 
-        let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
+        DecodingConfig::new(framing, decoding, LogNamespace::Legacy)
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http")]
+impl SourceConfig for SimpleHttpConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.encoding.is_some() && (self.framing.is_some() || self.decoding.is_some()) {
+            return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
+        }
+
+        let decoder = self.decoding_config().build();
         let source = SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
@@ -278,6 +285,10 @@ impl SourceConfig for SimpleHttpConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(self.decoding_config())
+    }
 }
 
 fn add_path(events: &mut [Event], key: &str, path: &str) {
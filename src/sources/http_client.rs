@@ -0,0 +1,931 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::{FutureExt, StreamExt};
+use http::{
+    header::{HeaderName, HeaderValue, LINK},
+    HeaderMap, Request, StatusCode, Uri,
+};
+use hyper::Body;
+use indexmap::IndexMap;
+use snafu::{ResultExt, Snafu};
+use tokio_stream::wrappers::IntervalStream;
+use tokio_util::codec::Decoder as _;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    event::{Event, LogEvent},
+    http::{Auth, HttpClient},
+    internal_events::{
+        EndpointBytesReceived, HttpClientCursorError, HttpClientEventsReceived,
+        HttpClientHttpError, HttpClientHttpResponseError, RequestCompleted, StreamClosedError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources,
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+const CHECKPOINTS_FILENAME: &str = "checkpoints.json";
+
+/// The field of the query templates holding the cursor of the next page.
+const CURSOR_FIELD: &str = "cursor";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "Query parameter {:?} refers to the field {:?}, but only `{}` is available",
+        param,
+        field,
+        CURSOR_FIELD
+    ))]
+    UnknownQueryField { param: String, field: String },
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    InvalidHeaderName {
+        name: String,
+        source: http::header::InvalidHeaderName,
+    },
+    #[snafu(display("Invalid value for header {:?}: {}", name, source))]
+    InvalidHeaderValue {
+        name: String,
+        source: http::header::InvalidHeaderValue,
+    },
+    #[snafu(display("Could not read checkpoints from {:?}: {}", path, source))]
+    ReadCheckpoints { path: PathBuf, source: io::Error },
+}
+
+/// Pagination of the responses of an endpoint.
+///
+/// Each poll follows the pages of an endpoint until a response has no next page. The cursor of the last page received
+/// is persisted in the data directory, so that the next poll, even after a restart, resumes after it rather than
+/// polling all pages again. That page is requested again only to find out its next page, and its events aren't
+/// emitted again.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum PaginationConfig {
+    /// The next page is requested from the `next` link of the `Link` response header, as described in [RFC
+    /// 8288](https://www.rfc-editor.org/rfc/rfc8288).
+    Link,
+
+    /// The next page is requested with a cursor read from the response body.
+    Cursor {
+        /// The field of the JSON response body holding the cursor of the next page.
+        ///
+        /// Nested fields are separated with `.`. A missing or `null` field means there is no next page.
+        cursor_path: String,
+
+        /// The query parameter the cursor is sent in.
+        query_param: String,
+    },
+}
+
+/// Configuration for the `http_client` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+pub struct HttpClientConfig {
+    /// Endpoints to poll.
+    endpoints: Vec<String>,
+
+    /// The interval between polls, in seconds.
+    #[serde(default = "default_scrape_interval_secs")]
+    scrape_interval_secs: u64,
+
+    /// Custom parameters for the request query string.
+    ///
+    /// One or more values for the same parameter key can be provided, and are appended to any parameters provided in
+    /// the endpoint. Values are templates, which can refer to the cursor of the next page as `{{ cursor }}`, and
+    /// format the current time with `strftime` specifiers. A value referring to the cursor is left out of the first
+    /// request, when there is no cursor yet.
+    #[serde(default)]
+    query: HashMap<String, Vec<Template>>,
+
+    /// Custom headers to send with each request.
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    pagination: Option<PaginationConfig>,
+
+    /// The directory used to persist the cursors of the endpoints.
+    ///
+    /// Only used with `pagination`. By default, the global `data_dir` option is used. Please make sure the user Vector
+    /// is running as has write permissions to this directory.
+    data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+}
+
+const fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+inventory::submit! {
+    SourceDescription::new::<HttpClientConfig>("http_client")
+}
+
+impl GenerateConfig for HttpClientConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            endpoints: vec!["http://localhost:8080/logs".to_string()],
+            scrape_interval_secs: default_scrape_interval_secs(),
+            query: HashMap::new(),
+            headers: IndexMap::new(),
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            pagination: None,
+            data_dir: None,
+            tls: None,
+            auth: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http_client")]
+impl SourceConfig for HttpClientConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let endpoints = self
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                endpoint
+                    .parse::<Uri>()
+                    .context(sources::UriParseSnafu)
+                    .map(|uri| (endpoint.clone(), uri))
+            })
+            .collect::<Result<Vec<_>, sources::BuildError>>()?;
+
+        for (param, templates) in &self.query {
+            for field in templates
+                .iter()
+                .flat_map(|t| t.get_fields().unwrap_or_default())
+            {
+                if field != CURSOR_FIELD {
+                    return Err(BuildError::UnknownQueryField {
+                        param: param.clone(),
+                        field,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let header_name =
+                HeaderName::from_bytes(name.as_bytes()).context(InvalidHeaderNameSnafu { name })?;
+            let header_value =
+                HeaderValue::from_str(value).context(InvalidHeaderValueSnafu { name })?;
+            headers.append(header_name, header_value);
+        }
+
+        let checkpoints = match self.pagination {
+            Some(_) => {
+                let data_dir = cx
+                    .globals
+                    .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+                Some(Checkpoints::load(data_dir.join(CHECKPOINTS_FILENAME)).await?)
+            }
+            None => None,
+        };
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+
+        let source = HttpClientSource {
+            endpoints,
+            query: self.query.clone(),
+            headers,
+            auth: self.auth.clone(),
+            pagination: self.pagination.clone(),
+            client,
+            decoder,
+            checkpoints,
+        };
+
+        Ok(source
+            .run(
+                Duration::from_secs(self.scrape_interval_secs),
+                cx.shutdown,
+                cx.out,
+            )
+            .boxed())
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http_client"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
+}
+
+struct HttpClientSource {
+    endpoints: Vec<(String, Uri)>,
+    query: HashMap<String, Vec<Template>>,
+    headers: HeaderMap,
+    auth: Option<Auth>,
+    pagination: Option<PaginationConfig>,
+    client: HttpClient,
+    decoder: Decoder,
+    checkpoints: Option<Checkpoints>,
+}
+
+/// A page of events, and the cursor of the next page.
+struct Page {
+    events: Vec<Event>,
+    next: Option<String>,
+}
+
+impl HttpClientSource {
+    async fn run(
+        mut self,
+        interval: Duration,
+        shutdown: ShutdownSignal,
+        mut out: SourceSender,
+    ) -> Result<(), ()> {
+        let mut ticks =
+            IntervalStream::new(tokio::time::interval(interval)).take_until(shutdown.clone());
+
+        while ticks.next().await.is_some() {
+            for index in 0..self.endpoints.len() {
+                self.poll(index, &mut out, &shutdown).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Polls an endpoint, following its pages until a response has no next page, the next page was already polled,
+    /// or the source is shut down.
+    async fn poll(
+        &mut self,
+        index: usize,
+        out: &mut SourceSender,
+        shutdown: &ShutdownSignal,
+    ) -> Result<(), ()> {
+        let (endpoint, uri) = self.endpoints[index].clone();
+        let mut cursor = self
+            .checkpoints
+            .as_ref()
+            .and_then(|checkpoints| checkpoints.get(&endpoint))
+            .cloned();
+        // The events of the page of the persisted cursor were emitted by an earlier poll.
+        let mut received = cursor.is_some();
+        // The cursors of the pages polled so far, so that pages linking back to each other can't loop forever.
+        let mut seen = cursor.iter().cloned().collect::<HashSet<_>>();
+
+        loop {
+            let url = match self.request_url(&uri, cursor.as_deref()) {
+                Ok(url) => url,
+                Err(error) => {
+                    emit!(HttpClientCursorError {
+                        error,
+                        endpoint: &endpoint,
+                        url: &endpoint,
+                    });
+                    return Ok(());
+                }
+            };
+
+            let page = tokio::select! {
+                biased;
+
+                _ = shutdown.clone() => return Ok(()),
+                page = self.fetch(&endpoint, &url) => match page {
+                    Some(page) => page,
+                    None => return Ok(()),
+                },
+            };
+
+            if !received {
+                let count = page.events.len();
+                if let Err(error) = out.send_batch(page.events).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(());
+                }
+
+                if let (Some(checkpoints), Some(cursor)) = (self.checkpoints.as_mut(), &cursor) {
+                    checkpoints.set(&endpoint, cursor.clone()).await;
+                }
+            }
+
+            match page.next {
+                Some(next) if seen.insert(next.clone()) => {
+                    cursor = Some(next);
+                    received = false;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// The URL of the request for the page of the cursor, or the first page if there's no cursor yet.
+    fn request_url(&self, uri: &Uri, cursor: Option<&str>) -> Result<Uri, String> {
+        if let (Some(PaginationConfig::Link), Some(next)) = (&self.pagination, cursor) {
+            return next
+                .parse()
+                .map_err(|error| format!("Invalid next link {:?}: {}", next, error));
+        }
+
+        let mut context = LogEvent::default();
+        context.insert(log_schema().timestamp_key(), Utc::now());
+        if let Some(cursor) = cursor {
+            context.insert(CURSOR_FIELD, cursor);
+        }
+
+        let mut params = Vec::new();
+        for (key, templates) in &self.query {
+            for template in templates {
+                // Only the cursor can be missing, before the first page is received.
+                if let Ok(value) = template.render_string(&context) {
+                    params.push((key.clone(), value));
+                }
+            }
+        }
+        if let (Some(PaginationConfig::Cursor { query_param, .. }), Some(cursor)) =
+            (&self.pagination, cursor)
+        {
+            params.push((query_param.clone(), cursor.to_owned()));
+        }
+
+        Ok(with_query(uri, params))
+    }
+
+    /// Fetches a page. The metrics are tagged with the configured endpoint rather than the URL of the request, which
+    /// changes with every page and poll.
+    async fn fetch(&self, endpoint: &str, url: &Uri) -> Option<Page> {
+        let url_string = url.to_string();
+
+        let mut request = Request::get(url)
+            .body(Body::empty())
+            .expect("error creating request");
+        request.headers_mut().extend(self.headers.clone());
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let start = Instant::now();
+        let response = match self.client.send(request).await {
+            Ok(response) => response,
+            Err(error) => {
+                emit!(HttpClientHttpError {
+                    error: error.into(),
+                    endpoint,
+                    url: &url_string,
+                });
+                return None;
+            }
+        };
+
+        let (parts, body) = response.into_parts();
+        let body = match hyper::body::to_bytes(body).await {
+            Ok(body) => body,
+            Err(error) => {
+                emit!(HttpClientHttpError {
+                    error: error.into(),
+                    endpoint,
+                    url: &url_string,
+                });
+                return None;
+            }
+        };
+        emit!(EndpointBytesReceived {
+            byte_size: body.len(),
+            protocol: "http",
+            endpoint,
+        });
+
+        if parts.status != StatusCode::OK {
+            emit!(HttpClientHttpResponseError {
+                code: parts.status,
+                endpoint,
+                url: &url_string,
+            });
+            return None;
+        }
+        emit!(RequestCompleted {
+            start,
+            end: Instant::now()
+        });
+
+        let events = self.decode(&body);
+        emit!(HttpClientEventsReceived {
+            byte_size: events.size_of(),
+            count: events.len(),
+            endpoint,
+            url: &url_string,
+        });
+
+        let next = match &self.pagination {
+            None => None,
+            Some(PaginationConfig::Link) => parts
+                .headers
+                .get_all(LINK)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .find_map(next_link)
+                .and_then(|link| resolve_link(url, link)),
+            Some(PaginationConfig::Cursor { cursor_path, .. }) => {
+                match cursor_at(&body, cursor_path) {
+                    Ok(next) => next,
+                    Err(error) => {
+                        emit!(HttpClientCursorError {
+                            error,
+                            endpoint,
+                            url: &url_string,
+                        });
+                        None
+                    }
+                }
+            }
+        };
+
+        Some(Page { events, next })
+    }
+
+    fn decode(&self, body: &Bytes) -> Vec<Event> {
+        let mut decoder = self.decoder.clone();
+        let mut buffer = BytesMut::from(body.as_ref());
+        let mut events = Vec::new();
+
+        // Errors are emitted by the decoder, and end the decoding of the response.
+        while let Ok(Some((next, _))) = decoder.decode_eof(&mut buffer) {
+            events.extend(next);
+        }
+
+        let now = Utc::now();
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("http_client"));
+                log.try_insert(log_schema().timestamp_key(), now);
+            }
+        }
+
+        events
+    }
+}
+
+/// Appends the parameters to the query of the URI.
+fn with_query(uri: &Uri, params: Vec<(String, String)>) -> Uri {
+    if params.is_empty() {
+        return uri.clone();
+    }
+
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(query) = uri.query() {
+        serializer.extend_pairs(url::form_urlencoded::parse(query.as_bytes()));
+    }
+    serializer.extend_pairs(params);
+
+    let mut builder = Uri::builder();
+    if let Some(scheme) = uri.scheme() {
+        builder = builder.scheme(scheme.clone());
+    }
+    if let Some(authority) = uri.authority() {
+        builder = builder.authority(authority.clone());
+    }
+    builder
+        .path_and_query(format!("{}?{}", uri.path(), serializer.finish()))
+        .build()
+        .expect("error building URI")
+}
+
+/// Finds the target of the `next` link in the value of a `Link` header.
+fn next_link(value: &str) -> Option<&str> {
+    value.split(',').find_map(|link| {
+        let (target, params) = link.trim().strip_prefix('<')?.split_once('>')?;
+        let is_next = params.split(';').any(|param| {
+            param.split_once('=').map_or(false, |(key, rels)| {
+                key.trim().eq_ignore_ascii_case("rel")
+                    && rels
+                        .trim()
+                        .trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+            })
+        });
+        is_next.then(|| target)
+    })
+}
+
+/// Resolves a link target, which may be relative to the URL of the request.
+fn resolve_link(url: &Uri, link: &str) -> Option<String> {
+    url::Url::parse(&url.to_string())
+        .and_then(|base| base.join(link))
+        .map(String::from)
+        .ok()
+}
+
+/// Reads the cursor at the path of the JSON body.
+fn cursor_at(body: &[u8], path: &str) -> Result<Option<String>, String> {
+    let json: serde_json::Value = serde_json::from_slice(body)
+        .map_err(|error| format!("Response body is not JSON: {}", error))?;
+
+    let value = path
+        .split('.')
+        .try_fold(&json, |value, field| value.get(field));
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(cursor)) if cursor.is_empty() => Ok(None),
+        Some(serde_json::Value::String(cursor)) => Ok(Some(cursor.clone())),
+        Some(serde_json::Value::Number(cursor)) => Ok(Some(cursor.to_string())),
+        Some(value) => Err(format!(
+            "Cursor at {:?} must be a string or a number, found {}",
+            path, value
+        )),
+    }
+}
+
+/// The cursors of the next pages of the endpoints.
+struct Checkpoints {
+    path: PathBuf,
+    cursors: HashMap<String, String>,
+}
+
+impl Checkpoints {
+    async fn load(path: PathBuf) -> Result<Self, BuildError> {
+        let cursors = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(io::Error::from)
+                .context(ReadCheckpointsSnafu { path: &path })?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(source) => return Err(BuildError::ReadCheckpoints { path, source }),
+        };
+
+        Ok(Self { path, cursors })
+    }
+
+    fn get(&self, endpoint: &str) -> Option<&String> {
+        self.cursors.get(endpoint)
+    }
+
+    async fn set(&mut self, endpoint: &str, cursor: String) {
+        self.cursors.insert(endpoint.to_owned(), cursor);
+        if let Err(error) = self.write().await {
+            error!(
+                message = "Could not write http_client checkpoints.",
+                %error,
+                path = ?self.path,
+            );
+        }
+    }
+
+    /// Writes the checkpoints to a temporary file first, so that a crash can't leave them half written.
+    async fn write(&self) -> Result<(), io::Error> {
+        let tmp_path = self.path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(&self.cursors)?;
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codecs::JsonDeserializerConfig;
+    use tempfile::tempdir;
+    use warp::{Filter, Reply};
+
+    use super::*;
+    use crate::{
+        event::Value,
+        test_util::{
+            collect_ready,
+            components::{run_and_assert_source_compliance_advanced, HTTP_PULL_SOURCE_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpClientConfig>();
+    }
+
+    fn config(endpoint: String) -> HttpClientConfig {
+        HttpClientConfig {
+            endpoints: vec![endpoint],
+            scrape_interval_secs: 1,
+            query: HashMap::new(),
+            headers: IndexMap::new(),
+            framing: default_framing_message_based(),
+            decoding: JsonDeserializerConfig::new().into(),
+            pagination: None,
+            data_dir: None,
+            tls: None,
+            auth: None,
+        }
+    }
+
+    async fn run(config: HttpClientConfig, data_dir: Option<PathBuf>, count: usize) -> Vec<Event> {
+        run_and_assert_source_compliance_advanced(
+            config,
+            |cx| cx.globals.data_dir = data_dir,
+            Some(Duration::from_secs(3)),
+            Some(count),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await
+    }
+
+    fn messages(events: &[Event]) -> Vec<Value> {
+        events
+            .iter()
+            .map(|event| event.as_log()["message"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn parses_next_link() {
+        assert_eq!(
+            next_link(r#"<https://example.com/items?page=2>; rel="next""#),
+            Some("https://example.com/items?page=2")
+        );
+        assert_eq!(
+            next_link(r#"</items?page=1>; rel="prev", </items?page=3>; rel="next last""#),
+            Some("/items?page=3")
+        );
+        assert_eq!(
+            next_link(r#"</items?page=3>; REL=NEXT"#),
+            Some("/items?page=3")
+        );
+        assert_eq!(next_link(r#"</items?page=1>; rel="first""#), None);
+        assert_eq!(next_link("/items?page=2; rel=next"), None);
+    }
+
+    #[test]
+    fn resolves_relative_links() {
+        let url = "http://example.com/api/items?page=1".parse().unwrap();
+        assert_eq!(
+            resolve_link(&url, "/api/items?page=2"),
+            Some("http://example.com/api/items?page=2".to_string())
+        );
+        assert_eq!(
+            resolve_link(&url, "https://other.example.com/items"),
+            Some("https://other.example.com/items".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_cursor() {
+        let body = br#"{"meta": {"next": "abc", "count": 3, "done": null}, "id": 7}"#;
+        assert_eq!(cursor_at(body, "meta.next"), Ok(Some("abc".to_string())));
+        assert_eq!(cursor_at(body, "id"), Ok(Some("7".to_string())));
+        assert_eq!(cursor_at(body, "meta.done"), Ok(None));
+        assert_eq!(cursor_at(body, "meta.missing"), Ok(None));
+        assert!(cursor_at(body, "meta").is_err());
+        assert!(cursor_at(b"not json", "meta.next").is_err());
+    }
+
+    #[tokio::test]
+    async fn renders_query() {
+        let mut config = config("http://example.com/logs?format=json".to_string());
+        config.query = HashMap::from([
+            (
+                "after".to_string(),
+                vec![Template::try_from("{{ cursor }}").unwrap()],
+            ),
+            ("day".to_string(), vec![Template::try_from("%Y").unwrap()]),
+        ]);
+        config.pagination = Some(PaginationConfig::Cursor {
+            cursor_path: "next".to_string(),
+            query_param: "page_token".to_string(),
+        });
+
+        let (endpoint, uri) = ("".to_string(), config.endpoints[0].parse::<Uri>().unwrap());
+        let source = HttpClientSource {
+            endpoints: vec![(endpoint, uri.clone())],
+            query: config.query.clone(),
+            headers: HeaderMap::new(),
+            auth: None,
+            pagination: config.pagination.clone(),
+            client: HttpClient::new(None, &Default::default()).unwrap(),
+            decoder: Decoder::default(),
+            checkpoints: None,
+        };
+        let year = Utc::now().format("%Y").to_string();
+
+        let first = source.request_url(&uri, None).unwrap();
+        assert_eq!(
+            first.to_string(),
+            format!("http://example.com/logs?format=json&day={}", year)
+        );
+
+        let next = source.request_url(&uri, Some("a b")).unwrap();
+        let mut params = url::form_urlencoded::parse(next.query().unwrap().as_bytes())
+            .into_owned()
+            .collect::<Vec<_>>();
+        params.sort();
+        assert_eq!(
+            params,
+            vec![
+                ("after".to_string(), "a b".to_string()),
+                ("day".to_string(), year),
+                ("format".to_string(), "json".to_string()),
+                ("page_token".to_string(), "a b".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_query_fields() {
+        let mut config = config("http://example.com/logs".to_string());
+        config.query = HashMap::from([(
+            "since".to_string(),
+            vec![Template::try_from("{{ timestamp }}").unwrap()],
+        )]);
+
+        let (tx, _rx) = SourceSender::new_test();
+        let error = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("\"timestamp\""));
+    }
+
+    #[tokio::test]
+    async fn polls_endpoint() {
+        let addr = next_addr();
+        let endpoint = warp::path!("logs")
+            .and(warp::header::exact("x-api-key", "secret"))
+            .map(|| r#"{"message": "hello"}"#);
+        tokio::spawn(warp::serve(endpoint).run(addr));
+
+        let mut config = config(format!("http://{}/logs", addr));
+        config
+            .headers
+            .insert("X-Api-Key".to_string(), "secret".to_string());
+
+        let events = run(config, None, 1).await;
+        assert_eq!(messages(&events), vec![Value::from("hello")]);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().source_type_key()], "http_client".into());
+        assert!(log.get(log_schema().timestamp_key()).is_some());
+    }
+
+    #[tokio::test]
+    async fn follows_link_header() {
+        let addr = next_addr();
+        let endpoint = warp::path!("items")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(|query: HashMap<String, String>| {
+                let page = query.get("page").map_or("1", String::as_str);
+                let reply = warp::reply::json(&serde_json::json!({ "message": page }));
+                match page {
+                    "1" => {
+                        warp::reply::with_header(reply, "link", r#"</items?page=2>; rel="next""#)
+                            .into_response()
+                    }
+                    _ => reply.into_response(),
+                }
+            });
+        tokio::spawn(warp::serve(endpoint).run(addr));
+
+        let mut config = config(format!("http://{}/items", addr));
+        config.pagination = Some(PaginationConfig::Link);
+        let data_dir = tempdir().unwrap();
+
+        let events = run(config, Some(data_dir.path().to_path_buf()), 2).await;
+        assert_eq!(messages(&events), vec![Value::from("1"), Value::from("2")]);
+    }
+
+    #[tokio::test]
+    async fn stops_at_cursor_cycle() {
+        let addr = next_addr();
+        let endpoint = warp::path!("feed")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(|query: HashMap<String, String>| {
+                // The pages "a" and "b" point to each other.
+                let (page, next) = match query.get("after").map(String::as_str) {
+                    None => ("start", "a"),
+                    Some("a") => ("a", "b"),
+                    _ => ("b", "a"),
+                };
+                warp::reply::json(&serde_json::json!({ "message": page, "next": next }))
+            });
+        tokio::spawn(warp::serve(endpoint).run(addr));
+
+        let config = config(format!("http://{}/feed", addr));
+        let endpoint = config.endpoints[0].clone();
+        let mut source = HttpClientSource {
+            endpoints: vec![(endpoint.clone(), endpoint.parse().unwrap())],
+            query: HashMap::new(),
+            headers: HeaderMap::new(),
+            auth: None,
+            pagination: Some(PaginationConfig::Cursor {
+                cursor_path: "next".to_string(),
+                query_param: "after".to_string(),
+            }),
+            client: HttpClient::new(None, &Default::default()).unwrap(),
+            decoder: DecodingConfig::new(config.framing, config.decoding, LogNamespace::Legacy)
+                .build(),
+            checkpoints: None,
+        };
+
+        let (mut out, rx) = SourceSender::new_test();
+        tokio::time::timeout(
+            Duration::from_secs(3),
+            source.poll(0, &mut out, &ShutdownSignal::noop()),
+        )
+        .await
+        .expect("polling didn't stop at the cycle")
+        .unwrap();
+
+        let events = collect_ready(rx).await;
+        assert_eq!(
+            messages(&events),
+            vec![Value::from("start"), Value::from("a"), Value::from("b")]
+        );
+    }
+
+    #[tokio::test]
+    async fn resumes_from_persisted_cursor() {
+        let addr = next_addr();
+        let endpoint = warp::path!("feed")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(|query: HashMap<String, String>| {
+                let page = query
+                    .get("after")
+                    .map_or(0, |after| after.parse::<u64>().unwrap());
+                // The feed has three pages, and the last one has no cursor.
+                let next = (page < 2).then(|| (page + 1).to_string());
+                warp::reply::json(&serde_json::json!({
+                    "message": page.to_string(),
+                    "next": next,
+                }))
+            });
+        tokio::spawn(warp::serve(endpoint).run(addr));
+
+        let mut config = config(format!("http://{}/feed", addr));
+        config.pagination = Some(PaginationConfig::Cursor {
+            cursor_path: "next".to_string(),
+            query_param: "after".to_string(),
+        });
+        let data_dir = tempdir().unwrap();
+
+        let events = run(config.clone(), Some(data_dir.path().to_path_buf()), 3).await;
+        assert_eq!(
+            messages(&events),
+            vec![Value::from("0"), Value::from("1"), Value::from("2")]
+        );
+
+        let checkpoints =
+            std::fs::read_to_string(data_dir.path().join("default").join(CHECKPOINTS_FILENAME))
+                .unwrap();
+        assert!(checkpoints.contains(r#""2""#));
+
+        // After a restart, polling resumes after the last page, which has no next page, so nothing is emitted again.
+        let (tx, rx) = SourceSender::new_test();
+        let mut cx = SourceContext::new_test(tx, None);
+        cx.globals.data_dir = Some(data_dir.path().to_path_buf());
+        let source = tokio::spawn(config.build(cx).await.unwrap());
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        source.abort();
+        assert!(collect_ready(rx).await.is_empty());
+    }
+}
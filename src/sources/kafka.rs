@@ -237,6 +237,14 @@ impl SourceConfig for KafkaSourceConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
 }
 
 async fn kafka_source(
@@ -6,6 +6,8 @@ pub mod apache_metrics;
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub mod aws_kinesis_firehose;
+#[cfg(feature = "sources-aws_kinesis_streams")]
+pub mod aws_kinesis_streams;
 #[cfg(feature = "sources-aws_s3")]
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
@@ -18,6 +20,8 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(feature = "sources-elasticsearch")]
+pub mod elasticsearch;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -28,12 +32,16 @@ pub mod file;
 pub mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-graphite")]
+pub mod graphite;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
 pub mod host_metrics;
 #[cfg(feature = "sources-http")]
 pub mod http;
+#[cfg(feature = "sources-http_client")]
+pub mod http_client;
 #[cfg(feature = "sources-internal_logs")]
 pub mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
@@ -102,6 +110,10 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_kinesis_firehose")]
     AwsKinesisFirehose(#[configurable(derived)] aws_kinesis_firehose::AwsKinesisFirehoseConfig),
 
+    /// AWS Kinesis Data Streams.
+    #[cfg(feature = "sources-aws_kinesis_streams")]
+    AwsKinesisStreams(#[configurable(derived)] aws_kinesis_streams::AwsKinesisStreamsConfig),
+
     /// AWS S3.
     #[cfg(feature = "sources-aws_s3")]
     AwsS3(#[configurable(derived)] aws_s3::AwsS3Config),
@@ -126,6 +138,10 @@ pub enum Sources {
     #[cfg(feature = "sources-docker_logs")]
     DockerLogs(#[configurable(derived)] docker_logs::DockerLogsConfig),
 
+    /// Elasticsearch.
+    #[cfg(feature = "sources-elasticsearch")]
+    Elasticsearch(#[configurable(derived)] elasticsearch::ElasticsearchConfig),
+
     /// EventStoreDB Metrics.
     #[cfg(feature = "sources-eventstoredb_metrics")]
     EventstoreDbMetrics(#[configurable(derived)] eventstoredb_metrics::EventStoreDbConfig),
@@ -150,6 +166,10 @@ pub enum Sources {
     #[cfg(feature = "sources-demo_logs")]
     Generator(#[configurable(derived)] demo_logs::DemoLogsCompatConfig),
 
+    /// Graphite.
+    #[cfg(feature = "sources-graphite")]
+    Graphite(#[configurable(derived)] graphite::GraphiteConfig),
+
     /// Heroku Logs.
     #[cfg(feature = "sources-heroku_logs")]
     HerokuLogs(#[configurable(derived)] heroku_logs::LogplexConfig),
@@ -162,6 +182,10 @@ pub enum Sources {
     #[cfg(feature = "sources-http")]
     Http(#[configurable(derived)] http::SimpleHttpConfig),
 
+    /// HTTP Client.
+    #[cfg(feature = "sources-http_client")]
+    HttpClient(#[configurable(derived)] http_client::HttpClientConfig),
+
     /// Internal Logs.
     #[cfg(feature = "sources-internal_logs")]
     InternalLogs(#[configurable(derived)] internal_logs::InternalLogsConfig),
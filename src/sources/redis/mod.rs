@@ -201,6 +201,14 @@ impl SourceConfig for RedisSourceConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        Some(DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        ))
+    }
 }
 
 async fn handle_line(
@@ -3,7 +3,10 @@ mod udp;
 #[cfg(unix)]
 mod unix;
 
-use codecs::NewlineDelimitedDecoderConfig;
+use codecs::{
+    decoding::{DeserializerConfig, FramingConfig},
+    NewlineDelimitedDecoderConfig,
+};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -105,29 +108,15 @@ impl SourceConfig for SocketConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         match self.mode.clone() {
             Mode::Tcp(config) => {
-                let (framing, decoding) = match (config.framing(), config.max_length()) {
-                    (Some(_), Some(_)) => {
-                        return Err("Using `max_length` is deprecated and does not have any effect when framing is provided. Configure `max_length` on the framing config instead.".into());
-                    }
-                    (Some(framing), None) => {
-                        let decoding = config.decoding().clone();
-                        let framing = framing.clone();
-                        (framing, decoding)
-                    }
-                    (None, Some(max_length)) => {
-                        let decoding = config.decoding().clone();
-                        let framing =
-                            NewlineDelimitedDecoderConfig::new_with_max_length(max_length).into();
-                        (framing, decoding)
-                    }
-                    (None, None) => {
-                        let decoding = config.decoding().clone();
-                        let framing = decoding.default_stream_framing();
-                        (framing, decoding)
-                    }
-                };
+                if config.framing().is_some() && config.max_length().is_some() {
+                    return Err("Using `max_length` is deprecated and does not have any effect when framing is provided. Configure `max_length` on the framing config instead.".into());
+                }
 
-                let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
+                let framing =
+                    stream_framing(config.framing(), config.max_length(), config.decoding());
+                let decoder =
+                    DecodingConfig::new(framing, config.decoding().clone(), LogNamespace::Legacy)
+                        .build();
 
                 let tcp = tcp::RawTcpSource::new(config.clone(), decoder);
                 let tls_config = config.tls().as_ref().map(|tls| tls.tls_config.clone());
@@ -186,28 +175,14 @@ impl SourceConfig for SocketConfig {
             }
             #[cfg(unix)]
             Mode::UnixStream(config) => {
-                let (framing, decoding) = match (config.framing, config.max_length) {
-                    (Some(_), Some(_)) => {
-                        return Err("Using `max_length` is deprecated and does not have any effect when framing is provided. Configure `max_length` on the framing config instead.".into());
-                    }
-                    (Some(framing), None) => {
-                        let decoding = config.decoding.clone();
-                        (framing, decoding)
-                    }
-                    (None, Some(max_length)) => {
-                        let decoding = config.decoding.clone();
-                        let framing =
-                            NewlineDelimitedDecoderConfig::new_with_max_length(max_length).into();
-                        (framing, decoding)
-                    }
-                    (None, None) => {
-                        let decoding = config.decoding.clone();
-                        let framing = decoding.default_stream_framing();
-                        (framing, decoding)
-                    }
-                };
+                if config.framing.is_some() && config.max_length.is_some() {
+                    return Err("Using `max_length` is deprecated and does not have any effect when framing is provided. Configure `max_length` on the framing config instead.".into());
+                }
 
-                let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
+                let framing = stream_framing(&config.framing, config.max_length, &config.decoding);
+                let decoder =
+                    DecodingConfig::new(framing, config.decoding.clone(), LogNamespace::Legacy)
+                        .build();
 
                 let host_key = config
                     .host_key
@@ -246,6 +221,45 @@ impl SourceConfig for SocketConfig {
     fn can_acknowledge(&self) -> bool {
         false
     }
+
+    fn decoding(&self, _global_log_namespace: LogNamespace) -> Option<DecodingConfig> {
+        let (framing, decoding) = match &self.mode {
+            Mode::Tcp(config) => (
+                stream_framing(config.framing(), config.max_length(), config.decoding()),
+                config.decoding().clone(),
+            ),
+            Mode::Udp(config) => (config.framing().clone(), config.decoding().clone()),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => (
+                config
+                    .framing
+                    .clone()
+                    .unwrap_or_else(default_framing_message_based),
+                config.decoding.clone(),
+            ),
+            #[cfg(unix)]
+            Mode::UnixStream(config) => (
+                stream_framing(&config.framing, config.max_length, &config.decoding),
+                config.decoding.clone(),
+            ),
+        };
+        Some(DecodingConfig::new(framing, decoding, LogNamespace::Legacy))
+    }
+}
+
+/// Resolves the framing of stream based modes, which falls back to the deprecated `max_length`.
+fn stream_framing(
+    framing: &Option<FramingConfig>,
+    max_length: Option<usize>,
+    decoding: &DeserializerConfig,
+) -> FramingConfig {
+    match (framing, max_length) {
+        (Some(framing), _) => framing.clone(),
+        (None, Some(max_length)) => {
+            NewlineDelimitedDecoderConfig::new_with_max_length(max_length).into()
+        }
+        (None, None) => decoding.default_stream_framing(),
+    }
 }
 
 #[cfg(test)]
@@ -295,6 +295,8 @@ pub async fn build_pieces(
             enrichment_tables: enrichment_tables.clone(),
             schema_definitions,
             merged_schema_definition: merged_definition.clone(),
+            vrl_clock: config.vrl_clock.clone(),
+            vrl_traces: config.vrl_traces.clone(),
         };
 
         let node = TransformNode::from_parts(key.clone(), transform, &merged_definition);
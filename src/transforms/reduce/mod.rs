@@ -2,12 +2,13 @@ use std::collections::BTreeMap;
 use std::{
     collections::{hash_map, HashMap},
     pin::Pin,
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use async_stream::stream;
 use futures::{stream, Stream, StreamExt};
 use indexmap::IndexMap;
+use tokio::time::Instant;
 use vector_config::configurable_component;
 
 use crate::{
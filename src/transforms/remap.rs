@@ -4,6 +4,7 @@ use std::{
     fs::File,
     io::{self, Read},
     path::PathBuf,
+    time::Duration,
 };
 
 use lookup::lookup_v2::Path;
@@ -16,12 +17,12 @@ use vector_core::compile_vrl;
 use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
 
-use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vector_vrl_functions::{set_semantic_meaning::MeaningList, store, StateStore};
 use vrl::state::LocalEnv;
 use vrl::{
     diagnostic::{Formatter, Note},
     prelude::{DiagnosticMessage, ExpressionError},
-    Program, Runtime, Terminate, VrlRuntime,
+    CompiledProgram, Program, Runtime, Terminate, Trace, TraceLog, VrlRuntime,
 };
 
 use crate::{
@@ -38,6 +39,45 @@ use crate::{
 
 const DROPPED: &str = "dropped";
 
+/// Configuration of the state kept by the stateful VRL functions, such as `incr_state`.
+///
+/// The state is kept for as long as the transform runs, including across configuration reloads
+/// that leave the transform unchanged.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StateConfig {
+    /// The maximum number of keys to keep.
+    ///
+    /// When the limit is reached, the least recently used key is evicted.
+    #[serde(default = "default_state_max_keys")]
+    pub max_keys: usize,
+
+    /// The number of seconds after which a key expires, counted from when it was last set.
+    ///
+    /// If not set, keys don't expire.
+    pub ttl_secs: Option<u64>,
+}
+
+const fn default_state_max_keys() -> usize {
+    store::DEFAULT_MAX_KEYS
+}
+
+impl Default for StateConfig {
+    fn default() -> Self {
+        Self {
+            max_keys: default_state_max_keys(),
+            ttl_secs: None,
+        }
+    }
+}
+
+impl StateConfig {
+    fn build(&self) -> StateStore {
+        StateStore::new(self.max_keys, self.ttl_secs.map(Duration::from_secs))
+    }
+}
+
 /// Configuration for the `remap` transform.
 #[configurable_component(transform)]
 #[derive(Clone, Debug, Derivative)]
@@ -46,12 +86,15 @@ const DROPPED: &str = "dropped";
 pub struct RemapConfig {
     /// The [Vector Remap Language](https://vector.dev/docs/reference/vrl) (VRL) program to execute for each event.
     ///
+    /// Relative paths of `import` statements are resolved against the current working directory.
+    ///
     /// Required if `file` is missing.
     pub source: Option<String>,
 
     /// File path to the [Vector Remap Language](https://vector.dev/docs/reference/vrl) (VRL) program to execute for each event.
     ///
-    /// If a relative path is provided, its root is the current working directory.
+    /// If a relative path is provided, its root is the current working directory. Relative paths of
+    /// `import` statements in the program are resolved against the directory of this file.
     ///
     /// Required if `source` is missing.
     pub file: Option<PathBuf>,
@@ -99,6 +142,14 @@ pub struct RemapConfig {
     /// the event was dropped.
     pub reroute_dropped: bool,
 
+    /// The maximum number of iterations of a single `for` or `while` loop in the VRL program.
+    ///
+    /// A loop that exceeds this limit aborts the program. Defaults to 10000 iterations.
+    pub max_loop_iterations: Option<usize>,
+
+    #[configurable(derived)]
+    pub state: StateConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub runtime: VrlRuntime,
@@ -109,6 +160,7 @@ impl RemapConfig {
         &self,
         enrichment_tables: enrichment::TableRegistry,
         merged_schema_definition: schema::Definition,
+        clock: Option<vrl::Clock>,
     ) -> Result<(
         vrl::Program,
         String,
@@ -140,6 +192,16 @@ impl RemapConfig {
         );
         state.set_external_context(enrichment_tables);
         state.set_external_context(MeaningList::default());
+        state.set_external_context(self.state.build());
+        if let Some(clock) = clock {
+            state.set_external_context(clock);
+        }
+        if let Some(dir) = self.file.as_ref().and_then(|path| path.parent()) {
+            state.set_external_context(vrl::ImportRoot::new(dir));
+        }
+        if let Some(limit) = self.max_loop_iterations {
+            state.set_external_context(vrl::IterationLimit::new(limit));
+        }
 
         compile_vrl(&source, &functions, &mut state, LocalEnv::default())
             .map_err(|diagnostics| {
@@ -169,11 +231,20 @@ impl_generate_config_from_default!(RemapConfig);
 #[typetag::serde(name = "remap")]
 impl TransformConfig for RemapConfig {
     async fn build(&self, context: &TransformContext) -> Result<Transform> {
-        let (transform, warnings) = match self.runtime {
-            VrlRuntime::Ast => {
+        let (transform, warnings) = match (&context.vrl_traces, self.runtime) {
+            // Tracing is only supported by the AST runtime.
+            (Some(traces), _) => {
+                let (remap, warnings) = Remap::new_traced(self.clone(), context, traces.clone())?;
+                (Transform::synchronous(remap), warnings)
+            }
+            (None, VrlRuntime::Ast) => {
                 let (remap, warnings) = Remap::new_ast(self.clone(), context)?;
                 (Transform::synchronous(remap), warnings)
             }
+            (None, VrlRuntime::Compiled) => {
+                let (remap, warnings) = Remap::new_compiled(self.clone(), context)?;
+                (Transform::synchronous(remap), warnings)
+            }
         };
 
         // TODO: We could improve on this by adding support for non-fatal error
@@ -199,6 +270,7 @@ impl TransformConfig for RemapConfig {
             .compile_vrl_program(
                 enrichment::TableRegistry::default(),
                 input_definition.clone(),
+                None,
             )
             .map(|(_, _, _, state)| {
                 let meaning = state
@@ -344,6 +416,7 @@ impl Remap<AstRunner> {
         let (program, warnings, _, _) = config.compile_vrl_program(
             context.enrichment_tables.clone(),
             context.merged_schema_definition.clone(),
+            context.vrl_clock.clone(),
         )?;
 
         let runtime = Runtime::default();
@@ -353,6 +426,117 @@ impl Remap<AstRunner> {
     }
 }
 
+#[derive(Debug)]
+pub struct CompiledRunner {
+    pub runtime: Runtime,
+    program: Arc<CompiledProgram>,
+}
+
+impl Clone for CompiledRunner {
+    fn clone(&self) -> Self {
+        Self {
+            runtime: Runtime::default(),
+            program: Arc::clone(&self.program),
+        }
+    }
+}
+
+impl VrlRunner for CompiledRunner {
+    fn run(
+        &mut self,
+        target: &mut VrlTarget,
+        _: &Program,
+        timezone: &TimeZone,
+    ) -> std::result::Result<value::Value, Terminate> {
+        let result = self
+            .runtime
+            .resolve_compiled(target, &self.program, timezone);
+        self.runtime.clear();
+        result
+    }
+}
+
+impl Remap<CompiledRunner> {
+    pub fn new_compiled(
+        config: RemapConfig,
+        context: &TransformContext,
+    ) -> crate::Result<(Self, String)> {
+        let (program, warnings, _, _) = config.compile_vrl_program(
+            context.enrichment_tables.clone(),
+            context.merged_schema_definition.clone(),
+            context.vrl_clock.clone(),
+        )?;
+
+        let runner = CompiledRunner {
+            runtime: Runtime::default(),
+            program: Arc::new(program.compile()),
+        };
+
+        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+    }
+}
+
+#[derive(Debug)]
+pub struct TracingRunner {
+    pub runtime: Runtime,
+    traces: TraceLog,
+    label: String,
+}
+
+impl Clone for TracingRunner {
+    fn clone(&self) -> Self {
+        Self {
+            runtime: Runtime::default(),
+            traces: self.traces.clone(),
+            label: self.label.clone(),
+        }
+    }
+}
+
+impl VrlRunner for TracingRunner {
+    fn run(
+        &mut self,
+        target: &mut VrlTarget,
+        program: &Program,
+        timezone: &TimeZone,
+    ) -> std::result::Result<value::Value, Terminate> {
+        let mut trace = Trace::default();
+        let result = self
+            .runtime
+            .resolve_traced(target, program, timezone, &mut trace);
+        self.runtime.clear();
+        self.traces.push(self.label.clone(), trace);
+        result
+    }
+}
+
+impl Remap<TracingRunner> {
+    /// Create a remap transform that records a trace of each run of its program into the given
+    /// log, labelled with the key of the component.
+    pub fn new_traced(
+        config: RemapConfig,
+        context: &TransformContext,
+        traces: TraceLog,
+    ) -> crate::Result<(Self, String)> {
+        let (program, warnings, _, _) = config.compile_vrl_program(
+            context.enrichment_tables.clone(),
+            context.merged_schema_definition.clone(),
+            context.vrl_clock.clone(),
+        )?;
+
+        let runner = TracingRunner {
+            runtime: Runtime::default(),
+            traces,
+            label: context
+                .key
+                .as_ref()
+                .map_or_else(|| "remap".to_owned(), ToString::to_string),
+        };
+
+        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+    }
+}
+
 impl<Runner> Remap<Runner>
 where
     Runner: VrlRunner,
@@ -688,6 +872,124 @@ mod tests {
         assert!(tform.runner().runtime.is_empty());
     }
 
+    #[test]
+    fn check_remap_compiled_runtime() {
+        let schema_definitions = HashMap::from([
+            (None, test_default_schema_definition()),
+            (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+        ]);
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                .foo = .sentinel
+                if .abort == true { abort }
+                .bar = "baz"
+            "#}
+                .to_string(),
+            ),
+            drop_on_abort: true,
+            runtime: VrlRuntime::Compiled,
+            ..Default::default()
+        };
+        let context = TransformContext::new_test(schema_definitions);
+        let mut tform = Remap::new_compiled(conf, &context).unwrap().0;
+        assert!(tform.runner().runtime.is_empty());
+
+        let event = {
+            let mut event = LogEvent::from("event");
+            event.insert("sentinel", "bar");
+            Event::from(event)
+        };
+        let result = transform_one(&mut tform, event).unwrap();
+        assert_eq!(get_field_string(&result, "foo"), "bar");
+        assert_eq!(get_field_string(&result, "bar"), "baz");
+        assert!(tform.runner().runtime.is_empty());
+
+        let event = {
+            let mut event = LogEvent::from("event");
+            event.insert("abort", true);
+            Event::from(event)
+        };
+        assert!(transform_one(&mut tform, event).is_none());
+        assert!(tform.runner().runtime.is_empty());
+    }
+
+    #[test]
+    fn check_remap_traced() {
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                if .status == 200 {
+                    .ok = true
+                } else {
+                    .ok = false
+                }
+            "#}
+                .to_string(),
+            ),
+            ..Default::default()
+        };
+        let traces = TraceLog::default();
+        let context = TransformContext {
+            key: Some(ComponentKey::from("remapper")),
+            ..Default::default()
+        };
+        let mut tform = Remap::new_traced(conf, &context, traces.clone()).unwrap().0;
+
+        let event = {
+            let mut event = LogEvent::from("event");
+            event.insert("status", 500);
+            Event::from(event)
+        };
+        let result = transform_one(&mut tform, event).unwrap();
+        assert_eq!(result.as_log()["ok"], false.into());
+        assert!(tform.runner().runtime.is_empty());
+
+        let traces = traces.take();
+        assert_eq!(traces.len(), 1);
+
+        let (label, trace) = &traces[0];
+        assert_eq!(label, "remapper");
+
+        let trace = trace.to_string();
+        assert!(trace.contains("+ .ok = false"), "{}", trace);
+        assert!(!trace.contains(".ok = true"), "{}", trace);
+    }
+
+    #[test]
+    fn check_remap_state() {
+        let conf = RemapConfig {
+            source: Some(".count = incr_state!(string!(.user))".to_string()),
+            state: StateConfig {
+                max_keys: 1,
+                ttl_secs: None,
+            },
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+        // Clones of the transform run concurrently, and share its state.
+        let mut clone = tform.clone();
+
+        let event = |user: &str| {
+            let mut event = LogEvent::from("event");
+            event.insert("user", user);
+            Event::from(event)
+        };
+
+        let result = transform_one(&mut tform, event("alice")).unwrap();
+        assert_eq!(result.as_log()["count"], 1.into());
+
+        let result = transform_one(&mut clone, event("alice")).unwrap();
+        assert_eq!(result.as_log()["count"], 2.into());
+
+        // Counting a second user evicts the first one.
+        let result = transform_one(&mut tform, event("bob")).unwrap();
+        assert_eq!(result.as_log()["count"], 1.into());
+
+        let result = transform_one(&mut tform, event("alice")).unwrap();
+        assert_eq!(result.as_log()["count"], 1.into());
+    }
+
     #[test]
     fn check_remap_adds() {
         let event = {
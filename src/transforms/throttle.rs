@@ -49,7 +49,7 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, TokioClock).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -65,6 +65,19 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
+/// A monotonic clock backed by tokio's clock, so that rate limiting follows paused or advanced
+/// time, such as when running unit tests against a virtual clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioClock;
+
+impl clock::Clock for TokioClock {
+    type Instant = std::time::Instant;
+
+    fn now(&self) -> Self::Instant {
+        tokio::time::Instant::now().into_std()
+    }
+}
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
     quota: Quota,
@@ -1,4 +1,9 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use clap::Parser;
 use colored::*;
@@ -38,6 +43,15 @@ pub struct Opts {
         use_value_delimiter(true)
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// Write the results of the tests to this file as a JUnit XML report.
+    #[clap(long)]
+    junit_report: Option<PathBuf>,
+
+    /// Print the expressions evaluated by the programs of `remap` transforms for each test
+    /// event, along with the values they resolved to and the changes they made to the event.
+    #[clap(long)]
+    trace_remap: bool,
 }
 
 impl Opts {
@@ -60,6 +74,7 @@ impl Opts {
 
 pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exitcode::ExitCode {
     let mut aggregated_test_errors: Vec<(String, Vec<String>)> = Vec::new();
+    let mut test_reports: Vec<TestReport> = Vec::new();
 
     let paths = opts.paths_with_formats();
     let paths = match config::process_paths(&paths) {
@@ -71,7 +86,7 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
     {
         println!("Running tests");
     }
-    match config::build_unit_tests_main(&paths, signal_handler).await {
+    match config::build_unit_tests_main(&paths, signal_handler, opts.trace_remap).await {
         Ok(tests) => {
             if tests.is_empty() {
                 #[allow(clippy::print_stdout)]
@@ -81,7 +96,13 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
             } else {
                 for test in tests {
                     let name = test.name.clone();
-                    let UnitTestResult { errors } = test.run().await;
+                    let start = Instant::now();
+                    let UnitTestResult { errors, traces } = test.run().await;
+                    test_reports.push(TestReport {
+                        name: name.clone(),
+                        duration: start.elapsed(),
+                        errors: errors.clone(),
+                    });
                     if !errors.is_empty() {
                         #[allow(clippy::print_stdout)]
                         {
@@ -94,6 +115,12 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
                             println!("test {} ... {}", name, "passed".green());
                         }
                     }
+                    for trace in traces {
+                        #[allow(clippy::print_stdout)]
+                        {
+                            println!("\n{}", trace.trim_end().replace('\n', "\n  "));
+                        }
+                    }
                 }
             }
         }
@@ -103,6 +130,17 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
         }
     }
 
+    if let Some(path) = &opts.junit_report {
+        if let Err(error) = write_junit_report(path, &test_reports) {
+            error!(
+                "Failed to write JUnit report to {:?}: {}.",
+                path.display(),
+                error
+            );
+            return exitcode::IOERR;
+        }
+    }
+
     if !aggregated_test_errors.is_empty() {
         #[allow(clippy::print_stdout)]
         {
@@ -126,3 +164,105 @@ pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exi
         exitcode::OK
     }
 }
+
+struct TestReport {
+    name: String,
+    duration: Duration,
+    errors: Vec<String>,
+}
+
+fn write_junit_report(path: &Path, reports: &[TestReport]) -> std::io::Result<()> {
+    fs::write(path, junit_report(reports))
+}
+
+fn junit_report(reports: &[TestReport]) -> String {
+    let failures = reports
+        .iter()
+        .filter(|report| !report.errors.is_empty())
+        .count();
+    let time = reports
+        .iter()
+        .map(|report| report.duration)
+        .sum::<Duration>()
+        .as_secs_f64();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        reports.len(),
+        failures,
+        time
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"vector\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        reports.len(),
+        failures,
+        time
+    );
+    for report in reports {
+        let _ = write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"vector\" time=\"{:.3}\"",
+            escape_xml(&report.name),
+            report.duration.as_secs_f64()
+        );
+        if report.errors.is_empty() {
+            xml.push_str(" />\n");
+        } else {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{} check(s) failed\">{}</failure>\n    </testcase>",
+                report.errors.len(),
+                escape_xml(&report.errors.join("\n\n"))
+            );
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_report_escapes_failures() {
+        let report = junit_report(&[
+            TestReport {
+                name: "passes".to_string(),
+                duration: Duration::from_millis(1500),
+                errors: Vec::new(),
+            },
+            TestReport {
+                name: "fails <badly>".to_string(),
+                duration: Duration::from_millis(500),
+                errors: vec!["expected \"a\" & \"b\"".to_string()],
+            },
+        ]);
+
+        assert!(report.contains(r#"<testsuites tests="2" failures="1" time="2.000">"#));
+        assert!(report.contains(r#"<testcase name="passes" classname="vector" time="1.500" />"#));
+        assert!(report
+            .contains(r#"<testcase name="fails &lt;badly&gt;" classname="vector" time="0.500">"#));
+        assert!(report.contains(
+            r#"<failure message="1 check(s) failed">expected &quot;a&quot; &amp; &quot;b&quot;</failure>"#
+        ));
+    }
+}
@@ -404,7 +404,12 @@ _values: {
 		},
 		{
 			name:        "datadog_search"
-			description: "A [Datadog Search](\(urls.datadog_search_syntax)) query string."
+			description: """
+				A [Datadog Search](\(urls.datadog_search_syntax)) query string. Logs and traces are matched by their
+				fields, and their tags can be either an array of `key:value` strings or an object. Metrics are matched by
+				their name as the default field, by their `@name`, `@namespace`, `@kind`, `@type` and `@value` facets,
+				and by their tags, which also provide the reserved `host`, `source`, `status` and `service` attributes.
+				"""
 			example:     #"*stack"#
 		},
 	]
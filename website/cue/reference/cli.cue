@@ -234,6 +234,16 @@ cli: {
 				out the [unit testing documentation](\(urls.vector_unit_tests)).
 				"""
 
+			flags: _default_flags & {
+				"trace-remap": {
+					description: """
+						Print the expressions evaluated by the programs of `remap` transforms
+						for each test event, along with the values they resolved to and the
+						changes they made to the event.
+						"""
+				}
+			}
+
 			options: {
 				"config-toml": {
 					description: env_vars.VECTOR_CONFIG_TOML.description
@@ -428,6 +438,13 @@ cli: {
 			description: "Vector Remap Language CLI"
 
 			flags: _default_flags & {
+				"trace": {
+					description: """
+						Print the expressions evaluated for each object to stderr, along with
+						the values they resolved to and the changes they made to the object.
+						Tracing always uses the AST runtime.
+						"""
+				}
 				"print-object": {
 					_short: "o"
 					description: """
@@ -467,6 +484,34 @@ cli: {
 				}
 			}
 		}
+
+		"vrl-lsp": {
+			description: """
+				Vector Remap Language language server. Editors start it to get compile
+				diagnostics, hover types, signature help, and completion while editing
+				VRL programs. Communicates over stdin and stdout.
+				"""
+
+			flags: _default_flags
+
+			options: {
+				"config": {
+					_short: "c"
+					description: """
+						Any number of Vector config files, used to look up the input schema of
+						the `component`.
+						"""
+					type: "string"
+				}
+				"component": {
+					description: """
+						The transform whose merged input schema is used to type check and
+						complete event fields.
+						"""
+					type: "string"
+				}
+			}
+		}
 	}
 
 	env_vars: {
@@ -196,6 +196,9 @@ components: sinks: [Name=string]: {
 											if codec == "avro" {
 												avro: "Avro encoded event with a given schema."
 											}
+											if codec == "syslog" {
+												syslog: "[Syslog](\(urls.syslog)) message, as described in RFC 5424 or RFC 3164."
+											}
 										}
 									}
 								}
@@ -224,6 +227,90 @@ components: sinks: [Name=string]: {
 											}
 										}
 									}
+									if codec == "syslog" {
+										syslog: {
+											common:        false
+											description:   "Options for the `syslog` codec. Header fields can be set to a fixed string or a template such as `{{ service }}`, which may also use `strftime` specifiers for the event timestamp, and are otherwise read from the fields of the same name that the `syslog` decoder produces. Events missing a field referred to by a template fail to encode."
+											required:      false
+											relevant_when: "codec = `syslog`"
+											type: object: options: {
+												rfc: {
+													description: "The format of the messages."
+													required:    false
+													type: string: {
+														default: "rfc5424"
+														enum: {
+															rfc3164: "The BSD syslog format of [RFC 3164](\(urls.syslog_3164))."
+															rfc5424: "The syslog format of [RFC 5424](\(urls.syslog_5424))."
+														}
+													}
+												}
+												facility: {
+													description: "The facility, either as a name such as `local0`, or as a number. If not set, the `facility` field is used, and `user` if it's missing."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["local0", "{{ facility }}"]
+														syntax: "template"
+													}
+												}
+												severity: {
+													description: "The severity, either as a name such as `warning`, or as a number. If not set, the `severity` field is used, and `info` if it's missing."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["notice", "{{ level }}"]
+														syntax: "template"
+													}
+												}
+												hostname: {
+													description: "The hostname. If not set, the `hostname` field is used, and the `host` field if it's missing."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["{{ host }}"]
+														syntax: "template"
+													}
+												}
+												app_name: {
+													description: "The application name, which is the tag of RFC 3164 messages. If not set, the `appname` field is used."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["vector", "{{ service }}"]
+														syntax: "template"
+													}
+												}
+												proc_id: {
+													description: "The process ID. If not set, the `procid` field is used."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["{{ pid }}"]
+														syntax: "template"
+													}
+												}
+												msg_id: {
+													description: "The message ID. Only used by RFC 5424. If not set, the `msgid` field is used."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["ID47", "{{ event_type }}"]
+														syntax: "template"
+													}
+												}
+												structured_data: {
+													description: "The field holding the structured data. Only used by RFC 5424. The field must be an object of structured data IDs, each holding an object of parameters."
+													required:    false
+													type: string: {
+														default: null
+														examples: ["structured_data"]
+														syntax: "field_path"
+													}
+												}
+											}
+										}
+									}
 								}
 							}
 
@@ -287,6 +374,7 @@ components: sinks: [Name=string]: {
 											character_delimited: "Byte frames are delimited by a chosen character."
 											length_delimited:    "Byte frames are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 											newline_delimited:   "Byte frames are delimited by a newline character."
+											octet_counting:      "Byte frames are prefixed by their length in bytes, as a decimal number followed by a space, as specified in [RFC 6587](\(urls.rfc_6587_3_4_1))."
 										}
 									}
 								}
@@ -92,6 +92,18 @@ components: sinks: clickhouse: {
 				examples: ["http://localhost:8123"]
 			}
 		}
+		format: {
+			common:      false
+			description: "The format rows are inserted in."
+			required:    false
+			type: string: {
+				default: "json_each_row"
+				enum: {
+					json_each_row: "Rows are inserted as JSON objects, using the `JSONEachRow` format."
+					row_binary:    "Rows are inserted in the native `RowBinary` format, coercing fields to the types of the columns of the table. See [Row binary format](#row-binary-format)."
+				}
+			}
+		}
 		table: {
 			description: "The table that data will be inserted into."
 			required:    true
@@ -101,7 +113,7 @@ components: sinks: clickhouse: {
 		}
 		skip_unknown_fields: {
 			common:      true
-			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema."
+			description: "Sets `input_format_skip_unknown_fields`, allowing Clickhouse to discard fields not present in the table schema. Only applies to the `json_each_row` format, as the `row_binary` format always ignores such fields."
 			required:    false
 			type: bool: default: false
 		}
@@ -113,7 +125,37 @@ components: sinks: clickhouse: {
 		traces:  false
 	}
 
+	how_it_works: {
+		row_binary_format: {
+			title: "Row binary format"
+			body:  """
+				With the `row_binary` format, Vector fetches the columns of the table with
+				`DESCRIBE TABLE` once the sink runs, retrying while ClickHouse is unavailable, and
+				encodes each event as a row of those columns. Materialized and alias columns are
+				skipped. If the table has a column of a type the sink doesn't support, like
+				`Decimal`, the health check fails and the sink stops.
+
+				The value of each column is read from the event field of the same name, and is
+				coerced to the type of the column: numbers and booleans are parsed from strings,
+				`Date`, `DateTime` and `DateTime64` columns accept timestamps, Unix timestamps in
+				seconds and RFC 3339 strings, `String` columns hold objects and arrays as JSON, and
+				`Enum` columns accept either names or values. `LowCardinality` columns are encoded
+				like their inner type, `Map` columns are read from objects, and `Array` and `Nested`
+				columns from arrays. The subcolumns of a `Nested` column are also read from the
+				fields of an array of objects: `spans.name` is read from the `name` field of each
+				object in `spans`.
+
+				Missing and null fields are inserted as `NULL` in `Nullable` columns, and as the
+				default value of the type of other columns. Events with a field that can't be
+				coerced to the type of its column are discarded, and the error is reported with
+				the name of the column.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
@@ -59,7 +59,7 @@ components: sinks: datadog_traces: {
 
 	support: {
 		requirements: []
-		warnings: ["APM stats are not yet supported, so they will dropped when using Vector to send traces received from the Datadog Trace agent to Datadog."]
+		warnings: []
 		notices: []
 	}
 
@@ -75,6 +75,24 @@ components: sinks: datadog_traces: {
 		traces:  true
 	}
 
+	how_it_works: {
+		apm_stats: {
+			title: "APM stats"
+			body: """
+				APM stats are computed out of the traces sent to Datadog, unless they were received by the `datadog_agent`
+				source from a Datadog Trace Agent that already computed them. In that case, the APM stats payloads of the
+				Trace Agent are relayed as is, as they account for the traces it sampled out.
+				"""
+		}
+		profiles: {
+			title: "Profiles"
+			body: """
+				Profiles received by the `datadog_agent` source are relayed untouched to the Datadog profiling intake,
+				`https://intake.profile.<site>/api/v2/profile`, or to the `/api/v2/profile` path of the configured `endpoint`.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
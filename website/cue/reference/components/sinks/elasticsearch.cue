@@ -137,12 +137,14 @@ components: sinks: elasticsearch: {
 						common:      false
 						description: """
 							Action to use when making requests to the [Elasticsearch Bulk API](\(urls.elasticsearch_bulk)).
-							Currently, Vector only supports `index` and `create`. `update` and `delete` actions are not supported.
+							One of `index`, `create`, `update`, `upsert` or `delete`. `update` sends the event as a partial
+							document, `upsert` also creates the document from the event if it doesn't exist, and `delete`
+							sends no document. `update`, `upsert` and `delete` need the document `id`, see `id_key`: events without one are dropped.
 							"""
 						required:    false
 						type: string: {
 							default: "index"
-							examples: ["index", "create", "{{ action }}"]
+							examples: ["index", "create", "upsert", "{{ action }}"]
 							syntax: "template"
 						}
 					}
@@ -156,6 +158,48 @@ components: sinks: elasticsearch: {
 							syntax: "template"
 						}
 					}
+					pipeline: {
+						common:      false
+						description: "Name of the ingest pipeline the event is sent through, overriding the `pipeline` option. Only applies to the `index` and `create` actions."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{{ pipeline }}", "enrich-{{ service }}"]
+							syntax: "template"
+						}
+					}
+					routing: {
+						common:      false
+						description: "The routing value of the document, which selects the shard it is stored in."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{{ tenant_id }}"]
+							syntax: "template"
+						}
+					}
+					version: {
+						common:      false
+						description: "The version of the document, which must render to an unsigned integer. Events whose version can't be rendered are dropped. Only applies to the `index`, `create` and `delete` actions."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["{{ sequence }}"]
+							syntax: "template"
+						}
+					}
+					version_type: {
+						common:      false
+						description: "How the `version` of the documents is compared to the version of the existing documents. Defaults to `external` when `version` is set."
+						required:    false
+						type: string: {
+							default: null
+							enum: {
+								external:     "The version must be greater than the version of the existing document."
+								external_gte: "The version must be greater than or equal to the version of the existing document."
+							}
+						}
+					}
 				}
 			}
 		}
@@ -297,6 +341,18 @@ components: sinks: elasticsearch: {
 				}
 			}
 		}
+		request_retry_partial: {
+			common:      false
+			description: """
+				Retries only the items of a bulk request that failed with a transient error, such as a full write
+				queue, rather than dropping the request. The events of each request are kept in memory until it
+				completes. Once its items are retried, the request is never sent again as a whole, so items that
+				were already indexed aren't duplicated. If a retry fails as a whole, or the retries run out, the
+				events of the request are reported as errored.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		suppress_type_name: {
 			common: false
 			description: """
@@ -345,6 +401,10 @@ components: sinks: elasticsearch: {
 				due to Elasticsearch index mapping errors, where data keys aren't consistently
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
+
+				Items that fail with a transient error, such as `429 Too Many Requests` when the write queue of a
+				node is full, can be sent again on their own with `request_retry_partial`. Other failed items are not
+				retried, and the events of the request are reported as rejected.
 				"""
 		}
 
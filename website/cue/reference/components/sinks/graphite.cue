@@ -0,0 +1,149 @@
+package metadata
+
+components: sinks: graphite: {
+	title: "Graphite"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: sinks.socket.features.acknowledgements
+		healthcheck:      sinks.socket.features.healthcheck
+		send: {
+			compression: sinks.socket.features.send.compression
+			encoding: enabled: false
+			request: sinks.socket.features.send.request
+			send_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp`"
+			}
+			keepalive: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.graphite
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "udp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: sinks.socket.support
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    false
+			set:          false
+			summary:      false
+		}
+		traces: false
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the Carbon daemon to connect to. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1:2003", "carbon-relay.local:2004"]
+			}
+		}
+		mode: {
+			description: "The type of socket to use."
+			required:    true
+			type: string: {
+				enum: {
+					tcp: "TCP socket"
+					udp: "UDP socket"
+				}
+			}
+		}
+		format: {
+			common:      true
+			description: "The Carbon protocol to encode metrics with."
+			required:    false
+			type: string: {
+				default: "plaintext"
+				enum: {
+					plaintext: "The [plaintext protocol](\(urls.graphite_plaintext_protocol)), one `<path> <value> <timestamp>` line per metric. Carbon usually listens for it on port 2003."
+					pickle:    "The [pickle protocol](\(urls.graphite_pickle_protocol)), usually served on port 2004. Only supported when `mode` is `tcp`."
+				}
+			}
+		}
+		default_namespace: {
+			common: true
+			description: """
+				Used as a namespace for metrics that don't have it.
+				A namespace will be prefixed to a metric's name.
+				Ignored when `path_template` is set.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["service"]
+			}
+		}
+		path_template: {
+			common: false
+			description: """
+				Builds the metric path from the metric's name, namespace and tags, flattening the
+				tags into path nodes instead of sending them as a tagged series. Tag values have
+				`.` and whitespace replaced with `_` so that each one stays a single path node.
+				Metrics missing a field referenced by the template are dropped.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["{{ tags.host }}.{{ namespace }}.{{ name }}", "servers.{{ tags.region }}.{{ tags.host }}.{{ name }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	how_it_works: {
+		metric_paths: {
+			title: "Metric paths"
+			body:  """
+				Without a `path_template`, the path is the metric's namespace and name joined by
+				`.`, followed by its tags using the [tagged series](\(urls.graphite_tags)) syntax,
+				for example `system.cpu.load;host=web01;region=us-east`. Tagged series require
+				Graphite 1.1 or later. Set `path_template` to flatten tags into the path instead
+				for Carbon daemons that don't support them.
+				"""
+		}
+		metric_types: {
+			title: "Metric types"
+			body:  """
+				Counters and gauges are sent with their current value and timestamp, falling back
+				to the current time for metrics without one. Other metric types have no Carbon
+				equivalent and are dropped and reported through `component_errors_total`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
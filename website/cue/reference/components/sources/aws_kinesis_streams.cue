@@ -0,0 +1,237 @@
+package metadata
+
+components: sources: aws_kinesis_streams: components._aws & {
+	title: "AWS Kinesis Data Streams"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_kinesis_data_streams
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+				The AWS Kinesis Data Streams source requires an existing stream. Checkpoints stored in DynamoDB
+				require an existing table with a string partition key named `checkpoint_key`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		stream_name: {
+			description: "The name of the stream to consume."
+			required:    true
+			type: string: {
+				examples: ["my-stream"]
+				syntax: "literal"
+			}
+		}
+		starting_position: {
+			common:      true
+			description: "Where to start reading a shard that has no checkpoint yet."
+			required:    false
+			type: string: {
+				default: "latest"
+				enum: {
+					latest:       "Start with the records added to the shard after the source started."
+					trim_horizon: "Start with the oldest record of the shard that is still retained by the stream."
+				}
+			}
+		}
+		max_records: {
+			common:      false
+			description: "The maximum number of records to fetch from a shard in a single request. Must be between 1 and 10000, the limit of Kinesis."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
+		poll_interval_ms: {
+			common:      false
+			description: "How long to wait before reading a shard again once it has no more records. Kinesis allows five reads per second per shard, shared by all of its consumers."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		shard_discovery_interval_secs: {
+			common:      false
+			description: "The interval between listings of the stream's shards. Shards are also listed as soon as a shard is closed by a reshard, to pick up its children."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		checkpoint: {
+			common:      false
+			description: "Where the source stores the sequence number of the last record delivered from each shard."
+			required:    false
+			type: object: options: {
+				type: {
+					description: "The checkpoint store."
+					required:    false
+					common:      false
+					type: string: {
+						default: "file"
+						enum: {
+							file:     "Checkpoints are stored in a file in the data directory."
+							dynamodb: "Checkpoints are stored in a [DynamoDB](\(urls.aws_dynamodb)) table, or in a table of a DynamoDB-compatible service."
+						}
+					}
+				}
+				table_name: {
+					description:   "The name of the table to store checkpoints in."
+					relevant_when: "type = \"dynamodb\""
+					required:      true
+					type: string: {
+						examples: ["vector-checkpoints"]
+						syntax: "literal"
+					}
+				}
+				region: {
+					description:   "The region of the table. Defaults to the region and endpoint of the stream when neither is set."
+					relevant_when: "type = \"dynamodb\""
+					required:      false
+					common:        false
+					type: string: {
+						default: null
+						examples: ["us-east-1"]
+					}
+				}
+				endpoint: {
+					description:   "Custom endpoint of the table, for use with DynamoDB-compatible services."
+					relevant_when: "type = \"dynamodb\""
+					required:      false
+					common:        false
+					type: string: {
+						default: null
+						examples: ["http://127.0.0.1:8000"]
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Kinesis record"
+		fields: {
+			message: {
+				description: "The data of the Kinesis record."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			partition_key: {
+				description: "The partition key of the record."
+				required:    true
+				type: string: {
+					examples: ["user-1234"]
+					syntax: "literal"
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the record within its shard."
+				required:    true
+				type: string: {
+					examples: ["49590338271490256608559692538361571095921575989136588898"]
+					syntax: "literal"
+				}
+			}
+			shard_id: {
+				description: "The ID of the shard the record was read from."
+				required:    true
+				type: string: {
+					examples: ["shardId-000000000000"]
+					syntax: "literal"
+				}
+			}
+			stream_name: {
+				description: "The name of the stream the record was read from."
+				required:    true
+				type: string: {
+					examples: ["my-stream"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The approximate time the record was added to the stream."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	how_it_works: {
+		shards: {
+			title: "Shards and resharding"
+			body: """
+				Each shard of the stream is read by its own task, which polls the shard for new records and
+				checkpoints the sequence number of the last record delivered. With acknowledgements enabled, a
+				checkpoint is only written once the sinks have acknowledged the records, and records that are
+				not delivered are read again.
+
+				When the stream is [resharded](\(urls.aws_kinesis_resharding)), the children of a closed shard
+				are only read once all of their parents have been read to their end, so that the records of a
+				partition key are delivered in order.
+				"""
+		}
+		multiple_instances: {
+			title: "Multiple instances"
+			body: """
+				Shards aren't leased, so a stream should be consumed by a single Vector instance. Running
+				several instances against the same stream and checkpoint store delivers each record once per
+				instance.
+				"""
+		}
+	}
+}
@@ -52,7 +52,8 @@ components: sources: aws_s3: components._aws & {
 			type: string: {
 				default: "sqs"
 				enum: {
-					sqs: "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					sqs:  "Consume S3 objects by polling for bucket notifications sent to an [AWS SQS queue](\(urls.aws_sqs))."
+					list: "Consume S3 objects by periodically listing a bucket, skipping the objects that were already processed."
 				}
 			}
 		}
@@ -112,6 +113,79 @@ components: sources: aws_s3: components._aws & {
 				}
 			}
 		}
+		list: {
+			common:      false
+			description: "List strategy options. Required if strategy=`list`."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					bucket: {
+						description: "The name of the bucket to list."
+						required:    true
+						type: string: {
+							examples: ["my-bucket"]
+						}
+					}
+					prefix: {
+						common:      true
+						description: "Only objects whose key starts with this prefix are read."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["AWSLogs/123456789012/elasticloadbalancing/"]
+						}
+					}
+					start_after: {
+						common:      false
+						description: "Only objects whose key sorts after this key are read. Keys are listed in UTF-8 binary order, so this can skip large parts of a bucket whose keys start with a date."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["logs/2022/08/01/"]
+						}
+					}
+					modified_after: {
+						common:      false
+						description: "Only objects last modified at or after this time are read, as an RFC 3339 timestamp."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["2022-08-01T00:00:00Z"]
+						}
+					}
+					modified_before: {
+						common:      false
+						description: "Only objects last modified before this time are read, as an RFC 3339 timestamp."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["2022-09-01T00:00:00Z"]
+						}
+					}
+					poll_secs: {
+						common:      false
+						description: "How long to wait between listings of the bucket. Objects added to the bucket since the previous listing are read by the next one."
+						required:    false
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+		data_dir: {
+			common:        false
+			description:   "The directory used to persist the keys of processed objects. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			relevant_when: "strategy = \"list\""
+			required:      false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
 	}
 
 	output: logs: object: {
@@ -187,6 +261,21 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+		list_strategy: {
+			title: "Listing a bucket"
+			body: """
+				The `list` strategy reads the objects of a bucket without bucket notifications,
+				which is useful to backfill historical objects. The bucket is listed every
+				`list.poll_secs`, and the objects matching the `prefix`, `start_after` and
+				time window options are read one at a time.
+
+				The keys and ETags of processed objects are recorded in the data directory,
+				so that they aren't read again by later listings or after a restart, unless
+				they are overwritten. An object that fails to be read, or whose events aren't
+				acknowledged, is read again by the next listing. Objects that are no longer
+				listed, such as deleted ones, are forgotten once a listing completes.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -200,6 +289,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `list`"
+				},
 			]
 		},
 		{
@@ -170,12 +170,34 @@ components: sources: datadog_agent: {
 			gauge:        output._passthrough_gauge
 		}
 		traces: {
-			description: "A trace received through an HTTP POST request sent by a Datadog Trace Agent."
+			description: "A trace, an APM stats payload or a profile, received through an HTTP POST request sent by a Datadog Trace Agent."
 			fields: {
 				spans: {
 					description: "The list of spans composing the trace."
-					required:    true
-					type: array: items: type: object: options: {}
+					required:    false
+					common:      true
+					type: array: {
+						default: null
+						items: type: object: options: {}
+					}
+				}
+				apm_stats: {
+					description: "The APM stats computed by the Datadog Trace Agent, set on APM stats payloads only."
+					required:    false
+					common:      false
+					type: array: {
+						default: null
+						items: type: object: options: {}
+					}
+				}
+				profile: {
+					description: "The body of a profile upload, along with its `content-type`, `content-encoding` and Datadog specific headers, set on profiles only."
+					required:    false
+					common:      false
+					type: object: {
+						examples: [{"body": "<multipart body>", "headers": {"content-type": "multipart/form-data; boundary=c0d9a1f3"}}]
+						options: {}
+					}
 				}
 			}
 		}
@@ -210,9 +232,21 @@ components: sources: datadog_agent: {
 		trace_support: {
 			title: "Trace support"
 			body: """
-				The `datadog_agent` source is capable of receiving traces from the Datadog Agent for versions < 6/7.33.
-				We are working on adding support for the newer agent versions as well as support for passing along APM
-				statistics used by Datadog.
+				The `datadog_agent` source receives traces, APM stats and profiles from the Datadog Trace Agent. To send them to
+				Vector, the [Datadog Agent](\(urls.datadog_agent_doc)) configuration must be updated to use:
+
+				```yaml
+				apm_config:
+					apm_dd_url: http://"<VECTOR_HOST>:<SOURCE_PORT>" # Use https if SSL is enabled in Vector source configuration
+					profiling_dd_url: http://"<VECTOR_HOST>:<SOURCE_PORT>"/api/v2/profile
+				```
+
+				Every span attribute and every field of the trace payloads is kept, so that the `datadog_traces` sink relays
+				traces without loss. The APM stats the Trace Agent computes before sampling traces are relayed as well, and
+				the `datadog_traces` sink doesn't compute APM stats for the traces they cover. APM stats sent as JSON by
+				Trace Agents older than 6/7.33 are discarded, and computed by the `datadog_traces` sink instead.
+
+				Profiles are passed through untouched: their body isn't decompressed nor decoded.
 				"""
 		}
 	}
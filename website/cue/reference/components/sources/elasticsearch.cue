@@ -0,0 +1,229 @@
+package metadata
+
+components: sources: elasticsearch: {
+	title: "Elasticsearch"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.elasticsearch
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		endpoint: {
+			description: "The Elasticsearch endpoint to search. The endpoint must contain an HTTP scheme, and may specify a hostname or IP address and port."
+			required:    true
+			type: string: {
+				examples: ["http://10.24.32.122:9200", "https://example.com"]
+			}
+		}
+		indices: {
+			description: "The indices to search. Wildcards, aliases and data streams are allowed."
+			required:    true
+			type: array: {
+				items: type: string: {
+					examples: ["logs-*", "application"]
+				}
+			}
+		}
+		query: {
+			common:      true
+			description: "The query to run, as a JSON object of the [query DSL](\(urls.elasticsearch_query_dsl)). By default, all documents of the indices are read."
+			required:    false
+			type: string: {
+				default: "{\"match_all\": {}}"
+				examples: ["{\"range\": {\"@timestamp\": {\"gte\": \"2022-08-01\", \"lt\": \"2022-09-01\"}}}"]
+			}
+		}
+		page_size: {
+			common:      false
+			description: "The number of documents requested per page."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
+		keep_alive_secs: {
+			common:      false
+			description: "How long the cluster keeps the search context alive between two pages."
+			required:    false
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+		pagination: {
+			common:      false
+			description: "Pagination of the search results."
+			required:    false
+			type: string: {
+				default: "point_in_time"
+				enum: {
+					point_in_time: "Pages through a [point in time](\(urls.elasticsearch_point_in_time)) with `search_after`. The position in the search is checkpointed after each page, so that a restart resumes the search rather than starting it over. Requires Elasticsearch 7.10 or later."
+					scroll:        "Pages through a [scroll](\(urls.elasticsearch_scroll)). Supported by older clusters and OpenSearch. A scroll can't be resumed, so a restart before the search completes starts it over."
+				}
+			}
+		}
+		metadata_key: {
+			common:      false
+			description: "The field the `index` and `id` of each document are inserted in."
+			required:    false
+			type: string: {
+				default: "@metadata"
+			}
+		}
+		auth: {
+			common:      false
+			description: "Options for the authentication strategy."
+			required:    false
+			type: object: {
+				examples: []
+				options: components._aws.configuration.auth.type.object.options & {
+					password: {
+						description: "The basic authentication password."
+						required:    true
+						type: string: {
+							examples: ["${ELASTICSEARCH_PASSWORD}", "password"]
+						}
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: {
+							enum: {
+								aws:   "Authentication strategy used for [AWS' hosted Elasticsearch service](\(urls.aws_elasticsearch))."
+								basic: "The [basic authentication strategy](\(urls.basic_auth))."
+							}
+						}
+					}
+					user: {
+						description: "The basic authentication user name."
+						required:    true
+						type: string: {
+							examples: ["${ELASTICSEARCH_USERNAME}", "username"]
+						}
+					}
+				}
+			}
+		}
+		aws: {
+			common:      false
+			description: "Options for the AWS connections."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					region: {
+						common:      true
+						description: "The [AWS region](\(urls.aws_regions)) of the searched service. Required with the `aws` authentication strategy."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["us-east-1"]
+						}
+					}
+				}
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the progress of the search. By default, the global `data_dir` option is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "file_system_path"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "A document matching the query."
+		fields: {
+			"*": {
+				description: "The fields of the `_source` of the document."
+				required:    true
+				type: "*": {}
+			}
+			"@metadata": {
+				description: "The `index` and `id` of the document, inserted in the `metadata_key` field."
+				required:    true
+				type: object: {
+					examples: [{"index": "logs-2022.08.01", "id": "zQ8YYYIBtFHBVCDyJg1R"}]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["elasticsearch"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		checkpointing: {
+			title: "Checkpointing"
+			body:  """
+				The source runs the query once, and finishes when all matching documents were read. The progress of
+				the search is persisted in `data_dir`, along with the indices and query it applies to. With
+				`point_in_time` pagination, a restart resumes the search from the last page sent. If the point in time
+				expired in the meantime, the search can't be resumed without reading documents again: the source stops
+				with an error, and the checkpoint has to be removed to start the search over. Once the search completes,
+				restarting Vector with the same indices and query reads nothing; changing either of them starts a new
+				search.
+
+				With acknowledgements enabled, a page is only checkpointed once its documents were delivered, and
+				documents that weren't delivered are sent again.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
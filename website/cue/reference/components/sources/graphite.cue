@@ -0,0 +1,140 @@
+package metadata
+
+components: sources: graphite: {
+	_port: 2003
+
+	title: "Graphite"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.graphite
+				interface: socket: {
+					api: {
+						title: "Carbon plaintext protocol"
+						url:   urls.graphite_plaintext_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp", "udp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp`"
+			}
+			keepalive: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description:   "The address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			relevant_when: "mode = `tcp` or `udp`"
+			required:      true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
+			}
+		}
+		mode: {
+			description: "The type of socket to use."
+			required:    true
+			type: string: {
+				enum: {
+					tcp: "TCP Socket."
+					udp: "UDP Socket."
+				}
+			}
+		}
+		shutdown_timeout_secs: {
+			common:        false
+			description:   "The timeout before a connection is forcefully closed during shutdown."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		connection_limit: {
+			common:        false
+			description:   "The max number of TCP connections that will be processed."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    "concurrency"
+			}
+		}
+	}
+
+	output: metrics: {
+		gauge: output._passthrough_gauge
+	}
+
+	how_it_works: {
+		line_format: {
+			title: "Line format"
+			body:  """
+				Each line received is parsed as `<path> <value> [<timestamp>]`, as described by the
+				[plaintext protocol](\(urls.graphite_plaintext_protocol)). The metric path becomes
+				the metric name as-is, including its dots, and every line is emitted as an absolute
+				gauge since the protocol carries no metric type. Blank lines are ignored and lines
+				that can't be parsed are dropped and reported through `component_errors_total`.
+
+				The pickle protocol is not supported by this source.
+				"""
+		}
+		tagged_series: {
+			title: "Tagged series"
+			body:  """
+				Paths using the [tagged series](\(urls.graphite_tags)) syntax, such as
+				`disk.used;datacenter=dc1;server=web01`, are split on `;`. The first segment becomes
+				the metric name and each `key=value` pair becomes a metric tag.
+				"""
+		}
+		timestamps: {
+			title: "Timestamps"
+			body:  """
+				Timestamps are read as seconds since the Unix epoch and may be fractional. Lines
+				without a timestamp, or with the `-1` placeholder Carbon uses for "now", produce
+				metrics with a `null` timestamp which downstream sinks substitute with the current
+				time. See the [metric data model](\(urls.vector_metric)) page for more info.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,205 @@
+package metadata
+
+components: sources: http_client: {
+	title: "HTTP Client"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar", "aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.http
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			proxy: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoints: {
+			description: "Endpoints to poll."
+			required:    true
+			type: array: {
+				items: type: string: {
+					examples: ["https://api.example.com/v1/logs"]
+				}
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between polls."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		query: {
+			common: false
+			description: """
+				Custom parameters for the request query string.
+				One or more values for the same parameter key can be provided.
+				The parameters provided in this option are appended to any parameters manually provided in the `endpoints` option.
+				Values can refer to the cursor of the next page as `{{ cursor }}`, and format the current time with `strftime` specifiers.
+				A value referring to the cursor is left out of the first request, when there is no cursor yet.
+				"""
+			required: false
+			type: object: {
+				examples: [{"since": ["{{ cursor }}"], "date": ["%Y-%m-%d"]}]
+				options: {
+					"*": {
+						common:      false
+						description: "Any query key"
+						required:    false
+						type: array: {
+							default: null
+							examples: [["{{ cursor }}"]]
+							items: type: string: {
+								examples: ["{{ cursor }}", "%Y-%m-%d"]
+								syntax: "template"
+							}
+						}
+					}
+				}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Custom headers to send with each request."
+			required:    false
+			type: object: {
+				examples: [{"Accept": "application/json", "X-Api-Key": "${API_KEY}"}]
+				options: {
+					"*": {
+						common:      false
+						description: "A header."
+						required:    false
+						type: string: {
+							examples: ["application/json"]
+						}
+					}
+				}
+			}
+		}
+		pagination: {
+			common:      false
+			description: """
+				Pagination of the responses of an endpoint. Each poll follows the pages of an endpoint until a response has
+				no next page.
+				"""
+			required:    false
+			type: object: options: {
+				strategy: {
+					description: "How the next page is requested."
+					required:    true
+					type: string: enum: {
+						link:   "The next page is requested from the `next` link of the `Link` response header, as described in [RFC 8288](\(urls.rfc_8288))."
+						cursor: "The next page is requested with a cursor read from the JSON response body."
+					}
+				}
+				cursor_path: {
+					description:   "The field of the JSON response body holding the cursor of the next page. Nested fields are separated with `.`. A missing or `null` field means there is no next page."
+					relevant_when: "strategy = \"cursor\""
+					required:      true
+					type: string: {
+						examples: ["next_cursor", "meta.next"]
+					}
+				}
+				query_param: {
+					description:   "The query parameter the cursor is sent in."
+					relevant_when: "strategy = \"cursor\""
+					required:      true
+					type: string: {
+						examples: ["cursor", "after"]
+					}
+				}
+			}
+		}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${API_PASSWORD}"
+			username_example: "${API_USERNAME}"
+		}}
+	}
+
+	output: logs: record: {
+		description: "An individual event decoded from a response."
+		fields: {
+			message: {
+				description: "The raw response body, or the decoded event fields, depending on the `decoding` option."
+				required:    true
+				type: string: {
+					examples: ["{\"id\": 1, \"level\": \"info\"}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["http_client"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		pagination: {
+			title: "Pagination"
+			body:  """
+				With `pagination`, each poll follows the pages of an endpoint until a response has no next page, or
+				its next page was already requested by the same poll. The
+				cursor of the last page received, which is the URL of the `next` link for the `link` strategy, is
+				persisted in `data_dir` once its events are emitted, so that the next poll, even after a restart,
+				resumes after it rather than polling all pages again.
+
+				The next poll requests that page again only to find out its next page, and doesn't emit its events
+				again. APIs that return a cursor with every response, such as incremental feeds, resume from exactly
+				where the last poll stopped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		requests_completed_total:             components.sources.internal_metrics.output.metrics.requests_completed_total
+		request_duration_seconds:             components.sources.internal_metrics.output.metrics.request_duration_seconds
+	}
+}
@@ -40,6 +40,8 @@ components: transforms: "remap": {
 			description: """
 				The [Vector Remap Language](\(urls.vrl_reference)) (VRL) program to execute for each event.
 
+				Relative paths of `import` statements are resolved against the current working directory.
+
 				Required if `file` is missing.
 				"""
 			common:      true
@@ -62,7 +64,8 @@ components: transforms: "remap": {
 			description: """
 				File path to the [Vector Remap Language](\(urls.vrl_reference)) (VRL) program to execute for each event.
 
-				If a relative path is provided, its root is the current working directory.
+				If a relative path is provided, its root is the current working directory. Relative paths of
+				`import` statements in the program are resolved against the directory of this file.
 
 				Required if `source` is missing.
 				"""
@@ -104,6 +107,49 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		max_loop_iterations: {
+			common:   false
+			required: false
+			description: """
+				The maximum number of iterations of a single `for` or `while` loop in the
+				VRL program. A loop that exceeds this limit aborts the program.
+				"""
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+		state: {
+			common: false
+			description: """
+				The state kept by the [stateful functions](\(urls.vrl_functions)/#state), such as
+				`incr_state`. The state is kept for as long as the transform runs, including across
+				configuration reloads that leave the transform unchanged.
+				"""
+			required: false
+			type: object: {
+				options: {
+					max_keys: {
+						common:      false
+						description: "The maximum number of keys to keep. When the limit is reached, the least recently used key is evicted."
+						required:    false
+						type: uint: {
+							default: 10000
+							unit:    null
+						}
+					}
+					ttl_secs: {
+						common:      false
+						description: "The number of seconds after which a key expires, counted from when it was last set. If not set, keys don't expire."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
 	}
 
 	input: {
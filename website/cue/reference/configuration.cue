@@ -306,12 +306,81 @@ configuration: {
 			common: false
 			description: """
 				Configuration options to retrieve secrets from external backend in order to avoid storing secrets in plaintext
-				in Vector config. Currently, only the exec backend is supported. Multiple backends can be configured. To signify
+				in Vector config. Multiple backends can be configured. To signify
 				Vector that it should look for a secret to retrieve use the `SECRET[<backend_name>.<secret_key>]`. This placeholder
 				will then be replaced by the secret retrieved from the relevant backend.
+
+				Secrets are retrieved again whenever the configuration is reloaded. When Vector watches its configuration
+				files, changes to the files read by the `file` and `directory` backends also trigger a reload, so rotated
+				secrets are picked up. These paths are updated on reload, so the paths of removed backends are no longer
+				watched, and paths that don't exist yet are watched once they are created.
 				"""
 			required: false
 			type: object: options: {
+				directory: {
+					required: true
+					description: """
+						Read secrets from a directory containing one file per secret, named after the secret key, such as a
+						Kubernetes secret mounted as a volume. `SECRET[<backend_name>.password]` is replaced by the contents of
+						the file `password` in the directory.
+						"""
+					type: object: options: {
+						path: {
+							description: "The directory containing the secret files."
+							required:    true
+							type: string: {
+								examples: ["/var/run/secrets/vector"]
+							}
+						}
+						remove_trailing_whitespace: {
+							description: "Whether to remove trailing whitespace, such as a final newline, from the secrets."
+							required:    false
+							common:      false
+							type: bool: default: false
+						}
+					}
+				}
+				environment: {
+					required: true
+					description: """
+						Read secrets from environment variables. `SECRET[<backend_name>.password]` is replaced by the value
+						of the environment variable `password`, preceded by the prefix.
+						"""
+					type: object: options: {
+						prefix: {
+							description: "The prefix of the names of the environment variables secrets are read from."
+							required:    false
+							common:      false
+							type: string: {
+								default: ""
+								examples: ["VECTOR_SECRET_"]
+							}
+						}
+					}
+				}
+				file: {
+					required: true
+					description: """
+						Read secrets from a file containing a map of secret keys to values. The file is parsed as JSON if its
+						name ends in `.json`, and as YAML otherwise:
+
+						```json
+						{
+							"username": "vector",
+							"password": "secret_value"
+						}
+						```
+						"""
+					type: object: options: {
+						path: {
+							description: "The file containing the secrets."
+							required:    true
+							type: string: {
+								examples: ["/etc/vector/secrets.json"]
+							}
+						}
+					}
+				}
 				exec: {
 					required: true
 					description: """
@@ -361,6 +430,105 @@ configuration: {
 						}
 					}
 				}
+				vault: {
+					required: true
+					description: """
+						Read secrets from the KV secrets engine of a HashiCorp Vault server.
+
+						Secret keys are made of the path of the secret within the secrets engine, with `.` in place of `/`,
+						followed by the name of the field to read: `SECRET[<backend_name>.app.database.password]` reads the
+						`password` field of the secret at `app/database`. Secrets sharing a path are read once. When the
+						configuration is reloaded, secrets are always read again, so that rotated secrets are picked up,
+						while the client token is reused until its lease expires.
+						"""
+					type: object: options: {
+						address: {
+							description: "The address of the Vault server."
+							required:    true
+							type: string: {
+								examples: ["https://vault.example.com:8200"]
+							}
+						}
+						auth: {
+							description: "How Vector authenticates to Vault."
+							required:    true
+							type: object: options: {
+								strategy: {
+									description: "The authentication strategy."
+									required:    true
+									type: string: enum: {
+										token:   "Authenticate with a Vault token."
+										approle: "Log in with the AppRole auth method, and use the resulting token."
+									}
+								}
+								token: {
+									description:   "The token, for the `token` strategy."
+									required:      false
+									relevant_when: "strategy = \"token\""
+									type: string: {}
+								}
+								role_id: {
+									description:   "The role ID, for the `approle` strategy."
+									required:      false
+									relevant_when: "strategy = \"approle\""
+									type: string: {}
+								}
+								secret_id: {
+									description:   "The secret ID, for the `approle` strategy."
+									required:      false
+									relevant_when: "strategy = \"approle\""
+									type: string: {}
+								}
+								mount: {
+									description:   "The path the AppRole auth method is mounted at."
+									required:      false
+									common:        false
+									relevant_when: "strategy = \"approle\""
+									type: string: default: "approle"
+								}
+							}
+						}
+						mount: {
+							description: "The path the KV secrets engine is mounted at."
+							required:    false
+							common:      false
+							type: string: default: "secret"
+						}
+						kv_version: {
+							description: "The version of the KV secrets engine."
+							required:    false
+							common:      false
+							type: string: {
+								default: "v2"
+								enum: {
+									v1: "Version 1 of the KV secrets engine."
+									v2: "Version 2 of the KV secrets engine, which versions secrets."
+								}
+							}
+						}
+						namespace: {
+							description: "The Vault Enterprise namespace to read secrets from."
+							required:    false
+							common:      false
+							type: string: default: null
+						}
+						timeout: {
+							description: "The amount of time Vector will wait for the secrets to be read."
+							required:    false
+							common:      false
+							type: uint: {
+								default: 5
+								unit:    "seconds"
+							}
+						}
+						tls: {
+							description: "TLS options to connect to the Vault server."
+							required:    false
+							common:      false
+							type: object: options: {}
+						}
+					}
+				}
 			}
 		}
 
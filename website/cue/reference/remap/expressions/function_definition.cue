@@ -0,0 +1,126 @@
+package metadata
+
+remap: expressions: function_definition: {
+	title:       "Function definition"
+	description: """
+		A _function definition_ expression defines a function that can be called by the expressions that follow
+		it, the same way as built-in [VRL functions](\(urls.vrl_functions)).
+
+		Function definitions can only appear at the top level of a program.
+		"""
+	return: """
+		Does not return a value, it only makes the function available to the rest of the program.
+		"""
+
+	grammar: {
+		source: """
+			"fn" ~ name ~ "(" ~ parameters? ~ ")" ~ return_type? ~ block
+			"""
+		definitions: {
+			name: {
+				description: """
+					`name` is the name of the function. It can't be the name of a built-in function or of a
+					function that is already defined.
+					"""
+			}
+			parameters: {
+				description: """
+					The `parameters` are comma-delimited names, each optionally followed by a colon (`:`) and
+					the types of the values it accepts, separated by `|`:
+
+					```coffee
+					fn format_duration(value: integer | float, unit: string) { ... }
+					```
+
+					Parameters without types accept any value. The available types are `any`, `string`,
+					`integer`, `float`, `boolean`, `null`, `timestamp`, `regex`, `array`, and `object`.
+
+					All parameters are required. Arguments can be passed by position or by name, as with
+					built-in functions.
+					"""
+			}
+			return_type: {
+				description: """
+					The `return_type` is an optional `->` followed by the types of the values the function
+					returns. The function fails to compile if its block can return a value of another type.
+					"""
+			}
+			block: {
+				description: """
+					The `block` is evaluated each time the function is called, and its last expression is the
+					return value of the function.
+
+					The block only has access to the parameters of the function. It can't read or modify the
+					event, or any variable defined outside of it, and the variables it assigns aren't visible
+					to the caller.
+					"""
+			}
+		}
+	}
+
+	characteristics: {
+		type_safety: {
+			title: "Argument type safety"
+			description: """
+				Arguments are checked against the types of the parameters when the program is compiled. If the
+				type of an argument isn't known, such as a field of the event, it's checked when the function is
+				called, and the call must handle the potential error:
+
+				```coffee
+				shout!(.message)
+				```
+				"""
+		}
+		fallibility: {
+			title: "Function fallibility"
+			description: """
+				A function is fallible if its block is fallible, in which case the error of any call to it must
+				be [handled](\(urls.vrl_errors_reference)), or the program aborted using `!`.
+				"""
+		}
+		imports: {
+			title: "Imports"
+			description: """
+				Functions can be shared between programs by defining them in a separate file, and importing that
+				file with an `import` expression at the top level of a program:
+
+				```coffee
+				import "lib/normalize.vrl"
+
+				.host = normalize_host(.host) ?? .host
+				```
+
+				Imported files can only contain function definitions and other imports. Imports are resolved when
+				the program is compiled: relative paths are resolved against the directory of the program's file
+				(the `file` option of the `remap` transform), or the current working directory if the program
+				isn't read from a file. Imports within an imported file are resolved against that file's
+				directory. Importing the same file more than once has no effect.
+				"""
+		}
+	}
+
+	examples: [
+		{
+			title: "Define and call a function"
+			source: #"""
+				fn shout(text: string) -> string {
+					upcase(text) + "!"
+				}
+
+				shout("hello")
+				"""#
+			return: "HELLO!"
+		},
+		{
+			title: "Call a function with named arguments"
+			source: #"""
+				fn join_key(prefix: string, name: string) -> string {
+					prefix + "." + name
+				}
+
+				join_key(name: "status", prefix: "http")
+				"""#
+			return: "http.status"
+		},
+	]
+}
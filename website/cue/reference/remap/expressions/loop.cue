@@ -0,0 +1,119 @@
+package metadata
+
+remap: expressions: loop: {
+	title: "Loop"
+	description: """
+		A _loop_ expression repeatedly evaluates a block. A `for` loop evaluates the block once for each element of
+		an array or object, a `while` loop evaluates the block for as long as a Boolean expression evaluates to
+		`true`.
+
+		Within the block, `break` stops the loop and `continue` skips to its next iteration. Both are statements:
+		they can't be used as a value, such as the value of an assignment.
+
+		A single loop can run at most 10,000 iterations. A loop that exceeds this limit aborts the program, the
+		same as an [abort](#abort) expression. The `remap` transform allows changing the limit through its
+		`max_loop_iterations` option.
+		"""
+	return: """
+		Always returns `null`.
+		"""
+
+	grammar: {
+		source: """
+			("for" ~ (key ~ ",")? ~ value ~ "in" ~ expression ~ block) | ("while" ~ predicate ~ block)
+			"""
+		definitions: {
+			key: {
+				description: """
+					`key` is the name of the variable assigned the index of each element of an array, or the key
+					of each field of an object. It can be `_` to ignore the key.
+					"""
+			}
+			value: {
+				description: """
+					`value` is the name of the variable assigned the value of each element of the array or object.
+					It can be `_` to ignore the value.
+
+					The `key` and `value` variables are only available within the block. Any variable of the same
+					name defined outside of the loop is restored once the loop ends.
+					"""
+			}
+			expression: {
+				description: """
+					The `expression` _must_ resolve to an array or an object. If it can't resolve to either, a
+					compile-time error is raised. If it can resolve to other types, the error must be handled, for
+					example by using `array!` or `object!`.
+
+					The expression is evaluated once before the first iteration, changes made to it within the
+					block don't affect the loop.
+					"""
+			}
+			predicate: {
+				description: """
+					The `predicate` _must_ be an expression that resolves to a Boolean. It's evaluated before each
+					iteration, and follows the same rules as the predicate of an [if](#if) expression.
+					"""
+			}
+			block: {
+				description: """
+					The `block` is evaluated for each iteration of the loop. It can use `break` and `continue` as
+					statements to control the loop, also within the blocks of an `if` statement, but not within the
+					block of a closure or function definition.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "For loop over an array"
+			source: #"""
+				sum = 0
+				for value in [1, 2, 3] {
+					sum = sum + value
+				}
+				sum
+				"""#
+			return: 6
+		},
+		{
+			title: "For loop over an object"
+			source: #"""
+				keys = []
+				for key, _value in { "b": 2, "a": 1 } {
+					keys = push(keys, key)
+				}
+				keys
+				"""#
+			return: ["a", "b"]
+		},
+		{
+			title: "While loop"
+			source: #"""
+				i = 0
+				while i < 3 {
+					i = i + 1
+				}
+				i
+				"""#
+			return: 3
+		},
+		{
+			title: "Break and continue"
+			source: #"""
+				found = []
+				for index, value in ["a", "", "b", "stop", "c"] {
+					if value == "" {
+						continue
+					}
+					if value == "stop" {
+						break
+					}
+					found = push(found, index)
+				}
+				found
+				"""#
+			return: [0, 2]
+		},
+	]
+}
@@ -26,7 +26,7 @@ remap: {
 		examples?: [remap.#Example, ...remap.#Example]
 	}
 
-	#FunctionCategory: "Array" | "Codec" | "Coerce" | "Convert" | "Debug" | "Enrichment" | "Enumerate" | "Event" | "Path" | "Cryptography" | "IP" | "Number" | "Object" | "Parse" | "Random" | "String" | "System" | "Timestamp" | "Type"
+	#FunctionCategory: "Array" | "Codec" | "Coerce" | "Convert" | "Debug" | "Enrichment" | "Enumerate" | "Event" | "Path" | "Cryptography" | "IP" | "Number" | "Object" | "Parse" | "Random" | "State" | "String" | "System" | "Timestamp" | "Type"
 
 	// A helper array for generating docs. At some point, we should generate this from the
 	// #FunctionCategory enum if CUE adds support for that.
@@ -46,6 +46,7 @@ remap: {
 		"Object",
 		"Parse",
 		"Random",
+		"State",
 		"String",
 		"System",
 		"Timestamp",
@@ -57,6 +58,12 @@ remap: {
 	}
 
 	// Reusable text
+	_state_explainer: """
+		The state is shared by all events processed by the program, and is kept for as long as the
+		`remap` transform runs, including across configuration reloads that leave the transform
+		unchanged. The number of keys and how long they're kept are limited by the `state` option of
+		the transform.
+		"""
 	_enrichment_table_explainer: """
 		For `file` enrichment tables this condition needs to be a VRL object in which
 		the key-value pairs indicate a field to	search mapped to a value to search in that field.
@@ -0,0 +1,79 @@
+package metadata
+
+remap: functions: community_id: {
+	category: "IP"
+	description: """
+		Computes the [Community ID](\(urls.community_id)) of a network flow, a hash of its endpoints and protocol
+		that is the same for both directions of the flow. It matches the IDs computed by tools such as Zeek and
+		Suricata.
+		"""
+	notices: [
+		"""
+			For ICMP and ICMPv6 flows, the `source_port` and `destination_port` are the ICMP message type and code.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "source_ip"
+			description: "The source IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "destination_ip"
+			description: "The destination IP address, of the same version as the source IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "protocol"
+			description: "The IANA protocol number of the flow, such as 6 for TCP or 17 for UDP."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "source_port"
+			description: "The source port. It must be given together with the `destination_port`."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "destination_port"
+			description: "The destination port. It must be given together with the `source_port`."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "seed"
+			description: "The seed of the hash, to tell apart the flows of separate networks."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`source_ip` or `destination_ip` isn't a valid IP address.",
+		"`source_ip` and `destination_ip` aren't of the same IP version.",
+		"Only one of `source_port` and `destination_port` is given.",
+		"`protocol` isn't between 0 and 255, or a port or the `seed` isn't between 0 and 65535.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Compute the Community ID of a TCP flow"
+			source: #"""
+				community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6)
+				"""#
+			return: "1:LQU9qZlK+B5F3KDmev6m5PMibrg="
+		},
+		{
+			title: "Compute the Community ID of a TCP flow with a seed"
+			source: #"""
+				community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6, seed: 1)
+				"""#
+			return: "1:3V71V58M3Ksw/yuFALMcW0LAHvc="
+		},
+	]
+}
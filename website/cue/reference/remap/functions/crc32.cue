@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: crc32: {
+	category:    "Cryptography"
+	description: """
+		Calculates the [CRC-32](\(urls.crc)) checksum of the `value`, as an unsigned 32-bit integer.
+
+		This isn't a cryptographic hash, and mustn't be used where collisions can be crafted on purpose.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the checksum for."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Calculate a CRC-32 checksum"
+			source: #"""
+				crc32("foobar")
+				"""#
+			return: 2666930069
+		},
+	]
+}
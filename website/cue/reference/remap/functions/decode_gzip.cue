@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: decode_gzip: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Gzip](\(urls.gzip)) string) into its original string. Concatenated
+		Gzip members, such as in batched AWS CloudWatch Logs payloads, are decoded into a single string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Gzip](\(urls.gzip)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Gzip string.",
+		"The decoded `value` is larger than 100 MiB.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Gzip data"
+			source: #"""
+				decode_gzip!(decode_base64!("H4sIAAAAAAACAyvISU0sTlVISU3OT0lVyE0FAJsZ870QAAAA"))
+				"""#
+			return: "please decode me"
+		},
+	]
+}
@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: decode_snappy: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Snappy](\(urls.snappy)) string in the raw format, as used by
+		Prometheus remote write requests) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Snappy](\(urls.snappy)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Snappy string.",
+		"The decoded `value` is larger than 100 MiB.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Snappy data"
+			source: #"""
+				decode_snappy!(decode_base64!("EDxwbGVhc2UgZGVjb2RlIG1l"))
+				"""#
+			return: "please decode me"
+		},
+	]
+}
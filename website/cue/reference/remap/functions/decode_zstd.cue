@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: decode_zstd: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Zstandard](\(urls.zstd)) string) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zstandard](\(urls.zstd)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Zstandard string.",
+		"The decoded `value` is larger than 100 MiB.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Zstandard data"
+			source: #"""
+				decode_zstd!(decode_base64!("KLUv/SQQgQAAcGxlYXNlIGRlY29kZSBtZeLOD6U="))
+				"""#
+			return: "please decode me"
+		},
+	]
+}
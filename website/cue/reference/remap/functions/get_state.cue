@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: get_state: {
+	category: "State"
+	description: """
+		Returns the value of the given `key` in the state of the program, or `null` if the key isn't
+		set or has expired.
+
+		\(remap._state_explainer)
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to get."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Get the last status of a host"
+			source: #"""
+				set_state("last_status:web-1", 200)
+				get_state("last_status:web-1")
+				"""#
+			return: 200
+		},
+	]
+}
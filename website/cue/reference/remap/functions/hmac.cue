@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: hmac: {
+	category:    "Cryptography"
+	description: """
+		Calculates a [HMAC](\(urls.hmac)) of the `value` using the given `key`, and returns it as a
+		hexadecimal string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the HMAC for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key to calculate the HMAC with."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hashing algorithm to use."
+			enum: {
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Calculate a HMAC"
+			source: #"""
+				hmac("Hello there", "super-secret-key")
+				"""#
+			return: "78b184f1832f8aff3934f5e0212454671b2d04d494e3b25075c5e45167029662"
+		},
+		{
+			title: "Calculate a HMAC using SHA-512"
+			source: #"""
+				hmac("Hello there", "super-secret-key", algorithm: "SHA-512")
+				"""#
+			return: "20c92a076b22f3432bfe918dfe4314d0243c8508643ab1d7d779a5658481ce2fd40821044010e978c136517f58ffc8e6c1f25830738858303cf0a71073c60e96"
+		},
+	]
+}
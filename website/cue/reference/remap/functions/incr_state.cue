@@ -0,0 +1,53 @@
+package metadata
+
+remap: functions: incr_state: {
+	category: "State"
+	description: """
+		Increments the integer value of the given `key` in the state of the program, and returns the
+		new value. A key that isn't set or has expired counts as `0`, which makes it easy to detect the
+		first time a key is seen.
+
+		\(remap._state_explainer)
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to increment."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "by"
+			description: "The amount to increment the key by."
+			required:    false
+			default:     1
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"The value of `key` isn't an integer.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Detect the first event of a user"
+			input: log: user: "alice"
+			source: #"""
+				.first_seen = incr_state!(.user) == 1
+				"""#
+			output: log: {
+				user:       "alice"
+				first_seen: true
+			}
+		},
+		{
+			title: "Increment by a given amount"
+			source: #"""
+				incr_state!("bytes", by: 42)
+				"""#
+			return: 42
+		},
+	]
+}
@@ -0,0 +1,64 @@
+package metadata
+
+remap: functions: ip_classify: {
+	category: "IP"
+	description: """
+		Classifies the IP address `value`, with its version, and flags for the
+		[special-purpose ranges](\(urls.iana_special_purpose_addresses)) it's in.
+		"""
+	notices: [
+		"""
+			An address is in at most one of the `unspecified`, `loopback`, `private`, `link_local`, `multicast` and
+			`reserved` classes. An address in none of them is `global`. IPv6 unique local addresses are `private`, and
+			IPv4 addresses mapped to IPv6 are classified as the IPv4 address.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Classify a private IPv4 address"
+			source: #"""
+				ip_classify!("192.168.10.32")
+				"""#
+			return: {
+				version:     4
+				unspecified: false
+				loopback:    false
+				private:     true
+				link_local:  false
+				multicast:   false
+				reserved:    false
+				global:      false
+			}
+		},
+		{
+			title: "Classify a global IPv6 address"
+			source: #"""
+				ip_classify!("2606:4700:4700::1111")
+				"""#
+			return: {
+				version:     6
+				unspecified: false
+				loopback:    false
+				private:     false
+				link_local:  false
+				multicast:   false
+				reserved:    false
+				global:      true
+			}
+		},
+	]
+}
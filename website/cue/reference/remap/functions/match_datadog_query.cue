@@ -4,6 +4,12 @@ remap: functions: match_datadog_query: {
 	category:    "Object"
 	description: """
 		Matches an object against a [Datadog Search Syntax](\(urls.datadog_search_syntax)) query.
+
+		Tags can be either an array of `key:value` strings or an object, as in metric and trace events. With
+		`metric` set, the object is matched as a metric, such as the root of a metric event: by its name as the
+		default field, by its `@name`, `@namespace`, `@kind` and `@type` facets, by the `@value` facet for the
+		`value` of its `counter` or `gauge`, and by its tags, which also provide the reserved `host`, `source`,
+		`status` and `service` attributes.
 		"""
 
 	arguments: [
@@ -19,6 +25,13 @@ remap: functions: match_datadog_query: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "metric"
+			description: "Whether the object is matched as a metric rather than as a log."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: []
 	return: types: ["boolean"]
@@ -52,5 +65,12 @@ remap: functions: match_datadog_query: {
 				"""#
 			return: true
 		},
+		{
+			title: "Metric"
+			source: #"""
+				match_datadog_query({"name": "cpu.usage", "kind": "absolute", "type": "gauge", "tags": {"host": "web-1"}}, "cpu host:web*", metric: true)
+				"""#
+			return: true
+		},
 	]
 }
@@ -0,0 +1,75 @@
+package metadata
+
+remap: functions: parse_cef: {
+	category:    "Parse"
+	description: """
+		Parses the `value` in the Common Event Format (CEF). Any text before the `CEF:` prefix, such as
+		a syslog header, is ignored.
+
+		The header fields are returned as `cefVersion`, `deviceVendor`, `deviceProduct`,
+		`deviceVersion`, `deviceEventClassId`, `name` and `severity`, alongside the unescaped fields of
+		the extension. `cefVersion` is an integer, and `severity` is an integer unless it's given by
+		name, such as `High`. Header fields take precedence over extension fields of the same name.
+		"""
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "translate_custom_fields"
+			description: """
+				Replace custom fields, such as `cs1`, with fields named after their label, such as
+				the value of `cs1Label`.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a CEF message",
+		"`value` is missing header fields",
+		"the extension of `value` isn't a list of `key=value` pairs",
+	]
+	return: types: ["object"]
+	examples: [
+		{
+			title: "Parse CEF"
+			source: #"""
+				parse_cef!(s'CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed.')
+				"""#
+			return: {
+				cefVersion:         0
+				deviceVendor:       "Security"
+				deviceProduct:      "threatmanager"
+				deviceVersion:      "1.0"
+				deviceEventClassId: "100"
+				name:               "worm successfully stopped"
+				severity:           10
+				src:                "10.0.0.1"
+				dst:                "2.1.2.2"
+				spt:                "1232"
+				msg:                "Detected a threat. No action needed."
+			}
+		},
+		{
+			title: "Parse CEF with custom fields"
+			source: #"""
+				parse_cef!(s'CEF:0|Vendor|Product|1|42|Blocked|High|cs1Label=policy cs1=default deny', translate_custom_fields: true)
+				"""#
+			return: {
+				cefVersion:         0
+				deviceVendor:       "Vendor"
+				deviceProduct:      "Product"
+				deviceVersion:      "1"
+				deviceEventClassId: "42"
+				name:               "Blocked"
+				severity:           "High"
+				policy:             "default deny"
+			}
+		},
+	]
+}
@@ -0,0 +1,55 @@
+package metadata
+
+remap: functions: parse_etld: {
+	category:    "Parse"
+	description: """
+		Parses the hostname `value` into its subdomain, registered domain (eTLD+1) and public suffix (eTLD),
+		using the [Public Suffix List](\(urls.public_suffix_list)) embedded in Vector.
+		"""
+	notices: [
+		"""
+			Hostnames with a top-level domain that isn't in the list, such as internal hostnames, are treated as
+			having the top-level domain as their public suffix, and `known_suffix` is `false`.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The hostname to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid hostname.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a hostname"
+			source: #"""
+				parse_etld!("www.vector.dev")
+				"""#
+			return: {
+				subdomain:         "www"
+				registered_domain: "vector.dev"
+				public_suffix:     "dev"
+				known_suffix:      true
+			}
+		},
+		{
+			title: "Parse a hostname with a multi-label public suffix"
+			source: #"""
+				parse_etld!("mail.eu.example.co.uk")
+				"""#
+			return: {
+				subdomain:         "mail.eu"
+				registered_domain: "example.co.uk"
+				public_suffix:     "co.uk"
+				known_suffix:      true
+			}
+		},
+	]
+}
@@ -0,0 +1,64 @@
+package metadata
+
+remap: functions: parse_leef: {
+	category:    "Parse"
+	description: """
+		Parses the `value` in the Log Event Extended Format (LEEF), version 1.0 or 2.0. Any text before
+		the `LEEF:` prefix, such as a syslog header, is ignored.
+
+		The header fields are returned as `leefVersion`, `vendor`, `productName`, `productVersion`
+		and `eventId`, alongside the unescaped attributes. Attributes are delimited by tabs, or by the
+		delimiter given in the header of LEEF 2.0 messages, either as a character or in hexadecimal,
+		such as `x5E`. `leefVersion` is a float, such as `1.0` or `2.0`. Header fields take precedence
+		over attributes of the same name.
+		"""
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a LEEF message",
+		"`value` has an unsupported LEEF version",
+		"`value` is missing header fields, or has an invalid delimiter",
+		"an attribute of `value` isn't a `key=value` pair",
+	]
+	return: types: ["object"]
+	examples: [
+		{
+			title: "Parse LEEF 1.0"
+			source: #"""
+				parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5")
+				"""#
+			return: {
+				leefVersion:    1.0
+				vendor:         "Microsoft"
+				productName:    "MSExchange"
+				productVersion: "4.0 SP1"
+				eventId:        "15345"
+				src:            "192.0.2.0"
+				dst:            "172.50.123.1"
+				sev:            "5"
+			}
+		},
+		{
+			title: "Parse LEEF 2.0 with a custom delimiter"
+			source: #"""
+				parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^usrName=joe")
+				"""#
+			return: {
+				leefVersion:    2.0
+				vendor:         "Lancope"
+				productName:    "StealthWatch"
+				productVersion: "1.0"
+				eventId:        "41"
+				src:            "10.0.1.8"
+				dst:            "10.0.0.5"
+				usrName:        "joe"
+			}
+		},
+	]
+}
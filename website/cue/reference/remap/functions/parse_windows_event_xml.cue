@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: parse_windows_event_xml: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as a rendered Windows event in XML, such as forwarded by Windows Event
+		Collector.
+
+		The fields of the `System` element are returned with snake case names, such as `event_id`
+		and `time_created`, with numeric fields as integers. The `Data` elements of the `EventData`
+		element are returned by name in `event_data`, with unnamed data named `param1`, `param2` and
+		so on. `UserData` is returned as parsed by [`parse_xml`](#parse_xml), and the messages of
+		`RenderingInfo`, if any, in `rendering_info`.
+		"""
+	arguments: [
+		{
+			name:        "value"
+			description: "The string representation of the XML event to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't valid XML",
+		"the root element of `value` isn't an `Event`",
+		"the `SystemTime` of the event isn't a valid timestamp",
+	]
+	return: types: ["object"]
+	examples: [
+		{
+			title: "Parse a Windows logon event"
+			source: #"""
+				parse_windows_event_xml!(s'<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event"><System><Provider Name="Microsoft-Windows-Security-Auditing"/><EventID>4624</EventID><Level>0</Level><TimeCreated SystemTime="2022-10-03T12:00:00.123Z"/><EventRecordID>8242</EventRecordID><Channel>Security</Channel><Computer>dc01.example.com</Computer></System><EventData><Data Name="TargetUserName">alice</Data><Data Name="LogonType">3</Data></EventData></Event>')
+				"""#
+			return: {
+				provider: name: "Microsoft-Windows-Security-Auditing"
+				event_id:        4624
+				level:           0
+				time_created:    "2022-10-03T12:00:00.123Z"
+				event_record_id: 8242
+				channel:         "Security"
+				computer:        "dc01.example.com"
+				event_data: {
+					TargetUserName: "alice"
+					LogonType:      "3"
+				}
+			}
+		},
+	]
+}
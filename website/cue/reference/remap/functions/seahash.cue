@@ -0,0 +1,36 @@
+package metadata
+
+remap: functions: seahash: {
+	category:    "Cryptography"
+	description: """
+		Calculates a [SeaHash](\(urls.seahash)) hash of the `value`.
+
+		This is a fast, non-cryptographic hash, suitable for sampling or sharding events by a key.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the hash for."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["integer"]
+		rules: [
+			"Due to limitations in the underlying VRL data types, hashes above the maximum signed 64-bit integer wrap around to negative integers.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Calculate a SeaHash hash"
+			source: #"""
+				seahash("to be or not to be")
+				"""#
+			return: 1988685042348123509
+		},
+	]
+}
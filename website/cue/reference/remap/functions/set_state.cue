@@ -0,0 +1,38 @@
+package metadata
+
+remap: functions: set_state: {
+	category: "State"
+	description: """
+		Sets the given `key` in the state of the program to `value`, and returns the value. Setting a
+		key to `null` removes it.
+
+		\(remap._state_explainer)
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to set."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The value to set the key to."
+			required:    true
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Remember the last status of a host"
+			source: #"""
+				set_state("last_status:web-1", 200)
+				"""#
+			return: 200
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: xxhash: {
+	category:    "Cryptography"
+	description: """
+		Calculates a [xxHash](\(urls.xxhash)) hash of the `value`.
+
+		This is a fast, non-cryptographic hash, suitable for sampling or sharding events by a key.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the hash for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "variant"
+			description: "The variant of the algorithm to use."
+			enum: {
+				"XXH32": "32-bit XXH32 algorithm"
+				"XXH64": "64-bit XXH64 algorithm"
+			}
+			required: false
+			default:  "XXH64"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["integer"]
+		rules: [
+			"Due to limitations in the underlying VRL data types, 64-bit hashes above the maximum signed 64-bit integer wrap around to negative integers.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Calculate a xxHash hash"
+			source: #"""
+				xxhash("foo")
+				"""#
+			return: 3728699739546630719
+		},
+		{
+			title: "Calculate a 32-bit xxHash hash"
+			source: #"""
+				xxhash("foo", variant: "XXH32")
+				"""#
+			return: 3792637401
+		},
+	]
+}
@@ -12,9 +12,11 @@ remap: syntax: keywords: {
 		* `continue`
 		* `else`
 		* `false`
+		* `fn`
 		* `for`
 		* `if`
 		* `impl`
+		* `import`
 		* `in`
 		* `let`
 		* `loop`
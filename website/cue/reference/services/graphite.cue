@@ -0,0 +1,10 @@
+package metadata
+
+services: graphite: {
+	name:     "Graphite"
+	thing:    "a \(name) Carbon daemon"
+	url:      urls.graphite
+	versions: null
+
+	description: "[Graphite](\(urls.graphite)) is a time-series monitoring tool. Its Carbon daemons (`carbon-cache`, `carbon-relay` and `carbon-aggregator`) receive metrics over the plaintext and pickle protocols and store them in Whisper databases."
+}
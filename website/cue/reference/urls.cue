@@ -50,6 +50,7 @@ urls: {
 	aws_iam:                                    "\(aws_docs)/IAM/latest/UserGuide/introduction.html"
 	aws_iam_role:                               "\(aws_docs)/IAM/latest/UserGuide/id_roles.html"
 	aws_imds_v1_security_problems:              "https://aws.amazon.com/blogs/security/defense-in-depth-open-firewalls-reverse-proxies-ssrf-vulnerabilities-ec2-instance-metadata-service/"
+	aws_dynamodb:                               "https://aws.amazon.com/dynamodb/"
 	aws_kinesis_firehose:                       "https://aws.amazon.com/kinesis/data-firehose/"
 	aws_kinesis_firehose_http_protocol:         "\(aws_docs)/firehose/latest/dev/create-destination.html#create-destination-http"
 	aws_firehose_http_request_spec:             "\(aws_docs)/firehose/latest/dev/httpdeliveryrequestresponse.html"
@@ -61,6 +62,7 @@ urls: {
 	aws_kinesis_streams_api:                    "\(aws_docs)/kinesis/latest/APIReference/API_PutRecords.html"
 	aws_kinesis_streams_service_limits:         "\(aws_docs)/streams/latest/dev/service-sizes-and-limits.html"
 	aws_kinesis_split_shards:                   "\(aws_docs)/streams/latest/dev/kinesis-using-sdk-java-resharding-split.html"
+	aws_kinesis_resharding:                     "\(aws_docs)/streams/latest/dev/kinesis-using-sdk-java-resharding.html"
 	aws_regions:                                "\(aws_docs)/AmazonRDS/latest/UserGuide/Concepts.RegionsAndAvailabilityZones.html"
 	aws_s3:                                     "https://aws.amazon.com/s3/"
 	aws_s3_acl:                                 "\(aws_docs)/AmazonS3/latest/dev/acl-overview.html"
@@ -104,6 +106,7 @@ urls: {
 	cloudsmith:                                 "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	community_id:                               "https://github.com/corelight/community-id-spec"
 	console:                                    "\(wikipedia)/wiki/System_console"
 	conventional_commits:                       "https://www.conventionalcommits.org"
 	contributing:                               "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -170,6 +173,9 @@ urls: {
 	elasticsearch_id_field:                     "https://www.elastic.co/guide/en/elasticsearch/reference/current/mapping-id-field.html"
 	elasticsearch_id_performance:               "https://www.elastic.co/guide/en/elasticsearch/reference/master/tune-for-indexing-speed.html#_use_auto_generated_ids"
 	elasticsearch_ignore_malformed:             "https://www.elastic.co/guide/en/elasticsearch/reference/current/ignore-malformed.html"
+	elasticsearch_point_in_time:                "https://www.elastic.co/guide/en/elasticsearch/reference/current/point-in-time-api.html"
+	elasticsearch_query_dsl:                    "https://www.elastic.co/guide/en/elasticsearch/reference/current/query-dsl.html"
+	elasticsearch_scroll:                       "https://www.elastic.co/guide/en/elasticsearch/reference/current/paginate-search-results.html#scroll-search-results"
 	encoding_charset_labels:                    "https://encoding.spec.whatwg.org/#concept-encoding-get"
 	encoding_standard:                          "https://encoding.spec.whatwg.org/"
 	endler_dev:                                 "https://endler.dev/"
@@ -219,6 +225,10 @@ urls: {
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                       "\(github)/google/glog"
+	graphite:                                   "https://graphite.readthedocs.io/en/latest/"
+	graphite_pickle_protocol:                   "\(graphite)feeding-carbon.html#the-pickle-protocol"
+	graphite_plaintext_protocol:                "\(graphite)feeding-carbon.html#the-plaintext-protocol"
+	graphite_tags:                              "\(graphite)tags.html"
 	graphql:                                    "https://graphql.org"
 	graphql_playground:                         "\(github)/graphql/graphql-playground"
 	graphviz:                                   "https://graphviz.org/"
@@ -231,6 +241,7 @@ urls: {
 	heroku:                                     "https://www.heroku.com"
 	heroku_http_log_drain:                      "https://devcenter.heroku.com/articles/log-drains#https-drains"
 	heroku_start:                               "https://devcenter.heroku.com/start"
+	hmac:                                       "\(wikipedia)/wiki/HMAC"
 	homebrew:                                   "https://brew.sh/"
 	homebrew_services:                          "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                  "https://honeycomb.io"
@@ -244,6 +255,7 @@ urls: {
 	humio_hec:                                  "https://docs.humio.com/integrations/data-shippers/hec/"
 	humio_hec_format_of_data:                   "https://docs.humio.com/integrations/data-shippers/hec/#format-of-data"
 	iam_instance_profile:                       "\(aws_docs)/IAM/latest/UserGuide/id_roles_use_switch-role-ec2_instance-profiles.html"
+	iana_special_purpose_addresses:             "https://www.iana.org/assignments/iana-ipv4-special-registry/iana-ipv4-special-registry.xhtml"
 	iana_time_zone_format:                      "\(wikipedia)/wiki/Tz_database#Names_of_time_zones"
 	iana_time_zones:                            "\(wikipedia)/wiki/List_of_tz_database_time_zones"
 	ieee_754:                                   "\(wikipedia)/wiki/IEEE_754"
@@ -395,6 +407,7 @@ urls: {
 	prometheus_remote_write:                    "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:           "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
+	public_suffix_list:                         "https://publicsuffix.org/"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
 	raspbian:                                   "https://www.raspbian.org/"
@@ -415,6 +428,7 @@ urls: {
 	rfc_4180:                                   "https://tools.ietf.org/html/rfc4180"
 	rfc_6587_3_4_1:                             "https://tools.ietf.org/html/rfc6587#section-3.4.1"
 	rfc_6891:                                   "https://tools.ietf.org/html/rfc6891"
+	rfc_8288:                                   "https://tools.ietf.org/html/rfc8288"
 	rhel:                                       "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rpm:                                        "https://rpm.org/"
 	rust:                                       "https://www.rust-lang.org/"
@@ -431,6 +445,7 @@ urls: {
 	rustup:                                     "https://rustup.rs"
 	redis:                                      "https://redis.io"
 	redis_rs:                                   "https://github.com/mitsuhiko/redis-rs"
+	seahash:                                    "https://docs.rs/seahash"
 	sematext:                                   "https://sematext.com"
 	sematext_create_logs_app:                   "https://apps.sematext.com/ui/integrations"
 	sematext_es:                                "https://sematext.com/docs/logs/index-events-via-elasticsearch-api/"
@@ -627,6 +642,7 @@ urls: {
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"
+	xxhash:                                     "https://cyan4973.github.io/xxHash/"
 	yaml:                                       "https://yaml.org/"
 	ytt:                                        "https://carvel.dev/ytt/"
 	yum:                                        "\(wikipedia)/wiki/Yum_(software)"